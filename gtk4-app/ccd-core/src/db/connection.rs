@@ -0,0 +1,324 @@
+use crate::db::storage::{self, CacheFlusher, LocalCache};
+use crate::db::{maintenance, schema, snapshots};
+use crate::models::{MaintenanceReport, SizeReport};
+use anyhow::{Context, Result};
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Connection pool type
+pub type DbPool = Pool<SqliteConnectionManager>;
+
+/// Shared database pool
+pub type SharedDbPool = Arc<DbPool>;
+
+/// Environment variable overriding the connection pool size
+pub const POOL_SIZE_ENV: &str = "CCD_DB_POOL_SIZE";
+
+/// Environment variable overriding how long (ms) to wait on a locked database
+pub const BUSY_TIMEOUT_ENV: &str = "CCD_DB_BUSY_TIMEOUT_MS";
+
+/// Connection pool and per-connection tuning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DatabaseConfig {
+    pub pool_size: u32,
+    pub busy_timeout: Duration,
+    pub local_cache: bool, // Work on a local copy when the database is on a network filesystem
+    pub flush_interval: Duration,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            pool_size: 5,
+            busy_timeout: Duration::from_secs(5),
+            local_cache: false,
+            flush_interval: storage::DEFAULT_FLUSH_INTERVAL,
+        }
+    }
+}
+
+impl DatabaseConfig {
+    /// Defaults, overridden by `CCD_DB_POOL_SIZE`, `CCD_DB_BUSY_TIMEOUT_MS`,
+    /// `CCD_DB_LOCAL_CACHE` and `CCD_DB_FLUSH_SECS`
+    pub fn from_env() -> Self {
+        let mut config = Self::default();
+
+        if let Some(size) = std::env::var(POOL_SIZE_ENV).ok().and_then(|v| v.parse().ok()) {
+            config.pool_size = u32::max(size, 1);
+        }
+        if let Some(ms) = std::env::var(BUSY_TIMEOUT_ENV).ok().and_then(|v| v.parse().ok()) {
+            config.busy_timeout = Duration::from_millis(ms);
+        }
+        if let Ok(value) = std::env::var(storage::LOCAL_CACHE_ENV) {
+            config.local_cache = matches!(value.to_lowercase().as_str(), "1" | "true" | "yes" | "on");
+        }
+        if let Some(secs) = std::env::var(storage::FLUSH_INTERVAL_ENV).ok().and_then(|v| v.parse().ok()) {
+            config.flush_interval = Duration::from_secs(u64::max(secs, 1));
+        }
+
+        config
+    }
+
+    /// Connection manager that tunes every connection as the pool opens it
+    ///
+    /// The GUI, background monitor and CLI can all use the database at once:
+    /// WAL lets readers proceed during a write and busy_timeout makes writers
+    /// wait for each other instead of failing with SQLITE_BUSY. Foreign keys
+    /// are a per-connection setting, so cascades need them enabled here too.
+    fn tune(&self, manager: SqliteConnectionManager) -> SqliteConnectionManager {
+        let busy_timeout = self.busy_timeout;
+        manager.with_init(move |conn| {
+            conn.busy_timeout(busy_timeout)?;
+            conn.execute_batch(
+                "PRAGMA journal_mode = WAL;
+                 PRAGMA synchronous = NORMAL;
+                 PRAGMA foreign_keys = ON;",
+            )
+        })
+    }
+}
+
+/// Database manager for Claude Context Tracker
+pub struct Database {
+    pool: DbPool,
+    db_path: PathBuf,
+    cache: Option<LocalCache>,
+    flush_interval: Duration,
+}
+
+impl Database {
+    /// Create a new database connection
+    ///
+    /// If db_path is None, uses `CCD_DB_PATH` or the XDG data directory
+    pub fn new(db_path: Option<PathBuf>) -> Result<Self> {
+        Self::with_config(db_path, DatabaseConfig::from_env())
+    }
+
+    /// Create a new database connection with explicit pool settings
+    pub fn with_config(db_path: Option<PathBuf>, config: DatabaseConfig) -> Result<Self> {
+        let path = db_path.unwrap_or_else(storage::configured_db_path);
+
+        log::info!("Opening database at: {}", path.display());
+
+        // Ensure parent directory exists
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .context("Failed to create database directory")?;
+        }
+
+        // A restore picked in the snapshot dialog is finished before anything opens the file
+        if snapshots::apply_staged_restore(&path)? {
+            log::warn!("Database was restored from a snapshot");
+        }
+
+        // SQLite locking misbehaves on NFS/SMB; work on a local copy if asked to
+        let cache = match storage::network_filesystem(&path) {
+            Some(fs_type) if config.local_cache => {
+                log::info!("Database is on a network filesystem ({}), working on a local copy", fs_type);
+                let cache = LocalCache::new(&path);
+                cache.load()?;
+                Some(cache)
+            }
+            Some(fs_type) => {
+                log::warn!(
+                    "Database {} is on a network filesystem ({}) where SQLite locking is unreliable; \
+                     set {} to a local path, or {}=1 to work on a local copy",
+                    path.display(),
+                    fs_type,
+                    storage::DB_PATH_ENV,
+                    storage::LOCAL_CACHE_ENV
+                );
+                None
+            }
+            None => None,
+        };
+        let open_path = cache.as_ref().map_or(path.as_path(), LocalCache::local_path);
+
+        // Create connection pool
+        let manager = config.tune(SqliteConnectionManager::file(open_path));
+        let pool = Pool::builder()
+            .max_size(config.pool_size)
+            .build(manager)
+            .context("Failed to create connection pool")?;
+
+        let db = Self {
+            pool,
+            db_path: path,
+            cache,
+            flush_interval: config.flush_interval,
+        };
+
+        // Initialize schema
+        db.initialize_schema()?;
+
+        Ok(db)
+    }
+
+    /// Get the database file path
+    pub fn db_path(&self) -> &Path {
+        &self.db_path
+    }
+
+    /// Get the local working copy, when the database is on a network filesystem
+    pub fn local_cache(&self) -> Option<&LocalCache> {
+        self.cache.as_ref()
+    }
+
+    /// Start writing the local working copy back periodically
+    ///
+    /// Returns None when there is no local copy. Keep the flusher alive for as long
+    /// as the database is used: dropping it writes the copy back one last time.
+    pub fn start_cache_flushing(&self) -> Option<CacheFlusher> {
+        let cache = self.cache.clone()?;
+        Some(CacheFlusher::start(cache, self.pool.clone(), self.flush_interval))
+    }
+
+    /// Get a connection from the pool
+    pub fn get_connection(&self) -> Result<r2d2::PooledConnection<SqliteConnectionManager>> {
+        self.pool.get().context("Failed to get database connection")
+    }
+
+    /// Get the connection pool
+    pub fn pool(&self) -> &DbPool {
+        &self.pool
+    }
+
+    /// Initialize the database schema, applying any pending migrations
+    fn initialize_schema(&self) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        match schema::current_version(&conn)? {
+            Some(version) if version >= schema::SCHEMA_VERSION => {
+                log::info!("Database schema is up to date (version {})", version);
+                return Ok(());
+            }
+            Some(version) => {
+                log::info!(
+                    "Migrating database from version {} to {}",
+                    version,
+                    schema::SCHEMA_VERSION
+                );
+            }
+            None => {
+                log::info!("Initializing database schema (version {})", schema::SCHEMA_VERSION);
+            }
+        }
+
+        schema::apply_migrations(&mut conn).context("Failed to migrate database schema")?;
+
+        log::info!("Database schema initialized successfully");
+
+        Ok(())
+    }
+
+    /// Size of the database file and of each table
+    pub fn size_report(&self) -> Result<SizeReport> {
+        let conn = self.get_connection()?;
+        maintenance::size_report(&conn)
+    }
+
+    /// Integrity check, incremental vacuum and ANALYZE
+    pub fn maintenance(&self) -> Result<MaintenanceReport> {
+        let conn = self.get_connection()?;
+        maintenance::run_maintenance(&conn)
+    }
+
+    /// Create a shared database pool
+    pub fn into_shared(self) -> SharedDbPool {
+        Arc::new(self.pool)
+    }
+}
+
+/// Create a new in-memory database for testing
+///
+/// Uses a uniquely named shared-cache database so repository methods that
+/// hold one pooled connection while acquiring another keep working.
+#[cfg(test)]
+pub fn create_test_db() -> Result<Database> {
+    use rusqlite::OpenFlags;
+
+    let uri = format!("file:test-{}?mode=memory&cache=shared", uuid::Uuid::new_v4());
+    let manager = DatabaseConfig::default().tune(SqliteConnectionManager::file(&uri).with_flags(
+        OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_CREATE | OpenFlags::SQLITE_OPEN_URI,
+    ));
+    let pool = Pool::builder()
+        .max_size(4)
+        .build(manager)
+        .context("Failed to create test connection pool")?;
+
+    let db = Database {
+        pool,
+        db_path: PathBuf::from(":memory:"),
+        cache: None,
+        flush_interval: storage::DEFAULT_FLUSH_INTERVAL,
+    };
+
+    db.initialize_schema()?;
+
+    Ok(db)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_in_memory_db() {
+        let db = create_test_db().expect("Failed to create test database");
+        let conn = db.get_connection().expect("Failed to get connection");
+
+        // Verify tables exist
+        let table_count: i32 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name IN ('projects', 'context_sections', 'session_history', 'extracted_facts')",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Failed to count tables");
+
+        assert_eq!(table_count, 4, "All tables should be created");
+    }
+
+    #[test]
+    fn test_schema_version() {
+        let db = create_test_db().expect("Failed to create test database");
+        let conn = db.get_connection().expect("Failed to get connection");
+
+        let version: i32 = conn
+            .query_row(
+                "SELECT version FROM schema_version ORDER BY version DESC LIMIT 1",
+                [],
+                |row| row.get(0),
+            )
+            .expect("Failed to get schema version");
+
+        assert_eq!(version, schema::SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_connection_pragmas() {
+        let dir = std::env::temp_dir().join(format!("ccd-db-{}", uuid::Uuid::new_v4()));
+        let config = DatabaseConfig {
+            pool_size: 2,
+            busy_timeout: Duration::from_millis(1500),
+            ..DatabaseConfig::default()
+        };
+        let db = Database::with_config(Some(dir.join("tracker.db")), config).expect("Failed to open database");
+        let conn = db.get_connection().expect("Failed to get connection");
+
+        let journal_mode: String = conn.query_row("PRAGMA journal_mode", [], |row| row.get(0)).unwrap();
+        let foreign_keys: i32 = conn.query_row("PRAGMA foreign_keys", [], |row| row.get(0)).unwrap();
+        let busy_timeout: i64 = conn.query_row("PRAGMA busy_timeout", [], |row| row.get(0)).unwrap();
+
+        assert_eq!(journal_mode, "wal");
+        assert_eq!(foreign_keys, 1);
+        assert_eq!(busy_timeout, 1500);
+        assert_eq!(db.pool().max_size(), 2);
+
+        drop(conn);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,97 @@
+-- Schema version 1 database fixture, as created by the original release.
+-- Frozen: do not update this file when the schema changes.
+
+CREATE TABLE schema_version (
+    version INTEGER PRIMARY KEY NOT NULL,
+    applied_at TEXT NOT NULL
+);
+
+CREATE TABLE projects (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    repo_path TEXT,
+    status TEXT NOT NULL DEFAULT 'active',
+    priority INTEGER NOT NULL DEFAULT 0,
+    tech_stack TEXT NOT NULL DEFAULT '[]',
+    description TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
+
+CREATE INDEX idx_projects_status ON projects(status);
+CREATE INDEX idx_projects_updated ON projects(updated DESC);
+
+CREATE TABLE context_sections (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    section_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL DEFAULT '',
+    "order" INTEGER NOT NULL DEFAULT 0,
+    auto_extracted INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_context_sections_project ON context_sections(project);
+CREATE INDEX idx_context_sections_order ON context_sections("order");
+
+CREATE TABLE session_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    facts_extracted INTEGER NOT NULL DEFAULT 0,
+    token_count INTEGER NOT NULL DEFAULT 0,
+    session_start TEXT NOT NULL,
+    session_end TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_session_history_project ON session_history(project);
+CREATE INDEX idx_session_history_session_start ON session_history(session_start DESC);
+
+CREATE TABLE extracted_facts (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    session TEXT,
+    fact_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    importance INTEGER NOT NULL DEFAULT 3,
+    stale INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (session) REFERENCES session_history(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_extracted_facts_project ON extracted_facts(project);
+CREATE INDEX idx_extracted_facts_session ON extracted_facts(session);
+CREATE INDEX idx_extracted_facts_importance ON extracted_facts(importance DESC);
+CREATE INDEX idx_extracted_facts_type ON extracted_facts(fact_type);
+CREATE INDEX idx_extracted_facts_stale ON extracted_facts(stale);
+
+INSERT INTO schema_version (version, applied_at) VALUES (1, '2024-12-01 10:00:00');
+
+INSERT INTO projects (id, name, slug, repo_path, status, priority, tech_stack, description, created, updated) VALUES
+    ('p-app', 'Web App', 'web-app', '/home/dev/web-app', 'active', 2, '["TypeScript","React"]', 'Customer facing app',
+     '2024-12-01T10:00:00+00:00', '2024-12-02T10:00:00+00:00'),
+    ('p-lib', 'Shared Lib', 'shared-lib', NULL, 'paused', 0, '["Rust"]', NULL,
+     '2024-12-01T10:00:00+00:00', '2024-12-01T10:00:00+00:00');
+
+INSERT INTO context_sections (id, project, section_type, title, content, "order", auto_extracted, created, updated) VALUES
+    ('s-1', 'p-app', 'architecture', 'Architecture', 'SPA talking to a REST API', 0, 0,
+     '2024-12-01T10:00:00+00:00', '2024-12-01T10:00:00+00:00');
+
+INSERT INTO session_history (id, project, summary, facts_extracted, token_count, session_start, session_end, created, updated) VALUES
+    ('h-1', 'p-app', 'Set up routing', 2, 42000, '2024-12-01T10:00:00+00:00', '2024-12-01T11:30:00+00:00',
+     '2024-12-01T10:00:00+00:00', '2024-12-01T11:30:00+00:00');
+
+INSERT INTO extracted_facts (id, project, session, fact_type, content, importance, stale, created, updated) VALUES
+    ('f-1', 'p-app', 'h-1', 'decision', 'Decided to use React Router', 4, 0,
+     '2024-12-01T10:30:00+00:00', '2024-12-01T10:30:00+00:00'),
+    ('f-2', 'p-app', 'h-1', 'blocker', 'Error: CORS failure on login', 5, 1,
+     '2024-12-01T10:45:00+00:00', '2024-12-01T10:45:00+00:00');
@@ -0,0 +1,131 @@
+use crate::models::{MaintenanceReport, SizeReport, TableSize};
+use anyhow::Result;
+use rusqlite::Connection;
+
+/// `PRAGMA auto_vacuum` value for incremental mode
+const AUTO_VACUUM_INCREMENTAL: i64 = 2;
+
+/// Run `PRAGMA integrity_check`, returning the problems found (empty when healthy)
+pub fn integrity_check(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare("PRAGMA integrity_check")?;
+    let problems = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|line| line != "ok")
+        .collect();
+
+    Ok(problems)
+}
+
+/// Size of the database file and of each table (indexes counted with their table)
+pub fn size_report(conn: &Connection) -> Result<SizeReport> {
+    let pragma = |name: &str| conn.query_row(&format!("PRAGMA {}", name), [], |row| row.get::<_, i64>(0));
+    let page_size = pragma("page_size")?;
+
+    let mut stmt = conn.prepare(
+        "SELECT COALESCE(m.tbl_name, d.name) AS owner, SUM(d.pgsize), MAX(m.type = 'table' AND m.name = m.tbl_name)
+         FROM dbstat d LEFT JOIN sqlite_master m ON m.name = d.name
+         GROUP BY owner ORDER BY 2 DESC, owner",
+    )?;
+    let sizes = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?, row.get::<_, Option<bool>>(2)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut tables = Vec::new();
+    for (name, bytes, is_table) in sizes {
+        let rows = if is_table.unwrap_or(false) {
+            conn.query_row(&format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\"")), [], |row| {
+                row.get(0)
+            })?
+        } else {
+            0
+        };
+        tables.push(TableSize { name, rows, bytes });
+    }
+
+    Ok(SizeReport {
+        file_bytes: pragma("page_count")? * page_size,
+        free_bytes: pragma("freelist_count")? * page_size,
+        tables,
+    })
+}
+
+/// Check integrity, give unused pages back to the file system and refresh planner statistics
+///
+/// A database that fails the integrity check is left untouched. The first run on a
+/// database created without incremental auto-vacuum does a full VACUUM to switch it over.
+pub fn run_maintenance(conn: &Connection) -> Result<MaintenanceReport> {
+    let problems = integrity_check(conn)?;
+    if !problems.is_empty() {
+        return Ok(MaintenanceReport {
+            problems,
+            ..Default::default()
+        });
+    }
+
+    let before = size_report(conn)?.file_bytes;
+
+    let auto_vacuum: i64 = conn.query_row("PRAGMA auto_vacuum", [], |row| row.get(0))?;
+    let full_vacuum = auto_vacuum != AUTO_VACUUM_INCREMENTAL;
+    if full_vacuum {
+        // Changing auto_vacuum only takes effect after a full VACUUM
+        conn.execute_batch("PRAGMA auto_vacuum = INCREMENTAL; VACUUM;")?;
+    } else {
+        conn.execute_batch("PRAGMA incremental_vacuum;")?;
+    }
+
+    conn.execute_batch("ANALYZE;")?;
+    // Also shrink the write-ahead log; busy readers just leave it for next time
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+
+    let after = size_report(conn)?.file_bytes;
+
+    Ok(MaintenanceReport {
+        problems,
+        reclaimed_bytes: (before - after).max(0),
+        full_vacuum,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+
+    #[test]
+    fn test_maintenance_reclaims_space() {
+        let dir = std::env::temp_dir().join(format!("ccd-maintenance-{}", uuid::Uuid::new_v4()));
+        let db = Database::new(Some(dir.join("tracker.db"))).unwrap();
+        let conn = db.get_connection().unwrap();
+
+        let padding = "x".repeat(4096);
+        for i in 0..200 {
+            conn.execute(
+                "INSERT INTO app_settings (key, value, updated) VALUES (?, ?, datetime('now'))",
+                rusqlite::params![format!("padding-{}", i), padding],
+            )
+            .unwrap();
+        }
+        conn.execute("DELETE FROM app_settings", []).unwrap();
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(())).unwrap();
+
+        let report = size_report(&conn).unwrap();
+        assert!(report.free_bytes > 0);
+        assert!(report.tables.iter().any(|t| t.name == "projects"));
+
+        let first = run_maintenance(&conn).unwrap();
+        assert!(first.is_healthy());
+        assert!(first.full_vacuum);
+        assert!(first.reclaimed_bytes > 0);
+
+        let second = run_maintenance(&conn).unwrap();
+        assert!(!second.full_vacuum);
+        assert_eq!(size_report(&conn).unwrap().free_bytes, 0);
+
+        drop(conn);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
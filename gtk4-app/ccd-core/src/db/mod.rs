@@ -1,6 +1,11 @@
 pub mod schema;
 pub mod connection;
 pub mod repository;
+pub mod maintenance;
+pub mod storage;
+pub mod snapshots;
 
 pub use connection::*;
 pub use repository::*;
+pub use storage::*;
+pub use snapshots::*;
@@ -0,0 +1,794 @@
+use crate::utils::content_hash;
+use anyhow::{bail, Context, Result};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashSet;
+
+/// Database schema for Claude Context Tracker
+/// Matches the PocketBase collections structure
+
+/// SQL for creating the projects table
+pub const CREATE_PROJECTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS projects (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL,
+    slug TEXT NOT NULL UNIQUE,
+    repo_path TEXT,
+    status TEXT NOT NULL DEFAULT 'active',
+    priority INTEGER NOT NULL DEFAULT 0,
+    tech_stack TEXT NOT NULL DEFAULT '[]',
+    description TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_projects_status ON projects(status);
+CREATE INDEX IF NOT EXISTS idx_projects_updated ON projects(updated DESC);
+"#;
+
+/// SQL for creating the context_sections table
+pub const CREATE_CONTEXT_SECTIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS context_sections (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    section_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL DEFAULT '',
+    "order" INTEGER NOT NULL DEFAULT 0,
+    auto_extracted INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_context_sections_project ON context_sections(project);
+CREATE INDEX IF NOT EXISTS idx_context_sections_order ON context_sections("order");
+"#;
+
+/// SQL for creating the session_history table
+pub const CREATE_SESSION_HISTORY_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS session_history (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    facts_extracted INTEGER NOT NULL DEFAULT 0,
+    token_count INTEGER NOT NULL DEFAULT 0,
+    session_start TEXT NOT NULL,
+    session_end TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_session_history_project ON session_history(project);
+CREATE INDEX IF NOT EXISTS idx_session_history_session_start ON session_history(session_start DESC);
+"#;
+
+/// SQL for creating the extracted_facts table
+pub const CREATE_EXTRACTED_FACTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS extracted_facts (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    session TEXT,
+    fact_type TEXT NOT NULL,
+    content TEXT NOT NULL,
+    importance INTEGER NOT NULL DEFAULT 3,
+    stale INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (session) REFERENCES session_history(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_project ON extracted_facts(project);
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_session ON extracted_facts(session);
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_importance ON extracted_facts(importance DESC);
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_type ON extracted_facts(fact_type);
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_stale ON extracted_facts(stale);
+"#;
+
+/// SQL for creating the project_dependencies table
+pub const CREATE_PROJECT_DEPENDENCIES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS project_dependencies (
+    id TEXT PRIMARY KEY NOT NULL,
+    project TEXT NOT NULL,
+    depends_on TEXT NOT NULL,
+    include_summary INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (depends_on) REFERENCES projects(id) ON DELETE CASCADE,
+    UNIQUE (project, depends_on)
+);
+
+CREATE INDEX IF NOT EXISTS idx_project_dependencies_project ON project_dependencies(project);
+CREATE INDEX IF NOT EXISTS idx_project_dependencies_depends_on ON project_dependencies(depends_on);
+"#;
+
+/// SQL for creating the workspaces tables (groups of related projects)
+pub const CREATE_WORKSPACES_TABLES: &str = r#"
+CREATE TABLE IF NOT EXISTS workspaces (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE,
+    description TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS workspace_members (
+    workspace TEXT NOT NULL,
+    project TEXT NOT NULL,
+    created TEXT NOT NULL,
+    PRIMARY KEY (workspace, project),
+    FOREIGN KEY (workspace) REFERENCES workspaces(id) ON DELETE CASCADE,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_workspace_members_project ON workspace_members(project);
+"#;
+
+/// SQL for creating the audit log of repository mutations
+pub const CREATE_AUDIT_LOG_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS audit_log (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    entity_type TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    project TEXT,
+    operation TEXT NOT NULL,
+    actor TEXT NOT NULL,
+    changes TEXT NOT NULL DEFAULT '{}',
+    created TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_entity ON audit_log(entity_type, entity_id);
+CREATE INDEX IF NOT EXISTS idx_audit_log_project ON audit_log(project);
+CREATE INDEX IF NOT EXISTS idx_audit_log_created ON audit_log(created DESC);
+"#;
+
+/// SQL for creating the processed_logs table (log files already ingested by the monitor)
+pub const CREATE_PROCESSED_LOGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS processed_logs (
+    project TEXT NOT NULL,
+    path TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    modified TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    session TEXT,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    PRIMARY KEY (project, path),
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE,
+    FOREIGN KEY (session) REFERENCES session_history(id) ON DELETE SET NULL
+);
+"#;
+
+/// SQL for creating the monitor_events table (notification history)
+pub const CREATE_MONITOR_EVENTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS monitor_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project TEXT NOT NULL,
+    event_type TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    read INTEGER NOT NULL DEFAULT 0,
+    created TEXT NOT NULL,
+    FOREIGN KEY (project) REFERENCES projects(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_monitor_events_project ON monitor_events(project);
+CREATE INDEX IF NOT EXISTS idx_monitor_events_unread ON monitor_events(read) WHERE read = 0;
+"#;
+
+/// SQL for creating the app_settings table (key/value preferences)
+pub const CREATE_APP_SETTINGS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS app_settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
+"#;
+
+/// SQL for creating the section_attachments table (local files referenced by a section)
+pub const CREATE_SECTION_ATTACHMENTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS section_attachments (
+    id TEXT PRIMARY KEY NOT NULL,
+    section TEXT NOT NULL,
+    path TEXT NOT NULL,
+    name TEXT NOT NULL,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    FOREIGN KEY (section) REFERENCES context_sections(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_section_attachments_section ON section_attachments(section);
+"#;
+
+/// SQL for creating the section_revisions table (earlier versions of a section's content)
+pub const CREATE_SECTION_REVISIONS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS section_revisions (
+    id TEXT PRIMARY KEY NOT NULL,
+    section TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created TEXT NOT NULL,
+    FOREIGN KEY (section) REFERENCES context_sections(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_section_revisions_section ON section_revisions(section, created DESC);
+"#;
+
+/// SQL for creating the session_commits table (commits made while a session ran)
+pub const CREATE_SESSION_COMMITS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS session_commits (
+    session TEXT NOT NULL,
+    sha TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    author TEXT NOT NULL,
+    committed TEXT NOT NULL,
+    PRIMARY KEY (session, sha),
+    FOREIGN KEY (session) REFERENCES session_history(id) ON DELETE CASCADE
+);
+"#;
+
+/// SQL for creating the session_transcripts table (gzip-compressed copies of transcripts)
+pub const CREATE_SESSION_TRANSCRIPTS_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS session_transcripts (
+    session TEXT NOT NULL,
+    path TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    data BLOB NOT NULL,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL,
+    PRIMARY KEY (session, path),
+    FOREIGN KEY (session) REFERENCES session_history(id) ON DELETE CASCADE
+);
+"#;
+
+/// SQL for creating the section_templates table (named outlines shared by all projects)
+pub const CREATE_SECTION_TEMPLATES_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS section_templates (
+    id TEXT PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    section_type TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created TEXT NOT NULL,
+    updated TEXT NOT NULL
+);
+"#;
+
+/// SQL for creating the schema_version table
+pub const CREATE_VERSION_TABLE: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_version (
+    version INTEGER PRIMARY KEY NOT NULL,
+    applied_at TEXT NOT NULL
+);
+"#;
+
+/// A single schema migration
+///
+/// Migrations are applied in order, each inside its own transaction. Once a
+/// migration has shipped it must never be edited: schema changes always go
+/// into a new migration appended to [`MIGRATIONS`].
+pub struct Migration {
+    pub version: i32,
+    pub description: &'static str,
+    pub up: fn(&Connection) -> rusqlite::Result<()>,
+}
+
+/// All migrations in version order
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Initial schema",
+        up: migrate_v1_initial_schema,
+    },
+    Migration {
+        version: 2,
+        description: "Project dependencies",
+        up: migrate_v2_project_dependencies,
+    },
+    Migration {
+        version: 3,
+        description: "Workspaces",
+        up: migrate_v3_workspaces,
+    },
+    Migration {
+        version: 4,
+        description: "Fact metadata",
+        up: migrate_v4_fact_metadata,
+    },
+    Migration {
+        version: 5,
+        description: "Soft delete",
+        up: migrate_v5_soft_delete,
+    },
+    Migration {
+        version: 6,
+        description: "Fact content hashes",
+        up: migrate_v6_fact_content_hash,
+    },
+    Migration {
+        version: 7,
+        description: "Audit log",
+        up: migrate_v7_audit_log,
+    },
+    Migration {
+        version: 8,
+        description: "Processed log files",
+        up: migrate_v8_processed_logs,
+    },
+    Migration {
+        version: 9,
+        description: "App settings",
+        up: migrate_v9_app_settings,
+    },
+    Migration {
+        version: 10,
+        description: "Per-record sync tracking",
+        up: migrate_v10_sync_tracking,
+    },
+    Migration {
+        version: 11,
+        description: "Per-project sync enablement",
+        up: migrate_v11_project_sync_enabled,
+    },
+    Migration {
+        version: 12,
+        description: "Section attachments",
+        up: migrate_v12_section_attachments,
+    },
+    Migration {
+        version: 13,
+        description: "Section revisions",
+        up: migrate_v13_section_revisions,
+    },
+    Migration {
+        version: 14,
+        description: "Processed log offsets",
+        up: migrate_v14_processed_log_offsets,
+    },
+    Migration {
+        version: 15,
+        description: "Monitor event history",
+        up: migrate_v15_monitor_events,
+    },
+    Migration {
+        version: 16,
+        description: "Fact review state",
+        up: migrate_v16_fact_review,
+    },
+    Migration {
+        version: 17,
+        description: "Session conversation IDs",
+        up: migrate_v17_session_conversations,
+    },
+    Migration {
+        version: 18,
+        description: "Project fact approval",
+        up: migrate_v18_project_fact_approval,
+    },
+    Migration {
+        version: 19,
+        description: "Section locks",
+        up: migrate_v19_section_locks,
+    },
+    Migration {
+        version: 20,
+        description: "Session git state",
+        up: migrate_v20_session_git,
+    },
+    Migration {
+        version: 21,
+        description: "Session usage and cost",
+        up: migrate_v21_session_usage,
+    },
+    Migration {
+        version: 22,
+        description: "Processed log message counts",
+        up: migrate_v22_processed_log_messages,
+    },
+    Migration {
+        version: 23,
+        description: "Session transcript archive",
+        up: migrate_v23_session_transcripts,
+    },
+    Migration {
+        version: 24,
+        description: "Project extraction preferences",
+        up: migrate_v24_project_extraction_preferences,
+    },
+    Migration {
+        version: 25,
+        description: "Pinned projects",
+        up: migrate_v25_pinned_projects,
+    },
+    Migration {
+        version: 26,
+        description: "Project export on save",
+        up: migrate_v26_project_export_on_save,
+    },
+    Migration {
+        version: 27,
+        description: "Section templates",
+        up: migrate_v27_section_templates,
+    },
+    Migration {
+        version: 28,
+        description: "Section export flags",
+        up: migrate_v28_section_export,
+    },
+];
+
+/// Database version for migrations (version of the last migration)
+pub const SCHEMA_VERSION: i32 = 28;
+
+fn migrate_v1_initial_schema(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_PROJECTS_TABLE)?;
+    conn.execute_batch(CREATE_CONTEXT_SECTIONS_TABLE)?;
+    conn.execute_batch(CREATE_SESSION_HISTORY_TABLE)?;
+    conn.execute_batch(CREATE_EXTRACTED_FACTS_TABLE)
+}
+
+fn migrate_v2_project_dependencies(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_PROJECT_DEPENDENCIES_TABLE)
+}
+
+fn migrate_v3_workspaces(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_WORKSPACES_TABLES)
+}
+
+fn migrate_v4_fact_metadata(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE extracted_facts ADD COLUMN metadata TEXT NOT NULL DEFAULT '{}';")
+}
+
+fn migrate_v5_soft_delete(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        r#"
+ALTER TABLE projects ADD COLUMN deleted_at TEXT;
+ALTER TABLE context_sections ADD COLUMN deleted_at TEXT;
+ALTER TABLE extracted_facts ADD COLUMN deleted_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_projects_deleted_at ON projects(deleted_at);
+CREATE INDEX IF NOT EXISTS idx_context_sections_deleted_at ON context_sections(deleted_at);
+CREATE INDEX IF NOT EXISTS idx_extracted_facts_deleted_at ON extracted_facts(deleted_at);
+"#,
+    )
+}
+
+fn migrate_v6_fact_content_hash(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE extracted_facts ADD COLUMN content_hash TEXT NOT NULL DEFAULT '';")?;
+
+    // Backfill hashes, keeping one fact per (project, type, hash): live facts
+    // win over trashed ones, then the most recently updated
+    let mut stmt = conn.prepare(
+        "SELECT id, project, fact_type, content FROM extracted_facts
+         ORDER BY deleted_at IS NOT NULL, updated DESC, id",
+    )?;
+    let facts = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut seen = HashSet::new();
+    for (id, project, fact_type, content) in facts {
+        let hash = content_hash(&content);
+        if seen.insert((project, fact_type, hash.clone())) {
+            conn.execute(
+                "UPDATE extracted_facts SET content_hash = ? WHERE id = ?",
+                [&hash, &id],
+            )?;
+        } else {
+            conn.execute("DELETE FROM extracted_facts WHERE id = ?", [&id])?;
+        }
+    }
+
+    conn.execute_batch(
+        "CREATE UNIQUE INDEX IF NOT EXISTS idx_extracted_facts_content_hash
+         ON extracted_facts(project, fact_type, content_hash);",
+    )
+}
+
+fn migrate_v7_audit_log(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_AUDIT_LOG_TABLE)
+}
+
+fn migrate_v8_processed_logs(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_PROCESSED_LOGS_TABLE)
+}
+
+fn migrate_v9_app_settings(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_APP_SETTINGS_TABLE)
+}
+
+/// Tables mirrored to/from PocketBase, which track per-record sync state
+const SYNCED_TABLES: &[&str] = &["projects", "context_sections", "session_history", "extracted_facts"];
+
+fn migrate_v10_sync_tracking(conn: &Connection) -> rusqlite::Result<()> {
+    // A database still in replica mode only holds what was last pulled
+    let last_synced: Option<String> = conn
+        .query_row(
+            "SELECT s.value FROM app_settings s
+             WHERE s.key = 'replica_last_synced'
+               AND EXISTS (SELECT 1 FROM app_settings WHERE key = 'replica_url' AND value != '')",
+            [],
+            |row| row.get(0),
+        )
+        .optional()?;
+
+    for table in SYNCED_TABLES {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN synced_at TEXT;
+             ALTER TABLE {table} ADD COLUMN dirty INTEGER NOT NULL DEFAULT 1;
+             CREATE INDEX IF NOT EXISTS idx_{table}_dirty ON {table}(dirty);"
+        ))?;
+
+        if let Some(synced_at) = &last_synced {
+            conn.execute(&format!("UPDATE {table} SET dirty = 0, synced_at = ?"), [synced_at])?;
+        }
+    }
+
+    Ok(())
+}
+
+fn migrate_v11_project_sync_enabled(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE projects ADD COLUMN sync_enabled INTEGER NOT NULL DEFAULT 1;")
+}
+
+fn migrate_v12_section_attachments(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_SECTION_ATTACHMENTS_TABLE)
+}
+
+fn migrate_v13_section_revisions(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_SECTION_REVISIONS_TABLE)
+}
+
+fn migrate_v14_processed_log_offsets(conn: &Connection) -> rusqlite::Result<()> {
+    // 0 = nothing to resume from; those files are reprocessed in full once
+    conn.execute_batch("ALTER TABLE processed_logs ADD COLUMN byte_offset INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v15_monitor_events(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_MONITOR_EVENTS_TABLE)
+}
+
+fn migrate_v16_fact_review(conn: &Connection) -> rusqlite::Result<()> {
+    // Facts from before review tracking count as reviewed rather than flooding triage
+    conn.execute_batch(
+        "ALTER TABLE extracted_facts ADD COLUMN needs_review INTEGER NOT NULL DEFAULT 0;
+         CREATE INDEX IF NOT EXISTS idx_extracted_facts_needs_review ON extracted_facts(needs_review);",
+    )
+}
+
+fn migrate_v17_session_conversations(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE session_history ADD COLUMN conversation_id TEXT;
+         CREATE INDEX IF NOT EXISTS idx_session_history_conversation ON session_history(project, conversation_id);",
+    )
+}
+
+fn migrate_v18_project_fact_approval(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE projects ADD COLUMN require_fact_approval INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v19_section_locks(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE context_sections ADD COLUMN locked INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v20_session_git(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE session_history ADD COLUMN git_branch TEXT;
+         ALTER TABLE session_history ADD COLUMN git_head TEXT;",
+    )?;
+    conn.execute_batch(CREATE_SESSION_COMMITS_TABLE)
+}
+
+fn migrate_v21_session_usage(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE session_history ADD COLUMN input_tokens INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE session_history ADD COLUMN output_tokens INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE session_history ADD COLUMN cache_write_tokens INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE session_history ADD COLUMN cache_read_tokens INTEGER NOT NULL DEFAULT 0;
+         ALTER TABLE session_history ADD COLUMN cost_usd REAL NOT NULL DEFAULT 0;",
+    )
+}
+
+/// Messages before `byte_offset`, so facts from appended lines know their place in
+/// the conversation; unknown (NULL) for files processed by older versions
+fn migrate_v22_processed_log_messages(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE processed_logs ADD COLUMN message_count INTEGER;")
+}
+
+fn migrate_v23_session_transcripts(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_SESSION_TRANSCRIPTS_TABLE)
+}
+
+fn migrate_v24_project_extraction_preferences(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(
+        "ALTER TABLE projects ADD COLUMN disabled_fact_types TEXT NOT NULL DEFAULT '[]';
+         ALTER TABLE projects ADD COLUMN min_fact_importance INTEGER NOT NULL DEFAULT 1;",
+    )
+}
+
+fn migrate_v25_pinned_projects(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE projects ADD COLUMN pinned INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v26_project_export_on_save(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE projects ADD COLUMN export_on_save INTEGER NOT NULL DEFAULT 0;")
+}
+
+fn migrate_v27_section_templates(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch(CREATE_SECTION_TEMPLATES_TABLE)
+}
+
+fn migrate_v28_section_export(conn: &Connection) -> rusqlite::Result<()> {
+    conn.execute_batch("ALTER TABLE context_sections ADD COLUMN export TEXT NOT NULL DEFAULT 'default';")
+}
+
+/// Get the current schema version (None for a fresh database)
+pub fn current_version(conn: &Connection) -> Result<Option<i32>> {
+    conn.execute_batch(CREATE_VERSION_TABLE)?;
+
+    let version = conn.query_row("SELECT MAX(version) FROM schema_version", [], |row| row.get(0))?;
+    Ok(version)
+}
+
+/// Apply all pending migrations, returning the resulting schema version
+pub fn apply_migrations(conn: &mut Connection) -> Result<i32> {
+    let current = current_version(conn)?.unwrap_or(0);
+
+    if current > SCHEMA_VERSION {
+        bail!(
+            "Database schema version {} is newer than supported version {}",
+            current,
+            SCHEMA_VERSION
+        );
+    }
+
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        log::info!(
+            "Applying migration {}: {}",
+            migration.version,
+            migration.description
+        );
+
+        let tx = conn.transaction()?;
+        (migration.up)(&tx).with_context(|| {
+            format!("Migration {} ({}) failed", migration.version, migration.description)
+        })?;
+        tx.execute(
+            "INSERT INTO schema_version (version, applied_at) VALUES (?, datetime('now'))",
+            [migration.version],
+        )?;
+        tx.commit()?;
+    }
+
+    Ok(SCHEMA_VERSION.max(current))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A database as created by schema version 1, with sample data
+    const V1_FIXTURE: &str = include_str!("fixtures/schema_v1.sql");
+
+    fn v1_database() -> Connection {
+        let conn = Connection::open_in_memory().expect("Failed to open database");
+        conn.execute_batch(V1_FIXTURE).expect("Failed to load v1 fixture");
+        conn
+    }
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<i32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<i32> = (1..=MIGRATIONS.len() as i32).collect();
+
+        assert_eq!(versions, expected, "Migrations must be numbered 1..=N without gaps");
+        assert_eq!(SCHEMA_VERSION, *versions.last().unwrap());
+    }
+
+    #[test]
+    fn test_fresh_database_migrates_to_latest() {
+        let mut conn = Connection::open_in_memory().unwrap();
+
+        assert_eq!(current_version(&conn).unwrap(), None);
+        assert_eq!(apply_migrations(&mut conn).unwrap(), SCHEMA_VERSION);
+        assert_eq!(current_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+
+        // Re-running is a no-op
+        assert_eq!(apply_migrations(&mut conn).unwrap(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_upgrade_v1_fixture_to_latest() {
+        let mut conn = v1_database();
+        assert_eq!(current_version(&conn).unwrap(), Some(1));
+
+        apply_migrations(&mut conn).expect("Failed to migrate v1 database");
+        assert_eq!(current_version(&conn).unwrap(), Some(SCHEMA_VERSION));
+
+        // Existing data survives the upgrade
+        let projects: i32 = conn
+            .query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(projects, 2);
+
+        let facts: i32 = conn
+            .query_row("SELECT COUNT(*) FROM extracted_facts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(facts, 2);
+
+        // New tables are usable
+        conn.execute(
+            "INSERT INTO project_dependencies (id, project, depends_on, include_summary, created, updated)
+             VALUES ('d1', 'p-app', 'p-lib', 1, datetime('now'), datetime('now'))",
+            [],
+        )
+        .expect("project_dependencies should exist after migration");
+    }
+
+    #[test]
+    fn test_upgrade_deduplicates_facts() {
+        let mut conn = v1_database();
+        conn.execute_batch(
+            "INSERT INTO extracted_facts (id, project, session, fact_type, content, importance, stale, created, updated)
+             VALUES ('f-3', 'p-app', NULL, 'decision', 'decided to use react router!', 4, 0,
+                     '2024-12-03T10:00:00+00:00', '2024-12-03T10:00:00+00:00');",
+        )
+        .unwrap();
+
+        apply_migrations(&mut conn).unwrap();
+
+        let ids: Vec<String> = conn
+            .prepare("SELECT id FROM extracted_facts WHERE fact_type = 'decision'")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+        assert_eq!(ids, vec!["f-3".to_string()], "Most recently updated duplicate should be kept");
+    }
+
+    #[test]
+    fn test_upgraded_schema_matches_fresh_schema() {
+        let mut upgraded = v1_database();
+        apply_migrations(&mut upgraded).unwrap();
+
+        let mut fresh = Connection::open_in_memory().unwrap();
+        apply_migrations(&mut fresh).unwrap();
+
+        assert_eq!(schema_snapshot(&upgraded), schema_snapshot(&fresh));
+    }
+
+    #[test]
+    fn test_newer_database_is_rejected() {
+        let mut conn = v1_database();
+        conn.execute(
+            "INSERT INTO schema_version (version, applied_at) VALUES (?, datetime('now'))",
+            [SCHEMA_VERSION + 1],
+        )
+        .unwrap();
+
+        assert!(apply_migrations(&mut conn).is_err());
+    }
+
+    /// Table and column layout of a database, for comparing schemas
+    fn schema_snapshot(conn: &Connection) -> Vec<(String, String)> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT m.name, p.name || ':' || p.type FROM sqlite_master m, pragma_table_info(m.name) p
+                 WHERE m.type = 'table' ORDER BY m.name, p.cid",
+            )
+            .unwrap();
+        stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+}
@@ -0,0 +1,342 @@
+use crate::db::{maintenance, Repository};
+use crate::models::{DbSnapshot, RetentionPolicy};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use rusqlite::{Connection, OpenFlags};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Snapshot file names: `tracker-YYYYMMDD-HHMMSS.db`, in local time
+const SNAPSHOT_PREFIX: &str = "tracker-";
+const SNAPSHOT_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Local hour at which a new snapshot day starts, so the nightly snapshot is
+/// taken in the early morning when the app is left running
+const SNAPSHOT_DAY_START_HOUR: i64 = 3;
+
+/// How often the scheduler checks whether a snapshot is due
+const CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// Delay before the first check, to stay out of the way at startup
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// Directory snapshots of a database are kept in (next to it)
+pub fn snapshots_dir(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join("snapshots")
+}
+
+/// Copy the database into `dir`, provided it passes the integrity check
+///
+/// `VACUUM INTO` writes a consistent, compacted copy without blocking other
+/// connections for long, and includes anything still in the WAL.
+pub fn take_snapshot(conn: &Connection, dir: &Path) -> Result<DbSnapshot> {
+    let problems = maintenance::integrity_check(conn)?;
+    if !problems.is_empty() {
+        bail!("Integrity check failed, not taking a snapshot: {}", problems.join("; "));
+    }
+
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    // Whole seconds, as recorded in the file name
+    let taken = Local::now().with_nanosecond(0).unwrap_or_else(Local::now);
+    let path = dir.join(snapshot_file_name(taken));
+    conn.execute("VACUUM INTO ?", [path.to_string_lossy()])
+        .with_context(|| format!("Failed to write snapshot {}", path.display()))?;
+
+    let size = std::fs::metadata(&path)?.len();
+    log::info!("Database snapshot written to {}", path.display());
+    Ok(DbSnapshot { path, taken, size })
+}
+
+fn snapshot_file_name(taken: DateTime<Local>) -> String {
+    format!("{}{}.db", SNAPSHOT_PREFIX, taken.format(SNAPSHOT_TIME_FORMAT))
+}
+
+/// Snapshots in `dir`, newest first (none when the directory doesn't exist)
+pub fn list_snapshots(dir: &Path) -> Result<Vec<DbSnapshot>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+
+    let mut snapshots = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let name = entry.file_name();
+        let Some(taken) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(SNAPSHOT_PREFIX)?.strip_suffix(".db"))
+            .and_then(|stamp| NaiveDateTime::parse_from_str(stamp, SNAPSHOT_TIME_FORMAT).ok())
+            .and_then(|stamp| Local.from_local_datetime(&stamp).earliest())
+        else {
+            continue;
+        };
+        snapshots.push(DbSnapshot {
+            path: entry.path(),
+            taken,
+            size: entry.metadata()?.len(),
+        });
+    }
+
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.taken));
+    Ok(snapshots)
+}
+
+/// Day a snapshot counts towards; the night belongs to the day before
+fn snapshot_day(time: DateTime<Local>) -> NaiveDate {
+    (time - chrono::Duration::hours(SNAPSHOT_DAY_START_HOUR)).date_naive()
+}
+
+/// Whether a nightly snapshot is due, given when the latest one was taken
+///
+/// A day without a snapshot (because nothing was running overnight) is made up
+/// for as soon as possible.
+pub fn snapshot_due(latest: Option<DateTime<Local>>, now: DateTime<Local>) -> bool {
+    latest.is_none_or(|latest| snapshot_day(latest) < snapshot_day(now))
+}
+
+/// Snapshots the policy doesn't keep, from a newest-first list
+pub fn expired_snapshots(snapshots: &[DbSnapshot], policy: RetentionPolicy) -> Vec<&DbSnapshot> {
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+
+    snapshots
+        .iter()
+        .filter(|snapshot| {
+            let day = snapshot_day(snapshot.taken);
+            let week = day.iso_week();
+            let daily = days.len() < policy.keep_daily && days.insert(day);
+            let weekly = weeks.len() < policy.keep_weekly && weeks.insert((week.year(), week.week()));
+            !daily && !weekly
+        })
+        .collect()
+}
+
+/// Delete the snapshots in `dir` the policy doesn't keep, returning how many
+pub fn prune_snapshots(dir: &Path, policy: RetentionPolicy) -> Result<usize> {
+    let snapshots = list_snapshots(dir)?;
+    let expired = expired_snapshots(&snapshots, policy);
+    for snapshot in &expired {
+        std::fs::remove_file(&snapshot.path)
+            .with_context(|| format!("Failed to remove {}", snapshot.path.display()))?;
+    }
+    Ok(expired.len())
+}
+
+/// File a staged restore waits in until the database is next opened
+fn staged_restore_path(db_path: &Path) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".restore");
+    db_path.with_file_name(name)
+}
+
+/// Arrange for the database to be replaced by a snapshot the next time it is opened
+///
+/// The database can't be swapped while connections are open, so the snapshot is
+/// checked and copied next to it, and `apply_staged_restore` finishes the job.
+pub fn stage_restore(snapshot: &Path, db_path: &Path) -> Result<()> {
+    let conn = Connection::open_with_flags(snapshot, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .with_context(|| format!("Failed to open snapshot {}", snapshot.display()))?;
+    let problems = maintenance::integrity_check(&conn)?;
+    if !problems.is_empty() {
+        bail!("Snapshot {} is damaged: {}", snapshot.display(), problems.join("; "));
+    }
+    drop(conn);
+
+    std::fs::copy(snapshot, staged_restore_path(db_path))
+        .with_context(|| format!("Failed to stage {}", snapshot.display()))?;
+    Ok(())
+}
+
+/// Replace the database with a staged snapshot, if one is waiting
+///
+/// Called before the database is opened. The current database is kept as a
+/// snapshot first, so a restore can itself be undone. Returns whether it restored.
+pub fn apply_staged_restore(db_path: &Path) -> Result<bool> {
+    let staged = staged_restore_path(db_path);
+    if !staged.exists() {
+        return Ok(false);
+    }
+
+    if db_path.exists() {
+        keep_replaced_database(db_path)?;
+    }
+    for suffix in ["-wal", "-shm"] {
+        let mut name = db_path.as_os_str().to_os_string();
+        name.push(suffix);
+        match std::fs::remove_file(PathBuf::from(name)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+    }
+    std::fs::rename(&staged, db_path).context("Failed to restore the staged snapshot")?;
+
+    log::info!("Restored database {} from a snapshot", db_path.display());
+    Ok(true)
+}
+
+/// Keep the database about to be replaced by a restore
+///
+/// A healthy database becomes an ordinary snapshot. A damaged one (often the
+/// reason for restoring) is copied as is, under a name the snapshot list skips.
+fn keep_replaced_database(db_path: &Path) -> Result<()> {
+    let dir = snapshots_dir(db_path);
+    let conn = Connection::open(db_path)?;
+    match take_snapshot(&conn, &dir) {
+        Ok(_) => Ok(()),
+        Err(e) => {
+            log::warn!("Keeping the replaced database as is: {:#}", e);
+            drop(conn);
+            let copy = dir.join(format!("damaged-{}.db", Local::now().format(SNAPSHOT_TIME_FORMAT)));
+            std::fs::create_dir_all(&dir)?;
+            std::fs::copy(db_path, &copy).context("Failed to keep the current database")?;
+            Ok(())
+        }
+    }
+}
+
+/// Callback told about snapshots that failed, called from the scheduler thread
+pub type SnapshotFailureHandler = Arc<dyn Fn(&anyhow::Error) + Send + Sync>;
+
+/// Takes the nightly snapshot and prunes old ones in a background thread
+///
+/// Follows the snapshot settings, read again before every check. Stops when dropped.
+pub struct SnapshotScheduler {
+    _alive: Arc<()>,
+}
+
+impl SnapshotScheduler {
+    pub fn start(repository: Repository, dir: PathBuf, on_failure: Option<SnapshotFailureHandler>) -> Self {
+        let alive = Arc::new(());
+        let background = Arc::downgrade(&alive);
+
+        std::thread::spawn(move || {
+            std::thread::sleep(FIRST_CHECK_DELAY);
+            while background.upgrade().is_some() {
+                if let Err(e) = Self::run_due(&repository, &dir) {
+                    log::error!("Nightly database snapshot failed: {:#}", e);
+                    if let Some(handler) = &on_failure {
+                        handler(&e);
+                    }
+                }
+                std::thread::sleep(CHECK_INTERVAL);
+            }
+        });
+
+        Self { _alive: alive }
+    }
+
+    /// Take a snapshot and prune if one is due, returning the new snapshot
+    fn run_due(repository: &Repository, dir: &Path) -> Result<Option<DbSnapshot>> {
+        let settings = repository.load_settings()?;
+        if !settings.snapshots_enabled {
+            return Ok(None);
+        }
+
+        let latest = list_snapshots(dir)?.first().map(|snapshot| snapshot.taken);
+        if !snapshot_due(latest, Local::now()) {
+            return Ok(None);
+        }
+
+        let snapshot = repository.take_snapshot(dir)?;
+        let pruned = prune_snapshots(dir, settings.snapshot_retention())?;
+        if pruned > 0 {
+            log::info!("Pruned {} old database snapshots", pruned);
+        }
+        Ok(Some(snapshot))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(date: &str) -> DateTime<Local> {
+        let time = NaiveDateTime::parse_from_str(date, "%Y-%m-%d %H:%M").unwrap();
+        Local.from_local_datetime(&time).earliest().unwrap()
+    }
+
+    fn snapshot(date: &str) -> DbSnapshot {
+        DbSnapshot {
+            path: PathBuf::from(date),
+            taken: at(date),
+            size: 0,
+        }
+    }
+
+    #[test]
+    fn test_snapshot_due() {
+        assert!(snapshot_due(None, at("2024-03-10 12:00")));
+        // Taken at 03:30 today: nothing more until tomorrow morning
+        assert!(!snapshot_due(Some(at("2024-03-10 03:30")), at("2024-03-10 23:00")));
+        assert!(!snapshot_due(Some(at("2024-03-10 03:30")), at("2024-03-11 02:00")));
+        assert!(snapshot_due(Some(at("2024-03-10 03:30")), at("2024-03-11 03:15")));
+        // Missed nights are made up for
+        assert!(snapshot_due(Some(at("2024-03-07 03:30")), at("2024-03-10 14:00")));
+    }
+
+    #[test]
+    fn test_expired_snapshots() {
+        // Nightly snapshots for 40 days, plus a second one on the last day
+        let mut snapshots: Vec<_> = (1..=40)
+            .rev()
+            .map(|day| {
+                let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap() + chrono::Duration::days(day - 1);
+                snapshot(&format!("{} 04:00", date))
+            })
+            .collect();
+        snapshots.insert(0, snapshot("2024-02-09 18:00"));
+
+        let expired = expired_snapshots(&snapshots, RetentionPolicy::default());
+        let kept: Vec<_> = snapshots
+            .iter()
+            .filter(|s| !expired.contains(s))
+            .map(|s| s.taken.format("%m-%d %H").to_string())
+            .collect();
+
+        // The newest of each of the last 7 days, then of each of the last 4 weeks
+        assert_eq!(
+            kept,
+            [
+                "02-09 18", "02-08 04", "02-07 04", "02-06 04", "02-05 04", "02-04 04", "02-03 04", "01-28 04",
+                "01-21 04",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_snapshot_and_restore() {
+        let dir = std::env::temp_dir().join(format!("ccd-snapshots-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let db_path = dir.join("tracker.db");
+
+        let conn = Connection::open(&db_path).unwrap();
+        conn.execute_batch("CREATE TABLE notes (text TEXT); INSERT INTO notes VALUES ('before');")
+            .unwrap();
+        let snapshot = take_snapshot(&conn, &snapshots_dir(&db_path)).unwrap();
+        assert!(snapshot.size > 0);
+        assert_eq!(list_snapshots(&snapshots_dir(&db_path)).unwrap(), vec![snapshot.clone()]);
+
+        conn.execute("UPDATE notes SET text = 'after'", []).unwrap();
+        drop(conn);
+
+        assert!(!apply_staged_restore(&db_path).unwrap());
+        stage_restore(&snapshot.path, &db_path).unwrap();
+        // Snapshot names have one-second resolution
+        std::thread::sleep(Duration::from_millis(1100));
+        assert!(apply_staged_restore(&db_path).unwrap());
+
+        let conn = Connection::open(&db_path).unwrap();
+        let text: String = conn.query_row("SELECT text FROM notes", [], |row| row.get(0)).unwrap();
+        assert_eq!(text, "before");
+        // The replaced database was kept
+        assert_eq!(list_snapshots(&snapshots_dir(&db_path)).unwrap().len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
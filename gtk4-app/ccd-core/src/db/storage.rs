@@ -0,0 +1,372 @@
+use anyhow::{Context, Result};
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::{Connection, OpenFlags};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Environment variable moving the database to another location
+pub const DB_PATH_ENV: &str = "CCD_DB_PATH";
+
+/// Environment variable enabling the local-cache mode for databases on network filesystems
+pub const LOCAL_CACHE_ENV: &str = "CCD_DB_LOCAL_CACHE";
+
+/// Environment variable overriding how often (seconds) the local cache is written back
+pub const FLUSH_INTERVAL_ENV: &str = "CCD_DB_FLUSH_SECS";
+
+/// How often the local cache is written back by default
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Filesystem types whose locking SQLite can't rely on
+const NETWORK_FILESYSTEMS: &[&str] = &[
+    "nfs", "nfs4", "cifs", "smb3", "smbfs", "ncpfs", "afs", "9p", "ceph", "glusterfs", "lustre", "gpfs", "davfs",
+];
+
+/// FUSE filesystems that are network mounts (sshfs, rclone, ...)
+const NETWORK_FUSE_FILESYSTEMS: &[&str] = &["sshfs", "rclone", "s3fs", "davfs2", "glusterfs", "gcsfuse", "juicefs"];
+
+/// Database location: `CCD_DB_PATH`, or tracker.db in the XDG data directory
+pub fn configured_db_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(DB_PATH_ENV).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("claude-context-tracker")
+        .join("tracker.db")
+}
+
+/// Whether a filesystem type (as in /proc/self/mountinfo) is a network filesystem
+pub fn is_network_filesystem(fs_type: &str) -> bool {
+    match fs_type.strip_prefix("fuse.") {
+        Some(fuse) => NETWORK_FUSE_FILESYSTEMS.contains(&fuse),
+        None => NETWORK_FILESYSTEMS.contains(&fs_type),
+    }
+}
+
+/// Type of the network filesystem `path` is on, or None for local disks
+///
+/// Only Linux exposes mount information this way; elsewhere this is always None.
+pub fn network_filesystem(path: &Path) -> Option<String> {
+    // The database file may not exist yet: look at the closest existing ancestor
+    let existing = path.ancestors().find(|ancestor| ancestor.exists())?;
+    let path = existing.canonicalize().ok()?;
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo").ok()?;
+
+    mount_filesystem(&mountinfo, &path).filter(|fs_type| is_network_filesystem(fs_type))
+}
+
+/// Filesystem type of the mount containing `path`, from /proc/self/mountinfo contents
+fn mount_filesystem(mountinfo: &str, path: &Path) -> Option<String> {
+    let mut best: Option<(usize, &str)> = None;
+
+    for line in mountinfo.lines() {
+        // 36 35 98:0 /root /mnt/point rw,noatime master:1 - ext4 /dev/sda1 rw
+        let Some((mount, filesystem)) = line.split_once(" - ") else {
+            continue;
+        };
+        let (Some(mount_point), Some(fs_type)) = (mount.split(' ').nth(4), filesystem.split(' ').next()) else {
+            continue;
+        };
+        let mount_point = unescape_mount_path(mount_point);
+        if !path.starts_with(&mount_point) {
+            continue;
+        }
+
+        // The deepest mount wins; later lines are mounted over earlier ones
+        let depth = Path::new(&mount_point).components().count();
+        if best.is_none_or(|(best_depth, _)| depth >= best_depth) {
+            best = Some((depth, fs_type));
+        }
+    }
+
+    best.map(|(_, fs_type)| fs_type.to_string())
+}
+
+/// Undo the octal escapes (e.g. `\040` for a space) in mountinfo paths
+fn unescape_mount_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(index) = rest.find('\\') {
+        result.push_str(&rest[..index]);
+        let escape = rest.get(index + 1..index + 4);
+        match escape.and_then(|digits| u8::from_str_radix(digits, 8).ok()) {
+            Some(byte) => {
+                result.push(byte as char);
+                rest = &rest[index + 4..];
+            }
+            None => {
+                result.push('\\');
+                rest = &rest[index + 1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Working copy of a database on a network filesystem, kept on local disk
+///
+/// SQLite locking is unreliable over NFS and SMB, so all reads and writes go to
+/// the copy and the copy is written back (atomically, by rename) now and then.
+/// Processes on the same machine share the copy; a remote file changed by another
+/// machine replaces it the next time the database is opened.
+#[derive(Debug, Clone)]
+pub struct LocalCache {
+    remote: PathBuf,
+    local: PathBuf,
+}
+
+impl LocalCache {
+    /// Cache for the database at `remote`, in the runtime (or temp) directory
+    pub fn new(remote: &Path) -> Self {
+        let dir = dirs::runtime_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("claude-context-tracker");
+        let key = crate::utils::hash_bytes(remote.to_string_lossy().as_bytes());
+
+        Self {
+            remote: remote.to_path_buf(),
+            local: dir.join(format!("{}.db", key)),
+        }
+    }
+
+    /// Database file on the network filesystem
+    pub fn remote_path(&self) -> &Path {
+        &self.remote
+    }
+
+    /// Local working copy
+    pub fn local_path(&self) -> &Path {
+        &self.local
+    }
+
+    /// Refresh the working copy from the remote database if the remote changed
+    pub fn load(&self) -> Result<()> {
+        if let Some(parent) = self.local.parent() {
+            std::fs::create_dir_all(parent).context("Failed to create database cache directory")?;
+        }
+
+        let Some(remote_modified) = modified(&self.remote) else {
+            log::info!("No database at {} yet, starting a new one", self.remote.display());
+            return Ok(());
+        };
+        if self.local.exists() && self.synced_stamp().as_deref() == Some(stamp(remote_modified).as_str()) {
+            log::debug!("Database cache is up to date: {}", self.local.display());
+            return Ok(());
+        }
+
+        log::info!("Copying {} to {}", self.remote.display(), self.local.display());
+        let remote = Connection::open_with_flags(&self.remote, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .with_context(|| format!("Failed to open {}", self.remote.display()))?;
+        let temp = with_suffix(&self.local, ".tmp");
+        remove_if_exists(&temp)?;
+        remote
+            .execute("VACUUM INTO ?", [temp.to_string_lossy()])
+            .context("Failed to copy the database to the local cache")?;
+        drop(remote);
+
+        for suffix in ["-wal", "-shm"] {
+            remove_if_exists(&with_suffix(&self.local, suffix))?;
+        }
+        std::fs::rename(&temp, &self.local).context("Failed to replace the database cache")?;
+        self.record_synced()
+    }
+
+    /// Write the working copy back to the remote location
+    pub fn flush(&self, conn: &Connection) -> Result<()> {
+        let temp = with_suffix(&self.remote, ".ccd-flush");
+        remove_if_exists(&temp)?;
+        conn.execute("VACUUM INTO ?", [temp.to_string_lossy()])
+            .with_context(|| format!("Failed to write the database to {}", temp.display()))?;
+
+        // Journal files left by earlier direct use would be replayed into the new file
+        for suffix in ["-wal", "-shm"] {
+            remove_if_exists(&with_suffix(&self.remote, suffix))?;
+        }
+        std::fs::rename(&temp, &self.remote)
+            .with_context(|| format!("Failed to replace {}", self.remote.display()))?;
+
+        log::debug!("Flushed database cache to {}", self.remote.display());
+        self.record_synced()
+    }
+
+    /// When the working copy last changed
+    fn local_modified(&self) -> Option<SystemTime> {
+        let wal = modified(&with_suffix(&self.local, "-wal"));
+        modified(&self.local).max(wal)
+    }
+
+    /// File remembering the remote's mtime when the two were last in sync
+    fn stamp_path(&self) -> PathBuf {
+        with_suffix(&self.local, ".synced")
+    }
+
+    fn synced_stamp(&self) -> Option<String> {
+        std::fs::read_to_string(self.stamp_path()).ok()
+    }
+
+    fn record_synced(&self) -> Result<()> {
+        let remote_modified = modified(&self.remote).context("Database disappeared after copying")?;
+        std::fs::write(self.stamp_path(), stamp(remote_modified)).context("Failed to record database cache state")
+    }
+}
+
+/// Writes a local cache back periodically, and once more when dropped
+pub struct CacheFlusher {
+    state: Arc<FlushState>,
+}
+
+struct FlushState {
+    cache: LocalCache,
+    pool: Pool<SqliteConnectionManager>,
+    last_flushed: Mutex<Option<SystemTime>>,
+}
+
+impl FlushState {
+    /// Flush when the working copy changed since the last flush
+    fn flush(&self) -> Result<bool> {
+        let mut last_flushed = self.last_flushed.lock().unwrap_or_else(|e| e.into_inner());
+        let modified = self.cache.local_modified();
+        if last_flushed.is_some() && modified <= *last_flushed {
+            return Ok(false);
+        }
+
+        let conn = self.pool.get().context("Failed to get database connection")?;
+        self.cache.flush(&conn)?;
+        *last_flushed = modified;
+        Ok(true)
+    }
+}
+
+impl CacheFlusher {
+    /// Start flushing `cache` every `interval`, reading it through `pool`
+    pub fn start(cache: LocalCache, pool: Pool<SqliteConnectionManager>, interval: Duration) -> Self {
+        let state = Arc::new(FlushState {
+            last_flushed: Mutex::new(cache.local_modified()),
+            cache,
+            pool,
+        });
+
+        let background = Arc::downgrade(&state);
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+            let Some(state) = background.upgrade() else {
+                break;
+            };
+            if let Err(e) = state.flush() {
+                log::warn!("Failed to flush database cache: {:#}", e);
+            }
+        });
+
+        Self { state }
+    }
+
+    /// Write the working copy back now if it changed, returning whether it did
+    pub fn flush(&self) -> Result<bool> {
+        self.state.flush()
+    }
+}
+
+impl Drop for CacheFlusher {
+    fn drop(&mut self) {
+        if let Err(e) = self.state.flush() {
+            log::error!("Failed to flush database cache to {}: {:#}", self.state.cache.remote.display(), e);
+        }
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+fn stamp(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH).unwrap_or_default().as_nanos().to_string()
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_os_string();
+    path.push(suffix);
+    PathBuf::from(path)
+}
+
+fn remove_if_exists(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MOUNTINFO: &str = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+35 22 0:32 / /home rw,relatime shared:20 - nfs4 server:/export/home rw,vers=4.2
+36 35 0:33 / /home/alice/scratch rw,relatime shared:21 - tmpfs tmpfs rw
+37 22 0:34 / /mnt/my\\040share rw,relatime shared:22 - cifs //nas/share rw
+38 22 0:35 / /mnt/box rw,relatime shared:23 - fuse.sshfs user@box: rw";
+
+    #[test]
+    fn test_mount_filesystem() {
+        let fs_type = |path: &str| mount_filesystem(MOUNTINFO, Path::new(path));
+
+        assert_eq!(fs_type("/var/lib").as_deref(), Some("ext4"));
+        assert_eq!(fs_type("/home/alice/.local/share").as_deref(), Some("nfs4"));
+        assert_eq!(fs_type("/home/alice/scratch/db").as_deref(), Some("tmpfs"));
+        assert_eq!(fs_type("/mnt/my share/tracker.db").as_deref(), Some("cifs"));
+        // A shared prefix is not a parent directory
+        assert_eq!(fs_type("/homework").as_deref(), Some("ext4"));
+    }
+
+    #[test]
+    fn test_is_network_filesystem() {
+        assert!(is_network_filesystem("nfs4"));
+        assert!(is_network_filesystem("cifs"));
+        assert!(is_network_filesystem("fuse.sshfs"));
+        assert!(!is_network_filesystem("ext4"));
+        assert!(!is_network_filesystem("fuse.portal"));
+        assert!(!is_network_filesystem("tmpfs"));
+    }
+
+    #[test]
+    fn test_local_cache_round_trip() {
+        let dir = std::env::temp_dir().join(format!("ccd-storage-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let remote = dir.join("tracker.db");
+        let conn = Connection::open(&remote).unwrap();
+        conn.execute_batch("CREATE TABLE notes (body TEXT); INSERT INTO notes VALUES ('remote');").unwrap();
+        drop(conn);
+
+        let cache = LocalCache {
+            remote: remote.clone(),
+            local: dir.join("cache").join("tracker.db"),
+        };
+        cache.load().unwrap();
+
+        let local = Connection::open(cache.local_path()).unwrap();
+        local.execute("INSERT INTO notes VALUES ('local')", []).unwrap();
+        cache.flush(&local).unwrap();
+
+        let count: i64 = Connection::open(&remote)
+            .unwrap()
+            .query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+
+        // Unchanged remote: the working copy is kept as is
+        local.execute("INSERT INTO notes VALUES ('unflushed')", []).unwrap();
+        cache.load().unwrap();
+        let count: i64 = local.query_row("SELECT COUNT(*) FROM notes", [], |row| row.get(0)).unwrap();
+        assert_eq!(count, 3);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
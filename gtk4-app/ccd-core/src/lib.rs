@@ -0,0 +1,37 @@
+//! Core of Claude Context Tracker, shared by the GTK app and the `ccd` CLI.
+//!
+//! Other tools (editor plugins, bots, scripts) can embed the tracker through this
+//! crate instead of shelling out to `ccd`. The crate has no GUI dependencies.
+//!
+//! - [`db`]: the SQLite database ([`db::Database`]) and the [`db::Repository`] that
+//!   every read and write goes through, with migrations, audit log and maintenance.
+//! - [`models`]: records stored in the database. Each `X` has an `XPayload` holding
+//!   the fields a caller supplies when creating or updating it.
+//! - [`monitor`]: watches Claude Code transcripts, extracts facts into the database
+//!   and reports what it found through [`monitor::MonitorEvent`]s.
+//! - [`utils`]: CLAUDE.md rendering, compressed briefings, diffs and language helpers.
+//!
+//! ```no_run
+//! use anyhow::Context;
+//! use ccd_core::db::{Database, Repository};
+//! use ccd_core::utils::generate_claude_md;
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! // Opens (and migrates) the same database the app uses
+//! let repository = Repository::new(Database::new(None)?.into_shared());
+//!
+//! let project = repository
+//!     .list_projects(None)?
+//!     .into_iter()
+//!     .find(|p| p.slug == "my-app")
+//!     .context("No project with slug my-app")?;
+//! let sections = repository.list_context_sections(&project.id)?;
+//! println!("{}", generate_claude_md(&project, &sections));
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod db;
+pub mod models;
+pub mod monitor;
+pub mod utils;
@@ -0,0 +1,71 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Largest text attachment inlined into a generated CLAUDE.md (larger ones are linked)
+pub const INLINE_ATTACHMENT_MAX_BYTES: u64 = 8 * 1024;
+
+/// Local file (diagram, schema dump, ...) referenced by a context section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionAttachment {
+    pub id: String,
+    pub section: String,
+    pub path: String, // Absolute path of the referenced file, not a copy
+    pub name: String, // Display name, the file name by default
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl SectionAttachment {
+    /// Code fence language for inlined content, from the file extension
+    pub fn language_hint(&self) -> &str {
+        let ext = Path::new(&self.path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("");
+
+        match ext.to_lowercase().as_str() {
+            "sql" => "sql",
+            "json" => "json",
+            "toml" => "toml",
+            "yaml" | "yml" => "yaml",
+            "rs" => "rust",
+            "py" => "python",
+            "ts" => "typescript",
+            "js" => "javascript",
+            "sh" => "sh",
+            "mmd" | "mermaid" => "mermaid",
+            "dot" | "gv" => "dot",
+            "md" => "markdown",
+            _ => "",
+        }
+    }
+}
+
+/// Request payload for attaching a file to a section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionAttachmentPayload {
+    pub section: String,
+    pub path: String,
+    pub name: String,
+}
+
+impl From<&SectionAttachment> for SectionAttachmentPayload {
+    fn from(attachment: &SectionAttachment) -> Self {
+        Self {
+            section: attachment.section.clone(),
+            path: attachment.path.clone(),
+            name: attachment.name.clone(),
+        }
+    }
+}
+
+/// How section attachments appear in generated context
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AttachmentMode {
+    /// Inline small text files, link everything else
+    #[default]
+    Inline,
+    /// Only link files
+    Link,
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Who made a change recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditActor {
+    Cli,
+    Gui,
+    Monitor,
+}
+
+impl AuditActor {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditActor::Cli => "cli",
+            AuditActor::Gui => "gui",
+            AuditActor::Monitor => "monitor",
+        }
+    }
+}
+
+/// Kind of mutation recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOperation {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Purge,
+}
+
+impl AuditOperation {
+    pub fn as_str(&self) -> &str {
+        match self {
+            AuditOperation::Create => "create",
+            AuditOperation::Update => "update",
+            AuditOperation::Delete => "delete",
+            AuditOperation::Restore => "restore",
+            AuditOperation::Purge => "purge",
+        }
+    }
+}
+
+/// One recorded mutation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub entity_type: String, // "project", "section", "session", "fact", ...
+    pub entity_id: String,
+    pub project: Option<String>, // Owning project ID, for filtering
+    pub operation: AuditOperation,
+    pub actor: AuditActor,
+    pub changes: Value, // {"field": {"old": ..., "new": ...}}
+    pub created: DateTime<Utc>,
+}
+
+impl AuditEntry {
+    /// Names of the fields that changed, in key order
+    pub fn changed_fields(&self) -> Vec<&str> {
+        self.changes
+            .as_object()
+            .map(|fields| fields.keys().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Fields that change on every write and would only add noise to a diff
+const IGNORED_FIELDS: &[&str] = &["id", "created", "updated"];
+
+/// Diff two JSON snapshots of a record into `{"field": {"old": .., "new": ..}}`
+///
+/// A missing snapshot (create/delete) diffs against null, so every field shows up.
+pub fn diff_json(before: Option<&Value>, after: Option<&Value>) -> Value {
+    let empty = Map::new();
+    let before = before.and_then(Value::as_object).unwrap_or(&empty);
+    let after = after.and_then(Value::as_object).unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = before.keys().chain(after.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    let mut changes = Map::new();
+    for key in keys {
+        if IGNORED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+
+        let old = before.get(key).unwrap_or(&Value::Null);
+        let new = after.get(key).unwrap_or(&Value::Null);
+        if old != new {
+            let mut change = Map::new();
+            change.insert("old".to_string(), old.clone());
+            change.insert("new".to_string(), new.clone());
+            changes.insert(key.clone(), Value::Object(change));
+        }
+    }
+
+    Value::Object(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_json_update() {
+        let before = json!({"id": "p1", "name": "Old", "priority": 1, "updated": "a"});
+        let after = json!({"id": "p1", "name": "New", "priority": 1, "updated": "b"});
+
+        let diff = diff_json(Some(&before), Some(&after));
+
+        assert_eq!(diff, json!({"name": {"old": "Old", "new": "New"}}));
+    }
+
+    #[test]
+    fn test_diff_json_create() {
+        let after = json!({"id": "f1", "content": "Use SQLite", "stale": false});
+
+        let diff = diff_json(None, Some(&after));
+
+        assert_eq!(
+            diff,
+            json!({
+                "content": {"old": null, "new": "Use SQLite"},
+                "stale": {"old": null, "new": false}
+            })
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Extraction throughput for one corpus size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub transcripts: usize,
+    pub lines: usize,
+    pub facts: usize,
+    pub elapsed_ms: f64,
+}
+
+impl BenchmarkResult {
+    pub fn transcripts_per_sec(&self) -> f64 {
+        per_second(self.transcripts, self.elapsed_ms)
+    }
+
+    pub fn facts_per_sec(&self) -> f64 {
+        per_second(self.facts, self.elapsed_ms)
+    }
+}
+
+fn per_second(count: usize, elapsed_ms: f64) -> f64 {
+    if elapsed_ms <= 0.0 {
+        return 0.0;
+    }
+    count as f64 * 1000.0 / elapsed_ms
+}
+
+/// One benchmark invocation across several corpus sizes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    pub version: String, // Application version that produced the results
+    pub results: Vec<BenchmarkResult>,
+    pub created: DateTime<Utc>,
+}
+
+impl BenchmarkRun {
+    /// Result for a corpus size, if this run measured it
+    pub fn result_for(&self, transcripts: usize) -> Option<&BenchmarkResult> {
+        self.results.iter().find(|r| r.transcripts == transcripts)
+    }
+}
@@ -47,6 +47,28 @@ impl SectionType {
         }
     }
 
+    /// Title offered for a new section of this type
+    pub fn default_title(&self) -> &str {
+        match self {
+            Self::Custom => "Notes",
+            _ => self.display_name(),
+        }
+    }
+
+    /// Boilerplate a new section of this type starts with
+    ///
+    /// Sections are exported under a `##` heading, so templates only use `###` and below.
+    pub fn template(&self) -> &str {
+        match self {
+            Self::Architecture => "### Components\n\n- \n\n### Data flow\n\n",
+            Self::CurrentState => "- Working: \n- In progress: \n- Broken: \n",
+            Self::NextSteps => "- [ ] \n",
+            Self::Gotchas => "- \n",
+            Self::Decisions => "### Decision\n\n**Context:** \n\n**Choice:** \n\n**Why:** \n",
+            Self::Custom => "",
+        }
+    }
+
     pub fn all() -> Vec<Self> {
         vec![
             Self::Architecture,
@@ -71,6 +93,49 @@ impl std::fmt::Display for SectionType {
     }
 }
 
+/// How a section is treated when context is exported
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SectionExport {
+    /// Exported in full to CLAUDE.md; compressed briefings may leave it out
+    #[default]
+    Default,
+    /// Also kept in full in compressed briefings, whatever the budget
+    Always,
+    /// Internal notes, never exported
+    Never,
+}
+
+impl SectionExport {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Default => "default",
+            Self::Always => "always",
+            Self::Never => "never",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Default => "Export",
+            Self::Always => "Always include",
+            Self::Never => "Exclude from CLAUDE.md",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::Default => "view-reveal-symbolic",
+            Self::Always => "view-pin-symbolic",
+            Self::Never => "view-conceal-symbolic",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::Default, Self::Always, Self::Never]
+    }
+}
+
 /// Context section model representing structured project context
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContextSection {
@@ -81,6 +146,10 @@ pub struct ContextSection {
     pub content: String,
     pub order: i32,
     pub auto_extracted: bool,
+    #[serde(default)]
+    pub locked: bool, // Hand-curated; automated changes are refused
+    #[serde(default)]
+    pub export: SectionExport,
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -96,6 +165,8 @@ impl ContextSection {
             content: String::new(),
             order: 0,
             auto_extracted: false,
+            locked: false,
+            export: SectionExport::Default,
             created: Utc::now(),
             updated: Utc::now(),
         }
@@ -140,3 +211,19 @@ impl From<&ContextSection> for ContextSectionPayload {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_nest_under_section_heading() {
+        for section_type in SectionType::all() {
+            assert!(!section_type.default_title().is_empty());
+            for line in section_type.template().lines() {
+                assert!(!line.starts_with("# ") && !line.starts_with("## "), "{:?}: {}", section_type, line);
+            }
+        }
+        assert_eq!(SectionType::NextSteps.default_title(), "Next Steps");
+    }
+}
@@ -0,0 +1,20 @@
+use crate::monitor::MonitorEvent;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A monitor event kept in the notification history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub id: i64,
+    pub project: String,
+    pub event: MonitorEvent,
+    pub read: bool,
+    pub created: DateTime<Utc>,
+}
+
+/// Request payload for recording a monitor event
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventRecordPayload {
+    pub project: String,
+    pub event: MonitorEvent,
+}
@@ -0,0 +1,502 @@
+use crate::models::SectionType;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Fact type enumeration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FactType {
+    Decision,
+    Blocker,
+    FileChange,
+    Dependency,
+    Todo,
+    Insight,
+    Snippet,
+}
+
+impl FactType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Decision => "decision",
+            Self::Blocker => "blocker",
+            Self::FileChange => "file_change",
+            Self::Dependency => "dependency",
+            Self::Todo => "todo",
+            Self::Insight => "insight",
+            Self::Snippet => "snippet",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Decision => "Decision",
+            Self::Blocker => "Blocker",
+            Self::FileChange => "File Change",
+            Self::Dependency => "Dependency",
+            Self::Todo => "Todo",
+            Self::Insight => "Insight",
+            Self::Snippet => "Snippet",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::Decision => "emblem-ok-symbolic",
+            Self::Blocker => "dialog-error-symbolic",
+            Self::FileChange => "document-edit-symbolic",
+            Self::Dependency => "package-x-generic-symbolic",
+            Self::Todo => "checkbox-symbolic",
+            Self::Insight => "dialog-information-symbolic",
+            Self::Snippet => "text-x-script-symbolic",
+        }
+    }
+
+    pub fn color_class(&self) -> &str {
+        match self {
+            Self::Decision => "success",
+            Self::Blocker => "error",
+            Self::FileChange => "accent",
+            Self::Dependency => "warning",
+            Self::Todo => "default",
+            Self::Insight => "accent",
+            Self::Snippet => "default",
+        }
+    }
+
+    /// Context section a fact of this type is promoted into, if any
+    pub fn promoted_section_type(&self) -> Option<SectionType> {
+        match self {
+            Self::Decision => Some(SectionType::Decisions),
+            Self::Blocker | Self::Insight => Some(SectionType::Gotchas),
+            Self::Todo => Some(SectionType::NextSteps),
+            Self::Dependency => Some(SectionType::Architecture),
+            Self::FileChange | Self::Snippet => None,
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Decision,
+            Self::Blocker,
+            Self::FileChange,
+            Self::Dependency,
+            Self::Todo,
+            Self::Insight,
+            Self::Snippet,
+        ]
+    }
+}
+
+impl Default for FactType {
+    fn default() -> Self {
+        Self::Insight
+    }
+}
+
+impl std::fmt::Display for FactType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+/// Additional structured information about a fact (stored as JSON)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FactMetadata {
+    /// Detected language of the content (ISO 639-3 code, e.g. "eng")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    /// Path of captured media the fact was derived from (voice note, screenshot)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attachment: Option<String>,
+    /// Full line the fact was extracted from, when the content is only part of it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_line: Option<String>,
+    /// Content of the later fact that reported this blocker or todo as dealt with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+    /// Programming or config language of a snippet, from its code fence (e.g. "sql")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_language: Option<String>,
+    /// File a snippet belongs in, when the message names one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_file: Option<String>,
+    /// Where in a transcript the fact was extracted from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<FactSource>,
+    /// How sure the extractor was that this is a fact, from 0 to 1 (see `LOW_CONFIDENCE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// ID of the context section the fact was promoted into
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promoted_to: Option<String>,
+}
+
+/// Origin of an extracted fact: the transcript message and the file it mentions
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FactSource {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+    /// Position of the message among the conversation's messages, from 0
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_index: Option<usize>,
+    /// File the fact is about, relative to the session's working directory when inside it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_line: Option<u32>,
+}
+
+impl FactSource {
+    /// The referenced file as `path:line`, or just the path when no line is known
+    pub fn file_location(&self) -> Option<String> {
+        let path = self.file_path.as_deref()?;
+        Some(match self.file_line {
+            Some(line) => format!("{}:{}", path, line),
+            None => path.to_string(),
+        })
+    }
+}
+
+/// Confidence below which an extracted fact waits for review before it is exported
+pub const LOW_CONFIDENCE: f64 = 0.6;
+
+/// Extracted fact model representing auto-extracted knowledge from sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedFact {
+    pub id: String,
+    pub project: String, // Project ID
+    pub session: Option<String>, // Session ID (optional)
+    pub fact_type: FactType,
+    pub content: String,
+    pub importance: i32, // 1-5 scale
+    pub stale: bool,
+    pub metadata: FactMetadata,
+    #[serde(default)]
+    pub needs_review: bool, // Extracted automatically and not looked at yet
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+impl ExtractedFact {
+    /// Create a new extracted fact
+    pub fn new(project_id: String, fact_type: FactType, content: String) -> Self {
+        Self {
+            id: String::new(), // Will be set by PocketBase
+            project: project_id,
+            session: None,
+            fact_type,
+            content,
+            importance: 3, // Default middle importance
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created: Utc::now(),
+            updated: Utc::now(),
+        }
+    }
+
+    /// Get importance as star rating (★★★★★)
+    pub fn importance_stars(&self) -> String {
+        let filled = "★".repeat(self.importance.clamp(1, 5) as usize);
+        let empty = "☆".repeat((5 - self.importance.clamp(1, 5)) as usize);
+        format!("{}{}", filled, empty)
+    }
+
+    /// Get a preview of the content (first 80 chars)
+    pub fn content_preview(&self) -> String {
+        if self.content.len() <= 80 {
+            self.content.clone()
+        } else {
+            format!("{}...", &self.content[..77])
+        }
+    }
+
+    /// Check if high importance (4-5 stars)
+    pub fn is_high_importance(&self) -> bool {
+        self.importance >= 4
+    }
+
+    /// Check if low importance (1-2 stars)
+    pub fn is_low_importance(&self) -> bool {
+        self.importance <= 2
+    }
+
+    /// Confidence of the extractor in the fact; facts without a score count as certain
+    pub fn confidence(&self) -> f64 {
+        self.metadata.confidence.unwrap_or(1.0)
+    }
+
+    /// Check if an unreviewed extracted fact is too uncertain to export
+    pub fn is_low_confidence(&self) -> bool {
+        self.needs_review && self.confidence() < LOW_CONFIDENCE
+    }
+
+    /// The fact as a markdown list item, later lines indented under the first
+    pub fn to_list_item(&self) -> String {
+        let mut lines = self.content.trim().lines();
+        let mut item = format!("- {}", lines.next().unwrap_or_default());
+        for line in lines {
+            item.push('\n');
+            if !line.is_empty() {
+                item.push_str("  ");
+                item.push_str(line);
+            }
+        }
+        item
+    }
+
+    /// Get age in days
+    pub fn age_days(&self) -> i64 {
+        let now = Utc::now();
+        let duration = now.signed_duration_since(self.created);
+        duration.num_days()
+    }
+
+    /// Get human-readable age
+    pub fn age_display(&self) -> String {
+        let days = self.age_days();
+        match days {
+            0 => String::from("Today"),
+            1 => String::from("Yesterday"),
+            2..=6 => format!("{} days ago", days),
+            7..=13 => String::from("1 week ago"),
+            14..=29 => format!("{} weeks ago", days / 7),
+            30..=59 => String::from("1 month ago"),
+            _ => format!("{} months ago", days / 30),
+        }
+    }
+}
+
+/// Request payload for creating/updating facts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExtractedFactPayload {
+    pub project: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub fact_type: FactType,
+    pub content: String,
+    pub importance: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stale: Option<bool>,
+    #[serde(default)]
+    pub metadata: FactMetadata,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub needs_review: Option<bool>,
+}
+
+impl From<&ExtractedFact> for ExtractedFactPayload {
+    fn from(fact: &ExtractedFact) -> Self {
+        Self {
+            project: fact.project.clone(),
+            session: fact.session.clone(),
+            fact_type: fact.fact_type,
+            content: fact.content.clone(),
+            importance: fact.importance,
+            stale: Some(fact.stale),
+            metadata: fact.metadata.clone(),
+            needs_review: Some(fact.needs_review),
+        }
+    }
+}
+
+/// Which facts a list shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FactFilter {
+    pub fact_type: Option<FactType>, // None shows every type
+    pub min_importance: i32,
+    pub include_stale: bool,
+}
+
+impl Default for FactFilter {
+    fn default() -> Self {
+        Self {
+            fact_type: None,
+            min_importance: 1,
+            include_stale: false,
+        }
+    }
+}
+
+impl FactFilter {
+    /// Check if a fact passes the filter
+    pub fn matches(&self, fact: &ExtractedFact) -> bool {
+        self.fact_type.is_none_or(|fact_type| fact.fact_type == fact_type)
+            && fact.importance >= self.min_importance
+            && (self.include_stale || !fact.stale)
+    }
+
+    /// Check if anything is filtered out beyond the default
+    pub fn is_active(&self) -> bool {
+        *self != Self::default()
+    }
+}
+
+/// A change made to several facts at once
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FactBulkAction {
+    MarkStale,
+    Delete,
+    SetType(FactType),
+    SetImportance(i32),
+}
+
+impl FactBulkAction {
+    /// What was done, for `count` facts (e.g. "Marked 3 facts stale")
+    pub fn summary(&self, count: usize) -> String {
+        let facts = if count == 1 { "fact" } else { "facts" };
+        match self {
+            Self::MarkStale => format!("Marked {} {} stale", count, facts),
+            Self::Delete => format!("Moved {} {} to the trash", count, facts),
+            Self::SetType(fact_type) => format!("Changed {} {} to {}", count, facts, fact_type.display_name()),
+            Self::SetImportance(importance) => format!("Set {} {} to {} stars", count, facts, importance),
+        }
+    }
+}
+
+/// Fact statistics for display
+#[derive(Debug, Clone, Default)]
+pub struct FactStats {
+    pub total: usize,
+    pub by_type: std::collections::HashMap<FactType, usize>,
+    pub high_importance: usize,
+    pub stale: usize,
+}
+
+impl FactStats {
+    /// Calculate statistics from a list of facts
+    pub fn from_facts(facts: &[ExtractedFact]) -> Self {
+        let mut stats = Self::default();
+        stats.total = facts.len();
+
+        for fact in facts {
+            *stats.by_type.entry(fact.fact_type).or_insert(0) += 1;
+
+            if fact.is_high_importance() {
+                stats.high_importance += 1;
+            }
+
+            if fact.stale {
+                stats.stale += 1;
+            }
+        }
+
+        stats
+    }
+
+    /// Get count for a specific fact type
+    pub fn count_for_type(&self, fact_type: FactType) -> usize {
+        self.by_type.get(&fact_type).copied().unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_importance_stars() {
+        let mut fact = ExtractedFact::new("test".to_string(), FactType::Decision, "Test".to_string());
+
+        fact.importance = 5;
+        assert_eq!(fact.importance_stars(), "★★★★★");
+
+        fact.importance = 3;
+        assert_eq!(fact.importance_stars(), "★★★☆☆");
+
+        fact.importance = 1;
+        assert_eq!(fact.importance_stars(), "★☆☆☆☆");
+    }
+
+    #[test]
+    fn test_to_list_item() {
+        let mut fact = ExtractedFact::new("test".to_string(), FactType::Blocker, " Tests hang on CI\n".to_string());
+        assert_eq!(fact.to_list_item(), "- Tests hang on CI");
+
+        fact.content = "Run migrations first:\n\ncargo run -- migrate".to_string();
+        assert_eq!(fact.to_list_item(), "- Run migrations first:\n\n  cargo run -- migrate");
+
+        assert_eq!(FactType::Blocker.promoted_section_type(), Some(SectionType::Gotchas));
+        assert_eq!(FactType::Todo.promoted_section_type(), Some(SectionType::NextSteps));
+        assert_eq!(FactType::FileChange.promoted_section_type(), None);
+    }
+
+    #[test]
+    fn test_fact_filter() {
+        let mut fact = ExtractedFact::new("test".to_string(), FactType::Blocker, "Test".to_string());
+        let mut filter = FactFilter::default();
+        assert!(!filter.is_active());
+        assert!(filter.matches(&fact));
+
+        filter.fact_type = Some(FactType::Todo);
+        assert!(!filter.matches(&fact));
+        filter.fact_type = Some(FactType::Blocker);
+        assert!(filter.matches(&fact));
+
+        filter.min_importance = 4;
+        assert!(filter.is_active());
+        assert!(!filter.matches(&fact));
+        fact.importance = 4;
+        assert!(filter.matches(&fact));
+
+        fact.stale = true;
+        assert!(!filter.matches(&fact));
+        filter.include_stale = true;
+        assert!(filter.matches(&fact));
+    }
+
+    #[test]
+    fn test_low_confidence() {
+        let mut fact = ExtractedFact::new("test".to_string(), FactType::Todo, "Test".to_string());
+        fact.needs_review = true;
+        assert_eq!(fact.confidence(), 1.0);
+        assert!(!fact.is_low_confidence());
+
+        fact.metadata.confidence = Some(0.4);
+        assert!(fact.is_low_confidence());
+
+        // Confirmed by review
+        fact.needs_review = false;
+        assert!(!fact.is_low_confidence());
+    }
+
+    #[test]
+    fn test_fact_stats() {
+        let facts = vec![
+            ExtractedFact {
+                id: "1".to_string(),
+                project: "test".to_string(),
+                session: None,
+                fact_type: FactType::Decision,
+                content: "Test".to_string(),
+                importance: 5,
+                stale: false,
+                metadata: FactMetadata::default(),
+                needs_review: false,
+                created: Utc::now(),
+                updated: Utc::now(),
+            },
+            ExtractedFact {
+                id: "2".to_string(),
+                project: "test".to_string(),
+                session: None,
+                fact_type: FactType::Blocker,
+                content: "Test".to_string(),
+                importance: 4,
+                stale: true,
+                metadata: FactMetadata::default(),
+                needs_review: false,
+                created: Utc::now(),
+                updated: Utc::now(),
+            },
+        ];
+
+        let stats = FactStats::from_facts(&facts);
+        assert_eq!(stats.total, 2);
+        assert_eq!(stats.high_importance, 2);
+        assert_eq!(stats.stale, 1);
+        assert_eq!(stats.count_for_type(FactType::Decision), 1);
+    }
+}
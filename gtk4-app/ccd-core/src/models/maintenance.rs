@@ -0,0 +1,102 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Default number of days with a snapshot kept
+pub const DEFAULT_KEEP_DAILY: usize = 7;
+
+/// Default number of weeks with a snapshot kept
+pub const DEFAULT_KEEP_WEEKLY: usize = 4;
+
+/// Space used by one table, its indexes included
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableSize {
+    pub name: String,
+    pub rows: i64,
+    pub bytes: i64,
+}
+
+/// Breakdown of the database file by table, largest first
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeReport {
+    pub file_bytes: i64,
+    pub free_bytes: i64, // Unused pages a vacuum would give back
+    pub tables: Vec<TableSize>,
+}
+
+/// Outcome of a maintenance run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MaintenanceReport {
+    pub problems: Vec<String>, // Integrity check findings, empty when healthy
+    pub reclaimed_bytes: i64,
+    pub full_vacuum: bool, // The database was switched to incremental auto-vacuum
+}
+
+impl MaintenanceReport {
+    pub fn is_healthy(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        let integrity = if self.is_healthy() {
+            "integrity ok".to_string()
+        } else {
+            format!("{} integrity problems", self.problems.len())
+        };
+        format!("{}, {} reclaimed", integrity, format_bytes(self.reclaimed_bytes))
+    }
+}
+
+/// Human readable byte count, e.g. "1.5 MiB"
+pub fn format_bytes(bytes: i64) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// How many nightly snapshots survive pruning
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetentionPolicy {
+    pub keep_daily: usize,  // Newest snapshot of each of the latest N days
+    pub keep_weekly: usize, // Newest snapshot of each of the latest N weeks
+}
+
+impl Default for RetentionPolicy {
+    fn default() -> Self {
+        Self {
+            keep_daily: DEFAULT_KEEP_DAILY,
+            keep_weekly: DEFAULT_KEEP_WEEKLY,
+        }
+    }
+}
+
+/// A copy of the database kept in the snapshots directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbSnapshot {
+    pub path: PathBuf,
+    pub taken: DateTime<Local>,
+    pub size: u64, // Bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+/// Outcome of merging one project into another
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MergeStats {
+    pub sections: usize,        // Sections moved to the target
+    pub sections_merged: usize, // Sections appended to a target section of the same type
+    pub sessions: usize,
+    pub facts: usize,
+    pub duplicate_facts: usize, // Facts the target already had, dropped
+}
+
+impl MergeStats {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} sections ({} merged), {} sessions, {} facts ({} duplicates dropped)",
+            self.sections + self.sections_merged,
+            self.sections_merged,
+            self.sessions,
+            self.facts,
+            self.duplicate_facts
+        )
+    }
+}
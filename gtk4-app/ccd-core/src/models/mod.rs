@@ -0,0 +1,49 @@
+pub mod project;
+pub mod context_section;
+pub mod session;
+pub mod fact;
+pub mod project_dependency;
+pub mod workspace;
+pub mod trash;
+pub mod audit;
+pub mod processed_log;
+pub mod settings;
+pub mod replica;
+pub mod stats;
+pub mod sync_status;
+pub mod merge;
+pub mod attachment;
+pub mod benchmark;
+pub mod section_revision;
+pub mod maintenance;
+pub mod event_record;
+pub mod triage;
+pub mod search;
+pub mod session_comparison;
+pub mod timeline;
+pub mod section_template;
+
+pub use project::*;
+pub use context_section::*;
+pub use session::*;
+pub use fact::*;
+pub use project_dependency::*;
+pub use workspace::*;
+pub use trash::*;
+pub use audit::*;
+pub use processed_log::*;
+pub use settings::*;
+pub use replica::*;
+pub use stats::*;
+pub use sync_status::*;
+pub use merge::*;
+pub use attachment::*;
+pub use benchmark::*;
+pub use section_revision::*;
+pub use maintenance::*;
+pub use event_record::*;
+pub use triage::*;
+pub use search::*;
+pub use session_comparison::*;
+pub use timeline::*;
+pub use section_template::*;
@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A conversation log file the monitor has already ingested
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessedLog {
+    pub project: String,
+    pub path: String,
+    pub size: i64,
+    pub modified: DateTime<Utc>, // File mtime when it was processed
+    pub content_hash: String,    // Hash of the first `offset` bytes
+    pub session: Option<String>, // Session created from this file
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    pub offset: i64, // Bytes processed so far; appended lines start here
+    pub message_count: Option<i64>, // Messages in those bytes; None if recorded by an older version
+}
+
+impl ProcessedLog {
+    /// Check if the file on disk still matches what was processed (cheap stat check)
+    pub fn matches_stat(&self, size: i64, modified: DateTime<Utc>) -> bool {
+        self.size == size && self.modified == modified
+    }
+}
+
+/// Request payload for recording a processed log file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProcessedLogPayload {
+    pub project: String,
+    pub path: String,
+    pub size: i64,
+    pub modified: DateTime<Utc>,
+    pub content_hash: String,
+    pub session: Option<String>,
+    pub offset: i64,
+    pub message_count: Option<i64>,
+}
@@ -1,8 +1,9 @@
+use crate::models::{ExtractedFactPayload, FactType};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
 /// Project status enumeration
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectStatus {
     Active,
@@ -47,6 +48,44 @@ impl std::fmt::Display for ProjectStatus {
     }
 }
 
+/// Order of the project list (pinned projects always come first)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectSort {
+    #[default]
+    Updated,     // Most recently updated first
+    Name,        // Alphabetical, ignoring case
+    Priority,    // Highest priority first
+    TokenUsage,  // Most tokens across all sessions first
+    LastSession, // Most recently started session first
+}
+
+impl ProjectSort {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Updated => "updated",
+            Self::Name => "name",
+            Self::Priority => "priority",
+            Self::TokenUsage => "token_usage",
+            Self::LastSession => "last_session",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Updated => "Recently Updated",
+            Self::Name => "Name",
+            Self::Priority => "Priority",
+            Self::TokenUsage => "Token Usage",
+            Self::LastSession => "Last Session",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::Updated, Self::Name, Self::Priority, Self::TokenUsage, Self::LastSession]
+    }
+}
+
 /// Project model representing a development project
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -58,6 +97,8 @@ pub struct Project {
     pub priority: i32,
     pub tech_stack: Vec<String>,
     pub description: Option<String>,
+    #[serde(default)]
+    pub pinned: bool, // Listed before all other projects
     pub created: DateTime<Utc>,
     pub updated: DateTime<Utc>,
 }
@@ -75,6 +116,7 @@ impl Project {
             priority: 0,
             tech_stack: Vec::new(),
             description: None,
+            pinned: false,
             created: Utc::now(),
             updated: Utc::now(),
         }
@@ -127,3 +169,42 @@ impl From<&Project> for ProjectPayload {
         }
     }
 }
+
+/// Which extracted facts a project keeps
+///
+/// A docs-only project can turn off file changes, a busy one raise the importance
+/// floor. Facts added by hand are not affected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionPreferences {
+    pub disabled_types: Vec<FactType>, // Fact types never extracted for the project
+    pub min_importance: i32,           // 1-5, less important facts are dropped
+}
+
+impl Default for ExtractionPreferences {
+    fn default() -> Self {
+        Self {
+            disabled_types: Vec::new(),
+            min_importance: 1,
+        }
+    }
+}
+
+impl ExtractionPreferences {
+    /// Check if facts of a type are extracted
+    pub fn is_enabled(&self, fact_type: FactType) -> bool {
+        !self.disabled_types.contains(&fact_type)
+    }
+
+    /// Turn extraction of a fact type on or off
+    pub fn set_enabled(&mut self, fact_type: FactType, enabled: bool) {
+        self.disabled_types.retain(|disabled| *disabled != fact_type);
+        if !enabled {
+            self.disabled_types.push(fact_type);
+        }
+    }
+
+    /// Check if an extracted fact is kept
+    pub fn allows(&self, fact: &ExtractedFactPayload) -> bool {
+        self.is_enabled(fact.fact_type) && fact.importance >= self.min_importance
+    }
+}
@@ -0,0 +1,49 @@
+use crate::models::{ExtractedFact, Project};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Dependency between two tracked projects (e.g. an app and its shared library)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDependency {
+    pub id: String,
+    pub project: String,    // Dependent project ID
+    pub depends_on: String, // Project ID that `project` depends on
+    pub include_summary: bool,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+/// Request payload for creating/updating project dependencies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectDependencyPayload {
+    pub project: String,
+    pub depends_on: String,
+    /// Include a condensed summary of the dependency's key decisions in generated context
+    pub include_summary: bool,
+}
+
+impl From<&ProjectDependency> for ProjectDependencyPayload {
+    fn from(dependency: &ProjectDependency) -> Self {
+        Self {
+            project: dependency.project.clone(),
+            depends_on: dependency.depends_on.clone(),
+            include_summary: dependency.include_summary,
+        }
+    }
+}
+
+/// A dependency project together with the facts relevant to its dependents
+#[derive(Debug, Clone)]
+pub struct DependencyContext {
+    pub project: Project,
+    pub include_summary: bool,
+    pub decisions: Vec<ExtractedFact>,
+    pub open_blockers: Vec<ExtractedFact>,
+}
+
+impl DependencyContext {
+    /// Check if the dependency has unresolved blockers
+    pub fn has_open_blockers(&self) -> bool {
+        !self.open_blockers.is_empty()
+    }
+}
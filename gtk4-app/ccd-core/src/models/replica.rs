@@ -0,0 +1,30 @@
+use crate::models::{ContextSection, ExtractedFact, Project, SessionHistory};
+use serde::{Deserialize, Serialize};
+
+/// Full copy of the replicated collections fetched from PocketBase
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplicaSnapshot {
+    pub projects: Vec<Project>,
+    pub sections: Vec<ContextSection>,
+    pub sessions: Vec<SessionHistory>,
+    pub facts: Vec<ExtractedFact>,
+}
+
+/// Outcome of applying a snapshot to the local database
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplicaStats {
+    pub projects: usize,
+    pub sections: usize,
+    pub sessions: usize,
+    pub facts: usize,
+    pub removed: usize, // Local records no longer present upstream
+}
+
+impl ReplicaStats {
+    pub fn summary(&self) -> String {
+        format!(
+            "{} projects, {} sections, {} sessions, {} facts ({} removed)",
+            self.projects, self.sections, self.sessions, self.facts, self.removed
+        )
+    }
+}
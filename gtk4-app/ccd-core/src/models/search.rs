@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of record a search result points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchKind {
+    Project,
+    Section,
+    Fact,
+}
+
+impl SearchKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SearchKind::Project => "project",
+            SearchKind::Section => "section",
+            SearchKind::Fact => "fact",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            SearchKind::Project => "Project",
+            SearchKind::Section => "Section",
+            SearchKind::Fact => "Fact",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            SearchKind::Project => "folder-symbolic",
+            SearchKind::Section => "text-x-generic-symbolic",
+            SearchKind::Fact => "starred-symbolic",
+        }
+    }
+}
+
+/// A live project, section or fact matching a search
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResult {
+    pub kind: SearchKind,
+    pub id: String,
+    pub project: String, // Owning project ID (the project itself for projects)
+    pub project_name: String,
+    pub title: String,   // Project name, section title or fact content
+}
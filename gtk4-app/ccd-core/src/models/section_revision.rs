@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Earlier version of a context section, recorded whenever its content changes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionRevision {
+    pub id: String,
+    pub section: String,
+    pub title: String,
+    pub content: String,
+    pub created: DateTime<Utc>, // When this version was replaced
+}
+
+impl SectionRevision {
+    /// Get a preview of the content (first 100 chars)
+    pub fn content_preview(&self) -> String {
+        match self.content.char_indices().nth(97) {
+            Some((end, _)) if self.content.chars().count() > 100 => format!("{}...", &self.content[..end]),
+            _ => self.content.clone(),
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::models::{ContextSection, SectionType};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A section's type, title and outline saved under a name, to start new
+/// sections from in any project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionTemplate {
+    pub id: String,
+    pub name: String,
+    pub section_type: SectionType,
+    pub title: String,
+    pub content: String,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+/// Request payload for saving section templates
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SectionTemplatePayload {
+    pub name: String,
+    pub section_type: SectionType,
+    pub title: String,
+    pub content: String,
+}
+
+impl SectionTemplatePayload {
+    /// Template named `name` starting sections like `section`
+    pub fn from_section(name: &str, section: &ContextSection) -> Self {
+        Self {
+            name: name.to_string(),
+            section_type: section.section_type,
+            title: section.title.clone(),
+            content: section.content.clone(),
+        }
+    }
+}
+
+impl From<&SectionTemplate> for SectionTemplatePayload {
+    fn from(template: &SectionTemplate) -> Self {
+        Self {
+            name: template.name.clone(),
+            section_type: template.section_type,
+            title: template.title.clone(),
+            content: template.content.clone(),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+use chrono::{DateTime, Datelike, Local, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Session history model representing a Claude Code conversation session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionHistory {
+    pub id: String,
+    pub project: String, // Project ID
+    pub summary: String,
+    pub facts_extracted: i32,
+    pub token_count: i64,
+    pub session_start: DateTime<Utc>,
+    pub session_end: Option<DateTime<Utc>>,
+    pub created: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+    #[serde(default)]
+    pub conversation_id: Option<String>, // Claude Code session ID of the transcript
+    #[serde(default)]
+    pub git_branch: Option<String>, // Branch checked out when the session started
+    #[serde(default)]
+    pub git_head: Option<String>, // Commit checked out when the session started
+    #[serde(default)]
+    pub usage: SessionUsage,
+}
+
+impl SessionHistory {
+    /// Create a new session
+    pub fn new(project_id: String, summary: String) -> Self {
+        Self {
+            id: String::new(), // Will be set by PocketBase
+            project: project_id,
+            summary,
+            facts_extracted: 0,
+            token_count: 0,
+            session_start: Utc::now(),
+            session_end: None,
+            created: Utc::now(),
+            updated: Utc::now(),
+            conversation_id: None,
+            git_branch: None,
+            git_head: None,
+            usage: SessionUsage::default(),
+        }
+    }
+
+    /// Get session duration as a human-readable string
+    pub fn duration_display(&self) -> String {
+        if let Some(end) = self.session_end {
+            format_duration(end.signed_duration_since(self.session_start))
+        } else {
+            String::from("In progress")
+        }
+    }
+
+    /// Get token usage percentage (out of 200K context window)
+    pub fn token_percentage(&self) -> f64 {
+        const MAX_TOKENS: f64 = 200_000.0;
+        (self.token_count as f64 / MAX_TOKENS) * 100.0
+    }
+
+    /// Format token count with thousands separator
+    pub fn token_count_display(&self) -> String {
+        format_number_with_separator(self.token_count)
+    }
+
+    /// Check if approaching context limit (> 85%)
+    pub fn is_near_limit(&self) -> bool {
+        self.token_percentage() > 85.0
+    }
+
+    /// Check if session is active (no end time)
+    pub fn is_active(&self) -> bool {
+        self.session_end.is_none()
+    }
+
+    /// Local calendar day the session started on
+    pub fn start_day(&self) -> NaiveDate {
+        self.session_start.with_timezone(&Local).date_naive()
+    }
+
+    /// Where the session started in git, e.g. "main @ 3f2a9c1"
+    pub fn git_display(&self) -> Option<String> {
+        let head = short_sha(self.git_head.as_deref()?);
+        Some(match &self.git_branch {
+            Some(branch) => format!("{} @ {}", branch, head),
+            None => head.to_string(),
+        })
+    }
+}
+
+/// Tokens a session was billed for, from the usage its transcript reports, and their cost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionUsage {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_write_tokens: i64,
+    pub cache_read_tokens: i64,
+    pub cost_usd: f64, // At the prices configured when the usage was recorded
+}
+
+impl SessionUsage {
+    /// Usage of both together
+    pub fn plus(&self, other: &SessionUsage) -> SessionUsage {
+        SessionUsage {
+            input_tokens: self.input_tokens + other.input_tokens,
+            output_tokens: self.output_tokens + other.output_tokens,
+            cache_write_tokens: self.cache_write_tokens + other.cache_write_tokens,
+            cache_read_tokens: self.cache_read_tokens + other.cache_read_tokens,
+            cost_usd: self.cost_usd + other.cost_usd,
+        }
+    }
+
+    /// All tokens billed, cached ones included
+    pub fn total_tokens(&self) -> i64 {
+        self.input_tokens + self.output_tokens + self.cache_write_tokens + self.cache_read_tokens
+    }
+
+    pub fn cost_display(&self) -> String {
+        format_cost(self.cost_usd)
+    }
+}
+
+/// Format a cost in US dollars, e.g. "$1.24" or "<$0.01"
+pub fn format_cost(usd: f64) -> String {
+    if usd > 0.0 && usd < 0.01 {
+        "<$0.01".to_string()
+    } else {
+        format!("${:.2}", usd)
+    }
+}
+
+/// Commits shown by hash in a one-line summary before the rest are elided
+const COMMITS_SHOWN: usize = 3;
+
+/// A commit made in the project's repository while a session ran
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionCommit {
+    pub sha: String,
+    pub summary: String, // First line of the commit message
+    pub author: String,
+    pub committed: DateTime<Utc>,
+}
+
+impl SessionCommit {
+    pub fn short_sha(&self) -> &str {
+        short_sha(&self.sha)
+    }
+}
+
+/// Abbreviated commit hash, as git shows it
+pub fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+/// One-line summary of a session's commits, e.g. "2 commits: 3f2a9c1 b81e004"
+pub fn commits_display(commits: &[SessionCommit]) -> Option<String> {
+    if commits.is_empty() {
+        return None;
+    }
+
+    let noun = if commits.len() == 1 { "commit" } else { "commits" };
+    let mut shas: Vec<&str> = commits.iter().take(COMMITS_SHOWN).map(SessionCommit::short_sha).collect();
+    if commits.len() > COMMITS_SHOWN {
+        shas.push("…");
+    }
+    Some(format!("{} {}: {}", commits.len(), noun, shas.join(" ")))
+}
+
+/// Compressed copy of a session's transcript kept in the database
+///
+/// Outlives the agent's own log, which may be rotated or deleted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionTranscript {
+    pub path: String, // Where the transcript was read from
+    pub size: i64,    // Bytes before compression
+    pub compressed_size: i64,
+    pub updated: DateTime<Utc>,
+}
+
+/// Request payload for creating/updating sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionPayload {
+    pub project: String,
+    pub summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub facts_extracted: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_start: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_end: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conversation_id: Option<String>,
+}
+
+impl From<&SessionHistory> for SessionPayload {
+    fn from(session: &SessionHistory) -> Self {
+        Self {
+            project: session.project.clone(),
+            summary: session.summary.clone(),
+            facts_extracted: Some(session.facts_extracted),
+            token_count: Some(session.token_count),
+            session_start: Some(session.session_start),
+            session_end: session.session_end,
+            conversation_id: session.conversation_id.clone(),
+        }
+    }
+}
+
+/// Heading for the sessions of `day`: "Today", "Yesterday", or the date itself
+pub fn day_label(day: NaiveDate, today: NaiveDate) -> String {
+    if day == today {
+        "Today".to_string()
+    } else if today.pred_opt() == Some(day) {
+        "Yesterday".to_string()
+    } else if day.year() == today.year() {
+        day.format("%A, %B %-d").to_string()
+    } else {
+        day.format("%B %-d, %Y").to_string()
+    }
+}
+
+/// Format a duration as hours and minutes, e.g. "1h 5m" or "12m"
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let hours = duration.num_hours();
+    let minutes = duration.num_minutes() % 60;
+
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Format a number with thousands separators, e.g. "12,345"
+pub fn format_number_with_separator(num: i64) -> String {
+    let num_str = num.to_string();
+    let mut result = String::new();
+    let mut count = 0;
+
+    for c in num_str.chars().rev() {
+        if count > 0 && count % 3 == 0 {
+            result.push(',');
+        }
+        result.push(c);
+        count += 1;
+    }
+
+    result.chars().rev().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_percentage() {
+        let mut session = SessionHistory::new("test".to_string(), "Test".to_string());
+        session.token_count = 100_000;
+        assert_eq!(session.token_percentage(), 50.0);
+
+        session.token_count = 170_000;
+        assert_eq!(session.token_percentage(), 85.0);
+        assert!(session.is_near_limit());
+    }
+
+    #[test]
+    fn test_session_usage() {
+        let first = SessionUsage {
+            input_tokens: 1_200,
+            output_tokens: 800,
+            cache_write_tokens: 0,
+            cache_read_tokens: 20_000,
+            cost_usd: 0.0246,
+        };
+        let total = first.plus(&SessionUsage {
+            output_tokens: 200,
+            cost_usd: 1.5,
+            ..Default::default()
+        });
+        assert_eq!(total.total_tokens(), 22_200);
+        assert_eq!(total.cost_display(), "$1.52");
+
+        assert_eq!(format_cost(0.0), "$0.00");
+        assert_eq!(format_cost(0.004), "<$0.01");
+    }
+
+    #[test]
+    fn test_day_label() {
+        let today = NaiveDate::from_ymd_opt(2026, 3, 2).unwrap();
+        assert_eq!(day_label(today, today), "Today");
+        assert_eq!(day_label(NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(), today), "Yesterday");
+        assert_eq!(day_label(NaiveDate::from_ymd_opt(2026, 2, 26).unwrap(), today), "Thursday, February 26");
+        assert_eq!(day_label(NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(), today), "December 31, 2025");
+    }
+
+    #[test]
+    fn test_format_number() {
+        assert_eq!(format_number_with_separator(1000), "1,000");
+        assert_eq!(format_number_with_separator(100000), "100,000");
+        assert_eq!(format_number_with_separator(1234567), "1,234,567");
+    }
+
+    #[test]
+    fn test_commits_display() {
+        let commit = |sha: &str| SessionCommit {
+            sha: sha.to_string(),
+            summary: "Store cents".to_string(),
+            author: "Dev".to_string(),
+            committed: Utc::now(),
+        };
+
+        assert_eq!(commits_display(&[]), None);
+        assert_eq!(commits_display(&[commit("3f2a9c1d0e")]).as_deref(), Some("1 commit: 3f2a9c1"));
+        let commits: Vec<_> = ["aaaaaaa1", "bbbbbbb2", "ccccccc3", "ddddddd4"].into_iter().map(commit).collect();
+        assert_eq!(commits_display(&commits).as_deref(), Some("4 commits: aaaaaaa bbbbbbb ccccccc …"));
+
+        let mut session = SessionHistory::new("test".to_string(), "Test".to_string());
+        assert_eq!(session.git_display(), None);
+        session.git_head = Some("3f2a9c1d0e".to_string());
+        assert_eq!(session.git_display().as_deref(), Some("3f2a9c1"));
+        session.git_branch = Some("main".to_string());
+        assert_eq!(session.git_display().as_deref(), Some("main @ 3f2a9c1"));
+    }
+}
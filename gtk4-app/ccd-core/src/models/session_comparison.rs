@@ -0,0 +1,38 @@
+use crate::models::{ExtractedFact, SessionHistory};
+use serde::{Deserialize, Serialize};
+
+/// A context section as it read after each of two sessions
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SectionChange {
+    pub section: String, // Section ID
+    pub title: String,
+    pub before: Option<String>, // None if the section didn't exist yet
+    pub after: Option<String>,  // None if it was deleted in between
+}
+
+/// What changed in a project from the end of one session to the end of a later one
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionComparison {
+    pub from: SessionHistory,
+    pub to: SessionHistory,
+    pub added_facts: Vec<ExtractedFact>,   // Created in between, most important first
+    pub removed_facts: Vec<ExtractedFact>, // Moved to the trash in between
+    pub changed_sections: Vec<SectionChange>,
+}
+
+impl SessionComparison {
+    /// Tokens the later session used more (or fewer) than the earlier one
+    pub fn token_delta(&self) -> i64 {
+        self.to.token_count - self.from.token_count
+    }
+
+    /// Facts the later session extracted more (or fewer) than the earlier one
+    pub fn fact_delta(&self) -> i32 {
+        self.to.facts_extracted - self.from.facts_extracted
+    }
+
+    /// Check if anything in the project changed between the sessions
+    pub fn has_changes(&self) -> bool {
+        !self.added_facts.is_empty() || !self.removed_facts.is_empty() || !self.changed_sections.is_empty()
+    }
+}
@@ -0,0 +1,428 @@
+use crate::models::{ProjectSort, RetentionPolicy, DEFAULT_KEEP_DAILY, DEFAULT_KEEP_WEEKLY};
+use serde::{Deserialize, Serialize};
+
+/// Default token count at which the context size warning fires
+pub const DEFAULT_TOKEN_THRESHOLD: i64 = 170_000;
+
+/// Default minutes without new transcript lines after which a session counts as ended
+pub const DEFAULT_SESSION_IDLE_MINUTES: u64 = 30;
+
+/// Default number of facts kept from one session, the most important first
+pub const DEFAULT_MAX_SESSION_FACTS: usize = 50;
+
+/// Default seconds between PocketBase replication runs
+pub const DEFAULT_REPLICA_INTERVAL_SECS: u64 = 300;
+
+/// Setting keys as stored in the `app_settings` table
+pub const SETTING_TOKEN_THRESHOLD: &str = "token_threshold";
+pub const SETTING_LOGS_DIR: &str = "logs_dir";
+pub const SETTING_THEME: &str = "theme";
+pub const SETTING_AUTOSTART_MONITORING: &str = "autostart_monitoring";
+pub const SETTING_REPLICA_URL: &str = "replica_url";
+pub const SETTING_REPLICA_INTERVAL: &str = "replica_interval_secs";
+pub const SETTING_MONITOR_AGGRESSIVENESS: &str = "monitor_aggressiveness";
+pub const SETTING_SESSION_IDLE_MINUTES: &str = "session_idle_minutes";
+pub const SETTING_MAX_SESSION_FACTS: &str = "max_session_facts";
+pub const SETTING_ARCHIVE_TRANSCRIPTS: &str = "archive_transcripts";
+pub const SETTING_LLM_PROVIDER: &str = "llm_provider";
+pub const SETTING_LLM_MODEL: &str = "llm_model";
+pub const SETTING_LLM_ENDPOINT: &str = "llm_endpoint";
+pub const SETTING_SNAPSHOTS_ENABLED: &str = "snapshots_enabled";
+pub const SETTING_SNAPSHOT_KEEP_DAILY: &str = "snapshot_keep_daily";
+pub const SETTING_SNAPSHOT_KEEP_WEEKLY: &str = "snapshot_keep_weekly";
+pub const SETTING_DASHBOARD_LAYOUT: &str = "dashboard_layout";
+pub const SETTING_DASHBOARD_SORT: &str = "dashboard_sort";
+/// Written by the replicator after each successful run, not part of `AppSettings`
+pub const SETTING_REPLICA_LAST_SYNCED: &str = "replica_last_synced";
+
+/// Preferred application color scheme
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemePreference {
+    #[default]
+    System,
+    Light,
+    Dark,
+}
+
+impl ThemePreference {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::System => "system",
+            Self::Light => "light",
+            Self::Dark => "dark",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::System => "System Default",
+            Self::Light => "Light",
+            Self::Dark => "Dark",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::System, Self::Light, Self::Dark]
+    }
+}
+
+/// How the dashboard shows the projects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardLayout {
+    #[default]
+    List,
+    Grid,  // Cards with status, priority and a token trend
+    Board, // Card columns by status
+}
+
+impl DashboardLayout {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::List => "list",
+            Self::Grid => "grid",
+            Self::Board => "board",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::List => "List",
+            Self::Grid => "Cards",
+            Self::Board => "Board",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::List => "view-list-symbolic",
+            Self::Grid => "view-grid-symbolic",
+            Self::Board => "view-columns-symbolic",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::List, Self::Grid, Self::Board]
+    }
+}
+
+/// How much CPU, memory and I/O the background monitor may use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackgroundAggressiveness {
+    Low,
+    #[default]
+    Normal,
+    High,
+}
+
+impl BackgroundAggressiveness {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Low => "Low",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::Low, Self::Normal, Self::High]
+    }
+}
+
+/// LLM service used to summarize sessions and extract facts the regexes miss
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LlmProvider {
+    Anthropic,
+    OpenAi,
+    Ollama,
+}
+
+impl LlmProvider {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Anthropic => "anthropic",
+            Self::OpenAi => "openai",
+            Self::Ollama => "ollama",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Anthropic => "Anthropic",
+            Self::OpenAi => "OpenAI",
+            Self::Ollama => "Ollama (local)",
+        }
+    }
+
+    /// Model used when none is configured
+    pub fn default_model(&self) -> &str {
+        match self {
+            Self::Anthropic => "claude-3-5-haiku-latest",
+            Self::OpenAi => "gpt-4o-mini",
+            Self::Ollama => "llama3.1",
+        }
+    }
+
+    /// API endpoint used when none is configured
+    pub fn default_endpoint(&self) -> &str {
+        match self {
+            Self::Anthropic => "https://api.anthropic.com/v1/messages",
+            Self::OpenAi => "https://api.openai.com/v1/chat/completions",
+            Self::Ollama => "http://localhost:11434/api/chat",
+        }
+    }
+
+    /// Whether requests need an API key (a local Ollama server doesn't)
+    pub fn needs_api_key(&self) -> bool {
+        !matches!(self, Self::Ollama)
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::Anthropic, Self::OpenAi, Self::Ollama]
+    }
+}
+
+/// Application preferences shared by the GUI, CLI and monitor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AppSettings {
+    pub token_threshold: i64,
+    pub logs_dir: Option<String>, // None = ~/.claude/projects (per project when it has a repo path)
+    pub theme: ThemePreference,
+    pub autostart_monitoring: bool,
+    pub replica_url: Option<String>, // PocketBase server mirrored read-only, if any
+    pub replica_interval_secs: u64,
+    pub monitor_aggressiveness: BackgroundAggressiveness,
+    pub session_idle_minutes: u64, // 0 = sessions are never ended for being idle
+    pub max_session_facts: usize,  // 0 = no limit
+    pub archive_transcripts: bool, // Keep a compressed copy of each transcript in the database
+    pub llm_provider: Option<LlmProvider>, // None = regex extraction only
+    pub llm_model: Option<String>,         // None = the provider's default model
+    pub llm_endpoint: Option<String>,      // None = the provider's public API (or local Ollama)
+    pub snapshots_enabled: bool,           // Nightly database snapshots
+    pub snapshot_keep_daily: usize,
+    pub snapshot_keep_weekly: usize,
+    pub dashboard_layout: DashboardLayout,
+    pub dashboard_sort: ProjectSort,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            token_threshold: DEFAULT_TOKEN_THRESHOLD,
+            logs_dir: None,
+            theme: ThemePreference::default(),
+            autostart_monitoring: false,
+            replica_url: None,
+            replica_interval_secs: DEFAULT_REPLICA_INTERVAL_SECS,
+            monitor_aggressiveness: BackgroundAggressiveness::default(),
+            session_idle_minutes: DEFAULT_SESSION_IDLE_MINUTES,
+            max_session_facts: DEFAULT_MAX_SESSION_FACTS,
+            archive_transcripts: false,
+            llm_provider: None,
+            llm_model: None,
+            llm_endpoint: None,
+            snapshots_enabled: true,
+            snapshot_keep_daily: DEFAULT_KEEP_DAILY,
+            snapshot_keep_weekly: DEFAULT_KEEP_WEEKLY,
+            dashboard_layout: DashboardLayout::default(),
+            dashboard_sort: ProjectSort::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Whether the local database is a read-only mirror of a PocketBase server
+    pub fn is_replica(&self) -> bool {
+        self.replica_url.is_some()
+    }
+
+    /// How many nightly snapshots to keep
+    pub fn snapshot_retention(&self) -> RetentionPolicy {
+        RetentionPolicy {
+            keep_daily: self.snapshot_keep_daily,
+            keep_weekly: self.snapshot_keep_weekly,
+        }
+    }
+
+    /// Apply a stored key/value pair; unknown keys and unparsable values are ignored
+    pub fn apply(&mut self, key: &str, value: &str) {
+        match key {
+            SETTING_TOKEN_THRESHOLD => {
+                if let Ok(threshold) = value.parse() {
+                    self.token_threshold = threshold;
+                }
+            }
+            SETTING_LOGS_DIR => {
+                self.logs_dir = Some(value.to_string()).filter(|dir| !dir.is_empty());
+            }
+            SETTING_THEME => {
+                if let Some(theme) = ThemePreference::all().into_iter().find(|t| t.as_str() == value) {
+                    self.theme = theme;
+                }
+            }
+            SETTING_AUTOSTART_MONITORING => {
+                if let Ok(enabled) = value.parse() {
+                    self.autostart_monitoring = enabled;
+                }
+            }
+            SETTING_REPLICA_URL => {
+                self.replica_url = Some(value.to_string()).filter(|url| !url.is_empty());
+            }
+            SETTING_REPLICA_INTERVAL => {
+                if let Ok(secs) = value.parse() {
+                    self.replica_interval_secs = secs;
+                }
+            }
+            SETTING_MONITOR_AGGRESSIVENESS => {
+                if let Some(level) = BackgroundAggressiveness::all().into_iter().find(|l| l.as_str() == value) {
+                    self.monitor_aggressiveness = level;
+                }
+            }
+            SETTING_SESSION_IDLE_MINUTES => {
+                if let Ok(minutes) = value.parse() {
+                    self.session_idle_minutes = minutes;
+                }
+            }
+            SETTING_MAX_SESSION_FACTS => {
+                if let Ok(max) = value.parse() {
+                    self.max_session_facts = max;
+                }
+            }
+            SETTING_ARCHIVE_TRANSCRIPTS => {
+                if let Ok(enabled) = value.parse() {
+                    self.archive_transcripts = enabled;
+                }
+            }
+            SETTING_LLM_PROVIDER => {
+                if value.is_empty() {
+                    self.llm_provider = None;
+                } else if let Some(provider) = LlmProvider::all().into_iter().find(|p| p.as_str() == value) {
+                    self.llm_provider = Some(provider);
+                }
+            }
+            SETTING_LLM_MODEL => {
+                self.llm_model = Some(value.to_string()).filter(|model| !model.is_empty());
+            }
+            SETTING_LLM_ENDPOINT => {
+                self.llm_endpoint = Some(value.to_string()).filter(|url| !url.is_empty());
+            }
+            SETTING_SNAPSHOTS_ENABLED => {
+                if let Ok(enabled) = value.parse() {
+                    self.snapshots_enabled = enabled;
+                }
+            }
+            SETTING_SNAPSHOT_KEEP_DAILY => {
+                if let Ok(days) = value.parse() {
+                    self.snapshot_keep_daily = days;
+                }
+            }
+            SETTING_SNAPSHOT_KEEP_WEEKLY => {
+                if let Ok(weeks) = value.parse() {
+                    self.snapshot_keep_weekly = weeks;
+                }
+            }
+            SETTING_DASHBOARD_LAYOUT => {
+                if let Some(layout) = DashboardLayout::all().into_iter().find(|l| l.as_str() == value) {
+                    self.dashboard_layout = layout;
+                }
+            }
+            SETTING_DASHBOARD_SORT => {
+                if let Some(sort) = ProjectSort::all().into_iter().find(|s| s.as_str() == value) {
+                    self.dashboard_sort = sort;
+                }
+            }
+            _ => log::debug!("Ignoring unknown setting: {}", key),
+        }
+    }
+
+    /// Key/value pairs to persist
+    pub fn entries(&self) -> Vec<(&'static str, String)> {
+        vec![
+            (SETTING_TOKEN_THRESHOLD, self.token_threshold.to_string()),
+            (SETTING_LOGS_DIR, self.logs_dir.clone().unwrap_or_default()),
+            (SETTING_THEME, self.theme.as_str().to_string()),
+            (SETTING_AUTOSTART_MONITORING, self.autostart_monitoring.to_string()),
+            (SETTING_REPLICA_URL, self.replica_url.clone().unwrap_or_default()),
+            (SETTING_REPLICA_INTERVAL, self.replica_interval_secs.to_string()),
+            (SETTING_MONITOR_AGGRESSIVENESS, self.monitor_aggressiveness.as_str().to_string()),
+            (SETTING_SESSION_IDLE_MINUTES, self.session_idle_minutes.to_string()),
+            (SETTING_MAX_SESSION_FACTS, self.max_session_facts.to_string()),
+            (SETTING_ARCHIVE_TRANSCRIPTS, self.archive_transcripts.to_string()),
+            (
+                SETTING_LLM_PROVIDER,
+                self.llm_provider.map(|p| p.as_str().to_string()).unwrap_or_default(),
+            ),
+            (SETTING_LLM_MODEL, self.llm_model.clone().unwrap_or_default()),
+            (SETTING_LLM_ENDPOINT, self.llm_endpoint.clone().unwrap_or_default()),
+            (SETTING_SNAPSHOTS_ENABLED, self.snapshots_enabled.to_string()),
+            (SETTING_SNAPSHOT_KEEP_DAILY, self.snapshot_keep_daily.to_string()),
+            (SETTING_SNAPSHOT_KEEP_WEEKLY, self.snapshot_keep_weekly.to_string()),
+            (SETTING_DASHBOARD_LAYOUT, self.dashboard_layout.as_str().to_string()),
+            (SETTING_DASHBOARD_SORT, self.dashboard_sort.as_str().to_string()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_roundtrip() {
+        let settings = AppSettings {
+            token_threshold: 150_000,
+            logs_dir: Some("/tmp/logs".to_string()),
+            theme: ThemePreference::Dark,
+            autostart_monitoring: true,
+            replica_url: Some("http://localhost:8090".to_string()),
+            replica_interval_secs: 60,
+            monitor_aggressiveness: BackgroundAggressiveness::Low,
+            session_idle_minutes: 45,
+            max_session_facts: 20,
+            archive_transcripts: true,
+            llm_provider: Some(LlmProvider::Ollama),
+            llm_model: Some("qwen2.5".to_string()),
+            llm_endpoint: Some("http://gpu-box:11434/api/chat".to_string()),
+            snapshots_enabled: false,
+            snapshot_keep_daily: 14,
+            snapshot_keep_weekly: 8,
+            dashboard_layout: DashboardLayout::Board,
+            dashboard_sort: ProjectSort::TokenUsage,
+        };
+
+        let mut loaded = AppSettings::default();
+        for (key, value) in settings.entries() {
+            loaded.apply(key, &value);
+        }
+
+        assert_eq!(loaded, settings);
+    }
+
+    #[test]
+    fn test_settings_ignore_invalid_values() {
+        let mut settings = AppSettings::default();
+        settings.apply(SETTING_TOKEN_THRESHOLD, "lots");
+        settings.apply(SETTING_THEME, "neon");
+        settings.apply(SETTING_REPLICA_INTERVAL, "-5");
+        settings.apply(SETTING_MONITOR_AGGRESSIVENESS, "turbo");
+        settings.apply(SETTING_SESSION_IDLE_MINUTES, "soon");
+        settings.apply(SETTING_LLM_PROVIDER, "skynet");
+        settings.apply(SETTING_SNAPSHOT_KEEP_DAILY, "all");
+        settings.apply(SETTING_DASHBOARD_LAYOUT, "mosaic");
+        settings.apply(SETTING_DASHBOARD_SORT, "random");
+        settings.apply("unknown", "value");
+
+        assert_eq!(settings, AppSettings::default());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::models::{FactType, ProjectStatus, SessionUsage};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Days covered by a project's token trend
+pub const TOKEN_TREND_DAYS: usize = 14;
+
+/// Weeks covered by the token usage charts
+pub const TOKEN_HISTORY_WEEKS: usize = 12;
+
+/// Aggregate activity for a single project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectStats {
+    pub project: String,
+    pub session_count: usize,
+    pub total_tokens: i64, // Sum over all sessions
+    pub usage: SessionUsage, // Billed tokens and cost, summed over all sessions
+    pub fact_count: usize, // Live facts, stale included
+    pub stale_facts: usize,
+    pub facts_by_type: HashMap<FactType, usize>,
+    pub last_activity: Option<DateTime<Utc>>, // Latest update to the project or anything in it
+    pub token_trend: Vec<i64>, // Session tokens per day (UTC) over the last TOKEN_TREND_DAYS days, oldest first
+}
+
+impl ProjectStats {
+    pub fn new(project_id: &str) -> Self {
+        Self {
+            project: project_id.to_string(),
+            token_trend: vec![0; TOKEN_TREND_DAYS],
+            ..Default::default()
+        }
+    }
+
+    /// Get count for a specific fact type
+    pub fn count_for_type(&self, fact_type: FactType) -> usize {
+        self.facts_by_type.get(&fact_type).copied().unwrap_or(0)
+    }
+
+    /// Record newer activity
+    pub fn touch(&mut self, at: Option<DateTime<Utc>>) {
+        self.last_activity = self.last_activity.max(at);
+    }
+
+    /// Add the tokens of a session started `days_ago` days before today to the trend
+    pub fn add_trend_tokens(&mut self, days_ago: i64, tokens: i64) {
+        let len = self.token_trend.len() as i64;
+        if (0..len).contains(&days_ago) {
+            self.token_trend[(len - 1 - days_ago) as usize] += tokens;
+        }
+    }
+}
+
+/// Aggregate activity across all projects
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GlobalStats {
+    pub project_count: usize,
+    pub projects_by_status: HashMap<ProjectStatus, usize>,
+    pub session_count: usize,
+    pub total_tokens: i64,
+    pub usage: SessionUsage,
+    pub fact_count: usize,
+    pub stale_facts: usize,
+    pub facts_by_type: HashMap<FactType, usize>,
+    pub last_activity: Option<DateTime<Utc>>,
+}
+
+impl GlobalStats {
+    /// Get count for a specific project status
+    pub fn count_for_status(&self, status: ProjectStatus) -> usize {
+        self.projects_by_status.get(&status).copied().unwrap_or(0)
+    }
+
+    /// Get count for a specific fact type
+    pub fn count_for_type(&self, fact_type: FactType) -> usize {
+        self.facts_by_type.get(&fact_type).copied().unwrap_or(0)
+    }
+}
+
+/// Tokens of one session, as plotted on the usage charts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionTokens {
+    pub session: String,
+    pub project: String,
+    pub start: DateTime<Utc>,
+    pub tokens: i64,
+}
+
+/// Session tokens over the last few weeks, per session and summed per week
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenHistory {
+    pub first_week: NaiveDate, // Monday (UTC) the first week starts on
+    pub sessions: Vec<SessionTokens>, // Oldest first
+    pub weekly: Vec<i64>, // Tokens per week, oldest first; the last week is the current one
+}
+
+impl TokenHistory {
+    /// Empty history of `weeks` weeks, ending with the week of `today`
+    pub fn new(today: NaiveDate, weeks: usize) -> Self {
+        let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+        Self {
+            first_week: monday - chrono::Duration::weeks(weeks.saturating_sub(1) as i64),
+            sessions: Vec::new(),
+            weekly: vec![0; weeks],
+        }
+    }
+
+    /// Monday of week `index`
+    pub fn week_start(&self, index: usize) -> NaiveDate {
+        self.first_week + chrono::Duration::weeks(index as i64)
+    }
+
+    /// Add a session, ignoring those outside the covered weeks
+    pub fn add_session(&mut self, session: SessionTokens) {
+        let days = (session.start.date_naive() - self.first_week).num_days();
+        let week = usize::try_from(days.div_euclid(7)).ok();
+        let Some(week) = week.and_then(|index| self.weekly.get_mut(index)) else {
+            return;
+        };
+        *week += session.tokens;
+        self.sessions.push(session);
+    }
+
+    /// Tokens of every session in the history
+    pub fn total_tokens(&self) -> i64 {
+        self.weekly.iter().sum()
+    }
+}
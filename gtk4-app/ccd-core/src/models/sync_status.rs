@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Sync state of a single record
+///
+/// There is no conflict state yet: nothing is uploaded, so a replica pull simply
+/// overwrites local changes. One is needed once local changes can be pushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncStatus {
+    Pending, // Changed locally since it was last synced (or never synced)
+    Synced,
+}
+
+impl SyncStatus {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Pending => "pending",
+            Self::Synced => "synced",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Pending => "Pending upload",
+            Self::Synced => "Synced",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::Pending => "network-transmit-symbolic",
+            Self::Synced => "emblem-ok-symbolic",
+        }
+    }
+}
+
+/// Local changes not yet synced, for one project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PendingChanges {
+    pub project: String,
+    pub name: String,
+    pub project_pending: bool, // The project record itself changed
+    pub sections: usize,
+    pub sessions: usize,
+    pub facts: usize,
+    pub last_synced: Option<DateTime<Utc>>,
+}
+
+impl PendingChanges {
+    /// Total number of records waiting to be synced
+    pub fn total(&self) -> usize {
+        self.project_pending as usize + self.sections + self.sessions + self.facts
+    }
+
+    pub fn status(&self) -> SyncStatus {
+        if self.total() > 0 {
+            SyncStatus::Pending
+        } else {
+            SyncStatus::Synced
+        }
+    }
+}
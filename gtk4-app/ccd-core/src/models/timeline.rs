@@ -0,0 +1,42 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of event on a project's timeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimelineKind {
+    Session,
+    Fact,         // Important facts only
+    StatusChange, // The project moved between active, paused, ...
+    ContextEdit,  // A context section was added or edited
+}
+
+impl TimelineKind {
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::Session => "Session",
+            Self::Fact => "Fact",
+            Self::StatusChange => "Status",
+            Self::ContextEdit => "Context",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::Session => "chat-bubbles-symbolic",
+            Self::Fact => "starred-symbolic",
+            Self::StatusChange => "emblem-default-symbolic",
+            Self::ContextEdit => "document-edit-symbolic",
+        }
+    }
+}
+
+/// One thing that happened on a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineEntry {
+    pub kind: TimelineKind,
+    pub entity_id: String, // Session, fact, project or section ID
+    pub title: String,
+    pub detail: String,
+    pub time: DateTime<Utc>,
+}
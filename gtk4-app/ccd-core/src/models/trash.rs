@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Kind of record that can be moved to the trash
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrashKind {
+    Project,
+    Section,
+    Fact,
+}
+
+impl TrashKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            TrashKind::Project => "project",
+            TrashKind::Section => "section",
+            TrashKind::Fact => "fact",
+        }
+    }
+
+    pub fn display_name(&self) -> &str {
+        match self {
+            TrashKind::Project => "Project",
+            TrashKind::Section => "Section",
+            TrashKind::Fact => "Fact",
+        }
+    }
+
+    /// Table holding records of this kind
+    pub fn table(&self) -> &'static str {
+        match self {
+            TrashKind::Project => "projects",
+            TrashKind::Section => "context_sections",
+            TrashKind::Fact => "extracted_facts",
+        }
+    }
+}
+
+/// A soft-deleted record waiting in the trash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashedItem {
+    pub kind: TrashKind,
+    pub id: String,
+    pub project: String, // Owning project ID (the project itself for projects)
+    pub title: String,   // Project name, section title or fact content
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// What goes to the trash along with a project
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectContents {
+    pub sections: usize,
+    pub sessions: usize,
+    pub facts: usize,
+}
+
+impl ProjectContents {
+    /// e.g. "3 sections, 1 session and 12 facts"
+    pub fn summary(&self) -> String {
+        let count = |n: usize, noun: &str| format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+        format!(
+            "{}, {} and {}",
+            count(self.sections, "section"),
+            count(self.sessions, "session"),
+            count(self.facts, "fact")
+        )
+    }
+}
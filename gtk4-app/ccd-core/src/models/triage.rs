@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+/// Cross-project fact lists worked through in the daily triage loop
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriageFilter {
+    NeedsReview,   // Extracted automatically, not looked at yet
+    LowConfidence, // Not looked at yet and too uncertain to export
+    Stale,         // Marked stale, waiting to be cleaned up
+    OpenBlockers,  // Blockers that are not stale
+}
+
+impl TriageFilter {
+    pub fn display_name(&self) -> &str {
+        match self {
+            Self::NeedsReview => "Needs review",
+            Self::LowConfidence => "Low confidence",
+            Self::Stale => "Stale facts",
+            Self::OpenBlockers => "Open blockers",
+        }
+    }
+
+    pub fn icon_name(&self) -> &str {
+        match self {
+            Self::NeedsReview => "mail-unread-symbolic",
+            Self::LowConfidence => "dialog-question-symbolic",
+            Self::Stale => "document-open-recent-symbolic",
+            Self::OpenBlockers => "dialog-error-symbolic",
+        }
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![Self::NeedsReview, Self::LowConfidence, Self::Stale, Self::OpenBlockers]
+    }
+}
+
+/// How many facts each triage list holds, across all projects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TriageCounts {
+    pub needs_review: usize,
+    pub low_confidence: usize,
+    pub stale: usize,
+    pub open_blockers: usize,
+}
+
+impl TriageCounts {
+    /// Get the count for one list
+    pub fn count(&self, filter: TriageFilter) -> usize {
+        match filter {
+            TriageFilter::NeedsReview => self.needs_review,
+            TriageFilter::LowConfidence => self.low_confidence,
+            TriageFilter::Stale => self.stale,
+            TriageFilter::OpenBlockers => self.open_blockers,
+        }
+    }
+
+    /// Label for the list's header chip, e.g. "Needs review: 12"
+    pub fn chip_label(&self, filter: TriageFilter) -> String {
+        format!("{}: {}", filter.display_name(), self.count(filter))
+    }
+}
@@ -0,0 +1,253 @@
+use crate::db::Repository;
+use crate::models::{ExtractedFactPayload, FactSource, SessionPayload};
+use crate::monitor::extractor::{ConversationLog, Message};
+use crate::monitor::{generate_session_title, parse_log_file, shorten, FactExtractor, TITLE_MAX_CHARS};
+use anyhow::{bail, Context, Result};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Sessions with fewer facts than this when they end get a second look from the
+/// session extractor, if one is configured
+pub const ENRICH_BELOW_FACTS: i32 = 3;
+
+/// What an extractor made of a conversation
+#[derive(Debug, Clone, Default)]
+pub struct Extraction {
+    pub summary: Option<String>, // Session title, if the extractor writes one
+    pub facts: Vec<ExtractedFactPayload>,
+}
+
+/// A way of turning a whole conversation into a session summary and facts
+///
+/// The regex-based [`FactExtractor`] runs on every transcript as it is written.
+/// A slower or costlier backend, such as an LLM, can be handed to the monitor to
+/// look again at sessions where that found little. The caller fills in the
+/// `project` and `session` of the returned facts.
+pub trait Extractor: Send + Sync {
+    /// Short description for logs, e.g. "regex" or "ollama (llama3.1)"
+    fn name(&self) -> String;
+
+    fn extract(&self, log: &ConversationLog) -> Result<Extraction>;
+}
+
+/// Extractor shared with the monitor thread
+pub type SessionExtractor = Arc<dyn Extractor>;
+
+impl Extractor for FactExtractor {
+    fn name(&self) -> String {
+        "regex".to_string()
+    }
+
+    fn extract(&self, log: &ConversationLog) -> Result<Extraction> {
+        let facts = self.extract_from_log(log, None);
+        Ok(Extraction {
+            summary: Some(generate_session_title(log.goal().as_deref(), &facts)),
+            facts,
+        })
+    }
+}
+
+/// Parse the most recent transcript recorded for a session
+///
+/// Once the agent has rotated the file away, the archived copy is read instead, if
+/// transcripts are archived.
+pub fn read_session_transcript(repository: &Repository, session_id: &str) -> Result<ConversationLog> {
+    let transcript = repository
+        .get_processed_log_for_session(session_id)?
+        .context("No transcript is recorded for this session")?;
+    let content = match std::fs::read_to_string(&transcript.path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => repository
+            .read_archived_transcript(session_id, &transcript.path)?
+            .with_context(|| format!("Transcript {} is gone and was not archived", transcript.path))?,
+        Err(e) => return Err(e).with_context(|| format!("Failed to read transcript {}", transcript.path)),
+    };
+    parse_log_file(Path::new(&transcript.path), &content)
+}
+
+/// The transcript message a fact was extracted from
+///
+/// Fails when the transcript is gone or no longer holds the conversation, as
+/// when aider has started a new chat in the same file.
+pub fn read_fact_message(source: &FactSource) -> Result<Message> {
+    let path = source.log_path.as_deref().context("The fact has no recorded transcript")?;
+    let index = source.message_index.context("The fact has no recorded message")?;
+    let content = std::fs::read_to_string(path).with_context(|| format!("Failed to read transcript {}", path))?;
+    let mut log = parse_log_file(Path::new(path), &content)?;
+
+    if let (Some(expected), Some(found)) = (&source.conversation_id, &log.conversation_id) {
+        if expected != found {
+            bail!("{} no longer holds conversation {}", path, expected);
+        }
+    }
+    if index >= log.messages.len() {
+        bail!("{} has no message {}", path, index + 1);
+    }
+    Ok(log.messages.swap_remove(index))
+}
+
+/// Run an extractor over a session's transcript, saving its summary and facts
+///
+/// Facts already known are merged as usual, so running this twice adds nothing.
+/// Returns the number of facts saved.
+pub fn enrich_session(repository: &Repository, extractor: &dyn Extractor, session_id: &str) -> Result<usize> {
+    let mut session = repository.get_session(session_id)?;
+    let log = read_session_transcript(repository, session_id)?;
+    let extraction = extractor
+        .extract(&log)
+        .with_context(|| format!("Extraction with {} failed", extractor.name()))?;
+
+    let preferences = repository.extraction_preferences(&session.project)?;
+    let facts: Vec<_> = extraction
+        .facts
+        .into_iter()
+        .map(|fact| ExtractedFactPayload {
+            project: session.project.clone(),
+            session: Some(session.id.clone()),
+            importance: fact.importance.clamp(1, 5),
+            needs_review: Some(true),
+            ..fact
+        })
+        .filter(|fact| preferences.allows(fact))
+        .collect();
+    let saved = repository.create_facts_batch(facts)?;
+
+    if let Some(summary) = extraction.summary.filter(|summary| !summary.trim().is_empty()) {
+        session.summary = shorten(summary.trim(), TITLE_MAX_CHARS);
+    }
+    session.facts_extracted += saved as i32;
+    repository.update_session(session_id, SessionPayload::from(&session))?;
+
+    log::info!("{} extracted {} facts from session {}", extractor.name(), saved, session_id);
+    Ok(saved)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_test_db;
+    use crate::models::{FactMetadata, FactType, ProjectPayload, ProjectStatus};
+    use crate::monitor::LogMonitor;
+
+    /// Stands in for an LLM: one fact per user message
+    struct EchoExtractor;
+
+    impl Extractor for EchoExtractor {
+        fn name(&self) -> String {
+            "echo".to_string()
+        }
+
+        fn extract(&self, log: &ConversationLog) -> Result<Extraction> {
+            let facts = log
+                .messages
+                .iter()
+                .filter(|message| message.role == "user")
+                .map(|message| ExtractedFactPayload {
+                    project: String::new(),
+                    session: None,
+                    fact_type: FactType::Insight,
+                    content: message.content.clone(),
+                    importance: 9,
+                    stale: None,
+                    metadata: FactMetadata::default(),
+                    needs_review: None,
+                })
+                .collect();
+            Ok(Extraction {
+                summary: Some("Cart totals in cents".to_string()),
+                facts,
+            })
+        }
+    }
+
+    #[test]
+    fn test_enrich_session() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-enrich-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"user","message":{"role":"user","content":"The cart totals are off by a cent."}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","content":"Let me look."}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .process_log_file(&path)
+            .unwrap();
+        let session = repo.list_sessions(&project.id).unwrap().remove(0);
+        assert_eq!(session.facts_extracted, 0);
+
+        assert_eq!(enrich_session(&repo, &EchoExtractor, &session.id).unwrap(), 1);
+        let session = repo.get_session(&session.id).unwrap();
+        assert_eq!(session.summary, "Cart totals in cents");
+        assert_eq!(session.facts_extracted, 1);
+
+        let facts = repo.list_facts(&project.id, false).unwrap();
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].content, "The cart totals are off by a cent.");
+        assert_eq!(facts[0].session.as_deref(), Some(session.id.as_str()));
+        assert_eq!(facts[0].importance, 5);
+        assert!(facts[0].needs_review);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_archived_transcript_outlives_the_log() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let mut settings = repo.load_settings().unwrap();
+        settings.archive_transcripts = true;
+        repo.save_settings(&settings).unwrap();
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-archive-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"user","message":{"role":"user","content":"The cart totals are off by a cent."}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .process_log_file(&path)
+            .unwrap();
+        let session = repo.list_sessions(&project.id).unwrap().remove(0);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let log = read_session_transcript(&repo, &session.id).unwrap();
+        assert_eq!(log.messages.len(), 1);
+        assert_eq!(log.messages[0].content, "The cart totals are off by a cent.");
+    }
+}
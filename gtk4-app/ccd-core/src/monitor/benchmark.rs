@@ -0,0 +1,166 @@
+use crate::models::{BenchmarkResult, BenchmarkRun};
+use crate::monitor::{parse_conversation_log, FactExtractor};
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+/// Default corpus sizes (number of transcripts) for `ccd bench`
+pub const DEFAULT_BENCHMARK_SIZES: &[usize] = &[10, 100, 1000];
+
+/// Messages per synthetic transcript
+const MESSAGES_PER_TRANSCRIPT: usize = 40;
+
+/// Lines per synthetic message
+const LINES_PER_MESSAGE: usize = 6;
+
+/// Line pool for synthetic transcripts: a mix of fact-bearing and plain lines
+const CORPUS_LINES: &[&str] = &[
+    "I decided to use SQLite with WAL mode for the local cache.",
+    "Let me look at the repository layout first.",
+    "Error: failed to connect to the PocketBase server on port 8090.",
+    "Here is the updated function with the extra check.",
+    "TODO: handle the empty state in the dashboard view.",
+    "Modified src/db/repository.rs to add the new query.",
+    "The tests pass locally now.",
+    "Ran cargo add regex to pull in the matcher.",
+    "I found that the watcher fires twice for every write.",
+    "Going with a single transaction for the batch insert.",
+    "This keeps the change small and easy to review.",
+    "We need to run the migration before the first query.",
+    "Important: the slug column has a unique index.",
+    "Next I'll wire the new command into main.rs.",
+];
+
+/// Where benchmark runs are kept for comparison
+pub fn benchmarks_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("claude-context-tracker")
+        .join("benchmarks.jsonl")
+}
+
+/// Deterministic synthetic conversation logs in the Claude Code JSON format
+pub fn synthetic_corpus(transcripts: usize) -> Vec<String> {
+    // Small LCG so the corpus is identical on every run
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut next_line = || {
+        state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        CORPUS_LINES[(state >> 33) as usize % CORPUS_LINES.len()]
+    };
+
+    (0..transcripts)
+        .map(|i| {
+            let messages: Vec<_> = (0..MESSAGES_PER_TRANSCRIPT)
+                .map(|m| {
+                    let content: Vec<&str> = (0..LINES_PER_MESSAGE).map(|_| next_line()).collect();
+                    serde_json::json!({
+                        "role": if m % 2 == 0 { "user" } else { "assistant" },
+                        "content": content.join("\n"),
+                    })
+                })
+                .collect();
+            serde_json::json!({ "conversation_id": format!("bench-{}", i), "messages": messages }).to_string()
+        })
+        .collect()
+}
+
+/// Parse and extract facts from a corpus the way the monitor does, timing the whole pass
+pub fn run_benchmark(corpus: &[String]) -> Result<BenchmarkResult> {
+    let extractor = FactExtractor::new("benchmark".to_string());
+    let (mut lines, mut facts) = (0, 0);
+
+    let start = Instant::now();
+    for transcript in corpus {
+        let log = parse_conversation_log(transcript).context("Failed to parse synthetic transcript")?;
+        for message in log.messages.iter().filter(|m| m.role == "assistant") {
+            lines += message.content.lines().count();
+            facts += extractor.extract_from_message(&message.content, None).len();
+        }
+    }
+    let elapsed = start.elapsed();
+
+    Ok(BenchmarkResult {
+        transcripts: corpus.len(),
+        lines,
+        facts,
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+    })
+}
+
+/// Benchmark every corpus size
+pub fn run_benchmarks(sizes: &[usize]) -> Result<BenchmarkRun> {
+    let results = sizes
+        .iter()
+        .map(|&size| run_benchmark(&synthetic_corpus(size)))
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(BenchmarkRun {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        results,
+        created: Utc::now(),
+    })
+}
+
+/// Load saved runs, oldest first (a missing file means no runs yet)
+pub fn load_benchmark_runs(path: &Path) -> Result<Vec<BenchmarkRun>> {
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to open {}", path.display())),
+    };
+
+    let mut runs = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line?;
+        match serde_json::from_str(&line) {
+            Ok(run) => runs.push(run),
+            Err(e) => log::warn!("Skipping unreadable benchmark run: {}", e),
+        }
+    }
+    Ok(runs)
+}
+
+/// Append a run to the results file
+pub fn save_benchmark_run(path: &Path, run: &BenchmarkRun) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create benchmark directory")?;
+    }
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(run)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_benchmark_run_roundtrip() {
+        let run = run_benchmarks(&[1, 3]).unwrap();
+        assert_eq!(run.results.len(), 2);
+
+        let small = run.result_for(3).unwrap();
+        assert_eq!(small.lines, 3 * MESSAGES_PER_TRANSCRIPT / 2 * LINES_PER_MESSAGE);
+        assert!(small.facts > 0);
+
+        // The corpus is deterministic, so fact counts are comparable between runs
+        assert_eq!(run_benchmark(&synthetic_corpus(3)).unwrap().facts, small.facts);
+
+        let path = std::env::temp_dir().join(format!("ccd-bench-{}.jsonl", uuid::Uuid::new_v4()));
+        save_benchmark_run(&path, &run).unwrap();
+        save_benchmark_run(&path, &run).unwrap();
+
+        let runs = load_benchmark_runs(&path).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].result_for(1).unwrap().facts, run.result_for(1).unwrap().facts);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,128 @@
+use crate::db::Repository;
+use crate::models::{AttachmentMode, Project};
+use crate::utils::{append_snapshot, context_file_edits, generate_claude_md_with_attachments, ContextFileEdit};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Name of the context file exported into a project's repository
+pub const CONTEXT_FILE_NAME: &str = "CLAUDE.md";
+
+/// The CLAUDE.md in a project's repository; None without a repository path
+pub fn context_file_path(project: &Project) -> Option<PathBuf> {
+    project
+        .repo_path
+        .as_deref()
+        .map(|repo_path| Path::new(repo_path).join(CONTEXT_FILE_NAME))
+}
+
+/// Edits made by hand to the CLAUDE.md at `path`, compared to exporting `project` now
+///
+/// A file that doesn't exist has no edits.
+pub fn read_context_file_edits(repository: &Repository, project: &Project, path: &Path) -> Result<Vec<ContextFileEdit>> {
+    let file = match std::fs::read_to_string(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    let sections = repository.list_context_sections(&project.id)?;
+    let generated = render_context_file(repository, project)?;
+
+    Ok(context_file_edits(&file, &generated, &sections))
+}
+
+/// CLAUDE.md for `project` as exported into its repository, attached files inlined
+pub fn render_context_file(repository: &Repository, project: &Project) -> Result<String> {
+    let sections = repository.list_exported_sections(&project.id)?;
+    let dependencies = repository.load_dependency_context(&project.id)?;
+    let attachments = repository.list_project_attachments(&project.id)?;
+    Ok(generate_claude_md_with_attachments(
+        project,
+        &sections,
+        &dependencies,
+        &attachments,
+        AttachmentMode::Inline,
+    ))
+}
+
+/// Write the project's CLAUDE.md into its repository, returning where
+///
+/// The file gets a snapshot footer so the next export can tell hand edits from
+/// sections changed since. None without a repository path, or while the file
+/// has edits made by hand that would be lost; those are imported first.
+pub fn export_context_file(repository: &Repository, project: &Project) -> Result<Option<PathBuf>> {
+    let Some(path) = context_file_path(project) else {
+        return Ok(None);
+    };
+    if !read_context_file_edits(repository, project, &path)?.is_empty() {
+        log::warn!("{} has edits not in the tracker, leaving it alone", path.display());
+        return Ok(None);
+    }
+
+    let markdown = append_snapshot(&render_context_file(repository, project)?, vec![project.id.clone()]);
+    std::fs::write(&path, markdown).with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(Some(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{create_test_db, Repository};
+    use crate::models::{ContextSectionPayload, ProjectPayload, ProjectStatus, SectionType};
+
+    #[test]
+    fn test_export_context_file_keeps_hand_edits() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let dir = std::env::temp_dir().join(format!("ccd-export-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let mut payload = ProjectPayload {
+            name: "Export".to_string(),
+            slug: "export".to_string(),
+            repo_path: None,
+            status: ProjectStatus::Active,
+            priority: 0,
+            tech_stack: Vec::new(),
+            description: None,
+        };
+        let unplaced = repo.create_project(payload.clone()).unwrap();
+        assert_eq!(export_context_file(&repo, &unplaced).unwrap(), None);
+
+        payload.slug = "export-2".to_string();
+        payload.repo_path = Some(dir.display().to_string());
+        let project = repo.create_project(payload).unwrap();
+        let section = repo
+            .create_context_section(ContextSectionPayload {
+                project: project.id.clone(),
+                section_type: SectionType::Gotchas,
+                title: "Gotchas".to_string(),
+                content: "Cache is per process".to_string(),
+                order: 0,
+                auto_extracted: None,
+            })
+            .unwrap();
+
+        let path = export_context_file(&repo, &project).unwrap().unwrap();
+        assert_eq!(path, dir.join(CONTEXT_FILE_NAME));
+        assert!(std::fs::read_to_string(&path).unwrap().contains("Cache is per process"));
+
+        // Saving a section rewrites the file...
+        repo.update_context_section(
+            &section.id,
+            ContextSectionPayload {
+                content: "Cache is shared".to_string(),
+                ..ContextSectionPayload::from(&section)
+            },
+        )
+        .unwrap();
+        export_context_file(&repo, &project).unwrap().unwrap();
+        let exported = std::fs::read_to_string(&path).unwrap();
+        assert!(exported.contains("Cache is shared"));
+
+        // ...unless that would overwrite a hand edit
+        std::fs::write(&path, exported.replace("Cache is shared", "Cache is shared, mind the TTL")).unwrap();
+        assert_eq!(export_context_file(&repo, &project).unwrap(), None);
+        assert!(std::fs::read_to_string(&path).unwrap().contains("mind the TTL"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,161 @@
+use crate::monitor::MonitorEvent;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Version of the JSON form events are published in (see `schema/events.v1.json`)
+///
+/// Within a version fields and event types are only ever added, so consumers must
+/// ignore fields and types they don't know. Renaming or removing a field, or changing its meaning, bumps
+/// the version.
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+/// A monitor event as handed to external integrations
+///
+/// Serializes to a flat object:
+/// `{"schema_version":1,"emitted":"…","type":"facts_extracted","project":"…","count":3}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventEnvelope {
+    pub schema_version: u32,
+    pub emitted: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: MonitorEvent,
+}
+
+impl EventEnvelope {
+    /// Wrap an event emitted now in the current schema version
+    pub fn new(event: MonitorEvent) -> Self {
+        Self {
+            schema_version: EVENT_SCHEMA_VERSION,
+            emitted: Utc::now(),
+            event,
+        }
+    }
+
+    /// Serialize to a single line of JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Parse a published event, rejecting versions newer than this build understands
+    pub fn from_json(json: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Version {
+            schema_version: u32,
+        }
+
+        let version: Version = serde_json::from_str(json)?;
+        if version.schema_version > EVENT_SCHEMA_VERSION {
+            bail!(
+                "Event schema version {} is newer than the supported version {}",
+                version.schema_version,
+                EVENT_SCHEMA_VERSION
+            );
+        }
+
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Published v1 events; these must keep parsing and serializing exactly like this
+    const V1_FACTS_EXTRACTED: &str = r#"{"schema_version":1,"emitted":"2026-03-01T12:00:00Z","type":"facts_extracted","project":"Shop","count":3}"#;
+    const V1_TOKEN_THRESHOLD: &str = r#"{"schema_version":1,"emitted":"2026-03-01T12:00:00Z","type":"token_threshold","project":"Shop","tokens":120000,"threshold":100000}"#;
+    const V1_SESSION_ENDED: &str = r#"{"schema_version":1,"emitted":"2026-03-01T12:00:00Z","type":"session_ended","project":"Shop","summary":"Fix the cart totals","duration_minutes":42,"facts":3,"tokens":56000}"#;
+    const V1_CONTEXT_FILE_EDITED: &str = r#"{"schema_version":1,"emitted":"2026-03-01T12:00:00Z","type":"context_file_edited","project":"Shop","path":"/src/shop/CLAUDE.md","sections":2}"#;
+
+    fn emitted() -> DateTime<Utc> {
+        "2026-03-01T12:00:00Z".parse().unwrap()
+    }
+
+    #[test]
+    fn test_v1_events_round_trip() {
+        let events = [
+            (
+                V1_FACTS_EXTRACTED,
+                MonitorEvent::FactsExtracted {
+                    project: "Shop".to_string(),
+                    count: 3,
+                },
+            ),
+            (
+                V1_TOKEN_THRESHOLD,
+                MonitorEvent::TokenThreshold {
+                    project: "Shop".to_string(),
+                    tokens: 120_000,
+                    threshold: 100_000,
+                },
+            ),
+            (
+                V1_SESSION_ENDED,
+                MonitorEvent::SessionEnded {
+                    project: "Shop".to_string(),
+                    summary: "Fix the cart totals".to_string(),
+                    duration_minutes: 42,
+                    facts: 3,
+                    tokens: 56_000,
+                },
+            ),
+            (
+                V1_CONTEXT_FILE_EDITED,
+                MonitorEvent::ContextFileEdited {
+                    project: "Shop".to_string(),
+                    path: "/src/shop/CLAUDE.md".to_string(),
+                    sections: 2,
+                },
+            ),
+        ];
+
+        for (json, event) in events {
+            let envelope = EventEnvelope {
+                schema_version: 1,
+                emitted: emitted(),
+                event,
+            };
+            assert_eq!(EventEnvelope::from_json(json).unwrap(), envelope);
+            assert_eq!(envelope.to_json().unwrap(), json);
+        }
+    }
+
+    #[test]
+    fn test_unknown_fields_are_ignored() {
+        let json = V1_FACTS_EXTRACTED.replace(r#""count":3"#, r#""count":3,"session":"abc""#);
+        let envelope = EventEnvelope::from_json(&json).unwrap();
+        assert!(matches!(envelope.event, MonitorEvent::FactsExtracted { count: 3, .. }));
+    }
+
+    #[test]
+    fn test_newer_schema_version_is_rejected() {
+        let json = V1_FACTS_EXTRACTED.replace(r#""schema_version":1"#, r#""schema_version":2"#);
+        assert!(EventEnvelope::from_json(&json).is_err());
+    }
+
+    #[test]
+    fn test_schema_document_matches() {
+        let schema: serde_json::Value =
+            serde_json::from_str(include_str!("../../schema/events.v1.json")).unwrap();
+        assert_eq!(schema["properties"]["schema_version"]["const"], EVENT_SCHEMA_VERSION);
+
+        // Every event type is described, with the fields it serializes to
+        for json in [V1_FACTS_EXTRACTED, V1_TOKEN_THRESHOLD, V1_SESSION_ENDED, V1_CONTEXT_FILE_EDITED] {
+            let event: serde_json::Value = serde_json::from_str(json).unwrap();
+            let described = schema["oneOf"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|variant| variant["properties"]["type"]["const"] == event["type"])
+                .unwrap_or_else(|| panic!("No schema for event type {}", event["type"]));
+
+            for field in event.as_object().unwrap().keys() {
+                assert!(
+                    schema["properties"].get(field).is_some() || described["properties"].get(field).is_some(),
+                    "Field {field} of {} is not in the schema",
+                    event["type"]
+                );
+            }
+        }
+    }
+}
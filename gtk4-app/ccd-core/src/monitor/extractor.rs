@@ -0,0 +1,1167 @@
+use crate::models::{ExtractedFact, ExtractedFactPayload, FactMetadata, FactSource, FactType};
+use crate::monitor::{detect_goal, load_configured_rules, ExtractionRule};
+use crate::utils::detect_language;
+use anyhow::Result;
+use regex::{Regex, RegexSet};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+/// Fact patterns with the type and importance of facts they produce, in match order
+///
+/// Each pattern captures the interesting part of the line as `fact`: the phrase up to
+/// the end of its sentence, or the path for file changes.
+const FACT_PATTERNS: &[(FactType, i32, &str)] = &[
+    // Decisions are high importance
+    (
+        FactType::Decision,
+        4,
+        r"(?i)(?P<fact>(?:decided to|chose to|going with|will use|opted for).*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+    // Blockers are highest importance
+    (
+        FactType::Blocker,
+        5,
+        r"(?i)(?P<fact>(?:blocked by|can't proceed|cannot continue|error:|failed to|exception).*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+    // Todos are medium importance
+    (
+        FactType::Todo,
+        3,
+        r"(?i)(?P<fact>(?:TODO:|FIXME:|need to|should|must|have to).*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+    // File changes are medium importance
+    (
+        FactType::FileChange,
+        3,
+        r#"(?i)(?:created?|modified?|updated?|deleted?|removed?)\s+.*?(?P<fact>[^\s"'`()<>\[\]]*\.(?:tsx|ts|jsx|js|py|go|java|cpp|cs|rs|h|c))\b"#,
+    ),
+    // Dependencies are high importance
+    (
+        FactType::Dependency,
+        4,
+        r"(?i)(?P<fact>(?:installed|added|npm install|cargo add|pip install|go get).*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+    // Insights are medium importance
+    (
+        FactType::Insight,
+        3,
+        r"(?i)(?P<fact>(?:discovered|found that|learned that|note that|important:).*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+    // Resolutions settle earlier blockers and todos (see `StalenessDetector::resolved_by`)
+    (
+        FactType::Insight,
+        3,
+        r"(?i)(?P<fact>(?:fixed|resolved|solved)\s+(?:the|a|an|that|this)\s.*?)(?:[.!?;](?:\s|$)|$)",
+    ),
+];
+
+/// Fact patterns compiled once: a set to find which patterns match a line in a single
+/// scan, and the individual regexes (with their fact type and importance) to pull out
+/// captures for the ones that did
+struct FactMatchers {
+    set: RegexSet,
+    patterns: Vec<(FactType, i32, Regex)>,
+}
+
+impl FactMatchers {
+    /// The built-in patterns followed by user-defined rules
+    fn new(rules: Vec<(ExtractionRule, Regex)>) -> Self {
+        let builtin = FACT_PATTERNS
+            .iter()
+            .map(|(fact_type, importance, pattern)| (*fact_type, *importance, Regex::new(pattern).unwrap()));
+        let custom = rules.into_iter().map(|(rule, regex)| (rule.fact_type, rule.importance, regex));
+        let patterns: Vec<_> = builtin.chain(custom).collect();
+
+        Self {
+            set: RegexSet::new(patterns.iter().map(|(_, _, regex)| regex.as_str())).unwrap(),
+            patterns,
+        }
+    }
+}
+
+static MATCHERS: OnceLock<Arc<FactMatchers>> = OnceLock::new();
+
+/// Matchers shared by every extractor, with the rules file read on first use
+fn matchers() -> Arc<FactMatchers> {
+    MATCHERS
+        .get_or_init(|| {
+            // Keep tests (and their golden files) independent of the developer's rules
+            let rules = if cfg!(test) { Vec::new() } else { load_configured_rules() };
+            Arc::new(FactMatchers::new(rules))
+        })
+        .clone()
+}
+
+/// Tools that change the file named in their `file_path` or `notebook_path` input
+const FILE_EDIT_TOOLS: &[&str] = &["Edit", "MultiEdit", "Write", "NotebookEdit"];
+
+/// Tool that runs the shell command in its `command` input
+pub(crate) const SHELL_TOOL: &str = "Bash";
+
+/// Package manager subcommands that add dependencies to a project
+const DEPENDENCY_COMMANDS: &[(&str, &[&str])] = &[
+    ("cargo", &["add"]),
+    ("npm", &["install", "i", "add"]),
+    ("yarn", &["add"]),
+    ("pnpm", &["add", "install", "i"]),
+    ("bun", &["add", "install", "i"]),
+    ("pip", &["install"]),
+    ("pip3", &["install"]),
+    ("uv", &["add"]),
+    ("poetry", &["add"]),
+    ("go", &["get"]),
+    ("bundle", &["add"]),
+    ("composer", &["require"]),
+    ("dotnet", &["add"]),
+];
+
+/// Code block languages kept as snippets whatever the block holds: schemas and configs
+const SNIPPET_LANGUAGES: &[&str] = &[
+    "sql", "graphql", "gql", "proto", "protobuf", "prisma", "json", "jsonc", "yaml", "yml", "toml", "ini", "hcl",
+    "terraform", "dockerfile", "nginx", "xml",
+];
+
+/// Definitions that make a code block in any other language a snippet: types, interfaces and tables
+const SNIPPET_DEFINITION_PATTERN: &str = r"(?m)^\s*(?:(?:pub(?:\([^)]*\))?|export|public|abstract|declare)\s+)*(?:interface|struct|enum|trait|protocol|type\s+\w+(?:<[^>]*>)?\s*=|type\s+\w+\s+(?:struct|interface)|(?i:create\s+(?:table|type|view)))\b";
+
+/// Lines a code block needs to be worth keeping, and the most it may have
+///
+/// Shorter blocks are usually commands or examples; longer ones whole files that
+/// would crowd out every other fact.
+const SNIPPET_MIN_LINES: usize = 3;
+const SNIPPET_MAX_LINES: usize = 80;
+
+/// Words that, shortly before a pattern's keyword, make the line a condition, guess or
+/// negation rather than a statement ("if we decided to", "might need to")
+const HEDGE_WORDS: &[&str] = &[
+    "if", "whether", "unless", "maybe", "perhaps", "probably", "might", "may", "could", "would", "not", "don't",
+    "didn't", "doesn't", "won't", "never",
+];
+
+/// How many words before a keyword are searched for a hedge
+const HEDGE_WINDOW: usize = 4;
+
+/// A keyword preceded by more words than this is buried in its sentence
+const BURIED_AFTER_WORDS: usize = 8;
+
+/// A file mentioned in a message, optionally with a line number (`src/main.rs:42`)
+const FILE_REFERENCE_PATTERN: &str = r"(?:^|[\s`'\x22(\[])(?P<path>(?:[\w.~-]+/)*[\w-][\w.-]*\.(?:tsx|ts|jsx|js|py|go|java|cpp|cs|rs|h|c|rb|php|kt|swift|vue|css|html|sql|json|toml|ya?ml|md|sh))(?::(?P<line>\d+))?\b";
+
+/// Fact extractor for Claude Code conversation logs
+pub struct FactExtractor {
+    project_id: String,
+    matchers: Arc<FactMatchers>,
+    log_path: Option<String>, // Transcript the facts are recorded as coming from
+}
+
+impl FactExtractor {
+    /// Create a new fact extractor for a project
+    ///
+    /// Besides the built-in patterns it applies the rules from the extraction
+    /// rules file (see `extraction_rules_path`), loaded once per process.
+    pub fn new(project_id: String) -> Self {
+        Self {
+            project_id,
+            matchers: matchers(),
+            log_path: None,
+        }
+    }
+
+    /// Create a fact extractor applying `rules` after the built-in patterns
+    pub fn with_rules(project_id: String, rules: Vec<(ExtractionRule, Regex)>) -> Self {
+        Self {
+            project_id,
+            matchers: Arc::new(FactMatchers::new(rules)),
+            log_path: None,
+        }
+    }
+
+    /// Record `path` as the transcript of the conversations this extractor reads
+    pub fn with_log_path(mut self, path: &Path) -> Self {
+        self.log_path = Some(path.to_string_lossy().into_owned());
+        self
+    }
+
+    /// Extract facts from a message
+    pub fn extract_from_message(&self, content: &str, session_id: Option<String>) -> Vec<ExtractedFactPayload> {
+        let matchers = &self.matchers;
+        let mut facts = Vec::new();
+
+        // Split into lines for better extraction
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let matches = matchers.set.matches(line);
+            if !matches.matched_any() {
+                continue;
+            }
+
+            // Record the source language of anything extracted from this line
+            let language = detect_language(line);
+            for index in matches.iter() {
+                let (fact_type, importance, regex) = &matchers.patterns[index];
+                let (fact_type, importance) = (*fact_type, *importance);
+                let caps = regex.captures(line);
+                let start = caps.as_ref().and_then(|caps| caps.get(0)).map_or(0, |m| m.start());
+                let captured = caps
+                    .as_ref()
+                    .and_then(|caps| caps.name("fact"))
+                    .map(|m| m.as_str().trim())
+                    .filter(|fact| !fact.is_empty());
+
+                let (content, source_line) = match captured {
+                    Some(fact) if fact != line => (fact.to_string(), Some(line.to_string())),
+                    _ => (line.to_string(), None),
+                };
+                let source = file_reference(line, &content);
+
+                facts.push(ExtractedFactPayload {
+                    project: self.project_id.clone(),
+                    session: session_id.clone(),
+                    fact_type,
+                    content,
+                    importance,
+                    stale: None,
+                    metadata: FactMetadata {
+                        language: language.clone(),
+                        source_line,
+                        source,
+                        confidence: Some(hit_confidence(line, start)),
+                        ..FactMetadata::default()
+                    },
+                    needs_review: Some(true),
+                });
+            }
+        }
+
+        facts.extend(self.extract_snippets(content, session_id));
+        facts
+    }
+
+    /// Extract the fenced code blocks of a message that hold schemas, configs or interfaces
+    ///
+    /// The code becomes the fact's content, with its language and the file it
+    /// belongs in (when the fence or the line above it names one) as metadata.
+    pub fn extract_snippets(&self, content: &str, session_id: Option<String>) -> Vec<ExtractedFactPayload> {
+        static DEFINITION: OnceLock<Regex> = OnceLock::new();
+        let definition = DEFINITION.get_or_init(|| Regex::new(SNIPPET_DEFINITION_PATTERN).unwrap());
+
+        code_blocks(content)
+            .into_iter()
+            .filter(|block| (SNIPPET_MIN_LINES..=SNIPPET_MAX_LINES).contains(&block.code.lines().count()))
+            .filter_map(|block| {
+                // A schema or config language is surer than a definition found in the code
+                let confidence = if block.language.as_deref().is_some_and(|language| SNIPPET_LANGUAGES.contains(&language)) {
+                    0.9
+                } else if definition.is_match(&block.code) {
+                    0.7
+                } else {
+                    return None;
+                };
+                Some(ExtractedFactPayload {
+                    project: self.project_id.clone(),
+                    session: session_id.clone(),
+                    fact_type: FactType::Snippet,
+                    content: block.code,
+                    importance: 3,
+                    stale: None,
+                    metadata: FactMetadata {
+                        code_language: block.language,
+                        source: block.target_file.clone().map(|path| FactSource {
+                            file_path: Some(path),
+                            ..FactSource::default()
+                        }),
+                        target_file: block.target_file,
+                        confidence: Some(confidence),
+                        ..FactMetadata::default()
+                    },
+                    needs_review: Some(true),
+                })
+            })
+            .collect()
+    }
+
+    /// Extract facts from every assistant message of a conversation, in order
+    ///
+    /// Once the tool calls record file changes (or dependencies) exactly, those are
+    /// no longer guessed from the prose around the calls, which only adds noise.
+    /// Each fact's source records the message it came from.
+    pub fn extract_from_log(&self, log: &ConversationLog, session_id: Option<String>) -> Vec<ExtractedFactPayload> {
+        let assistant = || log.messages.iter().enumerate().filter(|(_, m)| m.role == "assistant");
+        let from_tools: Vec<_> = assistant()
+            .map(|(_, message)| self.extract_from_tool_uses(&message.tool_uses, log.cwd.as_deref(), session_id.clone()))
+            .collect();
+        let exact: HashSet<FactType> = from_tools.iter().flatten().map(|fact| fact.fact_type).collect();
+
+        let mut facts = Vec::new();
+        for ((index, message), tool_facts) in assistant().zip(from_tools) {
+            let prose = self.extract_from_message(&message.content, session_id.clone());
+            let start = facts.len();
+            facts.extend(prose.into_iter().filter(|fact| !exact.contains(&fact.fact_type)));
+            facts.extend(tool_facts);
+
+            for fact in &mut facts[start..] {
+                let source = fact.metadata.source.take().unwrap_or_default();
+                fact.metadata.source = Some(FactSource {
+                    log_path: self.log_path.clone(),
+                    conversation_id: log.conversation_id.clone(),
+                    message_index: Some(log.first_message + index),
+                    ..source
+                });
+            }
+        }
+        facts
+    }
+
+    /// Extract file changes and dependencies from the tools an assistant message called
+    ///
+    /// Edited files come from the editing tools; shell commands add the packages
+    /// they installed and the files they moved or deleted. Paths inside the
+    /// session's working directory are made relative to it.
+    pub fn extract_from_tool_uses(
+        &self,
+        tool_uses: &[ToolUse],
+        cwd: Option<&str>,
+        session_id: Option<String>,
+    ) -> Vec<ExtractedFactPayload> {
+        let relative = |path: &str| {
+            cwd.and_then(|cwd| Path::new(path).strip_prefix(cwd).ok())
+                .map(|relative| relative.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.to_string())
+        };
+        let fact = |fact_type: FactType, content: String, importance: i32| ExtractedFactPayload {
+            project: self.project_id.clone(),
+            session: session_id.clone(),
+            metadata: FactMetadata {
+                source: (fact_type == FactType::FileChange).then(|| FactSource {
+                    file_path: Some(content.clone()),
+                    ..FactSource::default()
+                }),
+                // Read from what the tools did, not guessed from prose
+                confidence: Some(1.0),
+                ..FactMetadata::default()
+            },
+            fact_type,
+            content,
+            importance,
+            stale: None,
+            needs_review: Some(true),
+        };
+
+        let mut facts = Vec::new();
+        for tool in tool_uses {
+            if FILE_EDIT_TOOLS.contains(&tool.name.as_str()) {
+                let path = tool.input.get("file_path").or_else(|| tool.input.get("notebook_path"));
+                if let Some(path) = path.and_then(|path| path.as_str()) {
+                    facts.push(fact(FactType::FileChange, relative(path), 3));
+                }
+            } else if tool.name == SHELL_TOOL {
+                let Some(command) = tool.input.get("command").and_then(|command| command.as_str()) else {
+                    continue;
+                };
+                for words in shell_commands(command) {
+                    if let Some(install) = dependency_command(&words) {
+                        facts.push(fact(FactType::Dependency, install, 4));
+                    }
+                    for path in changed_paths(&words) {
+                        facts.push(fact(FactType::FileChange, relative(path), 3));
+                    }
+                }
+            }
+        }
+        facts
+    }
+}
+
+/// How strongly a pattern hit at byte `start` of `line` reads as a fact, from 0 to 1
+///
+/// A keyword opening the line (after any list marker or emphasis) is a strong hit;
+/// one buried deep in its sentence, following a hedge like "if" or "might", or in
+/// a question is a weak one.
+fn hit_confidence(line: &str, start: usize) -> f64 {
+    let before: Vec<String> = line[..start]
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| !c.is_alphanumeric() && c != '\'').to_lowercase())
+        .filter(|word| word.chars().any(char::is_alphabetic))
+        .collect();
+
+    // In tenths, to keep the scores exact
+    let mut tenths = 8;
+    if before.is_empty() {
+        tenths += 1;
+    } else if before.len() > BURIED_AFTER_WORDS {
+        tenths -= 2;
+    }
+    if before.iter().rev().take(HEDGE_WINDOW).any(|word| HEDGE_WORDS.contains(&word.as_str())) {
+        tenths -= 3;
+    }
+    if sentence_end(&line[start..]) == Some('?') {
+        tenths -= 4;
+    }
+    f64::from(tenths.clamp(1, 10)) / 10.0
+}
+
+/// The punctuation ending the first sentence of `text`, if it has one
+fn sentence_end(text: &str) -> Option<char> {
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if matches!(c, '.' | '!' | '?') && chars.peek().is_none_or(|next| next.is_whitespace()) {
+            return Some(c);
+        }
+    }
+    None
+}
+
+/// The file a fact's line refers to, as a source with just the file set
+///
+/// A file changes fact names its file; otherwise the first file mentioned
+/// on the line counts.
+fn file_reference(line: &str, content: &str) -> Option<FactSource> {
+    static FILE_REFERENCE: OnceLock<Regex> = OnceLock::new();
+    let regex = FILE_REFERENCE.get_or_init(|| Regex::new(FILE_REFERENCE_PATTERN).unwrap());
+
+    let references: Vec<_> = regex.captures_iter(line).collect();
+    let caps = references
+        .iter()
+        .find(|caps| &caps["path"] == content)
+        .or_else(|| references.first())?;
+    Some(FactSource {
+        file_path: Some(caps["path"].to_string()),
+        file_line: caps.name("line").and_then(|line| line.as_str().parse().ok()),
+        ..FactSource::default()
+    })
+}
+
+/// A fenced code block of a message
+#[derive(Debug, PartialEq)]
+struct CodeBlock {
+    language: Option<String>,
+    target_file: Option<String>,
+    code: String,
+}
+
+/// The closed ``` or ~~~ code blocks in markdown text, in order
+///
+/// The target file comes from the info string (`rust:src/lib.rs`,
+/// `sql title="db/schema.sql"`), from a line above the fence naming just a path
+/// (`` `src/lib.rs`: ``), or from a comment on the first line of the code.
+fn code_blocks(content: &str) -> Vec<CodeBlock> {
+    let mut blocks = Vec::new();
+    let mut lines = content.lines();
+    let mut previous: Option<&str> = None;
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim_start();
+        let (fence_char, fence_len) = match trimmed.chars().next() {
+            Some(c @ ('`' | '~')) => (c, trimmed.chars().take_while(|&d| d == c).count()),
+            _ => (' ', 0),
+        };
+        let info = trimmed[fence_len..].trim();
+        // A backtick in the info string makes it inline code, not a fence
+        if fence_len < 3 || (fence_char == '`' && info.contains('`')) {
+            if !trimmed.is_empty() {
+                previous = Some(trimmed);
+            }
+            continue;
+        }
+
+        let mut code = Vec::new();
+        let mut closed = false;
+        for line in lines.by_ref() {
+            let closing = line.trim();
+            if closing.len() >= fence_len && closing.chars().all(|c| c == fence_char) {
+                closed = true;
+                break;
+            }
+            code.push(line);
+        }
+        if !closed {
+            break;
+        }
+
+        let (language, info_file) = parse_info_string(info);
+        let target_file = info_file
+            .or_else(|| previous.and_then(path_heading))
+            .or_else(|| code.first().and_then(|line| path_comment(line)));
+        blocks.push(CodeBlock {
+            language,
+            target_file,
+            code: code.join("\n").trim_end().to_string(),
+        });
+        previous = None;
+    }
+
+    blocks
+}
+
+/// Language and target file named by a code fence's info string
+fn parse_info_string(info: &str) -> (Option<String>, Option<String>) {
+    let mut words = info.split_whitespace();
+    let Some(first) = words.next() else {
+        return (None, None);
+    };
+
+    let (language, mut file) = match first.split_once(':') {
+        Some((language, path)) if is_file_path(path) => (language, Some(path.to_string())),
+        _ => (first, None),
+    };
+    for word in words {
+        let word = word.trim_matches(|c| c == '{' || c == '}');
+        if let Some((key, value)) = word.split_once('=') {
+            let value = value.trim_matches(|c| c == '"' || c == '\'');
+            if matches!(key, "title" | "file" | "filename" | "path") && is_file_path(value) {
+                file = file.or(Some(value.to_string()));
+            }
+        }
+    }
+
+    let language = language.trim_start_matches('{').trim_start_matches('.').to_lowercase();
+    ((!language.is_empty()).then_some(language), file)
+}
+
+/// The path a line above a code block names, like `src/lib.rs`, **src/lib.rs** or `` In `src/lib.rs`: ``
+fn path_heading(line: &str) -> Option<String> {
+    let bare = line.trim_end_matches(':').trim_matches(|c| c == '`' || c == '*' || c == '#' || c == ' ');
+    if is_file_path(bare) {
+        return Some(bare.to_string());
+    }
+
+    // A sentence introducing the block: the last quoted path in it
+    if !line.ends_with(':') {
+        return None;
+    }
+    line.split('`').skip(1).step_by(2).filter(|quoted| is_file_path(quoted)).last().map(str::to_string)
+}
+
+/// The path named by a comment making up a line of code, like `// src/lib.rs` or `-- db/schema.sql`
+fn path_comment(line: &str) -> Option<String> {
+    let line = line.trim();
+    let comment = ["//", "#", "--", "/*", "<!--"].iter().find_map(|marker| line.strip_prefix(marker))?;
+    let path = comment.trim_end_matches("*/").trim_end_matches("-->").trim();
+    let path = path.strip_prefix("file:").or_else(|| path.strip_prefix("File:")).unwrap_or(path).trim();
+    is_file_path(path).then(|| path.to_string())
+}
+
+/// Check if text looks like a relative or absolute file path with an extension
+fn is_file_path(text: &str) -> bool {
+    let Some((stem, extension)) = text.rsplit_once('.') else {
+        return false;
+    };
+    !stem.is_empty()
+        && !text.contains("://")
+        && !extension.is_empty()
+        && extension.chars().all(|c| c.is_ascii_alphanumeric())
+        && extension.chars().any(|c| c.is_ascii_alphabetic())
+        && stem.chars().all(|c| c.is_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '~'))
+}
+
+/// Split a shell command line into its simple commands, as words
+///
+/// Commands are separated by newlines, `;`, `&&`, `||` and pipes. Leading
+/// `VAR=value` assignments and `sudo` are dropped. Quoting is not interpreted,
+/// which is enough for the package and file commands looked for here.
+fn shell_commands(command_line: &str) -> Vec<Vec<&str>> {
+    command_line
+        .split(['\n', ';', '&', '|'])
+        .map(|command| {
+            command
+                .split_whitespace()
+                .skip_while(|word| *word == "sudo" || word.split_once('=').is_some_and(|(name, _)| is_env_name(name)))
+                .collect::<Vec<_>>()
+        })
+        .filter(|words| !words.is_empty())
+        .collect()
+}
+
+fn is_env_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// The command as a dependency fact, if it adds packages to the project
+///
+/// Only commands naming packages count: a bare `npm install` restores what is
+/// already there.
+fn dependency_command(words: &[&str]) -> Option<String> {
+    // python -m pip install ..., uv pip install ...
+    let words = match words {
+        [python, "-m", rest @ ..] if python.starts_with("python") => rest,
+        ["uv", rest @ ..] if rest.first() == Some(&"pip") => rest,
+        _ => words,
+    };
+    let (program, rest) = words.split_first()?;
+    let (_, subcommands) = DEPENDENCY_COMMANDS.iter().find(|(name, _)| name == program)?;
+    let (subcommand, arguments) = rest.split_first()?;
+    if !subcommands.contains(subcommand) || !arguments.iter().any(|argument| !argument.starts_with('-')) {
+        return None;
+    }
+
+    Some(words.join(" "))
+}
+
+/// Files a command deletes or moves (to), for `rm`, `mv` and their git forms
+///
+/// Recursive deletes are skipped: they usually clear build output, not sources.
+fn changed_paths<'a>(words: &[&'a str]) -> Vec<&'a str> {
+    let (command, arguments) = match words {
+        ["git", command @ ("rm" | "mv"), arguments @ ..] | [command @ ("rm" | "mv"), arguments @ ..] => {
+            (*command, arguments)
+        }
+        _ => return Vec::new(),
+    };
+    let (flags, paths): (Vec<&str>, Vec<&str>) = arguments.iter().partition(|argument| argument.starts_with('-'));
+
+    if command == "mv" {
+        // The destination is the last argument
+        return paths.last().map(|path| vec![*path]).unwrap_or_default();
+    }
+    let recursive = flags.iter().any(|flag| {
+        *flag == "--recursive" || (!flag.starts_with("--") && (flag.contains('r') || flag.contains('R')))
+    });
+    if recursive {
+        return Vec::new();
+    }
+    paths
+}
+
+/// Parse a Claude Code conversation log file
+///
+/// Accepts every transcript layout Claude Code has written:
+/// - a JSON object with `messages` whose `content` is plain text (oldest),
+/// - the same object with `content` as a list of content blocks,
+/// - JSON Lines with one event per line, messages nested under `message`
+///   (the per-project `.jsonl` transcripts in `~/.claude/projects/<dir>/`).
+///
+/// Text blocks become the message content and tool calls are kept alongside;
+/// tool results and messages with neither are dropped.
+pub fn parse_conversation_log(content: &str) -> Result<ConversationLog> {
+    let mut log = match serde_json::from_str::<ConversationLog>(content) {
+        Ok(log) => log,
+        Err(e) => parse_transcript_events(content).ok_or(e)?,
+    };
+    log.finish();
+    Ok(log)
+}
+
+/// Parse a slice of a JSON Lines transcript, such as the lines appended since it was last read
+///
+/// Unlike `parse_conversation_log` this never fails: lines that aren't events are
+/// skipped and the result may hold no messages at all.
+pub fn parse_transcript_lines(content: &str) -> ConversationLog {
+    let mut log = parse_transcript_events(content).unwrap_or_default();
+    log.finish();
+    log
+}
+
+/// Parse a JSON Lines transcript, or `None` if no line holds a message
+fn parse_transcript_events(content: &str) -> Option<ConversationLog> {
+    #[derive(serde::Deserialize)]
+    struct TranscriptEvent {
+        #[serde(rename = "sessionId")]
+        session_id: Option<String>,
+        cwd: Option<String>,
+        message: Option<Message>,
+    }
+
+    let mut log = ConversationLog::default();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        // Skip event kinds this version doesn't know rather than failing the whole file
+        let Ok(event) = serde_json::from_str::<TranscriptEvent>(line) else {
+            continue;
+        };
+        log.conversation_id = log.conversation_id.or(event.session_id);
+        log.cwd = log.cwd.or(event.cwd);
+        log.messages.extend(event.message);
+    }
+
+    (!log.messages.is_empty()).then_some(log)
+}
+
+/// Simplified conversation log structure
+#[derive(Debug, Default, serde::Deserialize)]
+pub struct ConversationLog {
+    pub conversation_id: Option<String>,
+    #[serde(default)]
+    pub cwd: Option<String>, // Working directory of the session (JSON Lines transcripts only)
+    pub messages: Vec<Message>,
+    #[serde(skip)]
+    pub replies: Vec<ReplyUsage>, // Every assistant reply reporting usage, including ones without text
+    #[serde(skip)]
+    pub first_message: usize, // Index of the first message in the whole conversation, for lines parsed on their own
+}
+
+/// Token usage of one assistant reply and the model that wrote it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplyUsage {
+    pub model: Option<String>,
+    pub usage: TokenUsage,
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(from = "RawMessage")]
+pub struct Message {
+    pub id: Option<String>, // API message ID; a reply split over several transcript lines repeats it
+    pub role: String,
+    pub content: String, // Text blocks, one per line
+    pub tool_uses: Vec<ToolUse>,
+    pub model: Option<String>,
+    pub usage: Option<TokenUsage>,
+}
+
+impl Message {
+    /// Check if the message has text or tool calls worth keeping
+    fn has_content(&self) -> bool {
+        !self.content.trim().is_empty() || !self.tool_uses.is_empty()
+    }
+}
+
+/// A tool call made by the assistant
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ToolUse {
+    pub name: String,
+    #[serde(default)]
+    pub input: serde_json::Value,
+}
+
+/// Token usage reported with an assistant reply
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct TokenUsage {
+    pub input_tokens: i64,
+    pub output_tokens: i64,
+    pub cache_creation_input_tokens: i64,
+    pub cache_read_input_tokens: i64,
+}
+
+impl TokenUsage {
+    /// Size of the context window after this reply
+    pub fn context_tokens(&self) -> i64 {
+        self.input_tokens + self.cache_creation_input_tokens + self.cache_read_input_tokens + self.output_tokens
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct RawMessage {
+    #[serde(default)]
+    id: Option<String>,
+    role: String,
+    content: MessageContent,
+    #[serde(default)]
+    model: Option<String>,
+    usage: Option<TokenUsage>,
+}
+
+/// Message content as plain text or as a list of content blocks
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum MessageContent {
+    Text(String),
+    Blocks(Vec<ContentBlock>),
+}
+
+#[derive(serde::Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    text: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    input: serde_json::Value,
+}
+
+impl From<RawMessage> for Message {
+    fn from(raw: RawMessage) -> Self {
+        let (content, tool_uses) = match raw.content {
+            MessageContent::Text(text) => (text, Vec::new()),
+            MessageContent::Blocks(blocks) => {
+                let mut texts = Vec::new();
+                let mut tool_uses = Vec::new();
+                for block in blocks {
+                    match (block.kind.as_str(), block.text, block.name) {
+                        ("text", Some(text), _) => texts.push(text),
+                        ("tool_use", _, Some(name)) => tool_uses.push(ToolUse {
+                            name,
+                            input: block.input,
+                        }),
+                        _ => {}
+                    }
+                }
+                (texts.join("\n"), tool_uses)
+            }
+        };
+
+        Self {
+            id: raw.id,
+            role: raw.role,
+            content,
+            tool_uses,
+            model: raw.model,
+            usage: raw.usage,
+        }
+    }
+}
+
+impl ConversationLog {
+    /// Count tokens in the conversation context
+    ///
+    /// Uses the usage reported with the latest assistant reply when the transcript
+    /// has one, and estimates from the text otherwise.
+    pub fn estimate_tokens(&self) -> i64 {
+        if let Some(usage) = self.latest_usage() {
+            return usage.context_tokens();
+        }
+
+        // Rough estimate: 1 token ≈ 4 characters
+        let total_chars: usize = self.messages.iter()
+            .map(|m| m.content.len())
+            .sum();
+        (total_chars / 4) as i64
+    }
+
+    /// Token usage reported with the latest assistant reply, if any
+    ///
+    /// Falls back to replies without text, which some agents report usage on.
+    pub fn latest_usage(&self) -> Option<TokenUsage> {
+        self.messages
+            .iter()
+            .rev()
+            .find_map(|m| m.usage)
+            .or_else(|| self.replies.last().map(|reply| reply.usage))
+    }
+
+    /// Note reply usage, then drop messages without content
+    pub(crate) fn finish(&mut self) {
+        self.collect_replies();
+        self.messages.retain(Message::has_content);
+    }
+
+    /// Note the usage of every reply before messages without content are dropped
+    ///
+    /// Claude Code writes each content block of a reply on its own line, repeating
+    /// the reply's usage; only the last line of each reply counts.
+    fn collect_replies(&mut self) {
+        let mut replies = Vec::new();
+        let mut by_id: HashMap<&str, usize> = HashMap::new();
+        for message in &self.messages {
+            let Some(usage) = message.usage else {
+                continue;
+            };
+            let reply = ReplyUsage {
+                model: message.model.clone(),
+                usage,
+            };
+            match message.id.as_deref().and_then(|id| by_id.get(id)) {
+                Some(&i) => replies[i] = reply,
+                None => {
+                    if let Some(id) = message.id.as_deref() {
+                        by_id.insert(id, replies.len());
+                    }
+                    replies.push(reply);
+                }
+            }
+        }
+        self.replies = replies;
+    }
+
+    /// What the user set out to do, from their first message
+    pub fn goal(&self) -> Option<String> {
+        self.messages
+            .iter()
+            .find(|m| m.role == "user")
+            .and_then(|message| detect_goal(&message.content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_decision() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "I decided to use Rust for this project",
+            None,
+        );
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::Decision);
+        assert_eq!(facts[0].importance, 4);
+    }
+
+    #[test]
+    fn test_extract_with_custom_rules() {
+        let rule = ExtractionRule {
+            pattern: r"(?i)(?P<fact>ticket [A-Z]+-\d+)".to_string(),
+            fact_type: FactType::Todo,
+            importance: 2,
+        };
+        let regex = Regex::new(&rule.pattern).unwrap();
+        let extractor = FactExtractor::with_rules("test-project".to_string(), vec![(rule, regex)]);
+
+        let facts = extractor.extract_from_message("Follow up on ticket SHOP-142 tomorrow", None);
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::Todo);
+        assert_eq!(facts[0].importance, 2);
+        assert_eq!(facts[0].content, "ticket SHOP-142");
+
+        // Built-in patterns still apply
+        assert_eq!(extractor.extract_from_message("We decided to use Rust", None).len(), 1);
+    }
+
+    #[test]
+    fn test_extract_blocker() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "Error: failed to connect to database",
+            None,
+        );
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::Blocker);
+        assert_eq!(facts[0].importance, 5);
+    }
+
+    #[test]
+    fn test_extract_todo() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "TODO: implement error handling",
+            None,
+        );
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::Todo);
+    }
+
+    #[test]
+    fn test_extract_file_change() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "Created new file: src/main.rs",
+            None,
+        );
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::FileChange);
+    }
+
+    #[test]
+    fn test_extract_multiple() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "I decided to use SQLite. TODO: add migrations. Created database.rs file.",
+            None,
+        );
+        assert_eq!(facts.len(), 3);
+    }
+
+    #[test]
+    fn test_extract_captures() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message(
+            "After profiling I decided to use serde v1.0 for parsing. Then I updated `src/db/schema.rs` too.",
+            None,
+        );
+        assert_eq!(facts.len(), 2);
+
+        assert_eq!(facts[0].fact_type, FactType::Decision);
+        assert_eq!(facts[0].content, "decided to use serde v1.0 for parsing");
+        assert!(facts[0].metadata.source_line.as_deref().unwrap().starts_with("After profiling"));
+
+        assert_eq!(facts[1].fact_type, FactType::FileChange);
+        assert_eq!(facts[1].content, "src/db/schema.rs");
+    }
+
+    #[test]
+    fn test_hit_confidence() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let confidence = |line: &str| extractor.extract_from_message(line, None)[0].metadata.confidence;
+
+        assert_eq!(confidence("- **Decided to use SQLite** for storage"), Some(0.9));
+        assert_eq!(confidence("So we decided to use SQLite for storage"), Some(0.8));
+        assert_eq!(confidence("If we decided to use SQLite, the sync gets simpler"), Some(0.5));
+        assert_eq!(confidence("I might need to rewrite the cart totals"), Some(0.5));
+        assert_eq!(confidence("Should we store prices as cents? I think so."), Some(0.5));
+        assert_eq!(
+            confidence("After a long look at the profiles of the old and the new importer we decided to keep it"),
+            Some(0.6)
+        );
+    }
+
+    #[test]
+    fn test_parse_jsonl_usage_and_tool_uses() {
+        let transcript = [
+            r#"{"type":"summary","summary":"Fix login"}"#,
+            r#"{"type":"user","sessionId":"s1","cwd":"/home/user/app","message":{"role":"user","content":"Fix the login bug"}}"#,
+            r#"{"type":"assistant","sessionId":"s1","message":{"role":"assistant","content":[{"type":"text","text":"Editing the handler."},{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/home/user/app/src/login.rs"}}],"usage":{"input_tokens":10,"cache_read_input_tokens":2000,"output_tokens":50}}}"#,
+            r#"{"type":"user","sessionId":"s1","message":{"role":"user","content":[{"type":"tool_result","tool_use_id":"t1","content":"ok"}]}}"#,
+            r#"{"type":"assistant","sessionId":"s1","message":{"role":"assistant","content":[{"type":"tool_use","id":"t2","name":"Write","input":{"file_path":"/tmp/notes.md"}}],"usage":{"input_tokens":20,"cache_read_input_tokens":2100,"output_tokens":30}}}"#,
+        ]
+        .join("\n");
+
+        let log = parse_conversation_log(&transcript).unwrap();
+        assert_eq!(log.conversation_id.as_deref(), Some("s1"));
+        assert_eq!(log.cwd.as_deref(), Some("/home/user/app"));
+        // The tool result is dropped, the tool-only reply is kept
+        assert_eq!(log.messages.len(), 3);
+        assert_eq!(log.estimate_tokens(), 2150);
+
+        let extractor = FactExtractor::new("test-project".to_string());
+        let paths: Vec<_> = log
+            .messages
+            .iter()
+            .flat_map(|m| extractor.extract_from_tool_uses(&m.tool_uses, log.cwd.as_deref(), None))
+            .map(|fact| fact.content)
+            .collect();
+        assert_eq!(paths, ["src/login.rs", "/tmp/notes.md"]);
+    }
+
+    #[test]
+    fn test_extract_from_shell_commands() {
+        let bash = |command: &str| ToolUse {
+            name: "Bash".to_string(),
+            input: serde_json::json!({ "command": command }),
+        };
+        let tool_uses = [
+            bash("cd /home/user/app && cargo add serde --features derive && cargo build"),
+            bash("npm install"),
+            bash("CI=1 npm install --save-dev vitest | tail -5"),
+            bash("python3 -m pip install requests\nuv pip install httpx"),
+            bash("git mv src/old.rs src/new.rs; rm -f /home/user/app/notes.txt; rm -rf target"),
+        ];
+
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_tool_uses(&tool_uses, Some("/home/user/app"), None);
+        let found: Vec<_> = facts.iter().map(|fact| (fact.fact_type, fact.content.as_str())).collect();
+        assert_eq!(
+            found,
+            [
+                (FactType::Dependency, "cargo add serde --features derive"),
+                (FactType::Dependency, "npm install --save-dev vitest"),
+                (FactType::Dependency, "pip install requests"),
+                (FactType::Dependency, "pip install httpx"),
+                (FactType::FileChange, "src/new.rs"),
+                (FactType::FileChange, "notes.txt"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_uses_replace_prose_file_changes() {
+        let transcript = [
+            r#"{"type":"assistant","sessionId":"s1","cwd":"/app","message":{"role":"assistant","content":[{"type":"text","text":"I updated the config loading in main.rs."},{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/app/src/main.rs"}}]}}"#,
+        ]
+        .join("\n");
+        let log = parse_conversation_log(&transcript).unwrap();
+
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_log(&log, None);
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::FileChange);
+        assert_eq!(facts[0].content, "src/main.rs");
+    }
+
+    #[test]
+    fn test_extract_snippets() {
+        let message = [
+            "The orders table, in `db/schema.sql`:",
+            "```sql",
+            "CREATE TABLE orders (",
+            "    id INTEGER PRIMARY KEY,",
+            "    total_cents INTEGER NOT NULL",
+            ");",
+            "```",
+            "Run it with:",
+            "```bash",
+            "sqlite3 shop.db < db/schema.sql",
+            "```",
+            "```ts",
+            "// src/api/types.ts",
+            "export interface Order {",
+            "  id: number;",
+            "}",
+            "```",
+            "```python",
+            "for order in orders:",
+            "    print(order)",
+            "    total += order.total",
+            "```",
+        ]
+        .join("\n");
+
+        let extractor = FactExtractor::new("test-project".to_string());
+        let snippets = extractor.extract_snippets(&message, None);
+        let found: Vec<_> = snippets
+            .iter()
+            .map(|fact| (fact.metadata.code_language.as_deref(), fact.metadata.target_file.as_deref()))
+            .collect();
+        assert_eq!(found, [(Some("sql"), Some("db/schema.sql")), (Some("ts"), Some("src/api/types.ts"))]);
+        assert_eq!(snippets[0].fact_type, FactType::Snippet);
+        assert!(snippets[0].content.starts_with("CREATE TABLE orders (\n"));
+        assert!(snippets[0].content.ends_with(");"));
+
+        // Also part of the facts of the message
+        let facts = extractor.extract_from_message(&message, None);
+        assert_eq!(facts.iter().filter(|fact| fact.fact_type == FactType::Snippet).count(), 2);
+    }
+
+    #[test]
+    fn test_code_block_target_files() {
+        let blocks = code_blocks(
+            "```rust:src/lib.rs\nfn a() {}\n```\n\n**config/app.yaml**\n~~~yaml title=\"config/prod.yaml\"\nport: 80\n~~~\n```toml {file=\"Cargo.toml\"}\n[package]\n```\n```json\n{}\n",
+        );
+        let found: Vec<_> = blocks
+            .iter()
+            .map(|block| (block.language.as_deref(), block.target_file.as_deref()))
+            .collect();
+        // The unclosed last block is left out
+        assert_eq!(
+            found,
+            [
+                (Some("rust"), Some("src/lib.rs")),
+                (Some("yaml"), Some("config/prod.yaml")),
+                (Some("toml"), Some("Cargo.toml")),
+            ]
+        );
+        assert!(code_blocks("Use ```x``` inline").is_empty());
+    }
+
+    #[test]
+    fn test_fact_sources() {
+        let transcript = [
+            r#"{"type":"user","sessionId":"s1","cwd":"/app","message":{"role":"user","content":"Why does checkout fail?"}}"#,
+            r#"{"type":"assistant","sessionId":"s1","message":{"role":"assistant","content":"Found that the total is rounded twice in `src/cart.py:42`."}}"#,
+            r#"{"type":"assistant","sessionId":"s1","message":{"role":"assistant","content":[{"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/app/src/cart.py"}}]}}"#,
+        ]
+        .join("\n");
+        let log = parse_conversation_log(&transcript).unwrap();
+
+        let extractor = FactExtractor::new("test-project".to_string()).with_log_path(Path::new("/logs/s1.jsonl"));
+        let facts = extractor.extract_from_log(&log, None);
+        let sources: Vec<_> = facts.iter().map(|fact| fact.metadata.source.clone().unwrap()).collect();
+        assert_eq!(
+            sources[0],
+            FactSource {
+                log_path: Some("/logs/s1.jsonl".to_string()),
+                conversation_id: Some("s1".to_string()),
+                message_index: Some(1),
+                file_path: Some("src/cart.py".to_string()),
+                file_line: Some(42),
+            }
+        );
+        assert_eq!(sources[0].file_location().as_deref(), Some("src/cart.py:42"));
+        assert_eq!(sources[1].message_index, Some(2));
+        assert_eq!(sources[1].file_location().as_deref(), Some("src/cart.py"));
+
+        // A line without a file name only gets its message
+        assert!(extractor.extract_from_message("We decided to use cents", None)[0].metadata.source.is_none());
+    }
+
+    #[test]
+    fn test_extract_whole_line_keeps_no_source() {
+        let extractor = FactExtractor::new("test-project".to_string());
+        let facts = extractor.extract_from_message("TODO: implement error handling", None);
+        assert_eq!(facts[0].content, "TODO: implement error handling");
+        assert_eq!(facts[0].metadata.source_line, None);
+    }
+}
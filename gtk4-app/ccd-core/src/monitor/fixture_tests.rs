@@ -0,0 +1,120 @@
+use super::{generate_session_title, is_conversation_log, parse_log_file, FactExtractor, LogMonitor};
+use crate::db::{create_test_db, Repository};
+use crate::models::{ProjectPayload, ProjectStatus};
+use crate::utils::ContextDiff;
+use std::path::{Path, PathBuf};
+
+/// Set to regenerate golden files instead of comparing against them
+const UPDATE_GOLDEN_ENV: &str = "CCD_UPDATE_GOLDEN";
+
+fn transcripts_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src/monitor/fixtures/transcripts")
+}
+
+/// Every transcript in the corpus, sorted by name
+fn transcripts() -> Vec<PathBuf> {
+    let mut paths: Vec<_> = std::fs::read_dir(transcripts_dir())
+        .expect("Failed to read transcript fixtures")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| is_conversation_log(path))
+        .collect();
+    paths.sort();
+    assert!(!paths.is_empty(), "No transcript fixtures found");
+    paths
+}
+
+fn golden_path(transcript: &Path) -> PathBuf {
+    let name = transcript.file_name().unwrap().to_string_lossy();
+    transcript.with_file_name(format!("{}.golden", name))
+}
+
+/// What the monitor gets out of a transcript, in a stable and reviewable form
+fn render(transcript: &Path, content: &str) -> String {
+    let log = parse_log_file(transcript, content).expect("Failed to parse transcript");
+    let facts = FactExtractor::new("fixture".to_string()).extract_from_log(&log, None);
+
+    let mut out = format!(
+        "conversation: {}\nmessages: {} ({} from assistant)\ntokens: {}\ntitle: {}\n\nfacts:\n",
+        log.conversation_id.as_deref().unwrap_or("-"),
+        log.messages.len(),
+        log.messages.iter().filter(|m| m.role == "assistant").count(),
+        log.estimate_tokens(),
+        generate_session_title(log.goal().as_deref(), &facts)
+    );
+
+    for fact in facts {
+        out.push_str(&format!(
+            "- {} ({}, {:.1}): {}\n",
+            fact.fact_type.display_name(),
+            fact.importance,
+            fact.metadata.confidence.unwrap_or(1.0),
+            fact.content
+        ));
+    }
+    out
+}
+
+#[test]
+fn test_transcripts_match_golden_files() {
+    let update = std::env::var_os(UPDATE_GOLDEN_ENV).is_some();
+    let mut mismatches = Vec::new();
+
+    for transcript in transcripts() {
+        let actual = render(&transcript, &std::fs::read_to_string(&transcript).unwrap());
+        let golden = golden_path(&transcript);
+
+        if update {
+            std::fs::write(&golden, &actual).unwrap();
+            continue;
+        }
+
+        let expected = std::fs::read_to_string(&golden).unwrap_or_default();
+        if expected != actual {
+            let name = golden.file_name().unwrap().to_string_lossy().into_owned();
+            mismatches.push(ContextDiff::new(&expected, &actual, &name).unified().to_string());
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Golden files differ (set {}=1 to regenerate, then review the diff):\n{}",
+        UPDATE_GOLDEN_ENV,
+        mismatches.join("\n")
+    );
+}
+
+#[test]
+fn test_monitor_stores_fixture_sessions() {
+    let repo = Repository::new(create_test_db().unwrap().into_shared());
+
+    for transcript in transcripts() {
+        let name = transcript.file_stem().unwrap().to_string_lossy().into_owned();
+        let project = repo
+            .create_project(ProjectPayload {
+                name: name.clone(),
+                slug: name.replace('_', "-"),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(transcripts_dir())).unwrap();
+        monitor.process_log_file(&transcript).unwrap();
+
+        let log = parse_log_file(&transcript, &std::fs::read_to_string(&transcript).unwrap()).unwrap();
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        assert_eq!(sessions.len(), 1, "{}", name);
+        let facts = FactExtractor::new(project.id.clone()).extract_from_log(&log, None);
+        assert_eq!(sessions[0].summary, generate_session_title(log.goal().as_deref(), &facts), "{}", name);
+        assert_eq!(sessions[0].token_count, log.estimate_tokens(), "{}", name);
+        assert_eq!(
+            sessions[0].facts_extracted as usize,
+            repo.list_facts(&project.id, true).unwrap().len(),
+            "{}",
+            name
+        );
+    }
+}
@@ -0,0 +1,571 @@
+use crate::monitor::extractor::SHELL_TOOL;
+use crate::monitor::{parse_conversation_log, ConversationLog, Message, TokenUsage, ToolUse};
+use anyhow::{bail, Context, Result};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// File aider appends its chat history to, in the directory it runs in
+pub const AIDER_HISTORY_FILE: &str = ".aider.chat.history.md";
+
+/// Environment variable naming Codex CLI's home directory (default `~/.codex`)
+const CODEX_HOME_ENV: &str = "CODEX_HOME";
+
+/// Bytes at the start of a file looked at to tell formats apart
+const SNIFF_BYTES: usize = 4096;
+
+/// Line aider starts each chat with
+const AIDER_CHAT_START: &str = "# aider chat started at ";
+
+/// Context Codex CLI sends as user messages, which the user didn't write
+const CODEX_INJECTED_PREFIXES: &[&str] = &["<environment_context>", "<user_instructions>", "# AGENTS.md instructions"];
+
+/// Coding agent a transcript was written by, which decides how it is parsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    ClaudeCode, // JSON or JSON Lines under ~/.claude/projects
+    Aider,      // Markdown chat history in the repository
+    Codex,      // JSON Lines rollouts under ~/.codex/sessions
+    Gemini,     // JSON chats, checkpoints and prompt logs under ~/.gemini/tmp/<project hash>
+}
+
+impl LogFormat {
+    /// Format of a transcript going by its path, or `None` if it isn't one
+    ///
+    /// Files in `.codex` and `.gemini` are taken to be theirs; any other JSON is
+    /// assumed to be Claude Code's until `detect` looks inside.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?;
+        if name.ends_with(AIDER_HISTORY_FILE) {
+            return Some(Self::Aider);
+        }
+
+        let extension = path.extension()?.to_str()?;
+        if !matches!(extension, "json" | "jsonl") {
+            return None;
+        }
+        let under = |dir: &str| path.components().any(|component| component.as_os_str() == dir);
+        if under(".gemini") {
+            // Settings and other state are kept next to the chats
+            let is_chat = name == "logs.json" || name.starts_with("session-") || name.starts_with("checkpoint");
+            return is_chat.then_some(Self::Gemini);
+        }
+        if under(".codex") {
+            return (extension == "jsonl").then_some(Self::Codex);
+        }
+        Some(Self::ClaudeCode)
+    }
+
+    /// Format of a transcript from its path and the start of its content
+    pub fn detect(path: &Path, content: &[u8]) -> Self {
+        match Self::from_path(path) {
+            Some(Self::ClaudeCode) | None => Self::sniff(&content[..content.len().min(SNIFF_BYTES)]),
+            Some(format) => format,
+        }
+    }
+
+    /// Tell other agents' files apart by their first record
+    fn sniff(head: &[u8]) -> Self {
+        let head = String::from_utf8_lossy(head);
+        let head = head.trim_start();
+        if head.starts_with(AIDER_CHAT_START.trim_end()) {
+            return Self::Aider;
+        }
+        if head.starts_with('{') && head.contains("\"projectHash\"")
+            || head.starts_with('[') && (head.contains("\"messageId\"") || head.contains("\"parts\""))
+        {
+            return Self::Gemini;
+        }
+
+        let first_line = head.lines().next().unwrap_or_default();
+        match serde_json::from_str::<Value>(first_line) {
+            // Rollouts wrap each record in `payload`; older ones start with the bare session
+            Ok(record) if record.get("payload").is_some() && record.get("type").is_some() => Self::Codex,
+            Ok(record) if record.get("instructions").is_some() && record.get("type").is_none() => Self::Codex,
+            _ => Self::ClaudeCode,
+        }
+    }
+
+    /// Name of the agent, for messages
+    pub fn agent_name(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "Claude Code",
+            Self::Aider => "aider",
+            Self::Codex => "Codex CLI",
+            Self::Gemini => "Gemini CLI",
+        }
+    }
+
+    /// Parse a whole transcript in this format
+    pub fn parse(self, content: &str) -> Result<ConversationLog> {
+        let mut log = match self {
+            Self::ClaudeCode => return parse_conversation_log(content),
+            Self::Aider => parse_aider(content),
+            Self::Codex => parse_codex(content),
+            Self::Gemini => parse_gemini(content)?,
+        };
+        log.finish();
+        Ok(log)
+    }
+}
+
+/// Parse a transcript of any supported agent
+pub fn parse_log_file(path: &Path, content: &str) -> Result<ConversationLog> {
+    let format = LogFormat::detect(path, content.as_bytes());
+    format
+        .parse(content)
+        .with_context(|| format!("Failed to parse {} transcript", format.agent_name()))
+}
+
+/// Where other agents keep transcripts of sessions in `repo_paths`
+///
+/// Codex CLI files all sessions by date, so its whole directory is returned;
+/// Gemini CLI and aider keep one directory per project. Only existing
+/// directories are returned, with whether to watch them recursively.
+pub fn agent_log_dirs(repo_paths: &[&Path]) -> Vec<(PathBuf, bool)> {
+    let mut dirs = Vec::new();
+
+    let codex_home = std::env::var_os(CODEX_HOME_ENV)
+        .filter(|home| !home.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| home::home_dir().map(|home| home.join(".codex")));
+    dirs.extend(codex_home.map(|home| (home.join("sessions"), true)));
+
+    let gemini_tmp = home::home_dir().map(|home| home.join(".gemini").join("tmp"));
+    for repo in repo_paths {
+        dirs.extend(gemini_tmp.as_ref().map(|tmp| (tmp.join(gemini_project_hash(repo)), true)));
+        // The history file is created on the first chat; its directory is watched until then
+        dirs.push((repo.to_path_buf(), false));
+    }
+
+    dirs.retain(|(dir, _)| dir.is_dir());
+    dirs
+}
+
+/// Name Gemini CLI gives a project's directory under `~/.gemini/tmp`
+pub fn gemini_project_hash(project_root: &Path) -> String {
+    format!("{:x}", Sha256::digest(project_root.to_string_lossy().as_bytes()))
+}
+
+/// Project hash of a file in Gemini CLI's `~/.gemini/tmp/<hash>/`
+pub fn gemini_hash_dir(path: &Path) -> Option<&str> {
+    let components: Vec<_> = path.components().map(|component| component.as_os_str()).collect();
+    components
+        .windows(3)
+        .find(|window| window[0] == ".gemini" && window[1] == "tmp")
+        .and_then(|window| window[2].to_str())
+}
+
+fn message(role: &str, content: String, tool_uses: Vec<ToolUse>) -> Message {
+    Message {
+        id: None,
+        role: role.to_string(),
+        content,
+        tool_uses,
+        model: None,
+        usage: None,
+    }
+}
+
+fn edit(path: &str) -> ToolUse {
+    ToolUse {
+        name: "Edit".to_string(),
+        input: json!({ "file_path": path }),
+    }
+}
+
+fn shell(command: &str) -> ToolUse {
+    ToolUse {
+        name: SHELL_TOOL.to_string(),
+        input: json!({ "command": command }),
+    }
+}
+
+/// Parse aider's chat history, keeping only the latest chat
+///
+/// aider appends every chat to the same file; `#### ` lines are the user's, lines
+/// quoted with `> ` are aider's own output and the rest is the model's reply.
+fn parse_aider(content: &str) -> ConversationLog {
+    let start = content
+        .match_indices(AIDER_CHAT_START)
+        .filter(|(i, _)| *i == 0 || content.as_bytes()[i - 1] == b'\n')
+        .map(|(i, _)| i)
+        .last()
+        .unwrap_or(0);
+    let mut lines = content[start..].lines().peekable();
+
+    let mut log = ConversationLog::default();
+    if let Some(started) = lines.peek().and_then(|line| line.strip_prefix(AIDER_CHAT_START)) {
+        log.conversation_id = Some(format!("aider-{}", started.trim()));
+        lines.next();
+    }
+
+    for line in lines {
+        let line = line.trim_end();
+        let (role, text) = if let Some(prompt) = line.strip_prefix("#### ") {
+            ("user", prompt)
+        } else if let Some(note) = line.strip_prefix('>') {
+            if let Some(path) = note.trim().strip_prefix("Applied edit to ") {
+                match log.messages.last_mut() {
+                    Some(last) if last.role == "assistant" => last.tool_uses.push(edit(path)),
+                    _ => log.messages.push(message("assistant", String::new(), vec![edit(path)])),
+                }
+            }
+            continue;
+        } else {
+            ("assistant", line)
+        };
+
+        match log.messages.last_mut() {
+            Some(last) if last.role == role => {
+                last.content.push('\n');
+                last.content.push_str(text);
+            }
+            // Blank lines only separate the replies that follow a prompt
+            _ if text.is_empty() => {}
+            _ => log.messages.push(message(role, text.to_string(), Vec::new())),
+        }
+    }
+
+    for message in &mut log.messages {
+        message.content = message.content.trim().to_string();
+    }
+    log
+}
+
+/// Parse a Codex CLI rollout
+///
+/// Each line is one record; the session's ID and working directory come first,
+/// followed by messages, tool calls and token counts in the order they happened.
+fn parse_codex(content: &str) -> ConversationLog {
+    let mut log = ConversationLog::default();
+    let mut model: Option<String> = None;
+
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let Ok(record) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let (kind, item) = match record.get("payload") {
+            Some(payload) => (record["type"].as_str().unwrap_or_default(), payload),
+            // Older rollouts: the session first, then bare response items
+            None if record.get("type").is_none() => ("session_meta", &record),
+            None => ("response_item", &record),
+        };
+
+        match kind {
+            "session_meta" => {
+                log.conversation_id = log.conversation_id.or(item["id"].as_str().map(str::to_string));
+                log.cwd = log.cwd.or(item["cwd"].as_str().map(str::to_string));
+            }
+            "turn_context" => {
+                model = item["model"].as_str().map(str::to_string).or(model);
+                log.cwd = log.cwd.or(item["cwd"].as_str().map(str::to_string));
+            }
+            "event_msg" if item["type"] == "token_count" => {
+                let usage = &item["info"]["last_token_usage"];
+                if usage.is_object() {
+                    let count = |field: &str| usage[field].as_i64().unwrap_or(0);
+                    let mut reply = message("assistant", String::new(), Vec::new());
+                    reply.model = model.clone();
+                    // Cached input is part of the input count
+                    reply.usage = Some(TokenUsage {
+                        input_tokens: count("input_tokens") - count("cached_input_tokens"),
+                        output_tokens: count("output_tokens"),
+                        cache_creation_input_tokens: 0,
+                        cache_read_input_tokens: count("cached_input_tokens"),
+                    });
+                    log.messages.push(reply);
+                }
+            }
+            "response_item" => log.messages.extend(codex_item(item)),
+            _ => {}
+        }
+    }
+    log
+}
+
+/// Message or tool call of a Codex CLI response item
+fn codex_item(item: &Value) -> Option<Message> {
+    match item["type"].as_str()? {
+        "message" => {
+            let role = item["role"].as_str().filter(|role| matches!(*role, "user" | "assistant"))?;
+            let texts: Vec<&str> = item["content"]
+                .as_array()?
+                .iter()
+                .filter_map(|block| block["text"].as_str())
+                .filter(|text| !CODEX_INJECTED_PREFIXES.iter().any(|prefix| text.trim_start().starts_with(prefix)))
+                .collect();
+            Some(message(role, texts.join("\n"), Vec::new()))
+        }
+        "function_call" => {
+            let arguments = item["arguments"].as_str().and_then(|arguments| serde_json::from_str(arguments).ok())?;
+            Some(message("assistant", String::new(), codex_tool_uses(item["name"].as_str()?, &arguments)))
+        }
+        "custom_tool_call" => {
+            let input = Value::String(item["input"].as_str()?.to_string());
+            Some(message("assistant", String::new(), codex_tool_uses(item["name"].as_str()?, &input)))
+        }
+        "local_shell_call" => {
+            let tool_uses = shell_tool_uses(&item["action"]["command"]);
+            Some(message("assistant", String::new(), tool_uses))
+        }
+        _ => None,
+    }
+}
+
+/// A Codex CLI tool call as the Claude Code tools the extractor knows
+fn codex_tool_uses(name: &str, arguments: &Value) -> Vec<ToolUse> {
+    match name {
+        "apply_patch" => {
+            let patch = arguments.as_str().or_else(|| arguments["input"].as_str()).unwrap_or_default();
+            patched_files(patch).map(edit).collect()
+        }
+        "shell" | "shell_command" | "container.exec" | "exec_command" => {
+            shell_tool_uses(arguments.get("command").or_else(|| arguments.get("cmd")).unwrap_or(&Value::Null))
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Shell commands given as a string or as an argument list
+///
+/// `bash -lc <script>` becomes the script, and `apply_patch <patch>` the files it changes.
+fn shell_tool_uses(command: &Value) -> Vec<ToolUse> {
+    if let Some(command) = command.as_str() {
+        return vec![shell(command)];
+    }
+
+    let words: Vec<&str> = command.as_array().into_iter().flatten().filter_map(Value::as_str).collect();
+    match words.as_slice() {
+        [] => Vec::new(),
+        ["apply_patch", patch] => patched_files(patch).map(edit).collect(),
+        ["bash" | "sh" | "zsh", "-lc" | "-c", script] => vec![shell(script)],
+        words => vec![shell(&words.join(" "))],
+    }
+}
+
+/// Files added, changed or deleted by an `apply_patch` patch
+fn patched_files(patch: &str) -> impl Iterator<Item = &str> {
+    patch.lines().filter_map(|line| {
+        ["*** Add File: ", "*** Update File: ", "*** Delete File: "]
+            .iter()
+            .find_map(|prefix| line.strip_prefix(prefix))
+            .map(str::trim)
+    })
+}
+
+/// Parse a Gemini CLI chat recording, saved checkpoint or prompt log
+fn parse_gemini(content: &str) -> Result<ConversationLog> {
+    let mut log = ConversationLog::default();
+
+    match serde_json::from_str::<Value>(content)? {
+        // Chat recording: {"sessionId", "projectHash", "messages": [...]}
+        Value::Object(chat) => {
+            log.conversation_id = chat.get("sessionId").and_then(Value::as_str).map(str::to_string);
+            for entry in chat.get("messages").and_then(Value::as_array).into_iter().flatten() {
+                let role = match entry["type"].as_str() {
+                    Some("user") => "user",
+                    Some("gemini") => "assistant",
+                    _ => continue, // info, warning and error notes
+                };
+                let tool_uses = entry["toolCalls"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|call| gemini_tool_use(call["name"].as_str()?, &call["args"]))
+                    .collect();
+
+                let mut message = message(role, gemini_text(&entry["content"]), tool_uses);
+                message.id = entry["id"].as_str().map(str::to_string);
+                message.model = entry["model"].as_str().map(str::to_string);
+                let tokens = &entry["tokens"];
+                if tokens.is_object() {
+                    let count = |field: &str| tokens[field].as_i64().unwrap_or(0);
+                    // Cached input is part of the input count, and thinking is output
+                    message.usage = Some(TokenUsage {
+                        input_tokens: count("input") - count("cached"),
+                        output_tokens: count("output") + count("thoughts"),
+                        cache_creation_input_tokens: 0,
+                        cache_read_input_tokens: count("cached"),
+                    });
+                }
+                log.messages.push(message);
+            }
+        }
+        // Checkpoint: the API history, [{"role": "user" | "model", "parts": [...]}]
+        Value::Array(entries) if entries.first().is_some_and(|entry| entry.get("parts").is_some()) => {
+            for entry in &entries {
+                let role = if entry["role"] == "model" { "assistant" } else { "user" };
+                let parts = entry["parts"].as_array().map(Vec::as_slice).unwrap_or_default();
+                let tool_uses = parts
+                    .iter()
+                    .filter_map(|part| {
+                        let call = part.get("functionCall")?;
+                        gemini_tool_use(call["name"].as_str()?, &call["args"])
+                    })
+                    .collect();
+                log.messages.push(message(role, gemini_text(&entry["parts"]), tool_uses));
+            }
+        }
+        // Prompt log of every session in the project: [{"sessionId", "type": "user", "message"}]
+        Value::Array(entries) => {
+            log.conversation_id = entries
+                .last()
+                .and_then(|entry| entry["sessionId"].as_str())
+                .map(str::to_string);
+            let prompts = entries
+                .iter()
+                .filter(|entry| entry["sessionId"].as_str() == log.conversation_id.as_deref())
+                .filter(|entry| entry["type"] == "user")
+                .filter_map(|entry| entry["message"].as_str());
+            log.messages
+                .extend(prompts.map(|prompt| message("user", prompt.to_string(), Vec::new())));
+        }
+        _ => bail!("Not a Gemini CLI chat"),
+    }
+    Ok(log)
+}
+
+/// Text of Gemini content given as a string or as parts
+fn gemini_text(content: &Value) -> String {
+    match content {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts
+            .iter()
+            .filter_map(|part| part["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => String::new(),
+    }
+}
+
+/// A Gemini CLI tool call as the Claude Code tools the extractor knows
+fn gemini_tool_use(name: &str, args: &Value) -> Option<ToolUse> {
+    match name {
+        "run_shell_command" => Some(shell(args["command"].as_str()?)),
+        "write_file" | "replace" | "edit" => {
+            Some(edit(args["file_path"].as_str().or_else(|| args["absolute_path"].as_str())?))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(LogFormat::from_path(Path::new("/home/u/shop/.aider.chat.history.md")), Some(LogFormat::Aider));
+        assert_eq!(LogFormat::from_path(Path::new("/home/u/shop/README.md")), None);
+        assert_eq!(
+            LogFormat::from_path(Path::new("/home/u/.codex/sessions/2025/10/01/rollout-1.jsonl")),
+            Some(LogFormat::Codex)
+        );
+        assert_eq!(
+            LogFormat::from_path(Path::new("/home/u/.gemini/tmp/ab12/chats/session-2025-10-01.json")),
+            Some(LogFormat::Gemini)
+        );
+        assert_eq!(LogFormat::from_path(Path::new("/home/u/.gemini/tmp/ab12/shell_history.json")), None);
+        assert_eq!(
+            LogFormat::from_path(Path::new("/home/u/.claude/projects/-home-u-shop/s1.jsonl")),
+            Some(LogFormat::ClaudeCode)
+        );
+    }
+
+    #[test]
+    fn test_detect_by_content() {
+        let path = Path::new("/tmp/logs/session.jsonl");
+        let codex = r#"{"timestamp":"2025-10-01T09:00:00Z","type":"session_meta","payload":{"id":"c1","cwd":"/srv/shop"}}"#;
+        assert_eq!(LogFormat::detect(path, codex.as_bytes()), LogFormat::Codex);
+        let legacy_codex = r#"{"id":"c1","timestamp":"2025-05-01T09:00:00Z","instructions":null}"#;
+        assert_eq!(LogFormat::detect(path, legacy_codex.as_bytes()), LogFormat::Codex);
+        let claude = r#"{"type":"user","sessionId":"s1","message":{"role":"user","content":"Hi"}}"#;
+        assert_eq!(LogFormat::detect(path, claude.as_bytes()), LogFormat::ClaudeCode);
+
+        let path = Path::new("/tmp/logs/chat.json");
+        let gemini = r#"{"sessionId":"g1","projectHash":"ab12","messages":[]}"#;
+        assert_eq!(LogFormat::detect(path, gemini.as_bytes()), LogFormat::Gemini);
+    }
+
+    #[test]
+    fn test_parse_aider_keeps_latest_chat() {
+        let history = "# aider chat started at 2025-09-30 17:00:00\n\n#### Rename the module\n\nDone.\n\n\
+            # aider chat started at 2025-10-01 09:00:00\n\n> Aider v0.86.1\n> Main model: sonnet\n\n\
+            #### Fix the rounding in cart totals  \n#### use cents everywhere  \n\n\
+            We decided to store prices as integer cents.\n\nsrc/cart.py\n\n\
+            > Applied edit to src/cart.py  \n> Commit 3f2a9c1 fix: Store cents  \n";
+
+        let log = LogFormat::Aider.parse(history).unwrap();
+        assert_eq!(log.conversation_id.as_deref(), Some("aider-2025-10-01 09:00:00"));
+        assert_eq!(log.messages.len(), 2);
+        assert_eq!(log.messages[0].content, "Fix the rounding in cart totals\nuse cents everywhere");
+        assert_eq!(log.messages[1].content, "We decided to store prices as integer cents.\n\nsrc/cart.py");
+        assert_eq!(log.messages[1].tool_uses[0].input["file_path"], "src/cart.py");
+    }
+
+    #[test]
+    fn test_parse_codex_tool_calls_and_usage() {
+        let rollout = [
+            r#"{"type":"session_meta","payload":{"id":"c1","cwd":"/srv/shop"}}"#,
+            r#"{"type":"turn_context","payload":{"cwd":"/srv/shop","model":"gpt-5-codex"}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"<environment_context>\n</environment_context>"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"Add a cart test"}]}}"#,
+            r#"{"type":"response_item","payload":{"type":"function_call","name":"shell","arguments":"{\"command\":[\"bash\",\"-lc\",\"cargo add proptest\"]}"}}"#,
+            r#"{"type":"response_item","payload":{"type":"custom_tool_call","name":"apply_patch","input":"*** Begin Patch\n*** Add File: tests/cart.rs\n+fn t() {}\n*** End Patch"}}"#,
+            r#"{"type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":1200,"cached_input_tokens":1000,"output_tokens":80}}}}"#,
+            r#"{"type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"Added the test."}]}}"#,
+        ]
+        .join("\n");
+
+        let log = LogFormat::Codex.parse(&rollout).unwrap();
+        assert_eq!(log.conversation_id.as_deref(), Some("c1"));
+        assert_eq!(log.cwd.as_deref(), Some("/srv/shop"));
+        let texts: Vec<_> = log.messages.iter().map(|m| m.content.as_str()).collect();
+        assert_eq!(texts, ["Add a cart test", "", "", "Added the test."]);
+        assert_eq!(log.messages[1].tool_uses[0].input["command"], "cargo add proptest");
+        assert_eq!(log.messages[2].tool_uses[0].input["file_path"], "tests/cart.rs");
+        assert_eq!(log.replies.len(), 1);
+        assert_eq!(log.replies[0].model.as_deref(), Some("gpt-5-codex"));
+        assert_eq!(log.replies[0].usage.cache_read_input_tokens, 1000);
+        assert_eq!(log.estimate_tokens(), 1280);
+    }
+
+    #[test]
+    fn test_parse_gemini_variants() {
+        let chat = r#"{"sessionId":"g1","projectHash":"ab12","messages":[
+            {"id":"m1","type":"user","content":"Why does checkout fail?"},
+            {"id":"m2","type":"info","content":"Switched model"},
+            {"id":"m3","type":"gemini","content":"The root cause is a stale token.","model":"gemini-2.5-pro",
+             "toolCalls":[{"name":"replace","args":{"file_path":"/srv/shop/auth.py"}}],
+             "tokens":{"input":500,"output":40,"cached":100,"thoughts":10}}]}"#;
+        let log = LogFormat::Gemini.parse(chat).unwrap();
+        assert_eq!(log.conversation_id.as_deref(), Some("g1"));
+        assert_eq!(log.messages.len(), 2);
+        assert_eq!(log.messages[1].role, "assistant");
+        assert_eq!(log.messages[1].tool_uses[0].input["file_path"], "/srv/shop/auth.py");
+        assert_eq!(log.replies[0].usage.output_tokens, 50);
+
+        let checkpoint = r#"[{"role":"user","parts":[{"text":"Run the tests"}]},
+            {"role":"model","parts":[{"functionCall":{"name":"run_shell_command","args":{"command":"pytest"}}}]}]"#;
+        let log = LogFormat::Gemini.parse(checkpoint).unwrap();
+        assert_eq!(log.messages[1].tool_uses[0].input["command"], "pytest");
+
+        let prompts = r#"[{"sessionId":"old","messageId":0,"type":"user","message":"Old prompt"},
+            {"sessionId":"new","messageId":0,"type":"user","message":"Add login"}]"#;
+        let log = LogFormat::Gemini.parse(prompts).unwrap();
+        assert_eq!(log.conversation_id.as_deref(), Some("new"));
+        assert_eq!(log.messages.len(), 1);
+        assert_eq!(log.goal().as_deref(), Some("Add login"));
+    }
+
+    #[test]
+    fn test_gemini_project_hash() {
+        assert_eq!(
+            gemini_project_hash(Path::new("/srv/shop")),
+            format!("{:x}", Sha256::digest(b"/srv/shop"))
+        );
+        assert_eq!(gemini_hash_dir(Path::new("/home/u/.gemini/tmp/ab12/chats/session-1.json")), Some("ab12"));
+        assert_eq!(gemini_hash_dir(Path::new("/tmp/ab12/logs.json")), None);
+    }
+}
@@ -0,0 +1,134 @@
+use crate::models::SessionCommit;
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::path::Path;
+use std::process::Command;
+
+/// Git executable
+const GIT: &str = "git";
+
+/// Separates the fields of one commit in `git log` output (ASCII unit separator)
+const FIELD_SEPARATOR: char = '\x1f';
+
+/// What a repository has checked out
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitHead {
+    pub branch: Option<String>, // None when HEAD is detached
+    pub sha: String,
+}
+
+/// Run git in `repo`, returning its output, or None when git reports an error
+/// (not a repository, no commits yet)
+fn git(repo: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = Command::new(GIT)
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .with_context(|| format!("Failed to run {} (is git installed?)", GIT))?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+    Ok(Some(String::from_utf8_lossy(&output.stdout).trim().to_string()))
+}
+
+/// The branch and commit checked out in `repo`, if it is a git repository with commits
+pub fn git_head(repo: &Path) -> Result<Option<GitHead>> {
+    let Some(sha) = git(repo, &["rev-parse", "--verify", "-q", "HEAD"])? else {
+        return Ok(None);
+    };
+    let branch = git(repo, &["symbolic-ref", "--short", "-q", "HEAD"])?.filter(|branch| !branch.is_empty());
+
+    Ok(Some(GitHead { branch, sha }))
+}
+
+/// Commits made in `repo` between `since` and `until` (now, if None), oldest first
+///
+/// With the commit a session started on, only commits reachable from HEAD but
+/// not from it count, so commits on other branches and rebased history are left
+/// out.
+pub fn commits_between(
+    repo: &Path,
+    start: Option<&str>,
+    since: DateTime<Utc>,
+    until: Option<DateTime<Utc>>,
+) -> Result<Vec<SessionCommit>> {
+    let format = format!("--format=%H{0}%an{0}%cI{0}%s", FIELD_SEPARATOR);
+    // Commit dates have whole seconds; a fraction would leave out commits made the same second
+    let since = format!("--since={}", since.to_rfc3339_opts(SecondsFormat::Secs, true));
+    let until = until.map(|until| format!("--until={}", until.to_rfc3339_opts(SecondsFormat::Secs, true)));
+    let range = start.map(|start| format!("{}..HEAD", start));
+
+    let mut args = vec!["log", "--reverse", format.as_str(), since.as_str()];
+    args.extend(until.as_deref());
+    args.push(range.as_deref().unwrap_or("HEAD"));
+
+    let Some(output) = git(repo, &args)? else {
+        return Ok(Vec::new());
+    };
+    Ok(output.lines().filter_map(parse_commit_line).collect())
+}
+
+fn parse_commit_line(line: &str) -> Option<SessionCommit> {
+    let mut fields = line.splitn(4, FIELD_SEPARATOR);
+    let sha = fields.next()?.to_string();
+    let author = fields.next()?.to_string();
+    let committed = DateTime::parse_from_rfc3339(fields.next()?).ok()?.with_timezone(&Utc);
+    let summary = fields.next().unwrap_or_default().to_string();
+
+    Some(SessionCommit {
+        sha,
+        summary,
+        author,
+        committed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(repo: &Path, args: &[&str]) {
+        let status = Command::new(GIT)
+            .arg("-C")
+            .arg(repo)
+            .args(args)
+            .env("GIT_AUTHOR_NAME", "Dev")
+            .env("GIT_AUTHOR_EMAIL", "dev@example.com")
+            .env("GIT_COMMITTER_NAME", "Dev")
+            .env("GIT_COMMITTER_EMAIL", "dev@example.com")
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_commits_between() {
+        let repo = std::env::temp_dir().join(format!("ccd-git-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&repo).unwrap();
+        assert_eq!(git_head(&repo).unwrap(), None);
+
+        run(&repo, &["init", "-q", "-b", "main"]);
+        assert_eq!(git_head(&repo).unwrap(), None);
+        run(&repo, &["commit", "-q", "--allow-empty", "-m", "Initial commit"]);
+
+        let start = git_head(&repo).unwrap().unwrap();
+        assert_eq!(start.branch.as_deref(), Some("main"));
+        let since = Utc::now() - chrono::Duration::minutes(1);
+
+        run(&repo, &["commit", "-q", "--allow-empty", "-m", "Store prices as cents"]);
+        run(&repo, &["commit", "-q", "--allow-empty", "-m", "Migrate old carts\n\nWith a backfill."]);
+
+        let commits = commits_between(&repo, Some(&start.sha), since, None).unwrap();
+        let summaries: Vec<_> = commits.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(summaries, ["Store prices as cents", "Migrate old carts"]);
+        assert_eq!(commits[0].author, "Dev");
+        assert_eq!(commits[1].sha, git_head(&repo).unwrap().unwrap().sha);
+
+        // Commits after the session ended don't count
+        assert!(commits_between(&repo, Some(&start.sha), since, Some(since)).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&repo).unwrap();
+    }
+}
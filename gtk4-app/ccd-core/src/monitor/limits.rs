@@ -0,0 +1,161 @@
+use crate::db::Repository;
+use crate::models::BackgroundAggressiveness;
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+const MIB: u64 = 1024 * 1024;
+
+/// I/O scheduling applied to monitor threads (Linux only)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoPriority {
+    Unchanged,
+    Low,  // Lowest best-effort level
+    Idle, // Only when no other process needs the disk
+}
+
+/// Resource guards for the background monitor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceLimits {
+    pub max_log_bytes: u64,          // Larger log files are skipped instead of read into memory
+    pub max_concurrent_files: usize, // Log files parsed at once, across all monitors
+    pub nice: i32,                   // Niceness of monitor threads (0 = unchanged)
+    pub io_priority: IoPriority,
+    pub pause: Duration, // Pause after each processed file
+}
+
+impl ResourceLimits {
+    pub fn for_aggressiveness(level: BackgroundAggressiveness) -> Self {
+        match level {
+            BackgroundAggressiveness::Low => Self {
+                max_log_bytes: 16 * MIB,
+                max_concurrent_files: 1,
+                nice: 19,
+                io_priority: IoPriority::Idle,
+                pause: Duration::from_millis(500),
+            },
+            BackgroundAggressiveness::Normal => Self {
+                max_log_bytes: 64 * MIB,
+                max_concurrent_files: 2,
+                nice: 10,
+                io_priority: IoPriority::Low,
+                pause: Duration::from_millis(50),
+            },
+            BackgroundAggressiveness::High => Self {
+                max_log_bytes: 256 * MIB,
+                max_concurrent_files: 4,
+                nice: 0,
+                io_priority: IoPriority::Unchanged,
+                pause: Duration::ZERO,
+            },
+        }
+    }
+
+    /// Limits for the aggressiveness saved in settings
+    pub fn from_settings(repository: &Repository) -> Self {
+        let level = repository
+            .load_settings()
+            .map(|settings| settings.monitor_aggressiveness)
+            .unwrap_or_default();
+        Self::for_aggressiveness(level)
+    }
+
+    /// Lower the CPU and I/O priority of the calling thread
+    ///
+    /// Only ever lowers priority; failures are logged and otherwise ignored.
+    #[cfg(target_os = "linux")]
+    pub fn apply_to_current_thread(&self) {
+        // Linux keeps niceness and I/O priority per thread
+        let tid = unsafe { libc::syscall(libc::SYS_gettid) } as libc::id_t;
+
+        if self.nice > 0 {
+            let current = unsafe { libc::getpriority(libc::PRIO_PROCESS, tid) };
+            if self.nice > current && unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, self.nice) } != 0 {
+                log::debug!("Failed to set monitor niceness: {}", std::io::Error::last_os_error());
+            }
+        }
+
+        const IOPRIO_WHO_PROCESS: libc::c_long = 1;
+        const IOPRIO_CLASS_SHIFT: libc::c_long = 13;
+        let ioprio = match self.io_priority {
+            IoPriority::Unchanged => return,
+            IoPriority::Low => (2 << IOPRIO_CLASS_SHIFT) | 7, // best-effort, level 7
+            IoPriority::Idle => 3 << IOPRIO_CLASS_SHIFT,
+        };
+        if unsafe { libc::syscall(libc::SYS_ioprio_set, IOPRIO_WHO_PROCESS, tid as libc::c_long, ioprio) } != 0 {
+            log::debug!("Failed to set monitor I/O priority: {}", std::io::Error::last_os_error());
+        }
+    }
+
+    /// Lower the CPU and I/O priority of the calling thread
+    #[cfg(not(target_os = "linux"))]
+    pub fn apply_to_current_thread(&self) {}
+}
+
+/// Number of log files currently being parsed in this process
+static ACTIVE_FILES: Mutex<usize> = Mutex::new(0);
+static SLOT_RELEASED: Condvar = Condvar::new();
+
+/// A claimed processing slot, released on drop
+pub struct ProcessingSlot(());
+
+impl ProcessingSlot {
+    /// Wait until fewer than `max` files are being processed, then claim a slot
+    pub fn acquire(max: usize) -> Self {
+        let mut active = ACTIVE_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        while *active >= max.max(1) {
+            active = SLOT_RELEASED.wait(active).unwrap_or_else(|e| e.into_inner());
+        }
+        *active += 1;
+        Self(())
+    }
+}
+
+impl Drop for ProcessingSlot {
+    fn drop(&mut self) {
+        let mut active = ACTIVE_FILES.lock().unwrap_or_else(|e| e.into_inner());
+        *active -= 1;
+        SLOT_RELEASED.notify_all();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_limits_scale_with_aggressiveness() {
+        let low = ResourceLimits::for_aggressiveness(BackgroundAggressiveness::Low);
+        let high = ResourceLimits::for_aggressiveness(BackgroundAggressiveness::High);
+
+        assert!(low.max_log_bytes < high.max_log_bytes);
+        assert!(low.max_concurrent_files < high.max_concurrent_files);
+        assert!(low.nice > high.nice);
+    }
+
+    #[test]
+    fn test_processing_slots_cap_concurrency() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+
+        let workers: Vec<_> = (0..6)
+            .map(|_| {
+                let running = running.clone();
+                let peak = peak.clone();
+                std::thread::spawn(move || {
+                    let _slot = ProcessingSlot::acquire(2);
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        assert!(peak.load(Ordering::SeqCst) <= 2);
+    }
+}
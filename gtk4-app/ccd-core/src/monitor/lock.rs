@@ -0,0 +1,110 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the directory holding the monitors' project locks
+pub const MONITOR_LOCK_DIR_ENV: &str = "CCD_MONITOR_LOCK_DIR";
+
+/// Directory of the project locks: `CCD_MONITOR_LOCK_DIR`, or monitors/ in the runtime directory
+pub fn monitor_lock_dir() -> PathBuf {
+    if let Some(dir) = std::env::var_os(MONITOR_LOCK_DIR_ENV).filter(|dir| !dir.is_empty()) {
+        return PathBuf::from(dir);
+    }
+
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("claude-context-tracker")
+        .join("monitors")
+}
+
+/// Lock showing that a process monitors a project, so a second monitor (the GUI
+/// toggle and a `monitor` daemon, say) doesn't extract its facts again
+///
+/// The lock goes away with the process, so a lock file left behind by a crash
+/// doesn't keep the project from being monitored. The file itself stays; it only
+/// holds the PID of the last process to lock it.
+pub struct ProjectLock {
+    _file: File,
+}
+
+impl ProjectLock {
+    /// Lock `project_id` in `dir` and record this process's ID; None while another process holds it
+    pub fn try_acquire(dir: &Path, project_id: &str) -> Result<Option<Self>> {
+        std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let path = Self::path(dir, project_id);
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        if !try_lock_exclusive(&file).with_context(|| format!("Failed to lock {}", path.display()))? {
+            return Ok(None);
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        writeln!(file, "{}", std::process::id())?;
+
+        Ok(Some(Self { _file: file }))
+    }
+
+    /// ID of the process that last locked `project_id`, if it says
+    pub fn holder(dir: &Path, project_id: &str) -> Option<u32> {
+        let mut pid = String::new();
+        File::open(Self::path(dir, project_id)).ok()?.read_to_string(&mut pid).ok()?;
+        pid.trim().parse().ok()
+    }
+
+    fn path(dir: &Path, project_id: &str) -> PathBuf {
+        dir.join(format!("{}.lock", project_id))
+    }
+}
+
+/// Take an exclusive lock on `file` without waiting; false if someone else has it
+///
+/// The lock lasts until the file is closed, by drop or by the process exiting.
+#[cfg(target_os = "linux")]
+pub fn try_lock_exclusive(file: &File) -> std::io::Result<bool> {
+    use std::os::fd::AsRawFd;
+
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let error = std::io::Error::last_os_error();
+    if error.kind() == std::io::ErrorKind::WouldBlock {
+        return Ok(false);
+    }
+    Err(error)
+}
+
+/// Take an exclusive lock on `file` without waiting; false if someone else has it
+#[cfg(not(target_os = "linux"))]
+pub fn try_lock_exclusive(_file: &File) -> std::io::Result<bool> {
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_lock_is_exclusive() {
+        let dir = std::env::temp_dir().join(format!("ccd-locks-{}", uuid::Uuid::new_v4()));
+
+        let lock = ProjectLock::try_acquire(&dir, "shop").unwrap().unwrap();
+        assert_eq!(ProjectLock::holder(&dir, "shop"), Some(std::process::id()));
+        // flock locks belong to the open file, so a second open in the same process conflicts too
+        assert!(ProjectLock::try_acquire(&dir, "shop").unwrap().is_none());
+        assert!(ProjectLock::try_acquire(&dir, "blog").unwrap().is_some());
+
+        drop(lock);
+        assert!(ProjectLock::try_acquire(&dir, "shop").unwrap().is_some());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
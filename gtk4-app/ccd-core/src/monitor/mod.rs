@@ -0,0 +1,35 @@
+pub mod watcher;
+pub mod extractor;
+pub mod scorer;
+pub mod limits;
+pub mod benchmark;
+pub mod event;
+pub mod title;
+pub mod rules;
+pub mod backend;
+pub mod git;
+pub mod pricing;
+pub mod formats;
+pub mod context_file;
+pub mod quality;
+pub mod lock;
+
+/// Golden-file tests over the transcript corpus in `monitor/fixtures`
+#[cfg(test)]
+mod fixture_tests;
+
+pub use watcher::*;
+pub use extractor::*;
+pub use scorer::*;
+pub use limits::*;
+pub use benchmark::*;
+pub use event::*;
+pub use title::*;
+pub use rules::*;
+pub use backend::*;
+pub use git::*;
+pub use pricing::*;
+pub use formats::*;
+pub use context_file::*;
+pub use quality::*;
+pub use lock::*;
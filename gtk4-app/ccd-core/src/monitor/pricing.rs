@@ -0,0 +1,211 @@
+use crate::models::SessionUsage;
+use crate::monitor::ReplyUsage;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at the model pricing file
+pub const PRICING_CONFIG_ENV: &str = "CCD_PRICING_CONFIG";
+
+/// Tokens the prices are given for
+const TOKENS_PER_PRICE: f64 = 1_000_000.0;
+
+/// List prices in USD per million tokens: input, output, cache write (5 minutes), cache read
+const BUILTIN_PRICES: &[(&str, f64, f64, f64, f64)] = &[
+    ("claude-opus-4-5", 5.0, 25.0, 6.25, 0.50),
+    ("claude-opus-4", 15.0, 75.0, 18.75, 1.50),
+    ("claude-sonnet-4", 3.0, 15.0, 3.75, 0.30),
+    ("claude-haiku-4-5", 1.0, 5.0, 1.25, 0.10),
+    ("claude-3-7-sonnet", 3.0, 15.0, 3.75, 0.30),
+    ("claude-3-5-sonnet", 3.0, 15.0, 3.75, 0.30),
+    ("claude-3-5-haiku", 0.80, 4.0, 1.0, 0.08),
+    ("claude-3-opus", 15.0, 75.0, 18.75, 1.50),
+    ("claude-3-haiku", 0.25, 1.25, 0.30, 0.03),
+];
+
+/// Prices of the models whose IDs start with `model`, in USD per million tokens
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    pub model: String, // e.g. "claude-sonnet-4" for claude-sonnet-4-20250514 and claude-sonnet-4-5
+    pub input: f64,
+    pub output: f64,
+    pub cache_write: f64,
+    pub cache_read: f64,
+}
+
+/// Contents of the pricing file; its models are checked before the built-in ones
+#[derive(Debug, Default, Deserialize)]
+struct PricingFile {
+    #[serde(default)]
+    models: Vec<ModelPrice>,
+}
+
+/// Per-model prices used to cost session usage
+#[derive(Debug, Clone, PartialEq)]
+pub struct PricingTable {
+    prices: Vec<ModelPrice>,
+}
+
+impl Default for PricingTable {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl PricingTable {
+    /// `prices` followed by the built-in list prices
+    pub fn new(prices: Vec<ModelPrice>) -> Self {
+        let builtin = BUILTIN_PRICES
+            .iter()
+            .map(|(model, input, output, cache_write, cache_read)| ModelPrice {
+                model: model.to_string(),
+                input: *input,
+                output: *output,
+                cache_write: *cache_write,
+                cache_read: *cache_read,
+            });
+
+        Self {
+            prices: prices.into_iter().chain(builtin).collect(),
+        }
+    }
+
+    /// Table with the prices in the configured file
+    pub fn configured() -> Result<Self> {
+        match pricing_config_path() {
+            Some(path) => load_pricing_table(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Price of a model: the entry with the longest matching prefix, the first of equals
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        self.prices
+            .iter()
+            .filter(|price| model.starts_with(&price.model))
+            .fold(None, |best: Option<&ModelPrice>, price| match best {
+                Some(best) if best.model.len() >= price.model.len() => Some(best),
+                _ => Some(price),
+            })
+    }
+
+    /// Tokens and cost of a conversation's replies
+    ///
+    /// Replies by models without a price count towards the tokens but cost nothing.
+    pub fn session_usage(&self, replies: &[ReplyUsage]) -> SessionUsage {
+        let mut total = SessionUsage::default();
+        for reply in replies {
+            let usage = &reply.usage;
+            let price = reply.model.as_deref().and_then(|model| self.price_for(model));
+            if price.is_none() {
+                log::debug!("No price for model {:?}", reply.model);
+            }
+
+            total = total.plus(&SessionUsage {
+                input_tokens: usage.input_tokens,
+                output_tokens: usage.output_tokens,
+                cache_write_tokens: usage.cache_creation_input_tokens,
+                cache_read_tokens: usage.cache_read_input_tokens,
+                cost_usd: price.map_or(0.0, |price| {
+                    (usage.input_tokens as f64 * price.input
+                        + usage.output_tokens as f64 * price.output
+                        + usage.cache_creation_input_tokens as f64 * price.cache_write
+                        + usage.cache_read_input_tokens as f64 * price.cache_read)
+                        / TOKENS_PER_PRICE
+                }),
+            });
+        }
+        total
+    }
+}
+
+/// Location of the pricing file: `CCD_PRICING_CONFIG`, or pricing.json in the XDG config directory
+pub fn pricing_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(PRICING_CONFIG_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    dirs::config_dir().map(|dir| dir.join("claude-context-tracker").join("pricing.json"))
+}
+
+/// Read the prices in `path`; a missing file means the built-in prices only
+pub fn load_pricing_table(path: &Path) -> Result<PricingTable> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(PricingTable::default()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    let file: PricingFile =
+        serde_json::from_str(&contents).with_context(|| format!("Invalid model prices in {}", path.display()))?;
+
+    Ok(PricingTable::new(file.models))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::TokenUsage;
+
+    fn reply(model: &str, input: i64, output: i64, cache_write: i64, cache_read: i64) -> ReplyUsage {
+        ReplyUsage {
+            model: Some(model.to_string()),
+            usage: TokenUsage {
+                input_tokens: input,
+                output_tokens: output,
+                cache_creation_input_tokens: cache_write,
+                cache_read_input_tokens: cache_read,
+            },
+        }
+    }
+
+    #[test]
+    fn test_price_for_longest_prefix() {
+        let table = PricingTable::default();
+        assert_eq!(table.price_for("claude-opus-4-5-20251101").unwrap().input, 5.0);
+        assert_eq!(table.price_for("claude-opus-4-1-20250805").unwrap().input, 15.0);
+        assert_eq!(table.price_for("claude-sonnet-4-5-20250929").unwrap().output, 15.0);
+        assert!(table.price_for("<synthetic>").is_none());
+    }
+
+    #[test]
+    fn test_session_usage() {
+        let table = PricingTable::default();
+        let usage = table.session_usage(&[
+            reply("claude-sonnet-4-5-20250929", 1_000, 2_000, 10_000, 100_000),
+            reply("claude-haiku-4-5-20251001", 1_000_000, 0, 0, 0),
+            reply("<synthetic>", 50, 10, 0, 0),
+        ]);
+
+        assert_eq!(usage.input_tokens, 1_001_050);
+        assert_eq!(usage.output_tokens, 2_010);
+        // Sonnet: 0.003 + 0.03 + 0.0375 + 0.03; Haiku: 1.00
+        assert!((usage.cost_usd - 1.1005).abs() < 1e-9, "{}", usage.cost_usd);
+    }
+
+    #[test]
+    fn test_load_pricing_table() {
+        let dir = std::env::temp_dir().join(format!("ccd-pricing-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pricing.json");
+
+        assert_eq!(load_pricing_table(&path).unwrap(), PricingTable::default());
+
+        std::fs::write(
+            &path,
+            r#"{"models": [
+                {"model": "claude-sonnet-4", "input": 2.5, "output": 12.5, "cache_write": 3.0, "cache_read": 0.25},
+                {"model": "llama", "input": 0.0, "output": 0.0, "cache_write": 0.0, "cache_read": 0.0}
+            ]}"#,
+        )
+        .unwrap();
+        let table = load_pricing_table(&path).unwrap();
+        assert_eq!(table.price_for("claude-sonnet-4-20250514").unwrap().input, 2.5);
+        assert_eq!(table.price_for("llama-3.3-70b").unwrap().output, 0.0);
+        assert_eq!(table.price_for("claude-opus-4-5").unwrap().input, 5.0);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_pricing_table(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,233 @@
+use crate::db::Repository;
+use crate::models::{ExtractedFactPayload, FactType, DEFAULT_MAX_SESSION_FACTS};
+use crate::monitor::{extraction_rules_path, load_quality_rules};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+/// Shortest fact kept, in characters
+pub const DEFAULT_MIN_FACT_LENGTH: usize = 15;
+
+/// Phrases marking a line as the agent narrating its next step rather than stating a fact
+const DEFAULT_STOP_PHRASES: &[&str] = &[
+    "let me",
+    "let's",
+    "i'll",
+    "need to check",
+    "need to look",
+    "need to read",
+    "need to see",
+    "need to find",
+    "need to understand",
+    "have to check",
+    "should work",
+    "should be fine",
+    "should be good",
+    "should now",
+    "should see",
+];
+
+/// Fact types whose content is a path, package or code rather than prose; the gate lets them through
+const EXEMPT_TYPES: &[FactType] = &[FactType::FileChange, FactType::Dependency, FactType::Snippet];
+
+/// Filter that extracted facts pass before they are saved
+///
+/// Drops short facts, facts containing a stop phrase and all but the most
+/// important fact taken from any one line, then keeps the most important facts
+/// up to the per-session cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FactQuality {
+    pub min_length: usize,
+    pub stop_phrases: Vec<String>, // Lowercase, matched as whole words
+    pub max_per_session: usize,    // 0 = no limit
+}
+
+impl Default for FactQuality {
+    fn default() -> Self {
+        Self {
+            min_length: DEFAULT_MIN_FACT_LENGTH,
+            stop_phrases: DEFAULT_STOP_PHRASES.iter().map(|phrase| phrase.to_string()).collect(),
+            max_per_session: DEFAULT_MAX_SESSION_FACTS,
+        }
+    }
+}
+
+impl FactQuality {
+    /// The cap from the settings, with the length and stop phrases from the extraction rules file
+    pub fn configured(repository: &Repository) -> Self {
+        let mut quality = Self::default();
+        if let Ok(settings) = repository.load_settings() {
+            quality.max_per_session = settings.max_session_facts;
+        }
+
+        // Keep tests independent of the developer's rules
+        let Some(path) = extraction_rules_path().filter(|_| !cfg!(test)) else {
+            return quality;
+        };
+        match load_quality_rules(&path) {
+            Ok(rules) => {
+                if let Some(min_length) = rules.min_length {
+                    quality.min_length = min_length;
+                }
+                if let Some(stop_phrases) = rules.stop_phrases {
+                    quality.stop_phrases = stop_phrases.iter().map(|phrase| phrase.to_lowercase()).collect();
+                }
+            }
+            Err(e) => log::warn!("Ignoring quality rules: {:#}", e),
+        }
+        quality
+    }
+
+    /// Whether a fact is worth keeping on its own
+    pub fn passes(&self, fact: &ExtractedFactPayload) -> bool {
+        if EXEMPT_TYPES.contains(&fact.fact_type) {
+            return true;
+        }
+
+        let content = fact.content.trim().to_lowercase();
+        content.chars().count() >= self.min_length
+            && !self.stop_phrases.iter().any(|phrase| contains_phrase(&content, phrase))
+    }
+
+    /// The facts of a session worth saving, in their original order
+    ///
+    /// `saved` is the number of facts the session already has, which count towards the cap.
+    pub fn filter(&self, facts: Vec<ExtractedFactPayload>, saved: usize) -> Vec<ExtractedFactPayload> {
+        let mut facts: Vec<_> = facts.into_iter().filter(|fact| self.passes(fact)).collect();
+
+        // A line matching several patterns (or repeated later) keeps its most important fact
+        let mut best: HashMap<String, usize> = HashMap::new();
+        for (index, fact) in facts.iter().enumerate() {
+            if EXEMPT_TYPES.contains(&fact.fact_type) {
+                continue;
+            }
+            best.entry(line_key(fact))
+                .and_modify(|kept| {
+                    if fact.importance > facts[*kept].importance {
+                        *kept = index;
+                    }
+                })
+                .or_insert(index);
+        }
+        let mut index = 0;
+        facts.retain(|fact| {
+            let keep = EXEMPT_TYPES.contains(&fact.fact_type) || best.get(&line_key(fact)) == Some(&index);
+            index += 1;
+            keep
+        });
+
+        if self.max_per_session == 0 {
+            return facts;
+        }
+        let limit = self.max_per_session.saturating_sub(saved);
+        if facts.len() > limit {
+            let mut ranked: Vec<usize> = (0..facts.len()).collect();
+            ranked.sort_by_key(|&index| Reverse(facts[index].importance));
+            let kept: HashSet<usize> = ranked.into_iter().take(limit).collect();
+            log::info!("Keeping the {} most important of {} facts for the session", limit, facts.len());
+
+            let mut index = 0;
+            facts.retain(|_| {
+                index += 1;
+                kept.contains(&(index - 1))
+            });
+        }
+        facts
+    }
+}
+
+/// The line a fact was taken from, lowercased with its whitespace collapsed
+fn line_key(fact: &ExtractedFactPayload) -> String {
+    let line = fact.metadata.source_line.as_deref().unwrap_or(&fact.content);
+    line.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Whether `phrase` occurs in `text` as whole words
+fn contains_phrase(text: &str, phrase: &str) -> bool {
+    text.match_indices(phrase).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + phrase.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FactMetadata;
+
+    fn fact(fact_type: FactType, content: &str, importance: i32, line: Option<&str>) -> ExtractedFactPayload {
+        ExtractedFactPayload {
+            project: "p1".to_string(),
+            session: None,
+            fact_type,
+            content: content.to_string(),
+            importance,
+            stale: None,
+            metadata: FactMetadata {
+                source_line: line.map(str::to_string),
+                ..FactMetadata::default()
+            },
+            needs_review: Some(true),
+        }
+    }
+
+    #[test]
+    fn test_quality_gate() {
+        let quality = FactQuality::default();
+
+        assert!(quality.passes(&fact(FactType::Decision, "Decided to use SQLite for storage", 4, None)));
+        assert!(!quality.passes(&fact(FactType::Todo, "need to fix it", 3, None)));
+        assert!(!quality.passes(&fact(FactType::Todo, "Need to check the config loader first", 3, None)));
+        assert!(!quality.passes(&fact(FactType::Todo, "That should work now for every user", 3, None)));
+        // Whole words only, and paths are exempt
+        assert!(quality.passes(&fact(FactType::Todo, "must keep the outlet's balance checks", 3, None)));
+        assert!(quality.passes(&fact(FactType::FileChange, "src/a.rs", 3, None)));
+    }
+
+    #[test]
+    fn test_one_fact_per_line() {
+        let line = "Failed to connect, need to retry the database migration";
+        let facts = vec![
+            fact(FactType::Todo, "need to retry the database migration", 3, Some(line)),
+            fact(FactType::Blocker, "Failed to connect, need to retry", 5, Some(line)),
+            fact(FactType::Decision, "Decided to use SQLite for storage", 4, None),
+            fact(FactType::Decision, "decided to use  SQLite for storage", 4, None),
+        ];
+
+        let kept = FactQuality::default().filter(facts, 0);
+        let contents: Vec<&str> = kept.iter().map(|fact| fact.content.as_str()).collect();
+        assert_eq!(contents, ["Failed to connect, need to retry", "Decided to use SQLite for storage"]);
+    }
+
+    #[test]
+    fn test_session_cap_keeps_most_important() {
+        let facts = vec![
+            fact(FactType::Insight, "Found that the cache is per process", 3, None),
+            fact(FactType::Blocker, "Blocked by the expired staging certificate", 5, None),
+            fact(FactType::Todo, "TODO: document the release checklist", 3, None),
+            fact(FactType::Decision, "Decided to use SQLite for storage", 4, None),
+        ];
+        let quality = FactQuality {
+            max_per_session: 5,
+            ..FactQuality::default()
+        };
+
+        let kept = quality.filter(facts.clone(), 2);
+        let contents: Vec<&str> = kept.iter().map(|fact| fact.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            [
+                "Found that the cache is per process",
+                "Blocked by the expired staging certificate",
+                "Decided to use SQLite for storage"
+            ]
+        );
+        assert!(quality.filter(facts.clone(), 5).is_empty());
+
+        let unlimited = FactQuality {
+            max_per_session: 0,
+            ..FactQuality::default()
+        };
+        assert_eq!(unlimited.filter(facts, 100).len(), 4);
+    }
+}
@@ -0,0 +1,149 @@
+use crate::models::FactType;
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Environment variable pointing at the extraction rules file
+pub const EXTRACTION_RULES_ENV: &str = "CCD_EXTRACTION_RULES";
+
+/// Importance of facts from rules that don't set one
+const DEFAULT_RULE_IMPORTANCE: i32 = 3;
+
+/// A fact pattern defined in the extraction rules file
+///
+/// Like the built-in patterns, a `fact` named group picks the part of the line
+/// to keep; without one the whole line becomes the fact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractionRule {
+    pub pattern: String,
+    pub fact_type: FactType,
+    #[serde(default = "default_importance")]
+    pub importance: i32,
+}
+
+fn default_importance() -> i32 {
+    DEFAULT_RULE_IMPORTANCE
+}
+
+/// Contents of the extraction rules file
+#[derive(Debug, Default, Deserialize)]
+struct RulesFile {
+    #[serde(default)]
+    rules: Vec<ExtractionRule>,
+    #[serde(default)]
+    quality: QualityRules,
+}
+
+/// Overrides for the quality gate facts pass before they are saved (see `FactQuality`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct QualityRules {
+    pub min_length: Option<usize>,
+    pub stop_phrases: Option<Vec<String>>, // Replaces the built-in list
+}
+
+/// Location of the extraction rules file: `CCD_EXTRACTION_RULES`, or
+/// extraction-rules.json in the XDG config directory
+pub fn extraction_rules_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(EXTRACTION_RULES_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    dirs::config_dir().map(|dir| dir.join("claude-context-tracker").join("extraction-rules.json"))
+}
+
+/// Load and compile the rules in `path`
+///
+/// A missing file means no extra rules. Rules whose pattern doesn't compile are
+/// skipped with a warning so one typo doesn't stop extraction altogether.
+pub fn load_extraction_rules(path: &Path) -> Result<Vec<(ExtractionRule, Regex)>> {
+    let file = read_rules_file(path)?;
+
+    let mut rules = Vec::new();
+    for mut rule in file.rules {
+        match Regex::new(&rule.pattern) {
+            Ok(regex) => {
+                rule.importance = rule.importance.clamp(1, 5);
+                rules.push((rule, regex));
+            }
+            Err(e) => log::warn!("Skipping extraction rule {:?}: {}", rule.pattern, e),
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Load the quality gate overrides in `path`; a missing file overrides nothing
+pub fn load_quality_rules(path: &Path) -> Result<QualityRules> {
+    Ok(read_rules_file(path)?.quality)
+}
+
+fn read_rules_file(path: &Path) -> Result<RulesFile> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(RulesFile::default()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    serde_json::from_str(&contents).with_context(|| format!("Invalid extraction rules in {}", path.display()))
+}
+
+/// Extraction rules from the configured file, logging (rather than failing on) problems
+pub fn load_configured_rules() -> Vec<(ExtractionRule, Regex)> {
+    let Some(path) = extraction_rules_path() else {
+        return Vec::new();
+    };
+
+    match load_extraction_rules(&path) {
+        Ok(rules) => {
+            if !rules.is_empty() {
+                log::info!("Loaded {} extraction rules from {}", rules.len(), path.display());
+            }
+            rules
+        }
+        Err(e) => {
+            log::warn!("Ignoring extraction rules: {:#}", e);
+            Vec::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_extraction_rules() {
+        let dir = std::env::temp_dir().join(format!("ccd-rules-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("extraction-rules.json");
+
+        assert!(load_extraction_rules(&path).unwrap().is_empty());
+
+        std::fs::write(
+            &path,
+            r#"{"rules": [
+                {"pattern": "(?i)(?P<fact>ticket [A-Z]+-\\d+)", "fact_type": "todo"},
+                {"pattern": "(?i)compliance:", "fact_type": "blocker", "importance": 9},
+                {"pattern": "unclosed (", "fact_type": "insight"}
+            ]}"#,
+        )
+        .unwrap();
+        let rules = load_extraction_rules(&path).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].0.fact_type, FactType::Todo);
+        assert_eq!(rules[0].0.importance, DEFAULT_RULE_IMPORTANCE);
+        assert_eq!(rules[1].0.importance, 5);
+        assert_eq!(load_quality_rules(&path).unwrap(), QualityRules::default());
+
+        std::fs::write(&path, r#"{"quality": {"min_length": 20, "stop_phrases": ["let me"]}}"#).unwrap();
+        assert!(load_extraction_rules(&path).unwrap().is_empty());
+        let quality = load_quality_rules(&path).unwrap();
+        assert_eq!(quality.min_length, Some(20));
+        assert_eq!(quality.stop_phrases, Some(vec!["let me".to_string()]));
+
+        std::fs::write(&path, "not json").unwrap();
+        assert!(load_extraction_rules(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
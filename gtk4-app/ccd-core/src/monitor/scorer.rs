@@ -0,0 +1,425 @@
+use crate::models::{ExtractedFact, FactType};
+use crate::utils::normalize_content;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Share of an open fact's subject words a later fact must mention to resolve it
+const RESOLUTION_OVERLAP: f64 = 0.6;
+
+/// Fewest subject words the two facts must share, so "fixed it" resolves nothing
+const RESOLUTION_MIN_SHARED_WORDS: usize = 2;
+
+/// Words that say nothing about what a fact is about
+const IGNORED_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "was", "were", "has", "have", "had", "now", "not", "but",
+    "from", "into", "when", "then", "all", "are", "can", "cannot", "still", "need", "needs", "should", "must",
+    "todo", "fixme", "blocked", "proceed", "continue", "fix", "fixed", "resolved", "done", "completed",
+    "finished", "merged", "closed", "issue", "problem",
+];
+
+/// Environment variable pointing at the importance scoring config file
+pub const SCORING_CONFIG_ENV: &str = "CCD_SCORING_CONFIG";
+
+/// Words that raise a fact's importance, and by how much
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeywordBonus {
+    pub keywords: Vec<String>, // Matched case-insensitively anywhere in the content
+    pub bonus: i32,
+}
+
+/// Weights of the importance scorer; every field is optional in the config file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScoringWeights {
+    /// Base score per fact type, for the types that differ from the built-in ones
+    pub base_scores: HashMap<FactType, i32>,
+    pub keyword_bonuses: Vec<KeywordBonus>,
+    pub long_content_chars: usize, // Content longer than this gets `long_content_bonus`
+    pub long_content_bonus: i32,
+    pub max_content_bonus: i32, // Cap on keyword and length bonuses together
+    pub recent_hours: i64,      // Facts younger than this get `recent_bonus`
+    pub recent_bonus: i32,
+    pub old_hours: i64, // Facts older than this get `old_bonus`
+    pub old_bonus: i32,
+}
+
+impl Default for ScoringWeights {
+    fn default() -> Self {
+        let keywords = |words: &[&str], bonus| KeywordBonus {
+            keywords: words.iter().map(|word| word.to_string()).collect(),
+            bonus,
+        };
+
+        Self {
+            base_scores: HashMap::new(),
+            keyword_bonuses: vec![
+                keywords(&["critical", "urgent", "blocker", "security"], 1), // Critical keywords
+                keywords(&["breaking", "incompatible"], 1),                  // Breaking changes
+                keywords(&["slow", "performance", "optimization"], 1),       // Performance issues
+            ],
+            long_content_chars: 200,
+            long_content_bonus: 1,
+            max_content_bonus: 2,
+            recent_hours: 1,
+            recent_bonus: 1,
+            old_hours: 24,
+            old_bonus: -1,
+        }
+    }
+}
+
+/// Location of the scoring config file: `CCD_SCORING_CONFIG`, or scoring.json
+/// in the XDG config directory
+pub fn scoring_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(SCORING_CONFIG_ENV).filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+
+    dirs::config_dir().map(|dir| dir.join("claude-context-tracker").join("scoring.json"))
+}
+
+/// Read the scoring weights in `path`; a missing file means the defaults
+pub fn load_scoring_weights(path: &Path) -> Result<ScoringWeights> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(ScoringWeights::default()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+    serde_json::from_str(&contents).with_context(|| format!("Invalid scoring weights in {}", path.display()))
+}
+
+/// Importance scorer for extracted facts
+#[derive(Debug, Clone, Default)]
+pub struct ImportanceScorer {
+    weights: ScoringWeights,
+}
+
+impl ImportanceScorer {
+    pub fn new(weights: ScoringWeights) -> Self {
+        Self { weights }
+    }
+
+    /// Scorer with the weights in the configured file
+    pub fn configured() -> Result<Self> {
+        let weights = match scoring_config_path() {
+            Some(path) => load_scoring_weights(&path)?,
+            None => ScoringWeights::default(),
+        };
+        Ok(Self::new(weights))
+    }
+
+    /// Calculate final importance score (1-5) for a fact
+    pub fn calculate_score(&self, fact: &ExtractedFact) -> i32 {
+        let base_score = self.base_score_for_type(fact.fact_type);
+        let content_bonus = self.analyze_content(&fact.content);
+        let recency_bonus = self.recency_bonus(&fact.created);
+
+        let total = base_score + content_bonus + recency_bonus;
+
+        // Clamp to 1-5 range
+        total.clamp(1, 5)
+    }
+
+    /// Base score by fact type
+    fn base_score_for_type(&self, fact_type: FactType) -> i32 {
+        if let Some(score) = self.weights.base_scores.get(&fact_type) {
+            return *score;
+        }
+
+        match fact_type {
+            FactType::Blocker => 5,      // Blockers are always high priority
+            FactType::Decision => 4,     // Decisions are very important
+            FactType::Dependency => 4,   // New dependencies are important
+            FactType::FileChange => 3,   // File changes are medium
+            FactType::Todo => 3,         // Todos are medium
+            FactType::Insight => 3,      // Insights are medium
+            FactType::Snippet => 3,      // Snippets are medium
+        }
+    }
+
+    /// Analyze content for importance keywords
+    fn analyze_content(&self, content: &str) -> i32 {
+        let content_lower = content.to_lowercase();
+        let mut bonus = 0;
+
+        for group in &self.weights.keyword_bonuses {
+            if group.keywords.iter().any(|keyword| content_lower.contains(&keyword.to_lowercase())) {
+                bonus += group.bonus;
+            }
+        }
+
+        // Longer content might be more important
+        if content.len() > self.weights.long_content_chars {
+            bonus += self.weights.long_content_bonus;
+        }
+
+        bonus.min(self.weights.max_content_bonus)
+    }
+
+    /// Recency bonus (newer facts are more important)
+    fn recency_bonus(&self, created: &DateTime<Utc>) -> i32 {
+        let now = Utc::now();
+        let age = now.signed_duration_since(*created);
+
+        if age < Duration::hours(self.weights.recent_hours) {
+            self.weights.recent_bonus
+        } else if age < Duration::hours(self.weights.old_hours) {
+            0
+        } else {
+            self.weights.old_bonus
+        }
+    }
+}
+
+/// Staleness detector for facts
+pub struct StalenessDetector;
+
+impl StalenessDetector {
+    /// Check if a fact should be marked as stale
+    pub fn is_stale(fact: &ExtractedFact) -> bool {
+        let now = Utc::now();
+        let age = now.signed_duration_since(fact.created);
+
+        // Content-based staleness; a snippet's code says nothing about whether it is done
+        if fact.fact_type != FactType::Snippet && Self::has_completion_keywords(&fact.content) {
+            return true;
+        }
+
+        // Time-based staleness by type
+        let stale_threshold = match fact.fact_type {
+            FactType::Blocker => Duration::days(3),       // Blockers should be resolved quickly
+            FactType::Todo => Duration::days(14),         // Todos have 2 weeks
+            FactType::FileChange => Duration::days(30),   // File changes are relevant for a month
+            FactType::Dependency => Duration::days(90),   // Dependencies stay relevant longer
+            FactType::Decision => Duration::days(180),    // Decisions are long-lived
+            FactType::Insight => Duration::days(90),      // Insights stay relevant
+            FactType::Snippet => Duration::days(60),      // Schemas and configs change now and then
+        };
+
+        age > stale_threshold
+    }
+
+    /// The fact among `facts` that says `open` has been dealt with, if any
+    ///
+    /// Only blockers and todos can be resolved, by a fact recorded no earlier that
+    /// reports completion ("fixed the database connection error") and mentions
+    /// most of what the open fact is about.
+    pub fn resolved_by<'a>(open: &ExtractedFact, facts: &'a [ExtractedFact]) -> Option<&'a ExtractedFact> {
+        if open.stale || !matches!(open.fact_type, FactType::Blocker | FactType::Todo) {
+            return None;
+        }
+
+        let subject = Self::subject_words(&open.content);
+        if subject.len() < RESOLUTION_MIN_SHARED_WORDS {
+            return None;
+        }
+
+        facts.iter().find(|later| {
+            later.id != open.id
+                && later.created >= open.created
+                && Self::has_completion_keywords(&later.content)
+                && {
+                    let shared = subject.intersection(&Self::subject_words(&later.content)).count();
+                    shared >= RESOLUTION_MIN_SHARED_WORDS && shared as f64 / subject.len() as f64 >= RESOLUTION_OVERLAP
+                }
+        })
+    }
+
+    /// Words of a fact that name its subject, roughly stemmed so that
+    /// "connections" matches "connection" and "failing" matches "failed"
+    fn subject_words(content: &str) -> HashSet<String> {
+        normalize_content(content)
+            .split(' ')
+            .filter(|word| word.len() > 2 && !IGNORED_WORDS.contains(word))
+            .map(|word| {
+                ["ing", "ed", "es", "s"]
+                    .iter()
+                    .find(|suffix| word.len() > suffix.len() + 3 && word.ends_with(*suffix))
+                    .map_or(word, |suffix| &word[..word.len() - suffix.len()])
+                    .to_string()
+            })
+            .collect()
+    }
+
+    /// Check for keywords indicating completion/resolution
+    fn has_completion_keywords(content: &str) -> bool {
+        let content_lower = content.to_lowercase();
+
+        content_lower.contains("resolved") ||
+        content_lower.contains("fixed") ||
+        content_lower.contains("done") ||
+        content_lower.contains("completed") ||
+        content_lower.contains("finished") ||
+        content_lower.contains("merged") ||
+        content_lower.contains("closed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FactMetadata;
+
+    #[test]
+    fn test_blocker_has_high_score() {
+        let fact = ExtractedFact {
+            id: "test".to_string(),
+            project: "proj".to_string(),
+            session: None,
+            fact_type: FactType::Blocker,
+            content: "Error in production".to_string(),
+            importance: 0,
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created: Utc::now(),
+            updated: Utc::now(),
+        };
+
+        let score = ImportanceScorer::default().calculate_score(&fact);
+        assert!(score >= 4, "Blockers should have high score");
+    }
+
+    #[test]
+    fn test_critical_keyword_bonus() {
+        let fact = ExtractedFact {
+            id: "test".to_string(),
+            project: "proj".to_string(),
+            session: None,
+            fact_type: FactType::Todo,
+            content: "CRITICAL: Fix security vulnerability".to_string(),
+            importance: 0,
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created: Utc::now(),
+            updated: Utc::now(),
+        };
+
+        let score = ImportanceScorer::default().calculate_score(&fact);
+        assert!(score >= 4, "Critical todos should get bonus");
+    }
+
+    #[test]
+    fn test_configured_weights() {
+        let dir = std::env::temp_dir().join(format!("ccd-scoring-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("scoring.json");
+
+        assert_eq!(load_scoring_weights(&path).unwrap(), ScoringWeights::default());
+
+        std::fs::write(
+            &path,
+            r#"{"base_scores": {"insight": 1},
+                "keyword_bonuses": [{"keywords": ["GDPR"], "bonus": 3}],
+                "max_content_bonus": 3,
+                "recent_bonus": 0}"#,
+        )
+        .unwrap();
+        let weights = load_scoring_weights(&path).unwrap();
+        assert_eq!(weights.old_hours, 24);
+        let scorer = ImportanceScorer::new(weights);
+
+        let mut insight = fact("i", FactType::Insight, "Found that the cache is slow", 0);
+        assert_eq!(scorer.calculate_score(&insight), 1);
+        insight.content = "Found that the export breaks gdpr rules".to_string();
+        assert_eq!(scorer.calculate_score(&insight), 4);
+        let blocker = fact("b", FactType::Blocker, "Failed to deploy", 0);
+        assert_eq!(scorer.calculate_score(&blocker), 5);
+
+        std::fs::write(&path, r#"{"base_scores": {"rumor": 2}}"#).unwrap();
+        assert!(load_scoring_weights(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_old_blocker_is_stale() {
+        let fact = ExtractedFact {
+            id: "test".to_string(),
+            project: "proj".to_string(),
+            session: None,
+            fact_type: FactType::Blocker,
+            content: "Some old blocker".to_string(),
+            importance: 5,
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created: Utc::now() - Duration::days(5),
+            updated: Utc::now() - Duration::days(5),
+        };
+
+        assert!(StalenessDetector::is_stale(&fact), "Old blocker should be stale");
+    }
+
+    #[test]
+    fn test_resolved_is_stale() {
+        let fact = ExtractedFact {
+            id: "test".to_string(),
+            project: "proj".to_string(),
+            session: None,
+            fact_type: FactType::Todo,
+            content: "TODO: Fix bug - RESOLVED".to_string(),
+            importance: 3,
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created: Utc::now(),
+            updated: Utc::now(),
+        };
+
+        assert!(StalenessDetector::is_stale(&fact), "Resolved fact should be stale");
+    }
+
+    fn fact(id: &str, fact_type: FactType, content: &str, minutes_ago: i64) -> ExtractedFact {
+        let created = Utc::now() - Duration::minutes(minutes_ago);
+        ExtractedFact {
+            id: id.to_string(),
+            project: "proj".to_string(),
+            session: None,
+            fact_type,
+            content: content.to_string(),
+            importance: 3,
+            stale: false,
+            metadata: FactMetadata::default(),
+            needs_review: false,
+            created,
+            updated: created,
+        }
+    }
+
+    #[test]
+    fn test_later_fact_resolves_blocker() {
+        let blocker = fact("b", FactType::Blocker, "Error: database connections refused by the pool", 60);
+        let todo = fact("t", FactType::Todo, "TODO: add retries to the payment webhook", 60);
+        let facts = vec![
+            blocker.clone(),
+            todo.clone(),
+            fact("other", FactType::Insight, "Fixed the login redirect", 30),
+            fact("fix", FactType::Insight, "Fixed the database connection refusal in the pool", 10),
+        ];
+
+        assert_eq!(StalenessDetector::resolved_by(&blocker, &facts).map(|f| f.id.as_str()), Some("fix"));
+        assert!(StalenessDetector::resolved_by(&todo, &facts).is_none());
+
+        // A report from before the blocker was seen doesn't resolve it
+        let earlier = [fact("old", FactType::Insight, "Fixed the database connections pool", 120)];
+        assert!(StalenessDetector::resolved_by(&blocker, &earlier).is_none());
+    }
+
+    #[test]
+    fn test_only_open_facts_are_resolved() {
+        let facts = vec![fact("fix", FactType::Insight, "Done with the payment webhook retries", 0)];
+
+        let decision = fact("d", FactType::Decision, "Decided to add payment webhook retries", 60);
+        assert!(StalenessDetector::resolved_by(&decision, &facts).is_none());
+
+        let mut todo = fact("t", FactType::Todo, "TODO: payment webhook retries", 60);
+        assert!(StalenessDetector::resolved_by(&todo, &facts).is_some());
+        todo.stale = true;
+        assert!(StalenessDetector::resolved_by(&todo, &facts).is_none());
+    }
+}
@@ -0,0 +1,292 @@
+use crate::db::Repository;
+use crate::models::{ExtractedFactPayload, FactType, SessionPayload};
+use crate::monitor::{read_session_transcript, FactExtractor};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Longest session title, in characters
+pub const TITLE_MAX_CHARS: usize = 100;
+
+/// Longest goal or decision kept in a title, in characters
+const TITLE_PART_MAX_CHARS: usize = 40;
+
+/// Shortest a decision is cut to, however long the rest of the title
+const DECISION_MIN_CHARS: usize = 24;
+
+/// Files named in a title; the rest are counted
+const TITLE_FILES: usize = 1;
+
+/// Opening phrases that say nothing about the goal (lowercase)
+const GOAL_FILLER: &[&str] = &[
+    "hey", "hi", "hello", "ok", "okay", "so", "please", "can you", "could you", "would you", "i want to",
+    "i'd like to", "i would like to", "i need to", "we need to", "help me", "let's", "lets",
+];
+
+/// Words a cut title part must not end on (lowercase)
+const DANGLING_WORDS: &[&str] = &[
+    "a", "an", "the", "to", "of", "for", "and", "or", "in", "on", "with", "from", "by", "so", "that", "die", "der",
+    "das", "und",
+];
+
+/// Openings of decision facts that repeat what the fact type already says (lowercase)
+const DECISION_FILLER: &[&str] = &["we decided to", "decided to", "we chose to", "chose to", "i'll", "we'll"];
+
+/// Goal of a conversation: the first sentence of the opening message, without filler
+///
+/// Returns `None` when nothing is left, e.g. for a bare greeting.
+pub fn detect_goal(message: &str) -> Option<String> {
+    let first_line = message.lines().map(str::trim).find(|line| !line.is_empty())?;
+    let text = strip_filler(first_line, GOAL_FILLER);
+    let sentence = text
+        .split_inclusive(['.', '!', '?'])
+        .next()
+        .unwrap_or(text)
+        .trim_end_matches(['.', '!', '?', ':', ',']);
+
+    (!sentence.is_empty()).then(|| capitalize(cut_at_word(sentence, TITLE_PART_MAX_CHARS)))
+}
+
+/// One-line session title from the goal, the most touched files and the key decision
+///
+/// For example `Auth middleware: added JWT validation in src/auth.rs`. Facts should
+/// not be deduplicated yet: how often a file comes up decides which files are named.
+pub fn generate_session_title(goal: Option<&str>, facts: &[ExtractedFactPayload]) -> String {
+    let files = top_files(facts);
+
+    // The decision gets what room the goal and files leave
+    let reserved = goal.map_or(0, |goal| goal.chars().count() + 2)
+        + files.as_ref().map_or(0, |files| files.chars().count() + 4);
+    let decision_chars = TITLE_MAX_CHARS.saturating_sub(reserved).clamp(DECISION_MIN_CHARS, TITLE_PART_MAX_CHARS);
+    let decision = facts
+        .iter()
+        .filter(|fact| fact.fact_type == FactType::Decision)
+        .max_by_key(|fact| fact.importance) // The first of the most important
+        .map(|fact| decapitalize(cut_at_word(strip_filler(&fact.content, DECISION_FILLER), decision_chars)))
+        .filter(|decision| !decision.is_empty());
+    let detail = match (decision, files) {
+        (Some(decision), Some(files)) => Some(format!("{} in {}", decision, files)),
+        (Some(decision), None) => Some(decision),
+        (None, Some(files)) => Some(format!("changes in {}", files)),
+        (None, None) => None,
+    };
+
+    let title = match (goal, detail) {
+        (Some(goal), Some(detail)) => format!("{}: {}", goal, detail),
+        (Some(goal), None) => goal.to_string(),
+        (None, Some(detail)) => capitalize(&detail),
+        (None, None) => "Conversation".to_string(),
+    };
+    shorten(&title, TITLE_MAX_CHARS)
+}
+
+/// Rebuild a session's title from its transcript and save it
+pub fn regenerate_session_title(repository: &Repository, session_id: &str) -> Result<String> {
+    let mut session = repository.get_session(session_id)?;
+    let log = read_session_transcript(repository, session_id)?;
+
+    let facts = FactExtractor::new(session.project.clone()).extract_from_log(&log, None);
+    session.summary = generate_session_title(log.goal().as_deref(), &facts);
+    repository.update_session(session_id, SessionPayload::from(&session))?;
+
+    Ok(session.summary)
+}
+
+/// Most often changed file and how many others changed, e.g. `src/auth.rs +3 more`
+///
+/// Files outside the working directory are still absolute; only their name is shown.
+fn top_files(facts: &[ExtractedFactPayload]) -> Option<String> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    let mut index = HashMap::new();
+    for fact in facts.iter().filter(|fact| fact.fact_type == FactType::FileChange) {
+        let i = *index.entry(fact.content.as_str()).or_insert_with(|| {
+            counts.push((fact.content.as_str(), 0));
+            counts.len() - 1
+        });
+        counts[i].1 += 1;
+    }
+    if counts.is_empty() {
+        return None;
+    }
+
+    // Stable sort keeps first-touched order among equals
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    let named: Vec<&str> = counts
+        .iter()
+        .take(TITLE_FILES)
+        .map(|(path, _)| match Path::new(path).file_name() {
+            Some(name) if Path::new(path).is_absolute() => name.to_str().unwrap_or(path),
+            _ => path,
+        })
+        .collect();
+    Some(match counts.len().saturating_sub(TITLE_FILES) {
+        0 => named.join(", "),
+        more => format!("{} +{} more", named.join(", "), more),
+    })
+}
+
+/// Drop any of `fillers` (and the punctuation after them) from the start of `text`, repeatedly
+fn strip_filler<'a>(text: &'a str, fillers: &[&str]) -> &'a str {
+    let mut text = text.trim();
+    while let Some(filler) = fillers.iter().find(|filler| {
+        text.get(..filler.len()).is_some_and(|start| start.eq_ignore_ascii_case(filler))
+            && text[filler.len()..].chars().next().is_none_or(|c| !c.is_alphanumeric())
+    }) {
+        text = text[filler.len()..].trim_start_matches([',', '!', ' ']).trim_start();
+    }
+    text
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Lowercase the first letter, unless it starts an acronym like "JWT"
+fn decapitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match (chars.next(), chars.next()) {
+        (Some(first), second) if !second.is_some_and(char::is_uppercase) => {
+            first.to_lowercase().chain(text[first.len_utf8()..].chars()).collect()
+        }
+        _ => text.to_string(),
+    }
+}
+
+/// Cut `text` to at most `max_chars`, at a word boundary where possible
+fn cut_at_word(text: &str, max_chars: usize) -> &str {
+    let Some((end, _)) = text.char_indices().nth(max_chars) else {
+        return text;
+    };
+
+    let mut cut = &text[..end];
+    if let Some(space) = cut.rfind(' ').filter(|&space| space > cut.len() / 2) {
+        cut = &cut[..space];
+    }
+    loop {
+        cut = cut.trim_end_matches([' ', ',', ':', ';']);
+        match cut.rsplit_once(' ') {
+            Some((rest, word)) if DANGLING_WORDS.iter().any(|w| w.eq_ignore_ascii_case(word)) => cut = rest,
+            _ => return cut,
+        }
+    }
+}
+
+/// Cut `text` to `max_chars` like [`cut_at_word`], marking the cut with "..."
+pub(crate) fn shorten(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    format!("{}...", cut_at_word(text, max_chars - 3))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_test_db;
+    use crate::models::{FactMetadata, ProjectPayload, ProjectStatus};
+    use crate::monitor::LogMonitor;
+
+    fn fact(fact_type: FactType, content: &str, importance: i32) -> ExtractedFactPayload {
+        ExtractedFactPayload {
+            project: "test-project".to_string(),
+            session: None,
+            fact_type,
+            content: content.to_string(),
+            importance,
+            stale: None,
+            metadata: FactMetadata::default(),
+            needs_review: None,
+        }
+    }
+
+    #[test]
+    fn test_detect_goal() {
+        assert_eq!(
+            detect_goal("Hi! Can you add JWT validation to the middleware? It should reject expired tokens.").as_deref(),
+            Some("Add JWT validation to the middleware")
+        );
+        assert_eq!(detect_goal("Let's migrate the job queue.\nDetails follow").as_deref(), Some("Migrate the job queue"));
+        assert_eq!(detect_goal("Hello!"), None);
+        assert_eq!(
+            detect_goal("Im Warenkorb werden Rabattcodes doppelt angewendet, wenn man die Seite neu lädt").as_deref(),
+            Some("Im Warenkorb werden Rabattcodes doppelt")
+        );
+    }
+
+    #[test]
+    fn test_generate_session_title() {
+        let facts = [
+            fact(FactType::FileChange, "src/main.rs", 3),
+            fact(FactType::Decision, "Decided to add JWT validation", 4),
+            fact(FactType::FileChange, "src/auth.rs", 3),
+            fact(FactType::FileChange, "src/auth.rs", 3),
+            fact(FactType::Todo, "need to add refresh tokens", 3),
+        ];
+        assert_eq!(
+            generate_session_title(Some("Auth middleware"), &facts),
+            "Auth middleware: add JWT validation in src/auth.rs +1 more"
+        );
+
+        let files = ["/outside/a.rs", "b.rs", "c.rs"].map(|path| fact(FactType::FileChange, path, 3));
+        assert_eq!(generate_session_title(Some("Refactor"), &files), "Refactor: changes in a.rs +2 more");
+        assert_eq!(generate_session_title(None, &facts[1..2]), "Add JWT validation");
+        assert_eq!(generate_session_title(None, &[]), "Conversation");
+    }
+
+    #[test]
+    fn test_title_is_shortened() {
+        let goal = "Goal ".repeat(30);
+        let title = generate_session_title(Some(&goal), &[]);
+        assert!(title.chars().count() <= TITLE_MAX_CHARS);
+        assert!(title.ends_with("Goal..."));
+    }
+
+    #[test]
+    fn test_regenerate_session_title() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-title-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"user","message":{"role":"user","content":"Please fix the cart totals."}}"#,
+                "\n",
+                r#"{"type":"assistant","message":{"role":"assistant","content":"We decided to store cents."}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+        LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .process_log_file(&path)
+            .unwrap();
+
+        let mut session = repo.list_sessions(&project.id).unwrap().remove(0);
+        assert_eq!(session.summary, "Fix the cart totals: store cents");
+
+        session.summary = "Edited".to_string();
+        repo.update_session(&session.id, SessionPayload::from(&session)).unwrap();
+        assert_eq!(regenerate_session_title(&repo, &session.id).unwrap(), "Fix the cart totals: store cents");
+        assert_eq!(repo.get_session(&session.id).unwrap().summary, "Fix the cart totals: store cents");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(regenerate_session_title(&repo, &session.id).is_err());
+    }
+}
@@ -0,0 +1,2191 @@
+use crate::db::Repository;
+use crate::models::{
+    AuditActor, DEFAULT_SESSION_IDLE_MINUTES, DEFAULT_TOKEN_THRESHOLD, EventRecordPayload, ExtractionPreferences,
+    ProcessedLogPayload, Project, SessionHistory, SessionPayload, SessionUsage, format_duration,
+};
+use crate::monitor::extractor::ConversationLog;
+use crate::monitor::{
+    FactExtractor, FactQuality, ImportanceScorer, PricingTable, ProcessingSlot, ProjectLock, ResourceLimits,
+    SessionExtractor, StalenessDetector, ENRICH_BELOW_FACTS, LogFormat, agent_log_dirs, commits_between,
+    context_file_path, enrich_session, gemini_hash_dir, gemini_project_hash, git_head, generate_session_title,
+    monitor_lock_dir, parse_transcript_lines, read_context_file_edits,
+};
+use crate::utils::hash_bytes;
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use notify::{Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher as NotifyWatcher};
+use std::collections::{HashMap, HashSet};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// How often the event loop checks whether it was asked to stop
+const STOP_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A file is processed once it has seen no events for this long
+const DEBOUNCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// ...or at the latest this long after its first event, for files written non-stop
+const DEBOUNCE_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// How often the event loop looks for sessions that went idle
+const IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Something the monitor noticed that a frontend may want to tell the user about
+///
+/// Published to external integrations through [`EventEnvelope`](crate::monitor::EventEnvelope);
+/// changing a variant's name or fields changes that schema.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MonitorEvent {
+    /// New facts were saved from a conversation log
+    FactsExtracted { project: String, count: usize },
+    /// A conversation grew past the configured token threshold
+    TokenThreshold { project: String, tokens: usize, threshold: usize },
+    /// A conversation saw no new lines for the configured idle period
+    SessionEnded {
+        project: String,
+        summary: String,
+        duration_minutes: u64,
+        facts: usize,
+        tokens: usize,
+    },
+    /// The CLAUDE.md in the project's repository was edited by hand
+    ContextFileEdited { project: String, path: String, sections: usize },
+}
+
+impl MonitorEvent {
+    /// Every event type with its display name, in the form stored and published
+    pub const TYPES: [(&'static str, &'static str); 4] = [
+        ("facts_extracted", "Facts extracted"),
+        ("token_threshold", "Token threshold"),
+        ("session_ended", "Session ended"),
+        ("context_file_edited", "CLAUDE.md edited"),
+    ];
+
+    /// Event type as stored and published (the `type` field)
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            MonitorEvent::FactsExtracted { .. } => "facts_extracted",
+            MonitorEvent::TokenThreshold { .. } => "token_threshold",
+            MonitorEvent::SessionEnded { .. } => "session_ended",
+            MonitorEvent::ContextFileEdited { .. } => "context_file_edited",
+        }
+    }
+
+    /// Name of the project the event is about
+    pub fn project(&self) -> &str {
+        match self {
+            MonitorEvent::FactsExtracted { project, .. }
+            | MonitorEvent::TokenThreshold { project, .. }
+            | MonitorEvent::SessionEnded { project, .. }
+            | MonitorEvent::ContextFileEdited { project, .. } => project,
+        }
+    }
+
+    /// One-line description for lists and notifications
+    pub fn description(&self) -> String {
+        match self {
+            MonitorEvent::FactsExtracted { count, .. } => {
+                format!("Extracted {} new fact{}", count, if *count == 1 { "" } else { "s" })
+            }
+            MonitorEvent::TokenThreshold { tokens, threshold, .. } => {
+                format!("Context size is {} tokens (threshold: {})", tokens, threshold)
+            }
+            MonitorEvent::SessionEnded {
+                summary,
+                duration_minutes,
+                facts,
+                ..
+            } => format!(
+                "Session ended after {}: {} ({} fact{})",
+                format_duration(chrono::Duration::minutes(*duration_minutes as i64)),
+                summary,
+                facts,
+                if *facts == 1 { "" } else { "s" }
+            ),
+            MonitorEvent::ContextFileEdited { sections, .. } => format!(
+                "CLAUDE.md was edited: {} section{} to import",
+                sections,
+                if *sections == 1 { "" } else { "s" }
+            ),
+        }
+    }
+}
+
+/// Callback receiving monitor events, called from the monitor thread
+pub type MonitorEventHandler = Arc<dyn Fn(MonitorEvent) + Send + Sync>;
+
+/// Callback run once the monitor is watching the logs directory
+pub type MonitorReadyHandler = Arc<dyn Fn() + Send + Sync>;
+
+/// Live progress of a running monitor, for frontends that show it as it happens
+///
+/// Unlike [`MonitorEvent`]s these are neither recorded nor published.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MonitorUpdate {
+    /// An event was recorded: facts extracted, token threshold crossed or session ended
+    Event { project_id: String, event: MonitorEvent },
+    /// A session was created, grew or ended
+    SessionUpdated { project_id: String, session_id: String },
+    /// A log file couldn't be processed, or the monitor stopped on an error
+    Error { message: String },
+    /// Projects another monitor process holds are left to it until it stops; 0 once all are taken over
+    Deferred { projects: usize, pid: Option<u32> },
+}
+
+impl MonitorUpdate {
+    /// ID of the project the update is about; errors and deferrals aren't about one
+    pub fn project_id(&self) -> Option<&str> {
+        match self {
+            MonitorUpdate::Event { project_id, .. } | MonitorUpdate::SessionUpdated { project_id, .. } => {
+                Some(project_id)
+            }
+            MonitorUpdate::Error { .. } | MonitorUpdate::Deferred { .. } => None,
+        }
+    }
+}
+
+/// Outcome of [`LogMonitor::backfill`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BackfillReport {
+    pub files: usize,     // Transcripts found (modified since the cutoff, if any)
+    pub unchanged: usize, // Already processed, per the processed-files ledger
+    pub failed: usize,
+    pub sessions_created: usize,
+    pub facts_created: usize,
+}
+
+/// Updates buffered for a frontend that is busy; further ones are dropped until it catches up
+const UPDATE_BUFFER: usize = 256;
+
+/// Most lines read from the start of a transcript looking for its working directory
+const CWD_SCAN_LINES: usize = 50;
+
+/// Claude Code log monitor
+pub struct LogMonitor {
+    project_id: Option<String>, // None = route each transcript to its project
+    repository: Repository,
+    logs_dir: PathBuf,
+    agent_dirs: Vec<(PathBuf, bool)>, // Other agents' transcript directories, and whether to watch them recursively
+    context_files: Vec<(PathBuf, String)>, // Each project's CLAUDE.md, with the project's ID
+    limits: ResourceLimits,
+    quality: FactQuality,
+    archive_transcripts: bool,
+    lock_dir: PathBuf,
+    locks: Mutex<Option<HashMap<String, ProjectLock>>>, // Held while monitoring, by project ID
+    deferred: AtomicUsize, // Projects left to another monitor at the last check
+    pricing: PricingTable,
+    stop: Arc<AtomicBool>,
+    on_event: Option<MonitorEventHandler>,
+    on_ready: Option<MonitorReadyHandler>,
+    updates: Option<async_channel::Sender<MonitorUpdate>>,
+    session_extractor: Option<SessionExtractor>,
+    record_events: bool,
+}
+
+impl LogMonitor {
+    /// Create a log monitor recording every transcript under one project
+    pub fn new(project_id: String, repository: Repository, logs_dir: Option<PathBuf>) -> Result<Self> {
+        Self::with_project(Some(project_id), repository, logs_dir)
+    }
+
+    /// Create a log monitor that files each transcript under the project it belongs to
+    ///
+    /// A transcript belongs to the project whose `repo_path` contains the working
+    /// directory the agent ran in; transcripts matching no project are ignored.
+    pub fn for_all_projects(repository: Repository, logs_dir: Option<PathBuf>) -> Result<Self> {
+        Self::with_project(None, repository, logs_dir)
+    }
+
+    fn with_project(project_id: Option<String>, repository: Repository, logs_dir: Option<PathBuf>) -> Result<Self> {
+        let projects = Self::watched_projects(&repository, project_id.as_deref());
+
+        // Other agents are followed too, unless told to watch one directory only
+        let agent_dirs = match &logs_dir {
+            Some(_) => Vec::new(),
+            None => {
+                let repo_paths: Vec<&Path> =
+                    projects.iter().filter_map(|project| project.repo_path.as_deref().map(Path::new)).collect();
+                agent_log_dirs(&repo_paths)
+            }
+        };
+        let context_files = projects
+            .iter()
+            .filter_map(|project| Some((context_file_path(project)?, project.id.clone())))
+            .collect();
+
+        // Explicit directory wins, then the one saved in settings, then Claude Code's own
+        let logs_dir = logs_dir
+            .or_else(|| repository.load_settings().ok()?.logs_dir.map(PathBuf::from))
+            .unwrap_or_else(|| Self::default_logs_dir(&repository, project_id.as_deref()));
+
+        if !logs_dir.exists() {
+            log::warn!("Claude Code logs directory does not exist: {}", logs_dir.display());
+        }
+
+        let limits = ResourceLimits::from_settings(&repository);
+        let quality = FactQuality::configured(&repository);
+        let archive_transcripts = repository.load_settings().is_ok_and(|settings| settings.archive_transcripts);
+        // Keep tests from locking the developer's running monitor out
+        let lock_dir = if cfg!(test) {
+            std::env::temp_dir().join("ccd-test-monitors")
+        } else {
+            monitor_lock_dir()
+        };
+        let pricing = PricingTable::configured().unwrap_or_else(|e| {
+            log::warn!("Using built-in model prices: {:#}", e);
+            PricingTable::default()
+        });
+
+        Ok(Self {
+            project_id,
+            repository: repository.with_actor(AuditActor::Monitor),
+            logs_dir,
+            agent_dirs,
+            context_files,
+            limits,
+            quality,
+            archive_transcripts,
+            lock_dir,
+            locks: Mutex::new(None),
+            deferred: AtomicUsize::new(0),
+            pricing,
+            stop: Arc::new(AtomicBool::new(false)),
+            on_event: None,
+            on_ready: None,
+            updates: None,
+            session_extractor: None,
+            record_events: true,
+        })
+    }
+
+    /// Call `handler` for facts extracted and token threshold warnings
+    pub fn with_event_handler(mut self, handler: MonitorEventHandler) -> Self {
+        self.on_event = Some(handler);
+        self
+    }
+
+    /// Call `handler` once the logs directory is watched, before existing
+    /// transcripts are caught up on
+    pub fn with_ready_handler(mut self, handler: MonitorReadyHandler) -> Self {
+        self.on_ready = Some(handler);
+        self
+    }
+
+    /// Neither record nor report events, e.g. while catching up on old transcripts
+    pub fn without_events(mut self) -> Self {
+        self.record_events = false;
+        self
+    }
+
+    /// Send live progress to `updates`
+    pub fn with_updates(mut self, updates: async_channel::Sender<MonitorUpdate>) -> Self {
+        self.updates = Some(updates);
+        self
+    }
+
+    /// Let `extractor` summarize and extract facts from ended sessions where the
+    /// regex extraction found fewer than `ENRICH_BELOW_FACTS` facts
+    pub fn with_session_extractor(mut self, extractor: SessionExtractor) -> Self {
+        self.session_extractor = Some(extractor);
+        self
+    }
+
+    /// Keep the project locks in `dir` instead of the runtime directory
+    pub fn with_lock_dir(mut self, dir: PathBuf) -> Self {
+        self.lock_dir = dir;
+        self
+    }
+
+    /// Get default Claude Code logs directory
+    ///
+    /// Claude Code keeps the transcripts of each working directory in its own
+    /// directory under `~/.claude/projects`. A project whose repository has one
+    /// watches just that; otherwise all of them are watched. Installs that predate
+    /// per-project transcripts fall back to `~/.claude/logs`.
+    fn default_logs_dir(repository: &Repository, project_id: Option<&str>) -> PathBuf {
+        let Some(claude_dir) = home::home_dir().map(|home| home.join(".claude")) else {
+            return PathBuf::from("./logs");
+        };
+
+        let projects_dir = claude_dir.join("projects");
+        let legacy_dir = claude_dir.join("logs");
+        if !projects_dir.exists() && legacy_dir.exists() {
+            return legacy_dir;
+        }
+
+        project_id
+            .and_then(|id| repository.get_project(id).ok())
+            .and_then(|project| project.repo_path)
+            .map(|repo_path| projects_dir.join(transcript_dir_name(Path::new(&repo_path))))
+            .filter(|dir| dir.exists())
+            .unwrap_or(projects_dir)
+    }
+
+    /// The monitored project, or all of them
+    fn watched_projects(repository: &Repository, project_id: Option<&str>) -> Vec<Project> {
+        match project_id {
+            Some(id) => repository.get_project(id).map(|project| vec![project]),
+            None => repository.list_projects(None),
+        }
+        .unwrap_or_else(|e| {
+            log::warn!("Failed to load projects: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Start monitoring (blocking until stopped)
+    ///
+    /// Fails when monitoring a single project that another process monitors
+    /// already (see `lock_projects`).
+    pub fn start_monitoring(&self) -> Result<()> {
+        log::info!("Starting log monitoring for {}", self.describe_target());
+        self.lock_projects()?;
+        let result = self.watch();
+        *self.locks.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        result
+    }
+
+    fn watch(&self) -> Result<()> {
+        log::info!("Watching directory: {}", self.logs_dir.display());
+
+        self.limits.apply_to_current_thread();
+
+        let (tx, rx) = channel();
+
+        let mut watcher = RecommendedWatcher::new(
+            tx,
+            Config::default().with_poll_interval(Duration::from_secs(2)),
+        )?;
+
+        watcher.watch(&self.logs_dir, RecursiveMode::Recursive)?;
+        for (dir, recursive) in &self.agent_dirs {
+            let mode = if *recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+            match watcher.watch(dir, mode) {
+                Ok(()) => log::info!("Watching directory: {}", dir.display()),
+                Err(e) => log::warn!("Failed to watch {}: {}", dir.display(), e),
+            }
+        }
+        // Editors often save by replacing the file, so the repository directory is watched
+        for dir in self.context_files.iter().filter_map(|(path, _)| path.parent()) {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                log::warn!("Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+
+        log::info!("File watcher initialized successfully");
+        if let Some(on_ready) = &self.on_ready {
+            on_ready();
+        }
+
+        // Process existing files first
+        let started = Utc::now();
+        self.process_existing_files()?;
+        let mut next_idle_check = Instant::now();
+
+        // Watch for new files, coalescing each burst of events into one pass per file
+        let mut pending = PendingFiles::default();
+        while !self.is_stopped() {
+            let timeout = pending.next_due().map_or(STOP_POLL_INTERVAL, |due| {
+                due.saturating_duration_since(Instant::now()).min(STOP_POLL_INTERVAL)
+            });
+
+            match rx.recv_timeout(timeout) {
+                Ok(Ok(event)) => self.handle_event(event, &mut pending),
+                Ok(Err(e)) => log::error!("Watch error: {}", e),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            for path in pending.take_due(Instant::now()) {
+                if self.is_stopped() {
+                    break;
+                }
+                if let Some(project_id) = self.context_file_project(&path) {
+                    if self.holds_lock(project_id) {
+                        self.check_context_file(project_id, &path);
+                    }
+                    continue;
+                }
+                log::info!("New/modified log file detected: {}", path.display());
+                if let Err(e) = self.process_with_limits(&path) {
+                    log::error!("Failed to process log file: {}", e);
+                    self.send_update(MonitorUpdate::Error {
+                        message: format!("Failed to process {}: {}", path.display(), e),
+                    });
+                }
+            }
+
+            if Instant::now() >= next_idle_check {
+                if let Err(e) = self.lock_projects() {
+                    log::warn!("Failed to lock projects: {}", e);
+                }
+                if let Err(e) = self.end_idle_sessions(Utc::now(), started) {
+                    log::warn!("Failed to end idle sessions: {}", e);
+                    self.send_update(MonitorUpdate::Error {
+                        message: format!("Failed to end idle sessions: {}", e),
+                    });
+                }
+                next_idle_check = Instant::now() + IDLE_CHECK_INTERVAL;
+            }
+        }
+
+        // Release the file watches before the thread goes away
+        drop(watcher);
+        log::info!("Stopped log monitoring for {}", self.describe_target());
+
+        Ok(())
+    }
+
+    /// Lock the watched projects, so that a second monitor leaves them alone
+    ///
+    /// A monitor of one project fails when another process monitors it already. A
+    /// monitor of all projects leaves such projects to the other process and tries
+    /// again on every idle check, taking them over once it stops.
+    fn lock_projects(&self) -> Result<()> {
+        let mut locks = self.locks.lock().unwrap_or_else(|e| e.into_inner());
+        let held = locks.get_or_insert_with(HashMap::new);
+        let mut deferred = Vec::new();
+        for project in Self::watched_projects(&self.repository, self.project_id.as_deref()) {
+            if held.contains_key(&project.id) {
+                continue;
+            }
+            match ProjectLock::try_acquire(&self.lock_dir, &project.id)? {
+                Some(lock) => {
+                    held.insert(project.id, lock);
+                }
+                None => deferred.push(project),
+            }
+        }
+
+        let pid = deferred.first().and_then(|project| ProjectLock::holder(&self.lock_dir, &project.id));
+        let by = pid.map_or_else(|| "another process".to_string(), |pid| format!("PID {}", pid));
+        if let (Some(_), Some(project)) = (&self.project_id, deferred.first()) {
+            *locks = None;
+            bail!("{} is already monitored by {}", project.name, by);
+        }
+        drop(locks);
+
+        if self.deferred.swap(deferred.len(), Ordering::Relaxed) != deferred.len() {
+            if deferred.is_empty() {
+                log::info!("Took over monitoring of the projects another monitor held");
+            } else {
+                log::info!("Leaving {} projects to the monitor running as {}", deferred.len(), by);
+            }
+            self.send_update(MonitorUpdate::Deferred {
+                projects: deferred.len(),
+                pid,
+            });
+        }
+        Ok(())
+    }
+
+    /// Whether this monitor handles `project_id`: always, unless it is monitoring
+    /// and another monitor holds the project
+    fn holds_lock(&self, project_id: &str) -> bool {
+        self.locks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .is_none_or(|held| held.contains_key(project_id))
+    }
+
+    fn describe_target(&self) -> String {
+        match &self.project_id {
+            Some(id) => format!("project: {}", id),
+            None => "all projects".to_string(),
+        }
+    }
+
+    /// Ask a running `start_monitoring` loop to return
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Flag that stops a running `start_monitoring` loop once set, e.g. from a signal handler
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// Every transcript in the logs directory and the other agents' directories
+    fn collect_logs(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        if self.logs_dir.exists() {
+            collect_conversation_logs(&self.logs_dir, &mut paths)?;
+        } else {
+            log::warn!("Logs directory does not exist yet");
+        }
+
+        for (dir, recursive) in &self.agent_dirs {
+            if *recursive {
+                collect_conversation_logs(dir, &mut paths)?;
+            } else {
+                paths.extend(std::fs::read_dir(dir)?.filter_map(|entry| {
+                    let path = entry.ok()?.path();
+                    (path.is_file() && is_conversation_log(&path)).then_some(path)
+                }));
+            }
+        }
+        Ok(paths)
+    }
+
+    /// Process all existing log files
+    fn process_existing_files(&self) -> Result<()> {
+        log::info!("Processing existing log files...");
+
+        let paths = self.collect_logs()?;
+
+        // Work through the backlog with up to `max_concurrent_files` workers
+        let next = AtomicUsize::new(0);
+        let count = AtomicUsize::new(0);
+        let workers = self.limits.max_concurrent_files.clamp(1, paths.len().max(1));
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                scope.spawn(|| {
+                    self.limits.apply_to_current_thread();
+                    while let Some(path) = paths.get(next.fetch_add(1, Ordering::Relaxed)) {
+                        if self.is_stopped() {
+                            break;
+                        }
+                        if let Err(e) = self.process_with_limits(path) {
+                            log::warn!("Failed to process {}: {}", path.display(), e);
+                            self.send_update(MonitorUpdate::Error {
+                                message: format!("Failed to process {}: {}", path.display(), e),
+                            });
+                        } else {
+                            count.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        log::info!("Processed {} existing log files", count.into_inner());
+        Ok(())
+    }
+
+    /// Process every transcript in the logs directory once, oldest first
+    ///
+    /// Only transcripts modified at or after `since` are read. Files the ledger has
+    /// seen unchanged are skipped as usual, so running it again is cheap.
+    /// `on_progress` is called before each file with its index and the file count.
+    pub fn backfill(
+        &self,
+        since: Option<DateTime<Utc>>,
+        mut on_progress: impl FnMut(usize, usize, &Path),
+    ) -> Result<BackfillReport> {
+        let paths = self.collect_logs()?;
+
+        // Oldest first, so facts and sessions are created in the order they happened
+        let mut dated: Vec<(DateTime<Utc>, PathBuf)> = paths
+            .into_iter()
+            .filter_map(|path| {
+                let modified: DateTime<Utc> = std::fs::metadata(&path).ok()?.modified().ok()?.into();
+                Some((modified, path))
+            })
+            .filter(|(modified, _)| since.is_none_or(|since| *modified >= since))
+            .collect();
+        dated.sort();
+
+        let (sessions_before, facts_before) = self.totals()?;
+        let mut report = BackfillReport {
+            files: dated.len(),
+            ..Default::default()
+        };
+
+        for (i, (modified, path)) in dated.iter().enumerate() {
+            if self.is_stopped() {
+                break;
+            }
+            on_progress(i, report.files, path);
+
+            if self.is_unchanged(path, *modified)? {
+                report.unchanged += 1;
+                continue;
+            }
+            if let Err(e) = self.process_log_file(path) {
+                log::warn!("Failed to process {}: {}", path.display(), e);
+                report.failed += 1;
+            }
+        }
+
+        let (sessions_after, facts_after) = self.totals()?;
+        report.sessions_created = sessions_after.saturating_sub(sessions_before);
+        report.facts_created = facts_after.saturating_sub(facts_before);
+        Ok(report)
+    }
+
+    /// Whether the ledger already has this file at its current size and mtime
+    fn is_unchanged(&self, path: &Path, modified: DateTime<Utc>) -> Result<bool> {
+        let Some(project_id) = self.route(path)? else {
+            return Ok(false);
+        };
+        let size = std::fs::metadata(path)?.len() as i64;
+        let previous = self.repository.get_processed_log(&project_id, &path.to_string_lossy())?;
+        Ok(previous.is_some_and(|p| p.matches_stat(size, modified)))
+    }
+
+    /// Sessions and facts (stale included) of the monitored projects
+    fn totals(&self) -> Result<(usize, usize)> {
+        let project_ids = match &self.project_id {
+            Some(id) => vec![id.clone()],
+            None => self.repository.list_projects(None)?.into_iter().map(|p| p.id).collect(),
+        };
+
+        let (mut sessions, mut facts) = (0, 0);
+        for id in &project_ids {
+            sessions += self.repository.count_sessions(id)?;
+            facts += self.repository.count_facts(id, true)?;
+        }
+        Ok((sessions, facts))
+    }
+
+    /// Handle file system event
+    fn handle_event(&self, event: Event, pending: &mut PendingFiles) {
+        match event.kind {
+            EventKind::Create(_) | EventKind::Modify(_) => {
+                let now = Instant::now();
+                for path in event.paths {
+                    if is_conversation_log(&path) || self.context_file_project(&path).is_some() {
+                        pending.touch(path, now);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// ID of the project whose CLAUDE.md is at `path`, if any
+    fn context_file_project(&self, path: &Path) -> Option<&str> {
+        self.context_files
+            .iter()
+            .find(|(context_file, _)| context_file == path)
+            .map(|(_, project_id)| project_id.as_str())
+    }
+
+    /// Report hand edits to a project's CLAUDE.md, so they can be imported
+    ///
+    /// Exports write what the database holds, so they don't count as edits.
+    pub(crate) fn check_context_file(&self, project_id: &str, path: &Path) {
+        let edits = self
+            .repository
+            .get_project(project_id)
+            .and_then(|project| read_context_file_edits(&self.repository, &project, path));
+        match edits {
+            Ok(edits) if edits.is_empty() => {}
+            Ok(edits) => {
+                log::info!("{} was edited: {} sections to import", path.display(), edits.len());
+                self.emit(project_id, |project| MonitorEvent::ContextFileEdited {
+                    project,
+                    path: path.display().to_string(),
+                    sections: edits.len(),
+                });
+            }
+            Err(e) => log::warn!("Failed to compare {} with the database: {}", path.display(), e),
+        }
+    }
+
+    /// Process a log file once a processing slot is free, then pause
+    fn process_with_limits(&self, path: &Path) -> Result<()> {
+        let result = {
+            let _slot = ProcessingSlot::acquire(self.limits.max_concurrent_files);
+            self.process_log_file(path)
+        };
+
+        if !self.limits.pause.is_zero() {
+            std::thread::sleep(self.limits.pause);
+        }
+        result
+    }
+
+    /// Process a single log file
+    ///
+    /// Files already recorded in `processed_logs` are skipped when unchanged; a file
+    /// that grew updates the session it produced earlier instead of creating another.
+    /// A new file continuing a known conversation (same `sessionId`) updates that
+    /// conversation's session too, so each conversation has a single session.
+    /// Lines appended to a JSON Lines transcript are parsed on their own, starting
+    /// at the recorded offset, as long as the part before it is unchanged.
+    pub(crate) fn process_log_file(&self, path: &Path) -> Result<()> {
+        let Some(project_id) = self.route(path)? else {
+            log::debug!("No project for log file: {}", path.display());
+            return Ok(());
+        };
+        if !self.holds_lock(&project_id) {
+            log::debug!("Leaving {} to the monitor holding its project", path.display());
+            return Ok(());
+        }
+
+        let metadata = std::fs::metadata(path).context("Failed to stat log file")?;
+        let size = metadata.len() as i64;
+        let modified: DateTime<Utc> = metadata.modified().context("Failed to read log file mtime")?.into();
+        let path_key = path.to_string_lossy().into_owned();
+
+        let previous = self.repository.get_processed_log(&project_id, &path_key)?;
+        if previous.as_ref().is_some_and(|p| p.matches_stat(size, modified)) {
+            log::debug!("Skipping unchanged log file: {}", path.display());
+            return Ok(());
+        }
+
+        if metadata.len() > self.limits.max_log_bytes {
+            log::warn!(
+                "Skipping log file larger than {} MiB: {}",
+                self.limits.max_log_bytes / (1024 * 1024),
+                path.display()
+            );
+            return Ok(());
+        }
+
+        log::debug!("Processing log file: {}", path.display());
+
+        let bytes = std::fs::read(path).context("Failed to read log file")?;
+        // Only Claude Code transcripts are read from where they were left
+        let format = LogFormat::detect(path, &bytes);
+        let is_jsonl = format == LogFormat::ClaudeCode && path.extension().is_some_and(|ext| ext == "jsonl");
+        // A line still being written is left for the next event
+        let end = if is_jsonl { complete_lines_len(&bytes) } else { bytes.len() };
+
+        // Resuming needs the number of messages already read, to place the new ones
+        let resume_from = previous
+            .as_ref()
+            .filter(|p| is_jsonl && p.offset > 0 && p.offset as usize <= end)
+            .filter(|p| hash_bytes(&bytes[..p.offset as usize]) == p.content_hash)
+            .filter(|p| p.session.as_deref().is_some_and(|id| self.repository.get_session(id).is_ok()))
+            .and_then(|p| Some((p.offset as usize, p.message_count? as usize)));
+
+        let mut record = ProcessedLogPayload {
+            project: project_id.clone(),
+            path: path_key,
+            size,
+            modified,
+            content_hash: hash_bytes(&bytes[..end]),
+            session: previous.as_ref().and_then(|p| p.session.clone()),
+            offset: end as i64,
+            message_count: previous.as_ref().and_then(|p| p.message_count),
+        };
+
+        // Touched but not changed: just refresh the stat info
+        if previous.as_ref().is_some_and(|p| p.content_hash == record.content_hash) {
+            log::debug!("Log file content unchanged: {}", path.display());
+            self.repository.record_processed_log(record)?;
+            return Ok(());
+        }
+
+        let content = std::str::from_utf8(&bytes[resume_from.map_or(0, |(offset, _)| offset)..end])
+            .context("Log file is not valid UTF-8")?;
+        let log = match resume_from {
+            Some((_, first_message)) => ConversationLog {
+                first_message,
+                ..parse_transcript_lines(content)
+            },
+            None => format
+                .parse(content)
+                .with_context(|| format!("Failed to parse {} transcript", format.agent_name()))?,
+        };
+        record.message_count = Some((log.first_message + log.messages.len()) as i64);
+
+        // Extract facts from all messages; the title is built before duplicates are
+        // dropped so that files edited often rank first
+        let extractor = FactExtractor::new(project_id.clone()).with_log_path(path);
+        let mut facts = extractor.extract_from_log(&log, None);
+        let title = generate_session_title(log.goal().as_deref(), &facts);
+
+        // A file edited many times is still one change; keep the count honest
+        let mut seen = HashSet::new();
+        facts.retain(|fact| seen.insert((fact.fact_type, fact.content.clone())));
+
+        // Reuse the session from an earlier version of this file if it still exists,
+        // then the one for the same conversation. Appended lines alone say too little
+        // for a title, so it is kept.
+        // aider writes every chat to the same file, so a new conversation needs a new session
+        let known = record
+            .session
+            .as_deref()
+            .and_then(|id| self.repository.get_session(id).ok())
+            .filter(|session| match (&session.conversation_id, &log.conversation_id) {
+                (Some(known), Some(current)) => known == current,
+                _ => true,
+            })
+            .map(|session| session.id);
+        let conversation = match (&known, log.conversation_id.as_deref()) {
+            (None, Some(conversation_id)) => self.repository.find_session_by_conversation(&project_id, conversation_id)?,
+            _ => None,
+        };
+        // Facts from another file of the conversation add to its count
+        let adds_facts = resume_from.is_some() || conversation.is_some();
+        let session_id = match (resume_from, known, conversation) {
+            (Some(_), Some(id), _) => {
+                self.extend_session(&project_id, &id, &log)?;
+                id
+            }
+            (None, Some(id), _) | (_, None, Some(SessionHistory { id, .. })) => {
+                self.update_session(&project_id, &id, &log, title)?;
+                id
+            }
+            (_, None, None) => self.create_session(&project_id, &log, title)?,
+        };
+        // Kept so the conversation can still be read once the agent rotates its logs
+        if self.archive_transcripts {
+            if let Err(e) = self.repository.archive_session_transcript(&session_id, &record.path, &bytes[..end]) {
+                log::warn!("Failed to archive {}: {}", path.display(), e);
+            }
+        }
+
+        // Drop what the project doesn't want before the per-session cap is applied
+        let preferences = self.repository.extraction_preferences(&project_id).unwrap_or_else(|e| {
+            log::warn!("Failed to load extraction preferences: {}", e);
+            ExtractionPreferences::default()
+        });
+        facts.retain(|fact| preferences.allows(fact));
+
+        // Chatty sessions produce lots of narration; keep what is worth reading
+        let saved = if adds_facts {
+            self.repository.get_session(&session_id).map_or(0, |session| session.facts_extracted.max(0) as usize)
+        } else {
+            0
+        };
+        let mut facts = self.quality.filter(facts, saved);
+        for fact in &mut facts {
+            fact.session = Some(session_id.clone());
+        }
+
+        // Save all facts in a single transaction
+        let total_facts = match self.repository.create_facts_batch(facts) {
+            Ok(count) => count as i32,
+            Err(e) => {
+                log::warn!("Failed to save facts: {}", e);
+                0
+            }
+        };
+
+        log::info!("Extracted {} facts from session {}", total_facts, session_id);
+
+        // Update session with fact count
+        if let Ok(mut session) = self.repository.get_session(&session_id) {
+            session.facts_extracted = if adds_facts {
+                session.facts_extracted + total_facts
+            } else {
+                total_facts
+            };
+            let payload = SessionPayload::from(&session);
+            let _ = self.repository.update_session(&session_id, payload);
+            self.record_session_commits(&session);
+        }
+        self.send_update(MonitorUpdate::SessionUpdated {
+            project_id: project_id.clone(),
+            session_id: session_id.clone(),
+        });
+
+        record.session = Some(session_id);
+        self.repository.record_processed_log(record)?;
+
+        if total_facts > 0 {
+            self.emit(&project_id, |project| MonitorEvent::FactsExtracted {
+                project,
+                count: total_facts as usize,
+            });
+        }
+
+        // Update staleness for existing facts
+        self.update_stale_facts(&project_id)?;
+
+        Ok(())
+    }
+
+    /// Create a session record for this conversation
+    fn create_session(&self, project_id: &str, log: &ConversationLog, title: String) -> Result<String> {
+        let token_count = log.estimate_tokens();
+
+        let payload = SessionPayload {
+            project: project_id.to_string(),
+            summary: title,
+            facts_extracted: Some(0),
+            token_count: Some(token_count),
+            session_start: Some(chrono::Utc::now()),
+            session_end: None,
+            conversation_id: log.conversation_id.clone(),
+        };
+
+        let session = self.repository.create_session(payload)?;
+        self.record_usage(&session.id, self.pricing.session_usage(&log.replies));
+        self.check_token_threshold(project_id, token_count);
+        self.record_git_start(project_id, &session.id);
+
+        Ok(session.id)
+    }
+
+    /// Repository of a project, if it has one
+    fn repo_path(&self, project_id: &str) -> Option<PathBuf> {
+        self.repository.get_project(project_id).ok()?.repo_path.map(PathBuf::from)
+    }
+
+    /// Note the branch and commit the project's repository is on as a session starts
+    fn record_git_start(&self, project_id: &str, session_id: &str) {
+        let Some(repo) = self.repo_path(project_id) else {
+            return;
+        };
+        match git_head(&repo) {
+            Ok(Some(head)) => {
+                if let Err(e) = self.repository.set_session_git_start(session_id, head.branch.as_deref(), &head.sha) {
+                    log::warn!("Failed to record git state of session {}: {}", session_id, e);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("Not recording git state: {:#}", e),
+        }
+    }
+
+    /// Attach the commits made in the project's repository while a session ran
+    fn record_session_commits(&self, session: &SessionHistory) {
+        let Some(repo) = self.repo_path(&session.project) else {
+            return;
+        };
+        let commits = match commits_between(&repo, session.git_head.as_deref(), session.session_start, session.session_end) {
+            Ok(commits) => commits,
+            Err(e) => {
+                log::debug!("Not recording commits: {:#}", e);
+                return;
+            }
+        };
+        match self.repository.add_session_commits(&session.id, &commits) {
+            Ok(0) => {}
+            Ok(added) => log::info!("Session {} made {} new commits", session.id, added),
+            Err(e) => log::warn!("Failed to record commits of session {}: {}", session.id, e),
+        }
+    }
+
+    /// Refresh an existing session after its log file grew
+    fn update_session(&self, project_id: &str, session_id: &str, log: &ConversationLog, title: String) -> Result<()> {
+        let mut session = self.repository.get_session(session_id)?;
+        session.summary = title;
+        session.token_count = log.estimate_tokens();
+        session.session_end = None; // Active again if it had gone idle
+        session.conversation_id = session.conversation_id.or_else(|| log.conversation_id.clone());
+
+        self.repository.update_session(session_id, SessionPayload::from(&session))?;
+        self.record_usage(session_id, self.pricing.session_usage(&log.replies));
+        self.check_token_threshold(project_id, session.token_count);
+
+        Ok(())
+    }
+
+    /// Fold lines appended to a log file into the session it produced
+    fn extend_session(&self, project_id: &str, session_id: &str, appended: &ConversationLog) -> Result<()> {
+        let mut session = self.repository.get_session(session_id)?;
+        session.token_count = match appended.latest_usage() {
+            Some(usage) => usage.context_tokens(),
+            None => session.token_count + appended.estimate_tokens(),
+        };
+        session.session_end = None; // Active again if it had gone idle
+        session.conversation_id = session.conversation_id.or_else(|| appended.conversation_id.clone());
+
+        self.repository.update_session(session_id, SessionPayload::from(&session))?;
+        if !appended.replies.is_empty() {
+            self.record_usage(session_id, session.usage.plus(&self.pricing.session_usage(&appended.replies)));
+        }
+        self.check_token_threshold(project_id, session.token_count);
+
+        Ok(())
+    }
+
+    /// Store what a session's replies used and cost
+    fn record_usage(&self, session_id: &str, usage: SessionUsage) {
+        if let Err(e) = self.repository.set_session_usage(session_id, &usage) {
+            log::warn!("Failed to record usage of session {}: {}", session_id, e);
+        }
+    }
+
+    /// End the sessions whose transcripts have been quiet for the configured idle period
+    ///
+    /// A session ends when its transcript last changed, not when this notices. Only
+    /// sessions active since `watching_since` are announced (and enriched by the
+    /// session extractor); the rest went quiet while nothing was watching and are
+    /// ended silently.
+    pub(crate) fn end_idle_sessions(&self, now: DateTime<Utc>, watching_since: DateTime<Utc>) -> Result<usize> {
+        let idle_minutes = self
+            .repository
+            .load_settings()
+            .map(|settings| settings.session_idle_minutes)
+            .unwrap_or(DEFAULT_SESSION_IDLE_MINUTES);
+        if idle_minutes == 0 {
+            return Ok(0);
+        }
+
+        let cutoff = now - chrono::Duration::minutes(idle_minutes as i64);
+        let mut idle = self.repository.list_idle_sessions(self.project_id.as_deref(), cutoff)?;
+        idle.retain(|(session, _)| self.holds_lock(&session.project));
+        let count = idle.len();
+
+        for (mut session, last_activity) in idle {
+            let end = last_activity.max(session.session_start);
+            session.session_end = Some(end);
+            self.repository.update_session(&session.id, SessionPayload::from(&session))?;
+            log::info!("Session {} ended after {} idle minutes", session.id, idle_minutes);
+            self.record_session_commits(&session);
+            self.send_update(MonitorUpdate::SessionUpdated {
+                project_id: session.project.clone(),
+                session_id: session.id.clone(),
+            });
+
+            if last_activity >= watching_since {
+                if let Some(extractor) = self.session_extractor.as_deref() {
+                    if session.facts_extracted < ENRICH_BELOW_FACTS {
+                        match enrich_session(&self.repository, extractor, &session.id) {
+                            Ok(_) => session = self.repository.get_session(&session.id)?,
+                            Err(e) => log::warn!("Failed to enrich session {}: {:#}", session.id, e),
+                        }
+                    }
+                }
+
+                self.emit(&session.project, |project| MonitorEvent::SessionEnded {
+                    project,
+                    summary: session.summary.clone(),
+                    duration_minutes: end.signed_duration_since(session.session_start).num_minutes().max(0) as u64,
+                    facts: session.facts_extracted.max(0) as usize,
+                    tokens: session.token_count.max(0) as usize,
+                });
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Warn when a conversation approaches the context limit
+    fn check_token_threshold(&self, project_id: &str, token_count: i64) {
+        let threshold = self.repository
+            .load_settings()
+            .map(|settings| settings.token_threshold)
+            .unwrap_or(DEFAULT_TOKEN_THRESHOLD);
+        if token_count > threshold {
+            self.emit(project_id, |project| MonitorEvent::TokenThreshold {
+                project,
+                tokens: token_count as usize,
+                threshold: threshold as usize,
+            });
+        }
+    }
+
+    /// Record an event about a project and send it to the handler, if any
+    fn emit(&self, project_id: &str, event: impl FnOnce(String) -> MonitorEvent) {
+        if !self.record_events {
+            return;
+        }
+        let Ok(project) = self.repository.get_project(project_id) else {
+            return;
+        };
+        let event = event(project.name);
+
+        // Kept for the notification history, so nothing is lost once the popup is gone
+        if let Err(e) = self.repository.record_event(EventRecordPayload {
+            project: project.id.clone(),
+            event: event.clone(),
+        }) {
+            log::warn!("Failed to record monitor event: {}", e);
+        }
+
+        self.send_update(MonitorUpdate::Event {
+            project_id: project.id,
+            event: event.clone(),
+        });
+        if let Some(handler) = &self.on_event {
+            handler(event);
+        }
+    }
+
+    /// Pass live progress to the frontend, if one is listening and keeping up
+    fn send_update(&self, update: MonitorUpdate) {
+        if let Some(updates) = &self.updates {
+            if let Err(async_channel::TrySendError::Full(update)) = updates.try_send(update) {
+                log::debug!("Dropping monitor update, frontend is behind: {:?}", update);
+            }
+        }
+    }
+
+    /// Project a log file belongs to, if any
+    ///
+    /// A monitor for one project takes every file in its logs directory. Other
+    /// files are matched against the projects' repository paths by where the
+    /// agent ran: the working directory recorded in the transcript, the directory
+    /// Claude Code filed it in, the directory of aider's history file, or the
+    /// project hash Gemini CLI filed it under.
+    fn route(&self, path: &Path) -> Result<Option<String>> {
+        let projects = match &self.project_id {
+            Some(id) if path.starts_with(&self.logs_dir) => return Ok(Some(id.clone())),
+            Some(id) => vec![self.repository.get_project(id)?],
+            None => self.repository.list_projects(None)?,
+        };
+
+        let project = match LogFormat::from_path(path).unwrap_or(LogFormat::ClaudeCode) {
+            LogFormat::ClaudeCode | LogFormat::Codex => {
+                let by_cwd = read_transcript_cwd(path)?.and_then(|cwd| project_for_path(&projects, Path::new(&cwd)));
+                let by_dir = || {
+                    let dir_name = path.parent()?.file_name()?.to_str()?;
+                    projects.iter().find(|project| {
+                        project
+                            .repo_path
+                            .as_deref()
+                            .is_some_and(|repo| transcript_dir_name(Path::new(repo)) == dir_name)
+                    })
+                };
+                by_cwd.or_else(by_dir)
+            }
+            LogFormat::Aider => path.parent().and_then(|dir| project_for_path(&projects, dir)),
+            LogFormat::Gemini => gemini_hash_dir(path).and_then(|hash| {
+                projects.iter().find(|project| {
+                    project
+                        .repo_path
+                        .as_deref()
+                        .is_some_and(|repo| gemini_project_hash(Path::new(repo)) == hash)
+                })
+            }),
+        };
+
+        Ok(project.map(|project| project.id.clone()))
+    }
+
+    /// Update staleness for all facts of a project
+    ///
+    /// Open blockers and todos that a later fact reports as dealt with are
+    /// resolved first; the rest go stale by content and age.
+    fn update_stale_facts(&self, project_id: &str) -> Result<()> {
+        // Stale facts are kept as candidates: a "fixed ..." fact is stale from the start
+        let facts = self.repository.list_facts(project_id, true)?;
+
+        for fact in facts.iter().filter(|fact| !fact.stale) {
+            if let Some(resolution) = StalenessDetector::resolved_by(fact, &facts) {
+                log::debug!("Fact {} resolved by fact {}", fact.id, resolution.id);
+                let _ = self.repository.mark_fact_resolved(&fact.id, resolution);
+            } else if StalenessDetector::is_stale(fact) {
+                log::debug!("Marking fact {} as stale", fact.id);
+                let _ = self.repository.mark_fact_stale(&fact.id);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Check if a path looks like a transcript of a supported agent
+pub(crate) fn is_conversation_log(path: &Path) -> bool {
+    LogFormat::from_path(path).is_some()
+}
+
+/// Log files with events not yet acted on, waiting for their burst to end
+#[derive(Default)]
+struct PendingFiles {
+    files: HashMap<PathBuf, (Instant, Instant)>, // First and latest event
+}
+
+impl PendingFiles {
+    /// Note an event for `path`
+    fn touch(&mut self, path: PathBuf, now: Instant) {
+        self.files.entry(path).and_modify(|(_, latest)| *latest = now).or_insert((now, now));
+    }
+
+    /// When the next file becomes due
+    fn next_due(&self) -> Option<Instant> {
+        self.files.values().map(|&(first, latest)| Self::due(first, latest)).min()
+    }
+
+    /// Remove and return the files whose burst is over
+    fn take_due(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut due = Vec::new();
+        self.files.retain(|path, &mut (first, latest)| {
+            let ready = Self::due(first, latest) <= now;
+            if ready {
+                due.push(path.clone());
+            }
+            !ready
+        });
+        due
+    }
+
+    fn due(first: Instant, latest: Instant) -> Instant {
+        (latest + DEBOUNCE_WINDOW).min(first + DEBOUNCE_MAX_DELAY)
+    }
+}
+
+/// Length of `bytes` up to the end of its last complete line
+///
+/// A final line without a newline counts once it parses, so transcripts written
+/// in one go don't lose their last event.
+fn complete_lines_len(bytes: &[u8]) -> usize {
+    let last_line = bytes.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let tail = &bytes[last_line..];
+    if tail.trim_ascii().is_empty() || serde_json::from_slice::<serde::de::IgnoredAny>(tail).is_ok() {
+        bytes.len()
+    } else {
+        last_line
+    }
+}
+
+/// Working directory recorded in a JSON Lines transcript, from its first events
+///
+/// Claude Code records it on every event, Codex CLI in the `payload` of its first.
+fn read_transcript_cwd(path: &Path) -> Result<Option<String>> {
+    #[derive(serde::Deserialize)]
+    struct Event {
+        cwd: Option<String>,
+        payload: Option<Payload>,
+    }
+    #[derive(serde::Deserialize)]
+    struct Payload {
+        cwd: Option<String>,
+    }
+
+    let file = std::fs::File::open(path).context("Failed to open log file")?;
+    for line in BufReader::new(file).lines().take(CWD_SCAN_LINES) {
+        let Ok(event) = serde_json::from_str::<Event>(&line?) else {
+            continue;
+        };
+        if let Some(cwd) = event.cwd.or(event.payload.and_then(|payload| payload.cwd)) {
+            return Ok(Some(cwd));
+        }
+    }
+    Ok(None)
+}
+
+/// Project whose repository contains `path`, the innermost one if they nest
+pub fn project_for_path<'a>(projects: &'a [Project], path: &Path) -> Option<&'a Project> {
+    projects
+        .iter()
+        .filter_map(|project| Some((project, Path::new(project.repo_path.as_deref()?))))
+        .filter(|(_, repo)| !repo.as_os_str().is_empty() && path.starts_with(repo))
+        .max_by_key(|(_, repo)| repo.components().count())
+        .map(|(project, _)| project)
+}
+
+/// Collect conversation logs under `dir`, including per-project subdirectories
+fn collect_conversation_logs(dir: &Path, paths: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_conversation_logs(&path, paths)?;
+        } else if is_conversation_log(&path) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Name of the directory Claude Code keeps a working directory's transcripts in
+///
+/// Every character other than an ASCII letter or digit becomes `-`, so
+/// `/home/user/shop` maps to `-home-user-shop`.
+pub fn transcript_dir_name(repo_path: &Path) -> String {
+    repo_path
+        .to_string_lossy()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// A monitor running on its own thread; dropping the handle stops it
+pub struct MonitorHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+    updates: async_channel::Receiver<MonitorUpdate>,
+}
+
+impl MonitorHandle {
+    /// Live progress of the monitor; the channel closes once the monitor thread exits
+    ///
+    /// Every receiver shares one queue, so subscribe from a single place.
+    pub fn updates(&self) -> async_channel::Receiver<MonitorUpdate> {
+        self.updates.clone()
+    }
+
+    /// Signal the monitor to stop; it finishes the file in progress first
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if the monitor thread has exited
+    pub fn is_finished(&self) -> bool {
+        self.thread.as_ref().is_none_or(|thread| thread.is_finished())
+    }
+
+    /// Stop the monitor and wait for its thread to exit
+    pub fn join(mut self) {
+        self.stop();
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::error!("Monitor thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for MonitorHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Background monitoring thread, for one project or (`None`) all of them
+pub fn start_background_monitor(
+    project_id: Option<String>,
+    repository: Repository,
+    logs_dir: Option<PathBuf>,
+    on_event: Option<MonitorEventHandler>,
+    session_extractor: Option<SessionExtractor>,
+) -> Result<MonitorHandle> {
+    let (updates_tx, updates) = async_channel::bounded(UPDATE_BUFFER);
+    let mut monitor = LogMonitor::with_project(project_id, repository, logs_dir)?.with_updates(updates_tx);
+    monitor.on_event = on_event;
+    monitor.session_extractor = session_extractor;
+    let stop = monitor.stop.clone();
+
+    let thread = std::thread::spawn(move || {
+        log::info!("Background monitor thread started");
+
+        if let Err(e) = monitor.start_monitoring() {
+            log::error!("Monitor error: {}", e);
+            monitor.send_update(MonitorUpdate::Error {
+                message: format!("Monitoring stopped: {}", e),
+            });
+        }
+    });
+
+    Ok(MonitorHandle {
+        stop,
+        thread: Some(thread),
+        updates,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::create_test_db;
+    use crate::models::{FactType, ProjectPayload, ProjectStatus};
+    use crate::monitor::read_fact_message;
+
+    #[test]
+    fn test_unchanged_log_is_not_reprocessed() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Monitored".to_string(),
+                slug: "monitored".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.json");
+        std::fs::write(&path, r#"{"messages":[{"role":"user","content":"Hello"}]}"#).unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_log_file(&path).unwrap();
+        monitor.process_log_file(&path).unwrap();
+        assert_eq!(repo.list_sessions(&project.id).unwrap().len(), 1);
+
+        // A grown file updates the existing session
+        std::fs::write(
+            &path,
+            r#"{"messages":[{"role":"user","content":"Hello"},{"role":"assistant","content":"Hi there"}]}"#,
+        )
+        .unwrap();
+        monitor.process_log_file(&path).unwrap();
+
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        let record = repo.get_processed_log(&project.id, &path.to_string_lossy()).unwrap().unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(record.session.as_deref(), Some(sessions[0].id.as_str()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_background_monitor_stops() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let handle = start_background_monitor(Some("stop-test".to_string()), repo, Some(dir.clone()), None, None).unwrap();
+        assert!(!handle.is_finished());
+
+        // Returns only once the event loop has seen the stop flag
+        handle.join();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_stop_flag_ends_monitoring() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let (ready_tx, ready_rx) = channel();
+        let monitor = LogMonitor::new("stop-flag".to_string(), repo, Some(dir.clone()))
+            .unwrap()
+            .with_ready_handler(Arc::new(move || ready_tx.send(()).unwrap()));
+        let stop = monitor.stop_flag();
+
+        let thread = std::thread::spawn(move || monitor.start_monitoring());
+        ready_rx.recv_timeout(Duration::from_secs(10)).unwrap();
+        stop.store(true, Ordering::Relaxed);
+        thread.join().unwrap().unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_updates_are_sent() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Live".to_string(),
+                slug: "live".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"assistant","message":{"role":"assistant","content":"Blocked by the database connection error in CI."}}"#,
+        )
+        .unwrap();
+
+        let (tx, rx) = async_channel::bounded(UPDATE_BUFFER);
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap().with_updates(tx);
+        monitor.process_log_file(&path).unwrap();
+        monitor.process_log_file(&dir.join("missing.jsonl")).unwrap_err();
+        drop(monitor);
+
+        let session = repo.list_sessions(&project.id).unwrap().remove(0);
+        let updates: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert_eq!(
+            updates[0],
+            MonitorUpdate::SessionUpdated {
+                project_id: project.id.clone(),
+                session_id: session.id,
+            }
+        );
+        assert!(matches!(
+            &updates[1],
+            MonitorUpdate::Event { project_id, event: MonitorEvent::FactsExtracted { count: 1, .. } } if *project_id == project.id
+        ));
+        assert_eq!(updates.len(), 2);
+        assert!(rx.is_closed());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_context_file_edits_are_reported() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Docs".to_string(),
+                slug: "docs".to_string(),
+                repo_path: Some(dir.display().to_string()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+        let sections = vec![repo
+            .create_context_section(crate::models::ContextSectionPayload {
+                project: project.id.clone(),
+                section_type: crate::models::SectionType::Gotchas,
+                title: "Gotchas".to_string(),
+                content: "Cache is per process".to_string(),
+                order: 0,
+                auto_extracted: None,
+            })
+            .unwrap()];
+
+        let (tx, rx) = async_channel::bounded(UPDATE_BUFFER);
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.join("logs"))).unwrap().with_updates(tx);
+        let path = dir.join("CLAUDE.md");
+        assert_eq!(monitor.context_file_project(&path), Some(project.id.as_str()));
+
+        // A fresh export is not an edit
+        let exported = crate::utils::generate_claude_md(&project, &sections);
+        std::fs::write(&path, &exported).unwrap();
+        monitor.check_context_file(&project.id, &path);
+        assert!(rx.try_recv().is_err());
+
+        std::fs::write(&path, exported.replace("per process", "shared through Redis")).unwrap();
+        monitor.check_context_file(&project.id, &path);
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            MonitorUpdate::Event { event: MonitorEvent::ContextFileEdited { sections: 1, .. }, .. }
+        ));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_backfill() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "History".to_string(),
+                slug: "history".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, text: &str, days_ago: u64| {
+            let path = dir.join(name);
+            let line = format!(r#"{{"type":"assistant","message":{{"role":"assistant","content":"{text}"}}}}"#);
+            std::fs::write(&path, format!("{line}\n")).unwrap();
+            let modified = std::time::SystemTime::now() - Duration::from_secs(days_ago * 24 * 60 * 60);
+            std::fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        };
+        write("old.jsonl", "Blocked by the database connection error in CI.", 30);
+        write("recent.jsonl", "Fixed the database connection error.", 2);
+        write("newest.jsonl", "TODO: migrate the old carts.", 1);
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap().without_events();
+        let since = Utc::now() - chrono::Duration::days(7);
+        let mut seen = Vec::new();
+        let report = monitor
+            .backfill(Some(since), |i, total, path| seen.push((i, total, path.file_name().unwrap().to_owned())))
+            .unwrap();
+
+        assert_eq!(seen, [(0, 2, "recent.jsonl".into()), (1, 2, "newest.jsonl".into())]);
+        assert_eq!(
+            report,
+            BackfillReport {
+                files: 2,
+                unchanged: 0,
+                failed: 0,
+                sessions_created: 2,
+                facts_created: 2,
+            }
+        );
+        assert_eq!(repo.count_unread_events().unwrap(), 0);
+
+        // Everything, oldest first; what was done before is skipped
+        let report = monitor.backfill(None, |_, _, _| {}).unwrap();
+        assert_eq!((report.files, report.unchanged, report.sessions_created), (3, 2, 1));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_transcript_dir_name() {
+        assert_eq!(transcript_dir_name(Path::new("/home/user/shop")), "-home-user-shop");
+        assert_eq!(transcript_dir_name(Path::new("/srv/my.app/web_ui")), "-srv-my-app-web-ui");
+    }
+
+    #[test]
+    fn test_existing_project_transcripts_are_found() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: Some("/home/user/shop".to_string()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        let project_dir = dir.join("-home-user-shop");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        std::fs::write(
+            project_dir.join("0b6f.jsonl"),
+            r#"{"type":"user","sessionId":"0b6f","message":{"role":"user","content":"Hello"}}"#,
+        )
+        .unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_existing_files().unwrap();
+        assert_eq!(repo.list_sessions(&project.id).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_idle_sessions_are_ended() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Quiet".to_string(),
+                slug: "quiet".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let line = |text: &str| format!(r#"{{"type":"user","message":{{"role":"user","content":"{text}"}}}}"#);
+        std::fs::write(&path, format!("{}\n", line("Fix the cart totals"))).unwrap();
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .with_event_handler(Arc::new(move |event| recorded.lock().unwrap().push(event)));
+        let watching_since = Utc::now() - chrono::Duration::hours(1);
+        monitor.process_log_file(&path).unwrap();
+
+        // Still within the idle period
+        assert_eq!(monitor.end_idle_sessions(Utc::now(), watching_since).unwrap(), 0);
+        assert!(repo.list_sessions(&project.id).unwrap()[0].is_active());
+
+        let later = Utc::now() + chrono::Duration::minutes(DEFAULT_SESSION_IDLE_MINUTES as i64 + 1);
+        assert_eq!(monitor.end_idle_sessions(later, watching_since).unwrap(), 1);
+        let session = repo.list_sessions(&project.id).unwrap().remove(0);
+        assert!(!session.is_active());
+        assert!(matches!(
+            events.lock().unwrap().last(),
+            Some(MonitorEvent::SessionEnded { summary, .. }) if *summary == session.summary
+        ));
+
+        // Ended sessions are left alone until the conversation carries on
+        assert_eq!(monitor.end_idle_sessions(later, watching_since).unwrap(), 0);
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", line("And the tax")).as_bytes()).unwrap();
+        drop(file);
+        monitor.process_log_file(&path).unwrap();
+        assert!(repo.list_sessions(&project.id).unwrap()[0].is_active());
+
+        // Sessions that went quiet while nothing was watching end without a notification
+        let event_count = events.lock().unwrap().len();
+        assert_eq!(monitor.end_idle_sessions(later, later).unwrap(), 1);
+        assert_eq!(events.lock().unwrap().len(), event_count);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_appended_lines_are_processed_incrementally() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Growing".to_string(),
+                slug: "growing".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let user = r#"{"type":"user","sessionId":"s1","message":{"role":"user","content":"Fix the cart totals"}}"#;
+        let reply = |text: &str, tokens: i64| {
+            format!(
+                r#"{{"type":"assistant","sessionId":"s1","message":{{"role":"assistant","content":[{{"type":"text","text":"{text}"}}],"usage":{{"input_tokens":{tokens}}}}}}}"#
+            )
+        };
+        std::fs::write(&path, format!("{user}\n{}\n", reply("We decided to store cents.", 1000))).unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_log_file(&path).unwrap();
+
+        // One more reply plus half of the next line, still being written
+        let appended = reply("TODO: migrate the old carts.", 1500);
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{appended}\n{{\"type\":\"assis").as_bytes()).unwrap();
+        drop(file);
+        monitor.process_log_file(&path).unwrap();
+
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].token_count, 1500);
+        assert_eq!(sessions[0].facts_extracted, 2);
+        assert_eq!(sessions[0].summary, "Fix the cart totals: store cents");
+
+        let record = repo.get_processed_log(&project.id, &path.to_string_lossy()).unwrap().unwrap();
+        let complete = format!("{user}\n{}\n{appended}\n", reply("We decided to store cents.", 1000));
+        assert_eq!(record.offset, complete.len() as i64);
+        assert_eq!(record.message_count, Some(3));
+
+        // Facts from the appended lines know their place in the whole conversation
+        let todo = &repo.list_facts_by_type(&project.id, FactType::Todo).unwrap()[0];
+        let source = todo.metadata.source.as_ref().unwrap();
+        assert_eq!(source.log_path.as_deref(), Some(path.to_str().unwrap()));
+        assert_eq!(source.conversation_id.as_deref(), Some("s1"));
+        assert_eq!(source.message_index, Some(2));
+        assert_eq!(read_fact_message(source).unwrap().content, "TODO: migrate the old carts.");
+
+        // A rewritten file is processed from the start again
+        std::fs::write(&path, format!("{user}\n{}\n", reply("Nothing to note.", 200))).unwrap();
+        monitor.process_log_file(&path).unwrap();
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].token_count, 200);
+        assert_eq!(sessions[0].facts_extracted, 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_session_usage_and_cost() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Costly".to_string(),
+                slug: "costly".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let reply = |id: &str, text: &str, output: i64| {
+            format!(
+                r#"{{"type":"assistant","sessionId":"s1","message":{{"id":"{id}","model":"claude-sonnet-4-5-20250929","role":"assistant","content":[{{"type":"text","text":"{text}"}}],"usage":{{"input_tokens":1000,"output_tokens":{output},"cache_creation_input_tokens":0,"cache_read_input_tokens":100000}}}}}}"#
+            )
+        };
+        // Both lines belong to one reply, so its usage is counted once
+        std::fs::write(&path, format!("{}\n{}\n", reply("msg_1", "Looking.", 100), reply("msg_1", "Done.", 1000))).unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_log_file(&path).unwrap();
+
+        let usage = repo.list_sessions(&project.id).unwrap()[0].usage;
+        assert_eq!((usage.input_tokens, usage.output_tokens, usage.cache_read_tokens), (1000, 1000, 100_000));
+        // 0.003 input + 0.015 output + 0.03 cache reads
+        assert!((usage.cost_usd - 0.048).abs() < 1e-9, "{}", usage.cost_usd);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", reply("msg_2", "Again.", 1000)).as_bytes()).unwrap();
+        drop(file);
+        monitor.process_log_file(&path).unwrap();
+
+        let usage = repo.list_sessions(&project.id).unwrap()[0].usage;
+        assert_eq!(usage.input_tokens, 2000);
+        assert!((usage.cost_usd - 0.096).abs() < 1e-9, "{}", usage.cost_usd);
+        assert_eq!(repo.project_stats(&project.id).unwrap().usage, usage);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_later_facts_resolve_blockers() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Checkout".to_string(),
+                slug: "checkout".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        let reply = |text: &str| {
+            format!(r#"{{"type":"assistant","message":{{"role":"assistant","content":"{text}"}}}}"#)
+        };
+        std::fs::write(&path, format!("{}\n", reply("Blocked by the database connection error in CI."))).unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_log_file(&path).unwrap();
+        let blockers = repo.list_facts_by_type(&project.id, FactType::Blocker).unwrap();
+        assert_eq!(blockers.len(), 1);
+        assert!(!blockers[0].stale);
+
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", reply("Fixed the database connection error.")).as_bytes())
+            .unwrap();
+        drop(file);
+        monitor.process_log_file(&path).unwrap();
+
+        let blocker = repo.get_fact(&blockers[0].id).unwrap();
+        assert!(blocker.stale);
+        assert_eq!(blocker.metadata.resolved_by.as_deref(), Some("Fixed the database connection error"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extraction_preferences_are_applied() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Docs".to_string(),
+                slug: "docs".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+        let mut preferences = ExtractionPreferences::default();
+        preferences.set_enabled(FactType::FileChange, false);
+        preferences.min_importance = 4;
+        repo.set_extraction_preferences(&project.id, &preferences).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("session.jsonl");
+        std::fs::write(
+            &path,
+            concat!(
+                r#"{"type":"assistant","message":{"role":"assistant","content":"#,
+                r#""Decided to use mdBook for the guide. Updated the theme in book/theme/custom.js. "#,
+                r#"TODO: add the install page."}}"#,
+                "\n",
+            ),
+        )
+        .unwrap();
+
+        LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .process_log_file(&path)
+            .unwrap();
+        let facts = repo.list_facts(&project.id, true).unwrap();
+        assert_eq!(facts.len(), 1);
+        assert_eq!(facts[0].fact_type, FactType::Decision);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sessions_record_git_commits() {
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        let repo_dir = dir.join("shop");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&repo_dir)
+                .args(args)
+                .env("GIT_AUTHOR_NAME", "Dev")
+                .env("GIT_AUTHOR_EMAIL", "dev@example.com")
+                .env("GIT_COMMITTER_NAME", "Dev")
+                .env("GIT_COMMITTER_EMAIL", "dev@example.com")
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        git(&["init", "-q", "-b", "main"]);
+        git(&["commit", "-q", "--allow-empty", "-m", "Initial commit"]);
+
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: Some(repo_dir.to_string_lossy().to_string()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let logs = dir.join("logs");
+        std::fs::create_dir_all(&logs).unwrap();
+        let path = logs.join("session.jsonl");
+        let line = |text: &str| format!(r#"{{"type":"user","message":{{"role":"user","content":"{text}"}}}}"#);
+        std::fs::write(&path, format!("{}\n", line("Store prices as cents"))).unwrap();
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(logs.clone())).unwrap();
+        monitor.process_log_file(&path).unwrap();
+        let session = repo.list_sessions(&project.id).unwrap().remove(0);
+        assert_eq!(session.git_branch.as_deref(), Some("main"));
+        assert!(session.git_head.is_some());
+
+        git(&["commit", "-q", "--allow-empty", "-m", "Store prices as cents"]);
+        let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+        std::io::Write::write_all(&mut file, format!("{}\n", line("Thanks")).as_bytes()).unwrap();
+        drop(file);
+        monitor.process_log_file(&path).unwrap();
+
+        let commits = repo.list_session_commits(&session.id).unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].summary, "Store prices as cents");
+
+        // Seen again on the next pass, but recorded once
+        monitor.process_log_file(&path).unwrap();
+        assert_eq!(repo.list_session_commits(&session.id).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_sessions_are_keyed_by_conversation() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Resumed".to_string(),
+                slug: "resumed".to_string(),
+                repo_path: None,
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, conversation: &str, text: &str| {
+            let path = dir.join(name);
+            let line = format!(
+                r#"{{"type":"assistant","sessionId":"{conversation}","message":{{"role":"assistant","content":[{{"type":"text","text":"{text}"}}]}}}}"#
+            );
+            std::fs::write(&path, format!("{line}\n")).unwrap();
+            path
+        };
+
+        let monitor = LogMonitor::new(project.id.clone(), repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_log_file(&write("first.jsonl", "c1", "We decided to store cents.")).unwrap();
+        let first = repo.list_sessions(&project.id).unwrap().remove(0);
+        assert_eq!(first.conversation_id.as_deref(), Some("c1"));
+
+        // A resumed conversation written to a new file updates the same session
+        monitor.process_log_file(&write("resumed.jsonl", "c1", "TODO: migrate the old carts.")).unwrap();
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, first.id);
+        assert_eq!(sessions[0].facts_extracted, first.facts_extracted + 1);
+
+        // Another conversation gets its own session
+        monitor.process_log_file(&write("other.jsonl", "c2", "Nothing to note.")).unwrap();
+        assert_eq!(repo.list_sessions(&project.id).unwrap().len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_event_bursts_are_coalesced() {
+        let start = Instant::now();
+        let path = PathBuf::from("/logs/session.jsonl");
+        let mut pending = PendingFiles::default();
+
+        // Events keep arriving: nothing is due until the file goes quiet
+        for ms in [0, 100, 200, 300] {
+            pending.touch(path.clone(), start + Duration::from_millis(ms));
+        }
+        assert!(pending.take_due(start + Duration::from_millis(600)).is_empty());
+        assert_eq!(pending.next_due(), Some(start + Duration::from_millis(300) + DEBOUNCE_WINDOW));
+        assert_eq!(pending.take_due(start + Duration::from_millis(800)), std::slice::from_ref(&path));
+        assert!(pending.next_due().is_none());
+
+        // A file written non-stop is still processed after the maximum delay
+        let mut at = start;
+        while at < start + DEBOUNCE_MAX_DELAY {
+            pending.touch(path.clone(), at);
+            at += Duration::from_millis(100);
+        }
+        assert_eq!(pending.take_due(start + DEBOUNCE_MAX_DELAY), [path]);
+    }
+
+    #[test]
+    fn test_project_for_path() {
+        let project = |name: &str, repo_path: Option<&str>| Project {
+            repo_path: repo_path.map(str::to_string),
+            ..Project::new(name.to_string())
+        };
+        let projects = [
+            project("Shop", Some("/home/user/shop")),
+            project("Shop Admin", Some("/home/user/shop/admin")),
+            project("Notes", None),
+        ];
+
+        let name = |path: &str| project_for_path(&projects, Path::new(path)).map(|p| p.name.as_str());
+        assert_eq!(name("/home/user/shop"), Some("Shop"));
+        assert_eq!(name("/home/user/shop/src/cart"), Some("Shop"));
+        assert_eq!(name("/home/user/shop/admin/src"), Some("Shop Admin"));
+        assert_eq!(name("/home/user/shopping"), None);
+    }
+
+    #[test]
+    fn test_transcripts_are_routed_to_projects() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let create = |name: &str, repo_path: &str| {
+            repo.create_project(ProjectPayload {
+                name: name.to_string(),
+                slug: name.to_lowercase(),
+                repo_path: Some(repo_path.to_string()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap()
+        };
+        let shop = create("Shop", "/home/user/shop");
+        let blog = create("Blog", "/home/user/blog");
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        let transcript = |cwd: &str| {
+            format!(r#"{{"type":"user","sessionId":"s","cwd":"{cwd}","message":{{"role":"user","content":"Hello"}}}}"#)
+        };
+        std::fs::create_dir_all(dir.join("-home-user-blog")).unwrap();
+        std::fs::write(dir.join("a.jsonl"), transcript("/home/user/shop/src")).unwrap();
+        std::fs::write(dir.join("b.jsonl"), transcript("/tmp/scratch")).unwrap();
+        // No working directory recorded: the directory name decides
+        std::fs::write(
+            dir.join("-home-user-blog").join("c.json"),
+            r#"{"messages":[{"role":"user","content":"Hello"}]}"#,
+        )
+        .unwrap();
+
+        let monitor = LogMonitor::for_all_projects(repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_existing_files().unwrap();
+        assert_eq!(repo.list_sessions(&shop.id).unwrap().len(), 1);
+        assert_eq!(repo.list_sessions(&blog.id).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_locked_projects_are_left_to_their_monitor() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let shop = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: Some("/home/user/shop".to_string()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        let lock_dir = dir.join("locks");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","sessionId":"s","cwd":"/home/user/shop","message":{"role":"user","content":"Hello"}}"#,
+        )
+        .unwrap();
+
+        let gui = LogMonitor::new(shop.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .with_lock_dir(lock_dir.clone());
+        gui.lock_projects().unwrap();
+
+        // A second monitor of the project refuses to start...
+        let second = LogMonitor::new(shop.id.clone(), repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .with_lock_dir(lock_dir.clone());
+        let error = second.lock_projects().unwrap_err().to_string();
+        assert_eq!(error, format!("Shop is already monitored by PID {}", std::process::id()));
+        assert!(second.holds_lock(&shop.id));
+
+        // ...while one for all projects leaves it alone until the first stops
+        let daemon = LogMonitor::for_all_projects(repo.clone(), Some(dir.clone()))
+            .unwrap()
+            .with_lock_dir(lock_dir);
+        daemon.lock_projects().unwrap();
+        assert_eq!(daemon.deferred.load(Ordering::Relaxed), 1);
+        daemon.process_log_file(&path).unwrap();
+        assert!(repo.list_sessions(&shop.id).unwrap().is_empty());
+
+        *gui.locks.lock().unwrap() = None;
+        daemon.lock_projects().unwrap();
+        assert_eq!(daemon.deferred.load(Ordering::Relaxed), 0);
+        daemon.process_log_file(&path).unwrap();
+        assert_eq!(repo.list_sessions(&shop.id).unwrap().len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_other_agents_transcripts() {
+        let repo = Repository::new(create_test_db().unwrap().into_shared());
+        let dir = std::env::temp_dir().join(format!("ccd-watcher-{}", uuid::Uuid::new_v4()));
+        let repo_dir = dir.join("shop");
+        std::fs::create_dir_all(&repo_dir).unwrap();
+        let project = repo
+            .create_project(ProjectPayload {
+                name: "Shop".to_string(),
+                slug: "shop".to_string(),
+                repo_path: Some(repo_dir.to_string_lossy().into_owned()),
+                status: ProjectStatus::Active,
+                priority: 0,
+                tech_stack: Vec::new(),
+                description: None,
+            })
+            .unwrap();
+
+        // Codex CLI records where it ran in its first line
+        let rollout = [
+            format!(r#"{{"type":"session_meta","payload":{{"id":"c1","cwd":"{}"}}}}"#, repo_dir.display()),
+            r#"{"type":"response_item","payload":{"type":"message","role":"assistant","content":[{"type":"output_text","text":"We decided to store cents."}]}}"#.to_string(),
+        ];
+        std::fs::write(dir.join("rollout-c1.jsonl"), rollout.join("\n")).unwrap();
+        // aider keeps its history in the repository
+        let history = repo_dir.join(crate::monitor::AIDER_HISTORY_FILE);
+        std::fs::write(&history, "# aider chat started at 2025-10-01 09:00:00\n\n#### Fix totals\n\nTODO: migrate the old carts.\n").unwrap();
+
+        let monitor = LogMonitor::for_all_projects(repo.clone(), Some(dir.clone())).unwrap();
+        monitor.process_existing_files().unwrap();
+        let sessions = repo.list_sessions(&project.id).unwrap();
+        assert_eq!(sessions.len(), 2);
+        assert!(sessions.iter().any(|s| s.conversation_id.as_deref() == Some("c1")));
+
+        // A second chat appended to the history file is a session of its own
+        let mut file = std::fs::OpenOptions::new().append(true).open(&history).unwrap();
+        std::io::Write::write_all(&mut file, b"\n# aider chat started at 2025-10-02 10:00:00\n\n#### Add login\n\nDone.\n").unwrap();
+        drop(file);
+        monitor.process_log_file(&history).unwrap();
+        let mut conversations: Vec<_> = repo
+            .list_sessions(&project.id)
+            .unwrap()
+            .into_iter()
+            .filter_map(|s| s.conversation_id)
+            .collect();
+        conversations.sort();
+        assert_eq!(conversations, ["aider-2025-10-01 09:00:00", "aider-2025-10-02 10:00:00", "c1"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,489 @@
+use crate::models::{ContextSection, ExtractedFact, FactType, Project, SectionExport, SectionType};
+use std::collections::HashSet;
+
+/// Default token budget for compressed context
+pub const DEFAULT_COMPACT_BUDGET: usize = 8_000;
+
+/// Tokens reserved for the briefing footer
+const FOOTER_RESERVE: usize = 32;
+
+/// Heading above each project's facts
+const FACTS_HEADING: &str = "### Key Facts\n";
+
+/// Estimate token count for text (rough estimate: 1 token ≈ 4 characters)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Normalize fact content for duplicate detection
+///
+/// Lowercases, drops punctuation and collapses whitespace so that trivially
+/// different phrasings of the same line compare equal.
+pub fn normalize_content(content: &str) -> String {
+    content
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Distinct words of normalized fact content
+pub fn content_words(content: &str) -> HashSet<String> {
+    normalize_content(content).split_whitespace().map(str::to_string).collect()
+}
+
+/// Share of words two facts have in common (Jaccard index), from 0.0 to 1.0
+pub fn word_overlap(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Stable hash of normalized fact content, used to deduplicate facts
+pub fn content_hash(content: &str) -> String {
+    hash_bytes(normalize_content(content).as_bytes())
+}
+
+/// Stable 64-bit FNV-1a hash of raw bytes, as lowercase hex
+///
+/// Unlike `DefaultHasher` the output is guaranteed not to change between
+/// Rust releases, so it is safe to persist.
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let hash = bytes
+        .iter()
+        .fold(OFFSET_BASIS, |hash, byte| (hash ^ *byte as u64).wrapping_mul(PRIME));
+    format!("{:016x}", hash)
+}
+
+/// Truncate text to roughly the given number of tokens (on a char boundary)
+fn truncate_to_tokens(text: &str, tokens: usize) -> String {
+    let max_bytes = tokens * 4;
+    if text.len() <= max_bytes {
+        return text.to_string();
+    }
+
+    let cut = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .take_while(|i| *i < max_bytes.saturating_sub(3))
+        .last()
+        .unwrap_or(0);
+    format!("{}...", text[..cut].trim_end())
+}
+
+/// Input for one project in a compressed briefing
+#[derive(Debug, Clone)]
+pub struct ProjectBriefing {
+    pub project: Project,
+    pub current_state: Option<String>,
+    pub pinned: Vec<ContextSection>, // Always included in full, whatever the budget
+    pub facts: Vec<ExtractedFact>,
+}
+
+impl ProjectBriefing {
+    /// Build a briefing from a project's sections and facts
+    ///
+    /// Sections excluded from export are ignored; pinned ones (a pinned Current
+    /// State included) are kept whole instead of sharing the budget.
+    pub fn new(project: Project, sections: &[ContextSection], facts: Vec<ExtractedFact>) -> Self {
+        let current_state = sections
+            .iter()
+            .filter(|s| s.section_type == SectionType::CurrentState && s.export == SectionExport::Default)
+            .map(|s| s.content.trim())
+            .find(|c| !c.is_empty())
+            .map(str::to_string);
+
+        let mut pinned: Vec<_> = sections
+            .iter()
+            .filter(|s| s.export == SectionExport::Always && !s.content.trim().is_empty())
+            .cloned()
+            .collect();
+        pinned.sort_by_key(|s| s.order);
+
+        Self {
+            project,
+            current_state,
+            pinned,
+            facts,
+        }
+    }
+}
+
+/// Token budgets compared by `ccd compactable`
+pub const COMPACTION_PREVIEW_BUDGETS: &[usize] = &[150_000, 100_000, 50_000];
+
+/// What a compressed briefing keeps and leaves out at one budget
+#[derive(Debug, Clone)]
+pub struct CompactionPreview {
+    pub budget: usize,
+    pub tokens: usize, // Estimated size of the generated briefing
+    pub kept_facts: usize,
+    pub dropped_facts: Vec<ExtractedFact>, // Facts that did not fit, most important first
+    pub duplicate_facts: usize,
+    pub state_tokens_dropped: usize, // Current State text cut off to fit its share of the budget
+}
+
+impl CompactionPreview {
+    /// Check if nothing but duplicates would be left out
+    pub fn keeps_everything(&self) -> bool {
+        self.dropped_facts.is_empty() && self.state_tokens_dropped == 0
+    }
+}
+
+/// Sections and fact lines chosen for a briefing, before rendering
+struct BriefingPlan<'a> {
+    header: String,
+    blocks: Vec<String>,
+    fact_lines: Vec<Vec<String>>,
+    dropped: Vec<&'a ExtractedFact>,
+    duplicates: usize,
+    state_tokens_dropped: usize,
+}
+
+/// Decide what goes into a briefing
+///
+/// Each project gets a header, its pinned sections in full and a share of the
+/// budget for its current state; the remainder is filled with the most important
+/// non-stale facts across all projects, skipping duplicates. Pinned sections can
+/// push a briefing over budget.
+fn plan_briefing<'a>(title: &str, briefings: &'a [ProjectBriefing], budget: usize) -> BriefingPlan<'a> {
+    let mut header = format!("# {}\n\n", title);
+    if briefings.len() > 1 {
+        let names: Vec<_> = briefings.iter().map(|b| b.project.name.as_str()).collect();
+        header.push_str(&format!("Projects: {}\n\n", names.join(", ")));
+    }
+
+    let mut used = estimate_tokens(&header) + FOOTER_RESERVE;
+    let state_budget = budget / (3 * briefings.len().max(1));
+    let mut state_tokens_dropped = 0;
+
+    // Project headers and current state
+    let mut blocks: Vec<String> = Vec::new();
+    for briefing in briefings {
+        let project = &briefing.project;
+        let mut block = format!("## {} [{}]\n", project.name, project.status);
+        if !project.tech_stack.is_empty() {
+            block.push_str(&format!("Tech: {}\n", project.tech_stack.join(", ")));
+        }
+        block.push('\n');
+
+        if let Some(state) = &briefing.current_state {
+            let kept = truncate_to_tokens(state, state_budget);
+            if kept.len() < state.len() {
+                state_tokens_dropped += estimate_tokens(state).saturating_sub(estimate_tokens(&kept));
+            }
+            block.push_str("### Current State\n");
+            block.push_str(&kept);
+            block.push_str("\n\n");
+        }
+
+        for section in &briefing.pinned {
+            block.push_str(&format!("### {}\n{}\n\n", section.title, section.content.trim()));
+        }
+
+        used += estimate_tokens(&block);
+        blocks.push(block);
+    }
+
+    // Most important facts first, across all projects
+    let mut candidates: Vec<(usize, &ExtractedFact)> = briefings
+        .iter()
+        .enumerate()
+        .flat_map(|(i, b)| b.facts.iter().filter(|f| !f.stale).map(move |f| (i, f)))
+        .collect();
+    candidates.sort_by(|(_, a), (_, b)| {
+        b.importance
+            .cmp(&a.importance)
+            .then_with(|| b.updated.cmp(&a.updated))
+    });
+
+    let mut seen = HashSet::new();
+    let mut fact_lines: Vec<Vec<String>> = vec![Vec::new(); briefings.len()];
+    let mut dropped = Vec::new();
+    let mut duplicates = 0;
+
+    for (index, fact) in candidates {
+        if !seen.insert(normalize_content(&fact.content)) {
+            duplicates += 1;
+            continue;
+        }
+
+        let line = fact_markdown(fact);
+        let mut cost = estimate_tokens(&line);
+        if fact_lines[index].is_empty() {
+            cost += estimate_tokens(FACTS_HEADING);
+        }
+
+        if used + cost <= budget {
+            used += cost;
+            fact_lines[index].push(line);
+        } else {
+            dropped.push(fact);
+        }
+    }
+
+    BriefingPlan {
+        header,
+        blocks,
+        fact_lines,
+        dropped,
+        duplicates,
+        state_tokens_dropped,
+    }
+}
+
+/// A fact as a list item; snippets keep their code in a fenced block below it
+fn fact_markdown(fact: &ExtractedFact) -> String {
+    if fact.fact_type != FactType::Snippet {
+        return format!("- **{}**: {}\n", fact.fact_type.display_name(), fact.content);
+    }
+
+    let metadata = &fact.metadata;
+    let title = match &metadata.target_file {
+        Some(path) => format!("- **{}** `{}`:\n", fact.fact_type.display_name(), path),
+        None => format!("- **{}**:\n", fact.fact_type.display_name()),
+    };
+    // Use a longer fence if the code contains one itself
+    let fence = if fact.content.contains("```") { "````" } else { "```" };
+    let code: String = fact
+        .content
+        .lines()
+        .map(|line| if line.is_empty() { "\n".to_string() } else { format!("  {}\n", line) })
+        .collect();
+    format!(
+        "{}  {}{}\n{}  {}\n",
+        title,
+        fence,
+        metadata.code_language.as_deref().unwrap_or(""),
+        code,
+        fence
+    )
+}
+
+/// Render a planned briefing to markdown
+fn render_briefing(plan: &BriefingPlan, budget: usize) -> String {
+    let mut markdown = plan.header.clone();
+    for (block, lines) in plan.blocks.iter().zip(&plan.fact_lines) {
+        markdown.push_str(block);
+        if !lines.is_empty() {
+            markdown.push_str(FACTS_HEADING);
+            for line in lines {
+                markdown.push_str(line);
+            }
+            markdown.push('\n');
+        }
+    }
+
+    let omitted = plan.dropped.len();
+    markdown.push_str("---\n");
+    markdown.push_str(&format!(
+        "_Compressed to ~{} of {} tokens{}; generated {}_\n",
+        estimate_tokens(&markdown),
+        budget,
+        if omitted > 0 {
+            format!(", {} facts omitted", omitted)
+        } else {
+            String::new()
+        },
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    markdown
+}
+
+/// Generate a compressed briefing for one or more projects within a token budget
+pub fn generate_briefing(title: &str, briefings: &[ProjectBriefing], budget: usize) -> String {
+    render_briefing(&plan_briefing(title, briefings, budget), budget)
+}
+
+/// Preview what `generate_briefing` would leave out at each budget
+pub fn preview_compaction(title: &str, briefings: &[ProjectBriefing], budgets: &[usize]) -> Vec<CompactionPreview> {
+    budgets
+        .iter()
+        .map(|&budget| {
+            let plan = plan_briefing(title, briefings, budget);
+            CompactionPreview {
+                budget,
+                tokens: estimate_tokens(&render_briefing(&plan, budget)),
+                kept_facts: plan.fact_lines.iter().map(Vec::len).sum(),
+                dropped_facts: plan.dropped.iter().map(|f| (*f).clone()).collect(),
+                duplicate_facts: plan.duplicates,
+                state_tokens_dropped: plan.state_tokens_dropped,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fact(project: &str, fact_type: FactType, content: &str, importance: i32) -> ExtractedFact {
+        let mut fact = ExtractedFact::new(project.to_string(), fact_type, content.to_string());
+        fact.importance = importance;
+        fact
+    }
+
+    #[test]
+    fn test_normalize_content() {
+        assert_eq!(normalize_content("  Decided to use  SQLite!"), "decided to use sqlite");
+        assert_eq!(
+            normalize_content("decided to use sqlite"),
+            normalize_content("Decided to use SQLite.")
+        );
+    }
+
+    #[test]
+    fn test_word_overlap() {
+        let todo = content_words("TODO: migrate the old carts");
+        assert_eq!(todo.len(), 5);
+        assert!((word_overlap(&todo, &content_words("todo: Migrate the old carts, too")) - 5.0 / 6.0).abs() < 1e-9);
+        assert_eq!(word_overlap(&todo, &content_words("Decided to use SQLite")), 0.0);
+        assert_eq!(word_overlap(&HashSet::new(), &HashSet::new()), 0.0);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        assert_eq!(content_hash("Decided to use SQLite."), content_hash("decided to use  sqlite"));
+        assert_ne!(content_hash("Decided to use SQLite"), content_hash("Decided to use Postgres"));
+        assert_eq!(content_hash(""), "cbf29ce484222325");
+    }
+
+    #[test]
+    fn test_briefing_deduplicates_across_projects() {
+        let briefings = vec![
+            ProjectBriefing {
+                project: Project::new("App".to_string()),
+                current_state: Some("Login works".to_string()),
+                pinned: Vec::new(),
+                facts: vec![fact("app", FactType::Decision, "Decided to use JWT auth.", 4)],
+            },
+            ProjectBriefing {
+                project: Project::new("Api".to_string()),
+                current_state: None,
+                pinned: Vec::new(),
+                facts: vec![
+                    fact("api", FactType::Decision, "decided to use JWT auth", 4),
+                    fact("api", FactType::Blocker, "Error: token refresh fails", 5),
+                ],
+            },
+        ];
+
+        let md = generate_briefing("Workspace Briefing: Shop", &briefings, DEFAULT_COMPACT_BUDGET);
+
+        assert!(md.contains("Projects: App, Api"));
+        assert!(md.contains("## App [Active]"));
+        assert!(md.contains("Login works"));
+        assert_eq!(md.to_lowercase().matches("jwt auth").count(), 1);
+        assert!(md.contains("token refresh fails"));
+    }
+
+    #[test]
+    fn test_briefing_respects_budget() {
+        let facts: Vec<_> = (0..200)
+            .map(|i| {
+                fact(
+                    "p",
+                    FactType::Insight,
+                    &format!("Insight number {} about the caching layer behaviour", i),
+                    if i == 199 { 5 } else { 2 },
+                )
+            })
+            .collect();
+
+        let briefings = vec![ProjectBriefing {
+            project: Project::new("Big".to_string()),
+            current_state: Some("x".repeat(10_000)),
+            pinned: Vec::new(),
+            facts,
+        }];
+
+        let budget = 500;
+        let md = generate_briefing("Big", &briefings, budget);
+
+        assert!(estimate_tokens(&md) <= budget);
+        assert!(md.contains("Insight number 199"), "Most important fact should be kept");
+        assert!(md.contains("facts omitted"));
+    }
+
+    #[test]
+    fn test_briefing_section_export_flags() {
+        let section = |section_type, title: &str, content: &str, export| {
+            let mut section = ContextSection::new("p".to_string(), section_type, title.to_string());
+            section.content = content.to_string();
+            section.export = export;
+            section
+        };
+        let sections = vec![
+            section(SectionType::CurrentState, "Current State", "Checkout is half done", SectionExport::Default),
+            section(SectionType::Gotchas, "Gotchas", &"Never run migrations twice. ".repeat(40), SectionExport::Always),
+            section(SectionType::Custom, "Scratchpad", "Ask about the invoice format", SectionExport::Never),
+        ];
+        let facts: Vec<_> = (0..20)
+            .map(|i| fact("p", FactType::Insight, &format!("Insight number {} about the cart", i), 3))
+            .collect();
+        let briefings = vec![ProjectBriefing::new(Project::new("Shop".to_string()), &sections, facts)];
+        assert_eq!(briefings[0].pinned.len(), 1);
+
+        let md = generate_briefing("Shop", &briefings, 100);
+
+        assert_eq!(md.matches("Never run migrations twice.").count(), 40);
+        assert!(md.contains("Checkout is half done"));
+        assert!(!md.contains("Scratchpad"));
+        assert!(md.contains("facts omitted"));
+    }
+
+    #[test]
+    fn test_briefing_renders_snippets_in_fences() {
+        let mut snippet = fact("p", FactType::Snippet, "CREATE TABLE orders (\n\n    id INTEGER\n);", 3);
+        snippet.metadata.code_language = Some("sql".to_string());
+        snippet.metadata.target_file = Some("db/schema.sql".to_string());
+        let briefings = vec![ProjectBriefing {
+            project: Project::new("Shop".to_string()),
+            current_state: None,
+            pinned: Vec::new(),
+            facts: vec![snippet, fact("p", FactType::Decision, "Decided to store cents", 4)],
+        }];
+
+        let md = generate_briefing("Shop", &briefings, DEFAULT_COMPACT_BUDGET);
+
+        assert!(md.contains(
+            "- **Snippet** `db/schema.sql`:\n  ```sql\n  CREATE TABLE orders (\n\n      id INTEGER\n  );\n  ```\n"
+        ));
+        assert!(md.contains("- **Decision**: Decided to store cents\n"));
+    }
+
+    #[test]
+    fn test_preview_compaction() {
+        let facts: Vec<_> = (0..50)
+            .map(|i| fact("p", FactType::Insight, &format!("Insight number {} about the cache", i), 3))
+            .chain([fact("p", FactType::Insight, "insight number 0 about the cache!", 2)])
+            .collect();
+        let briefings = vec![ProjectBriefing {
+            project: Project::new("Big".to_string()),
+            current_state: Some("x".repeat(2_000)),
+            pinned: Vec::new(),
+            facts,
+        }];
+
+        let previews = preview_compaction("Big", &briefings, &[10_000, 300]);
+
+        assert!(previews[0].keeps_everything());
+        assert_eq!(previews[0].kept_facts, 50);
+        assert_eq!(previews[0].duplicate_facts, 1);
+
+        let small = &previews[1];
+        assert!(!small.keeps_everything());
+        assert!(small.state_tokens_dropped > 0);
+        assert_eq!(small.kept_facts + small.dropped_facts.len(), 50);
+        assert!(small.tokens <= 300);
+    }
+}
@@ -0,0 +1,150 @@
+use similar::{ChangeTag, TextDiff};
+
+/// Lines of unchanged context shown around each change
+const CONTEXT_RADIUS: usize = 3;
+
+const ANSI_RED: &str = "\x1b[31m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_CYAN: &str = "\x1b[36m";
+const ANSI_BOLD: &str = "\x1b[1m";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Kind of line in a rendered diff, used for coloring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffLineKind {
+    Header,
+    Hunk,
+    Added,
+    Removed,
+    Context,
+}
+
+/// Difference between an existing CLAUDE.md and a fresh render
+#[derive(Debug, Clone)]
+pub struct ContextDiff {
+    unified: String,
+    added: usize,
+    removed: usize,
+}
+
+impl ContextDiff {
+    /// Diff the current file contents against the new render
+    pub fn new(current: &str, new: &str, path: &str) -> Self {
+        let diff = TextDiff::from_lines(current, new);
+
+        let (mut added, mut removed) = (0, 0);
+        for change in diff.iter_all_changes() {
+            match change.tag() {
+                ChangeTag::Insert => added += 1,
+                ChangeTag::Delete => removed += 1,
+                ChangeTag::Equal => {}
+            }
+        }
+
+        let unified = diff
+            .unified_diff()
+            .context_radius(CONTEXT_RADIUS)
+            .header(&format!("{} (current)", path), &format!("{} (new)", path))
+            .to_string();
+
+        Self {
+            unified,
+            added,
+            removed,
+        }
+    }
+
+    /// Check if the new render differs from the current file
+    pub fn has_changes(&self) -> bool {
+        self.added > 0 || self.removed > 0
+    }
+
+    pub fn added(&self) -> usize {
+        self.added
+    }
+
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// Short summary such as "+12 -3 lines"
+    pub fn summary(&self) -> String {
+        format!("+{} -{} lines", self.added, self.removed)
+    }
+
+    /// Unified diff text
+    pub fn unified(&self) -> &str {
+        &self.unified
+    }
+
+    /// Unified diff lines classified for display
+    pub fn lines(&self) -> impl Iterator<Item = (DiffLineKind, &str)> {
+        self.unified.lines().map(|line| {
+            let kind = if line.starts_with("---") || line.starts_with("+++") {
+                DiffLineKind::Header
+            } else if line.starts_with("@@") {
+                DiffLineKind::Hunk
+            } else if line.starts_with('+') {
+                DiffLineKind::Added
+            } else if line.starts_with('-') {
+                DiffLineKind::Removed
+            } else {
+                DiffLineKind::Context
+            };
+            (kind, line)
+        })
+    }
+
+    /// Unified diff with ANSI colors for terminal output
+    pub fn colored(&self) -> String {
+        let mut out = String::new();
+        for (kind, line) in self.lines() {
+            let color = match kind {
+                DiffLineKind::Header => ANSI_BOLD,
+                DiffLineKind::Hunk => ANSI_CYAN,
+                DiffLineKind::Added => ANSI_GREEN,
+                DiffLineKind::Removed => ANSI_RED,
+                DiffLineKind::Context => "",
+            };
+
+            if color.is_empty() {
+                out.push_str(line);
+            } else {
+                out.push_str(&format!("{}{}{}", color, line, ANSI_RESET));
+            }
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_diff() {
+        let current = "# App\n\n## Current State\nLogin works\n";
+        let new = "# App\n\n## Current State\nLogin and signup work\n\n## Next Steps\nShip it\n";
+
+        let diff = ContextDiff::new(current, new, "CLAUDE.md");
+
+        assert!(diff.has_changes());
+        assert_eq!(diff.removed(), 1);
+        assert_eq!(diff.added(), 4);
+        assert_eq!(diff.summary(), "+4 -1 lines");
+        assert!(diff.unified().contains("-Login works"));
+        assert!(diff.unified().contains("+## Next Steps"));
+
+        let kinds: Vec<_> = diff.lines().map(|(kind, _)| kind).collect();
+        assert_eq!(kinds[..3], [DiffLineKind::Header, DiffLineKind::Header, DiffLineKind::Hunk]);
+        assert!(diff.colored().contains("\x1b[31m-Login works\x1b[0m"));
+    }
+
+    #[test]
+    fn test_context_diff_unchanged() {
+        let diff = ContextDiff::new("same\n", "same\n", "CLAUDE.md");
+        assert!(!diff.has_changes());
+        assert!(diff.unified().is_empty());
+    }
+}
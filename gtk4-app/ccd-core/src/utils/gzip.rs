@@ -0,0 +1,36 @@
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{Read, Write};
+
+/// Compress `bytes` in the gzip format
+pub fn gzip(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(bytes)?;
+    encoder.finish().context("Failed to compress")
+}
+
+/// Decompress gzip data
+pub fn gunzip(bytes: &[u8]) -> Result<Vec<u8>> {
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut decompressed)
+        .context("Failed to decompress")?;
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gzip_round_trip() {
+        let text = "{\"type\":\"user\",\"message\":{\"content\":\"Fix the cart totals\"}}\n".repeat(100);
+        let compressed = gzip(text.as_bytes()).unwrap();
+        assert!(compressed.len() < text.len() / 10);
+        assert_eq!(gunzip(&compressed).unwrap(), text.as_bytes());
+
+        assert!(gunzip(b"not gzip").is_err());
+    }
+}
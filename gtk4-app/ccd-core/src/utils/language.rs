@@ -0,0 +1,155 @@
+use crate::models::ExtractedFact;
+use anyhow::{anyhow, bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Environment variable holding the translation command
+pub const TRANSLATE_COMMAND_ENV: &str = "CCD_TRANSLATE_COMMAND";
+
+/// Detect the language of a piece of text
+///
+/// Returns an ISO 639-3 code (e.g. "eng", "deu") or `None` when the text is
+/// too short or ambiguous to classify reliably.
+pub fn detect_language(text: &str) -> Option<String> {
+    whatlang::detect(text)
+        .filter(|info| info.is_reliable())
+        .map(|info| info.lang().code().to_string())
+}
+
+/// Translation hook backed by an external command
+///
+/// The command is run through `sh -c` with the text on stdin and the target
+/// language in `CCD_TARGET_LANG`; whatever it prints on stdout is used as the
+/// translation. This lets users plug in a local model, a CLI client for a
+/// translation API, or anything else without the tracker depending on it.
+#[derive(Debug, Clone)]
+pub struct Translator {
+    command: String,
+}
+
+impl Translator {
+    /// Create a translator that runs the given shell command
+    pub fn new(command: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+        }
+    }
+
+    /// Create a translator from `CCD_TRANSLATE_COMMAND`, if it is set
+    pub fn from_env() -> Option<Self> {
+        std::env::var(TRANSLATE_COMMAND_ENV)
+            .ok()
+            .filter(|c| !c.trim().is_empty())
+            .map(Self::new)
+    }
+
+    /// Translate text into the target language
+    pub fn translate(&self, text: &str, target: &str) -> Result<String> {
+        let mut child = Command::new("sh")
+            .arg("-c")
+            .arg(&self.command)
+            .env("CCD_TARGET_LANG", target)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to run translation command '{}'", self.command))?;
+
+        let mut stdin = child.stdin.take().context("Failed to open translation command stdin")?;
+
+        // Feed stdin from another thread while stdout is drained, so a command that streams
+        // its output can't fill the stdout pipe and stall; stdin is closed once written
+        let (written, output) = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || stdin.write_all(text.as_bytes()));
+            let output = child.wait_with_output();
+            (writer.join(), output)
+        });
+
+        let output = output?;
+        if !output.status.success() {
+            bail!(
+                "Translation command failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        written
+            .map_err(|_| anyhow!("Translation command stdin writer panicked"))?
+            .context("Failed to write to translation command")?;
+
+        let translated = String::from_utf8(output.stdout)
+            .context("Translation command produced invalid UTF-8")?;
+        Ok(translated.trim().to_string())
+    }
+
+    /// Translate facts whose detected language differs from the target
+    ///
+    /// Facts with no detected language are left untouched, as are facts the
+    /// command fails on (a warning is logged). Returns the number translated.
+    pub fn translate_facts(&self, facts: &mut [ExtractedFact], target: &str) -> usize {
+        let mut translated = 0;
+
+        for fact in facts.iter_mut() {
+            match fact.metadata.language.as_deref() {
+                Some(lang) if lang != target => {}
+                _ => continue,
+            }
+
+            match self.translate(&fact.content, target) {
+                Ok(content) if !content.is_empty() => {
+                    fact.content = content;
+                    fact.metadata.language = Some(target.to_string());
+                    translated += 1;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to translate fact {}: {}", fact.id, e),
+            }
+        }
+
+        translated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::FactType;
+
+    #[test]
+    fn test_detect_language() {
+        assert_eq!(
+            detect_language("We decided to use SQLite because it is embedded and needs no server").as_deref(),
+            Some("eng")
+        );
+        assert_eq!(
+            detect_language("Wir haben entschieden, die Datenbank lokal zu speichern, weil es einfacher ist").as_deref(),
+            Some("deu")
+        );
+        assert_eq!(detect_language("ok"), None);
+    }
+
+    #[test]
+    fn test_translate_facts_only_foreign() {
+        let mut english = ExtractedFact::new("p".to_string(), FactType::Decision, "use sqlite".to_string());
+        english.metadata.language = Some("eng".to_string());
+        let mut german = ExtractedFact::new("p".to_string(), FactType::Decision, "sqlite nutzen".to_string());
+        german.metadata.language = Some("deu".to_string());
+        let unknown = ExtractedFact::new("p".to_string(), FactType::Todo, "todo".to_string());
+
+        let mut facts = vec![english, german, unknown];
+        let translator = Translator::new("tr a-z A-Z");
+
+        assert_eq!(translator.translate_facts(&mut facts, "eng"), 1);
+        assert_eq!(facts[0].content, "use sqlite");
+        assert_eq!(facts[1].content, "SQLITE NUTZEN");
+        assert_eq!(facts[1].metadata.language.as_deref(), Some("eng"));
+        assert_eq!(facts[2].content, "todo");
+    }
+
+    #[test]
+    fn test_translate_streams_large_text() {
+        // More than a pipe buffer, so the command must be read from while it is written to
+        let text = "sqlite nutzen ".repeat(10_000);
+        let translated = Translator::new("cat").translate(&text, "eng").unwrap();
+        assert_eq!(translated, text.trim());
+    }
+}
@@ -0,0 +1,543 @@
+use crate::models::{
+    AttachmentMode, ContextSection, DependencyContext, Project, SectionAttachment, SectionExport,
+    INLINE_ATTACHMENT_MAX_BYTES,
+};
+use crate::utils::read_snapshot;
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Generate markdown content from project and sections
+pub fn generate_claude_md(project: &Project, sections: &[ContextSection]) -> String {
+    generate_claude_md_with_dependencies(project, sections, &[])
+}
+
+/// Generate markdown content from project, sections and the projects it depends on
+pub fn generate_claude_md_with_dependencies(
+    project: &Project,
+    sections: &[ContextSection],
+    dependencies: &[DependencyContext],
+) -> String {
+    generate_claude_md_with_attachments(project, sections, dependencies, &HashMap::new(), AttachmentMode::Link)
+}
+
+/// Generate markdown content including the files attached to sections (keyed by section ID)
+///
+/// Sections excluded from export are left out.
+pub fn generate_claude_md_with_attachments(
+    project: &Project,
+    sections: &[ContextSection],
+    dependencies: &[DependencyContext],
+    attachments: &HashMap<String, Vec<SectionAttachment>>,
+    mode: AttachmentMode,
+) -> String {
+    let mut markdown = String::new();
+
+    // Header
+    markdown.push_str(&format!("# {}\n\n", project.name));
+
+    // Project overview section
+    markdown.push_str("## Project Overview\n");
+    if let Some(desc) = &project.description {
+        markdown.push_str(desc);
+        markdown.push_str("\n\n");
+    }
+
+    // Tech stack
+    if !project.tech_stack.is_empty() {
+        markdown.push_str("## Tech Stack\n");
+        for tech in &project.tech_stack {
+            markdown.push_str(&format!("- {}\n", tech));
+        }
+        markdown.push('\n');
+    }
+
+    // Sorted sections by order
+    let mut sorted_sections: Vec<_> = sections
+        .iter()
+        .filter(|s| s.export != SectionExport::Never)
+        .cloned()
+        .collect();
+    sorted_sections.sort_by_key(|s| s.order);
+
+    // Add each section
+    for section in sorted_sections {
+        markdown.push_str(&section.to_markdown());
+        if let Some(files) = attachments.get(&section.id) {
+            markdown.push_str(&generate_attachments_md(files, mode));
+        }
+    }
+
+    // Dependencies
+    let dependencies_md = generate_dependencies_md(dependencies);
+    if !dependencies_md.is_empty() {
+        markdown.push_str(&dependencies_md);
+    }
+
+    // Footer
+    markdown.push_str("---\n");
+    markdown.push_str(&format!("_Last updated: {}_\n", chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")));
+
+    markdown
+}
+
+/// Generate the dependencies section (summaries and open blockers)
+///
+/// Dependencies without a summary and without open blockers are omitted.
+pub fn generate_dependencies_md(dependencies: &[DependencyContext]) -> String {
+    let relevant: Vec<_> = dependencies
+        .iter()
+        .filter(|d| d.include_summary || d.has_open_blockers())
+        .collect();
+
+    if relevant.is_empty() {
+        return String::new();
+    }
+
+    let mut markdown = String::from("## Dependencies\n\n");
+
+    for dependency in relevant {
+        markdown.push_str(&format!("### {}\n", dependency.project.name));
+
+        if dependency.include_summary {
+            if let Some(desc) = &dependency.project.description {
+                markdown.push_str(desc);
+                markdown.push_str("\n\n");
+            }
+
+            if !dependency.decisions.is_empty() {
+                markdown.push_str("Key decisions:\n");
+                for decision in &dependency.decisions {
+                    markdown.push_str(&format!("- {}\n", decision.content));
+                }
+                markdown.push('\n');
+            }
+        }
+
+        if dependency.has_open_blockers() {
+            markdown.push_str("⚠ Open blockers:\n");
+            for blocker in &dependency.open_blockers {
+                markdown.push_str(&format!("- {}\n", blocker.content));
+            }
+            markdown.push('\n');
+        }
+    }
+
+    markdown
+}
+
+/// Render a section's attachments: small text files inline, everything else as links
+pub fn generate_attachments_md(attachments: &[SectionAttachment], mode: AttachmentMode) -> String {
+    let mut markdown = String::new();
+    let mut links = Vec::new();
+
+    for attachment in attachments {
+        match inline_content(attachment, mode) {
+            Some(content) => {
+                // Use a longer fence if the file contains one itself
+                let fence = if content.contains("```") { "````" } else { "```" };
+                markdown.push_str(&format!(
+                    "**{}**\n{}{}\n{}\n{}\n\n",
+                    attachment.name,
+                    fence,
+                    attachment.language_hint(),
+                    content.trim_end(),
+                    fence
+                ));
+            }
+            None => links.push(attachment),
+        }
+    }
+
+    if !links.is_empty() {
+        markdown.push_str("Attachments:\n");
+        for attachment in links {
+            markdown.push_str(&format!("- [{}](<{}>)\n", attachment.name, attachment.path));
+        }
+        markdown.push('\n');
+    }
+
+    markdown
+}
+
+/// Contents of an attachment if it is a small, readable text file
+fn inline_content(attachment: &SectionAttachment, mode: AttachmentMode) -> Option<String> {
+    if mode == AttachmentMode::Link {
+        return None;
+    }
+
+    let metadata = std::fs::metadata(&attachment.path).ok()?;
+    if !metadata.is_file() || metadata.len() > INLINE_ATTACHMENT_MAX_BYTES {
+        return None;
+    }
+
+    // Binary files fail UTF-8 decoding or contain NUL bytes
+    let content = std::fs::read_to_string(&attachment.path).ok()?;
+    (!content.contains('\0')).then_some(content)
+}
+
+/// Headings the generated CLAUDE.md fills from the project rather than from its sections
+const GENERATED_HEADINGS: &[&str] = &["Project Overview", "Tech Stack", "Dependencies"];
+
+/// Start of the "Last updated" footer of a generated CLAUDE.md
+const UPDATED_FOOTER: &str = "---\n_Last updated: ";
+
+/// A `## ` section of a CLAUDE.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownSection {
+    pub title: String,
+    pub content: String, // Without the heading, trimmed
+}
+
+/// Split a CLAUDE.md into its `## ` sections
+///
+/// Anything before the first section (the `# name` heading) and the footers are
+/// dropped; headings inside code blocks don't start a section.
+pub fn parse_claude_md(markdown: &str) -> Vec<MarkdownSection> {
+    let markdown = read_snapshot(markdown).map_or(markdown, |(content, _)| content);
+
+    let mut sections = Vec::new();
+    let mut current: Option<(&str, Vec<&str>)> = None;
+    let mut fence: Option<&str> = None;
+    for line in markdown.lines() {
+        let marker = fence_marker(line.trim());
+        match (fence, marker) {
+            (Some(open), Some(close)) if close.starts_with(open) && close == line.trim() => fence = None,
+            (Some(_), _) => {}
+            (None, Some(open)) => fence = Some(open),
+            (None, None) => {
+                if let Some(title) = line.strip_prefix("## ") {
+                    sections.extend(current.take().map(|(title, lines)| markdown_section(title, &lines)));
+                    current = Some((title.trim(), Vec::new()));
+                    continue;
+                }
+            }
+        }
+        if let Some((_, lines)) = &mut current {
+            lines.push(line);
+        }
+    }
+    sections.extend(current.map(|(title, lines)| markdown_section(title, &lines)));
+
+    sections
+}
+
+/// Leading run of three or more backticks or tildes opening or closing a code block
+fn fence_marker(line: &str) -> Option<&str> {
+    let fence_char = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.len() - line.trim_start_matches(fence_char).len();
+    (len >= 3).then(|| &line[..len])
+}
+
+fn markdown_section(title: &str, lines: &[&str]) -> MarkdownSection {
+    let content = lines.join("\n");
+    let content = match content.rfind(UPDATED_FOOTER) {
+        Some(start) if start == 0 || content[..start].ends_with('\n') => &content[..start],
+        _ => content.as_str(),
+    };
+
+    MarkdownSection {
+        title: title.to_string(),
+        content: content.trim().to_string(),
+    }
+}
+
+/// A change made by hand to an exported CLAUDE.md
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextFileEdit {
+    /// The text of a section was edited
+    Changed { section_id: String, title: String, content: String },
+    /// A section was added that the project doesn't have
+    Added { title: String, content: String },
+}
+
+impl ContextFileEdit {
+    pub fn title(&self) -> &str {
+        match self {
+            ContextFileEdit::Changed { title, .. } | ContextFileEdit::Added { title, .. } => title,
+        }
+    }
+
+    pub fn content(&self) -> &str {
+        match self {
+            ContextFileEdit::Changed { content, .. } | ContextFileEdit::Added { content, .. } => content,
+        }
+    }
+}
+
+/// Sections edited in `file`, compared to `generated`: what exporting `sections` renders now
+///
+/// Sections are matched by title, and what the export adds after a section's own
+/// text (its attachments) is left out. If the file's snapshot footer shows it is
+/// unchanged since the export there is nothing to report; sections updated in the
+/// database after that export are skipped, so an outdated file can't undo them.
+/// Sections missing from the file are left alone.
+pub fn context_file_edits(file: &str, generated: &str, sections: &[ContextSection]) -> Vec<ContextFileEdit> {
+    let snapshot = read_snapshot(file);
+    if snapshot.as_ref().is_some_and(|(content, snapshot)| snapshot.matches(content)) {
+        return Vec::new();
+    }
+    let exported = snapshot.map(|(_, snapshot)| snapshot.exported);
+
+    let rendered = parse_claude_md(generated);
+    let mut edits = Vec::new();
+    for edited in parse_claude_md(file) {
+        if GENERATED_HEADINGS.contains(&edited.title.as_str()) {
+            continue;
+        }
+
+        let Some(section) = sections.iter().find(|section| section.title == edited.title) else {
+            if !edited.content.is_empty() {
+                edits.push(ContextFileEdit::Added {
+                    title: edited.title,
+                    content: edited.content,
+                });
+            }
+            continue;
+        };
+
+        let rendered = rendered
+            .iter()
+            .find(|rendered| rendered.title == section.title)
+            .map_or("", |rendered| rendered.content.as_str());
+        if edited.content == rendered || exported.is_some_and(|exported| section.updated > exported) {
+            continue;
+        }
+
+        let own_text = section.content.trim();
+        let extra = rendered.strip_prefix(own_text).unwrap_or_default().trim_start();
+        let content = edited.content.strip_suffix(extra).unwrap_or(&edited.content).trim_end();
+        if content != own_text {
+            edits.push(ContextFileEdit::Changed {
+                section_id: section.id.clone(),
+                title: section.title.clone(),
+                content: content.to_string(),
+            });
+        }
+    }
+
+    edits
+}
+
+/// Save markdown content to a file
+pub fn save_markdown_to_file(content: &str, path: &Path) -> Result<()> {
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExtractedFact, FactType, SectionType, ProjectStatus};
+
+    #[test]
+    fn test_generate_claude_md() {
+        let project = Project {
+            id: "test".to_string(),
+            name: "Test Project".to_string(),
+            slug: "test-project".to_string(),
+            repo_path: None,
+            status: ProjectStatus::Active,
+            priority: 0,
+            tech_stack: vec!["Rust".to_string(), "GTK4".to_string()],
+            description: Some("A test project".to_string()),
+            pinned: false,
+            created: chrono::Utc::now(),
+            updated: chrono::Utc::now(),
+        };
+
+        let sections = vec![
+            ContextSection {
+                id: "1".to_string(),
+                project: "test".to_string(),
+                section_type: SectionType::Architecture,
+                title: "Architecture".to_string(),
+                content: "Test architecture content".to_string(),
+                order: 0,
+                auto_extracted: false,
+                locked: false,
+                export: SectionExport::Default,
+                created: chrono::Utc::now(),
+                updated: chrono::Utc::now(),
+            },
+        ];
+
+        let md = generate_claude_md(&project, &sections);
+
+        assert!(md.contains("# Test Project"));
+        assert!(md.contains("## Tech Stack"));
+        assert!(md.contains("- Rust"));
+        assert!(md.contains("## Architecture"));
+        assert!(md.contains("Test architecture content"));
+    }
+
+    #[test]
+    fn test_generate_claude_md_skips_excluded_sections() {
+        let project = Project::new("Notes".to_string());
+        let mut internal = ContextSection::new("notes".to_string(), SectionType::Custom, "Scratchpad".to_string());
+        internal.content = "Not for the assistant".to_string();
+        internal.export = SectionExport::Never;
+        let mut pinned = ContextSection::new("notes".to_string(), SectionType::Gotchas, "Gotchas".to_string());
+        pinned.export = SectionExport::Always;
+
+        let md = generate_claude_md(&project, &[internal, pinned]);
+        assert!(!md.contains("## Scratchpad"));
+        assert!(!md.contains("Not for the assistant"));
+        assert!(md.contains("## Gotchas"));
+    }
+
+    #[test]
+    fn test_generate_dependencies_md() {
+        let mut library = Project::new("Shared Lib".to_string());
+        library.description = Some("Common utilities".to_string());
+
+        let mut decision = ExtractedFact::new(
+            "lib".to_string(),
+            FactType::Decision,
+            "Decided to use semver".to_string(),
+        );
+        decision.importance = 4;
+        let blocker = ExtractedFact::new(
+            "lib".to_string(),
+            FactType::Blocker,
+            "Build fails on ARM".to_string(),
+        );
+
+        let dependencies = vec![DependencyContext {
+            project: library.clone(),
+            include_summary: true,
+            decisions: vec![decision],
+            open_blockers: vec![blocker],
+        }];
+
+        let md = generate_dependencies_md(&dependencies);
+        assert!(md.contains("## Dependencies"));
+        assert!(md.contains("### Shared Lib"));
+        assert!(md.contains("- Decided to use semver"));
+        assert!(md.contains("- Build fails on ARM"));
+
+        let quiet = vec![DependencyContext {
+            project: library,
+            include_summary: false,
+            decisions: Vec::new(),
+            open_blockers: Vec::new(),
+        }];
+        assert!(generate_dependencies_md(&quiet).is_empty());
+    }
+
+    #[test]
+    fn test_generate_attachments_md() {
+        let dir = std::env::temp_dir().join(format!("ccd-attachments-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let schema = dir.join("schema.sql");
+        std::fs::write(&schema, "CREATE TABLE projects (id TEXT);\n").unwrap();
+        let diagram = dir.join("diagram.png");
+        std::fs::write(&diagram, [0x89, b'P', b'N', b'G', 0, 0]).unwrap();
+
+        let attachment = |path: &Path| SectionAttachment {
+            id: path.display().to_string(),
+            section: "1".to_string(),
+            path: path.display().to_string(),
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            created: chrono::Utc::now(),
+            updated: chrono::Utc::now(),
+        };
+        let attachments = vec![attachment(&schema), attachment(&diagram)];
+
+        let md = generate_attachments_md(&attachments, AttachmentMode::Inline);
+        assert!(md.contains("**schema.sql**\n```sql\nCREATE TABLE projects (id TEXT);\n```"));
+        assert!(md.contains(&format!("- [diagram.png](<{}>)", diagram.display())));
+
+        let md = generate_attachments_md(&attachments, AttachmentMode::Link);
+        assert!(!md.contains("```"));
+        assert!(md.contains(&format!("- [schema.sql](<{}>)", schema.display())));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn section(id: &str, title: &str, content: &str, order: i32) -> ContextSection {
+        let mut section = ContextSection::new("shop".to_string(), SectionType::Custom, title.to_string());
+        section.id = id.to_string();
+        section.content = content.to_string();
+        section.order = order;
+        section
+    }
+
+    #[test]
+    fn test_parse_claude_md() {
+        let markdown = "# Shop\n\n## Project Overview\nAn online shop\n\n## Architecture\n\nAxum API\n\n```md\n## Not a heading\n```\n\n## Gotchas\n\nCache is per process\n\n---\n_Last updated: 2026-10-01 12:00 UTC_\n";
+
+        let sections = parse_claude_md(markdown);
+        let titles: Vec<&str> = sections.iter().map(|section| section.title.as_str()).collect();
+        assert_eq!(titles, ["Project Overview", "Architecture", "Gotchas"]);
+        assert_eq!(sections[1].content, "Axum API\n\n```md\n## Not a heading\n```");
+        assert_eq!(sections[2].content, "Cache is per process");
+
+        let exported = crate::utils::append_snapshot(markdown, vec!["shop".to_string()]);
+        assert_eq!(parse_claude_md(&exported), sections);
+    }
+
+    #[test]
+    fn test_context_file_edits() {
+        let project = Project::new("Shop".to_string());
+        let sections = vec![
+            section("1", "Architecture", "Axum API", 0),
+            section("2", "Gotchas", "Cache is per process", 1),
+        ];
+        let generated = generate_claude_md(&project, &sections);
+        assert!(context_file_edits(&generated, &generated, &sections).is_empty());
+
+        let edited = generated
+            .replace("Cache is per process", "Cache is shared through Redis")
+            .replace("---\n", "## Deployment\n\nFly.io, two regions\n\n---\n");
+        assert_eq!(
+            context_file_edits(&edited, &generated, &sections),
+            vec![
+                ContextFileEdit::Changed {
+                    section_id: "2".to_string(),
+                    title: "Gotchas".to_string(),
+                    content: "Cache is shared through Redis".to_string(),
+                },
+                ContextFileEdit::Added {
+                    title: "Deployment".to_string(),
+                    content: "Fly.io, two regions".to_string(),
+                },
+            ]
+        );
+
+        // Unchanged since the export, or changed in the database after it
+        let exported = crate::utils::append_snapshot(&generated, vec![project.id.clone()]);
+        assert!(context_file_edits(&exported, &generated, &sections).is_empty());
+        let exported = crate::utils::append_snapshot(&edited, vec![project.id.clone()]).replace("Redis", "Valkey");
+        let mut updated = sections.clone();
+        updated[1].updated = chrono::Utc::now() + chrono::Duration::minutes(1);
+        assert_eq!(context_file_edits(&exported, &generated, &updated).len(), 1);
+    }
+
+    #[test]
+    fn test_context_file_edits_leave_out_attachments() {
+        let project = Project::new("Shop".to_string());
+        let sections = vec![section("1", "Architecture", "Axum API", 0)];
+        let attachment = SectionAttachment {
+            id: "a".to_string(),
+            section: "1".to_string(),
+            path: "/docs/diagram.png".to_string(),
+            name: "diagram.png".to_string(),
+            created: chrono::Utc::now(),
+            updated: chrono::Utc::now(),
+        };
+        let attachments = HashMap::from([("1".to_string(), vec![attachment])]);
+        let generated =
+            generate_claude_md_with_attachments(&project, &sections, &[], &attachments, AttachmentMode::Link);
+
+        let edited = generated.replace("Axum API", "Axum API behind nginx");
+        assert_eq!(
+            context_file_edits(&edited, &generated, &sections),
+            vec![ContextFileEdit::Changed {
+                section_id: "1".to_string(),
+                title: "Architecture".to_string(),
+                content: "Axum API behind nginx".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,15 @@
+pub mod markdown;
+pub mod compress;
+pub mod language;
+pub mod diff;
+pub mod snapshot;
+pub mod redact;
+pub mod gzip;
+
+pub use markdown::*;
+pub use compress::*;
+pub use language::*;
+pub use diff::*;
+pub use snapshot::*;
+pub use redact::*;
+pub use gzip::*;
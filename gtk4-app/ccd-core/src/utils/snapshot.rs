@@ -0,0 +1,99 @@
+use crate::utils::{estimate_tokens, hash_bytes};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Version of the snapshot footer format
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Marker line introducing the snapshot footer of an exported CLAUDE.md
+pub const SNAPSHOT_MARKER: &str = "<!-- ccd:snapshot -->";
+
+/// Machine-readable record of the export a CLAUDE.md came from
+///
+/// Appended as a fenced JSON block after `SNAPSHOT_MARKER`. The hash covers
+/// everything before the footer, so edits made after the export can be detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExportSnapshot {
+    pub schema_version: u32,
+    pub projects: Vec<String>, // Project IDs (several for a workspace briefing)
+    pub exported: DateTime<Utc>,
+    pub tokens: usize, // Estimated tokens of the exported markdown
+    pub hash: String,
+}
+
+impl ExportSnapshot {
+    /// Snapshot of freshly generated markdown
+    pub fn new(projects: Vec<String>, markdown: &str) -> Self {
+        Self {
+            schema_version: SNAPSHOT_VERSION,
+            projects,
+            exported: Utc::now(),
+            tokens: estimate_tokens(markdown),
+            hash: hash_bytes(markdown.as_bytes()),
+        }
+    }
+
+    /// Whether `markdown` (without the footer) is still exactly what was exported
+    pub fn matches(&self, markdown: &str) -> bool {
+        self.hash == hash_bytes(markdown.as_bytes())
+    }
+
+    /// Render the footer block
+    pub fn to_markdown(&self) -> String {
+        // Serializing plain strings, numbers and a timestamp cannot fail
+        let json = serde_json::to_string(self).unwrap_or_default();
+        format!("{}\n```json\n{}\n```\n", SNAPSHOT_MARKER, json)
+    }
+}
+
+/// Append a snapshot footer for `projects` to generated markdown
+pub fn append_snapshot(markdown: &str, projects: Vec<String>) -> String {
+    let snapshot = ExportSnapshot::new(projects, markdown);
+    format!("{}\n{}", markdown, snapshot.to_markdown())
+}
+
+/// Split an exported file into its content and snapshot footer
+///
+/// Returns None when the file has no (readable) footer.
+pub fn read_snapshot(text: &str) -> Option<(&str, ExportSnapshot)> {
+    let start = text.rfind(SNAPSHOT_MARKER)?;
+    let json = text[start + SNAPSHOT_MARKER.len()..]
+        .trim()
+        .strip_prefix("```json")?
+        .trim_end()
+        .strip_suffix("```")?;
+    let snapshot = serde_json::from_str(json.trim()).ok()?;
+
+    let content = &text[..start];
+    Some((content.strip_suffix('\n').unwrap_or(content), snapshot))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let markdown = "# Shop\n\n## Current State\nCheckout works\n---\n_Last updated: today_\n";
+        let exported = append_snapshot(markdown, vec!["p1".to_string()]);
+        assert!(exported.starts_with(markdown));
+
+        let (content, snapshot) = read_snapshot(&exported).unwrap();
+        assert_eq!(content, markdown);
+        assert_eq!(snapshot.projects, vec!["p1".to_string()]);
+        assert_eq!(snapshot.schema_version, SNAPSHOT_VERSION);
+        assert_eq!(snapshot.tokens, estimate_tokens(markdown));
+        assert!(snapshot.matches(content));
+
+        // Edited after the export
+        let edited = exported.replace("Checkout works", "Checkout is broken");
+        let (content, snapshot) = read_snapshot(&edited).unwrap();
+        assert!(!snapshot.matches(content));
+    }
+
+    #[test]
+    fn test_read_snapshot_without_footer() {
+        assert!(read_snapshot("# Shop\n").is_none());
+        assert!(read_snapshot(&format!("# Shop\n{}\n```json\nnot json\n```\n", SNAPSHOT_MARKER)).is_none());
+    }
+}
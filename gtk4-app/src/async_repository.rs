@@ -0,0 +1,42 @@
+use crate::db::Repository;
+use crate::models::ContextSection;
+use anyhow::{anyhow, Result};
+
+/// Repository facade for the GUI that runs queries off the GTK main loop
+///
+/// Each call runs on the gio blocking thread pool with its own pooled connection and
+/// resolves once the query finishes, so views can await results from
+/// `glib::spawn_future_local` without freezing on slow disks or big tables.
+#[derive(Clone)]
+pub struct AsyncRepository {
+    repository: Repository,
+}
+
+impl AsyncRepository {
+    pub fn new(repository: Repository) -> Self {
+        Self { repository }
+    }
+
+    /// Run any repository work on a worker thread and await its result
+    pub async fn run<T, F>(&self, f: F) -> Result<T>
+    where
+        F: FnOnce(&Repository) -> Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let repository = self.repository.clone();
+        gio::spawn_blocking(move || f(&repository))
+            .await
+            .map_err(|_| anyhow!("Database task panicked"))?
+    }
+
+    pub async fn list_context_sections(&self, project_id: &str) -> Result<Vec<ContextSection>> {
+        let project_id = project_id.to_string();
+        self.run(move |repository| repository.list_context_sections(&project_id)).await
+    }
+}
+
+impl From<Repository> for AsyncRepository {
+    fn from(repository: Repository) -> Self {
+        Self::new(repository)
+    }
+}
@@ -0,0 +1,35 @@
+pub mod voice;
+pub mod ocr;
+
+pub use voice::*;
+pub use ocr::*;
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+/// Directory where captured media (audio, images) is kept for provenance
+pub fn attachments_dir() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("claude-context-tracker")
+        .join("attachments")
+}
+
+/// Copy a captured file into the attachments directory, returning its new path
+pub fn store_attachment(source: &Path) -> Result<PathBuf> {
+    let dir = attachments_dir();
+    std::fs::create_dir_all(&dir).context("Failed to create attachments directory")?;
+
+    let mut name = Uuid::new_v4().to_string();
+    if let Some(ext) = source.extension().and_then(|e| e.to_str()) {
+        name.push('.');
+        name.push_str(ext);
+    }
+
+    let target = dir.join(name);
+    std::fs::copy(source, &target)
+        .with_context(|| format!("Failed to copy attachment {}", source.display()))?;
+
+    Ok(target)
+}
@@ -0,0 +1,84 @@
+use crate::capture::store_attachment;
+use crate::db::Repository;
+use crate::models::ExtractedFact;
+use crate::monitor::FactExtractor;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// Environment variable selecting the tesseract language(s), e.g. "eng+deu"
+#[cfg(feature = "ocr")]
+pub const OCR_LANG_ENV: &str = "CCD_OCR_LANG";
+
+/// Default tesseract language
+#[cfg(feature = "ocr")]
+const DEFAULT_OCR_LANG: &str = "eng";
+
+/// Image file extensions accepted for capture
+pub const IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "tif", "tiff", "bmp", "webp", "gif"];
+
+/// Check whether a path looks like an image we can run OCR on
+pub fn is_image_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| IMAGE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Recognize the text in an image with tesseract
+#[cfg(feature = "ocr")]
+pub fn recognize_text(image: &Path) -> Result<String> {
+    use anyhow::Context;
+
+    let lang = std::env::var(OCR_LANG_ENV).unwrap_or_else(|_| DEFAULT_OCR_LANG.to_string());
+    let mut tess = leptess::LepTess::new(None, &lang)
+        .with_context(|| format!("Failed to initialize tesseract for '{}'", lang))?;
+
+    tess.set_image(image)
+        .with_context(|| format!("Failed to load image {}", image.display()))?;
+
+    let text = tess.get_utf8_text().context("Tesseract returned invalid text")?;
+    Ok(text)
+}
+
+/// Recognize the text in an image with tesseract
+#[cfg(not(feature = "ocr"))]
+pub fn recognize_text(_image: &Path) -> Result<String> {
+    bail!("OCR support is not enabled in this build (rebuild with `--features ocr`)")
+}
+
+/// Run OCR on a screenshot and store the facts found in it, with the image attached
+///
+/// Returns the stored facts; an image without any recognizable facts is not kept.
+pub fn capture_image(repository: &Repository, project_id: &str, image: &Path) -> Result<Vec<ExtractedFact>> {
+    if !image.is_file() {
+        bail!("Image file not found: {}", image.display());
+    }
+
+    let text = recognize_text(image)?;
+    let mut payloads = FactExtractor::new(project_id.to_string()).extract_from_message(&text, None);
+    if payloads.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let attachment = store_attachment(image)?.to_string_lossy().into_owned();
+    for payload in &mut payloads {
+        payload.metadata.attachment = Some(attachment.clone());
+    }
+
+    payloads
+        .into_iter()
+        .map(|payload| repository.create_fact(payload))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_image_file() {
+        assert!(is_image_file(Path::new("/tmp/screenshot.PNG")));
+        assert!(is_image_file(Path::new("whiteboard.jpeg")));
+        assert!(!is_image_file(Path::new("notes.wav")));
+        assert!(!is_image_file(Path::new("README")));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::capture::store_attachment;
+use crate::db::Repository;
+use crate::models::{ExtractedFact, ExtractedFactPayload, FactMetadata, FactType};
+use crate::utils::detect_language;
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+
+/// Environment variable pointing at the whisper.cpp binary (`whisper-cli` or `main`)
+pub const WHISPER_BIN_ENV: &str = "CCD_WHISPER_BIN";
+
+/// Environment variable pointing at the whisper.cpp model file (e.g. ggml-base.en.bin)
+pub const WHISPER_MODEL_ENV: &str = "CCD_WHISPER_MODEL";
+
+/// Environment variable overriding the command used to record voice notes
+pub const RECORD_COMMAND_ENV: &str = "CCD_RECORD_COMMAND";
+
+/// Default recorder: 16 kHz mono WAV, the input format whisper.cpp expects
+const DEFAULT_RECORD_COMMAND: &str = "arecord -q -f S16_LE -r 16000 -c 1 -t wav";
+
+/// Local whisper.cpp speech-to-text
+#[derive(Debug, Clone)]
+pub struct Whisper {
+    binary: PathBuf,
+    model: PathBuf,
+}
+
+impl Whisper {
+    pub fn new(binary: PathBuf, model: PathBuf) -> Self {
+        Self { binary, model }
+    }
+
+    /// Load the whisper.cpp configuration from the environment, if present
+    pub fn from_env() -> Option<Self> {
+        let binary = std::env::var_os(WHISPER_BIN_ENV)?;
+        let model = std::env::var_os(WHISPER_MODEL_ENV)?;
+        Some(Self::new(binary.into(), model.into()))
+    }
+
+    /// Transcribe an audio file (16 kHz WAV) to text
+    pub fn transcribe(&self, audio: &Path) -> Result<String> {
+        let output = Command::new(&self.binary)
+            .arg("-m")
+            .arg(&self.model)
+            .arg("-f")
+            .arg(audio)
+            .args(["-nt", "-np"])
+            .output()
+            .with_context(|| format!("Failed to run whisper at {}", self.binary.display()))?;
+
+        if !output.status.success() {
+            bail!(
+                "Whisper failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(parse_transcript(&String::from_utf8_lossy(&output.stdout)))
+    }
+}
+
+/// Clean whisper.cpp output into a single line of text
+///
+/// Drops non-speech markers such as `[BLANK_AUDIO]` or `(wind blowing)`.
+pub fn parse_transcript(output: &str) -> String {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter(|line| {
+            !((line.starts_with('[') && line.ends_with(']'))
+                || (line.starts_with('(') && line.ends_with(')')))
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Transcribe a voice note and store it as an Insight fact with the audio attached
+pub fn capture_voice_note(
+    repository: &Repository,
+    project_id: &str,
+    audio: &Path,
+    whisper: &Whisper,
+) -> Result<ExtractedFact> {
+    if !audio.is_file() {
+        bail!("Audio file not found: {}", audio.display());
+    }
+
+    let transcript = whisper.transcribe(audio)?;
+    if transcript.is_empty() {
+        bail!("No speech recognized in {}", audio.display());
+    }
+
+    let attachment = store_attachment(audio)?;
+
+    repository.create_fact(ExtractedFactPayload {
+        project: project_id.to_string(),
+        session: None,
+        fact_type: FactType::Insight,
+        content: transcript.clone(),
+        importance: 3,
+        stale: None,
+        metadata: FactMetadata {
+            language: detect_language(&transcript),
+            attachment: Some(attachment.to_string_lossy().into_owned()),
+            ..FactMetadata::default()
+        },
+        needs_review: None,
+    })
+}
+
+/// In-progress voice recording from the default microphone
+///
+/// Runs from the first click of the voice note toggle until the second.
+pub struct Recorder {
+    child: Child,
+    path: PathBuf,
+}
+
+impl Recorder {
+    /// Start recording to a temporary WAV file
+    ///
+    /// Uses `CCD_RECORD_COMMAND` if set (the output path is appended as the
+    /// last argument), otherwise `arecord`.
+    pub fn start() -> Result<Self> {
+        let command = std::env::var(RECORD_COMMAND_ENV).unwrap_or_else(|_| DEFAULT_RECORD_COMMAND.to_string());
+        let mut parts = command.split_whitespace();
+        let program = parts.next().context("Record command is empty")?;
+
+        let path = std::env::temp_dir().join(format!("ccd-voice-{}.wav", uuid::Uuid::new_v4()));
+        let child = Command::new(program)
+            .args(parts)
+            .arg(&path)
+            .stdin(Stdio::null())
+            .spawn()
+            .with_context(|| format!("Failed to start recorder '{}'", program))?;
+
+        Ok(Self { child, path })
+    }
+
+    /// Stop recording and return the path of the recorded file
+    pub fn stop(mut self) -> Result<PathBuf> {
+        // Interrupt rather than kill so the recorder finalizes the WAV header
+        let interrupted = Command::new("kill")
+            .args(["-INT", &self.child.id().to_string()])
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if !interrupted {
+            self.child.kill().ok();
+        }
+        self.child.wait().context("Failed to stop recorder")?;
+
+        if !self.path.is_file() {
+            bail!("Recorder did not produce {}", self.path.display());
+        }
+        Ok(self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_transcript() {
+        let output = "\n [BLANK_AUDIO]\n We decided to move auth to the gateway.\n (keyboard clicking)\n Ship it Friday.\n";
+        assert_eq!(
+            parse_transcript(output),
+            "We decided to move auth to the gateway. Ship it Friday."
+        );
+        assert_eq!(parse_transcript("[BLANK_AUDIO]\n"), "");
+    }
+}
@@ -1,31 +1,227 @@
-use crate::db::Repository;
-use crate::models::{ProjectPayload, ProjectStatus, SessionPayload};
-use crate::utils::generate_claude_md;
+use crate::capture::{capture_image, capture_voice_note, Whisper, WHISPER_BIN_ENV, WHISPER_MODEL_ENV};
+use crate::db::{AuditFilter, Page, Repository, CLONE_TOP_FACTS};
+use crate::cli::{DaemonAction, SectionAction, WorkspaceAction};
+use crate::daemon::{self, UNIT_NAME};
+use crate::models::{
+    commits_display, format_bytes, format_cost, format_number_with_separator, AttachmentMode, AuditEntry, AuditOperation, ContextSectionPayload, DependencyContext, ExtractedFactPayload,
+    ProjectDependencyPayload, ProjectPayload, ProjectStatus, SectionAttachmentPayload, SectionExport, SectionTemplatePayload, SectionType, SessionPayload, SessionUsage,
+    Workspace, WorkspacePayload, SETTING_REPLICA_URL,
+};
+use crate::monitor::{
+    benchmarks_path, context_file_path, LogMonitor, load_benchmark_runs, read_context_file_edits, read_fact_message,
+    run_benchmarks, save_benchmark_run, ImportanceScorer, DEFAULT_BENCHMARK_SIZES,
+};
+use crate::sync::{last_replicated, replicate_once, run_replication, PocketBaseClient};
+use crate::utils::{
+    append_snapshot, estimate_tokens, generate_briefing, generate_claude_md_with_attachments, preview_compaction,
+    read_snapshot, ContextDiff, ContextFileEdit, ProjectBriefing, Translator, COMPACTION_PREVIEW_BUDGETS, TRANSLATE_COMMAND_ENV,
+};
 use anyhow::{bail, Context, Result};
-use std::path::Path;
+use std::io::{IsTerminal, Read, Write};
+use std::path::{Path, PathBuf};
+
+/// Output path or argument meaning stdout (for output) or stdin (for input)
+const STDIO_PATH: &str = "-";
+
+/// Print a status line, on stderr when stdout carries generated context
+fn report(to_stdout: bool, line: &str) {
+    if to_stdout {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
+
+/// Write generated markdown to a file, or to stdout for `-`
+fn write_output(path: &str, markdown: &str) -> Result<()> {
+    if path == STDIO_PATH {
+        let mut stdout = std::io::stdout().lock();
+        stdout.write_all(markdown.as_bytes()).context("Failed to write to stdout")?;
+        return stdout.flush().context("Failed to write to stdout");
+    }
+    std::fs::write(path, markdown).with_context(|| format!("Failed to write {}", path))
+}
+
+/// Warn before replacing an export that was edited by hand since it was written
+///
+/// Only files carrying a snapshot footer (see `--snapshot`) can be checked.
+fn warn_if_edited(path: &str, to_stdout: bool) {
+    let Ok(current) = std::fs::read_to_string(path) else {
+        return;
+    };
+    if let Some((content, snapshot)) = read_snapshot(&current) {
+        if !snapshot.matches(content) {
+            report(
+                to_stdout,
+                &format!(
+                    "⚠ {} was edited by hand after it was exported on {}",
+                    path,
+                    snapshot.exported.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ),
+            );
+        }
+    }
+}
 
 /// Execute the pull command
-pub fn pull_command(repository: &Repository, project: &str, output: Option<String>) -> Result<()> {
+pub fn pull_command(
+    repository: &Repository,
+    project: &str,
+    output: Option<String>,
+    translate_to: Option<String>,
+    preview: bool,
+    link_attachments: bool,
+    snapshot: bool,
+) -> Result<()> {
+    let output_path = output.unwrap_or_else(|| "./CLAUDE.md".to_string());
+    let to_stdout = output_path == STDIO_PATH;
+    if preview && to_stdout {
+        bail!("--preview needs an output file, not stdout");
+    }
+
     // Find project by name or ID
     let proj = find_project(repository, project)?;
 
-    // Get context sections
-    let sections = repository.list_context_sections(&proj.id)?;
+    // Get context sections and their attached files
+    let sections = repository.list_exported_sections(&proj.id)?;
+    let attachments = repository.list_project_attachments(&proj.id)?;
+
+    // Get dependency summaries and blockers
+    let mut dependencies = repository.load_dependency_context(&proj.id)?;
+
+    // Normalize fact language if requested
+    if let Some(target) = translate_to {
+        let translator = load_translator()?;
+        let mut translated = 0;
+        for dependency in &mut dependencies {
+            translated += translator.translate_facts(&mut dependency.decisions, &target);
+            translated += translator.translate_facts(&mut dependency.open_blockers, &target);
+        }
+        report(to_stdout, &format!("✓ Translated {} facts to '{}'", translated, target));
+    }
 
     // Generate markdown
-    let markdown = generate_claude_md(&proj, &sections);
+    let mode = if link_attachments {
+        AttachmentMode::Link
+    } else {
+        AttachmentMode::Inline
+    };
+    let mut markdown = generate_claude_md_with_attachments(&proj, &sections, &dependencies, &attachments, mode);
+    if snapshot {
+        markdown = append_snapshot(&markdown, vec![proj.id.clone()]);
+    }
 
-    // Write to file
-    let output_path = output.unwrap_or_else(|| "./CLAUDE.md".to_string());
-    std::fs::write(&output_path, markdown)
-        .context("Failed to write CLAUDE.md")?;
+    if !to_stdout {
+        warn_if_edited(&output_path, to_stdout);
+    }
 
-    println!("✓ Pulled context for '{}' to {}", proj.name, output_path);
-    println!("  {} sections", sections.len());
+    // Show what would change and ask before overwriting
+    if preview && !confirm_overwrite(&output_path, &markdown)? {
+        println!("Aborted, {} left unchanged", output_path);
+        return Ok(());
+    }
 
-    // Send notification
-    let path = Path::new(&output_path).to_path_buf();
-    crate::notifications::notify_context_pulled(&proj.name, Some(&path));
+    write_output(&output_path, &markdown)?;
+
+    let destination = if to_stdout { "stdout" } else { output_path.as_str() };
+    report(to_stdout, &format!("✓ Pulled context for '{}' to {}", proj.name, destination));
+    report(to_stdout, &format!("  {} sections", sections.len()));
+    let attached: usize = attachments.values().map(Vec::len).sum();
+    if attached > 0 {
+        report(to_stdout, &format!("  {} attachments", attached));
+    }
+    warn_dependency_blockers(&dependencies);
+
+    // Send notification (a pipeline is its own confirmation)
+    if !to_stdout {
+        let path = Path::new(&output_path).to_path_buf();
+        crate::notifications::notify_context_pulled(&proj.name, Some(&path));
+    }
+
+    Ok(())
+}
+
+/// Print a diff between an existing file and its new contents and ask for confirmation
+///
+/// Returns true when the file should be written (new file, or the user agreed).
+fn confirm_overwrite(path: &str, new_contents: &str) -> Result<bool> {
+    let current = match std::fs::read_to_string(path) {
+        Ok(current) => current,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            println!("{} does not exist yet, it will be created", path);
+            return Ok(true);
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path)),
+    };
+
+    let diff = ContextDiff::new(&current, new_contents, path);
+    if !diff.has_changes() {
+        println!("{} is up to date", path);
+        return Ok(true);
+    }
+
+    if std::io::stdout().is_terminal() {
+        print!("{}", diff.colored());
+    } else {
+        print!("{}", diff.unified());
+    }
+    println!("\n{}", diff.summary());
+
+    ask(&format!("Write changes to {}?", path))
+}
+
+/// Ask a yes/no question on the terminal; anything but yes means no
+fn ask(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Execute the import command
+pub fn import_command(repository: &Repository, project: &str, file: Option<String>, yes: bool) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let path = match file {
+        Some(file) => PathBuf::from(file),
+        None => context_file_path(&proj).context("Project has no repository path, pass --file")?,
+    };
+    if !path.exists() {
+        bail!("{} does not exist", path.display());
+    }
+
+    let edits = read_context_file_edits(repository, &proj, &path)?;
+    if edits.is_empty() {
+        println!("{} has no changes to import into '{}'", path.display(), proj.name);
+        return Ok(());
+    }
+
+    // Show what each section would become
+    for edit in &edits {
+        let (current, label) = match edit {
+            ContextFileEdit::Changed { section_id, title, .. } => {
+                (repository.get_context_section(section_id)?.content, title.clone())
+            }
+            ContextFileEdit::Added { title, .. } => (String::new(), format!("{} (new section)", title)),
+        };
+        let diff = ContextDiff::new(&current, edit.content(), &label);
+        if std::io::stdout().is_terminal() {
+            print!("{}", diff.colored());
+        } else {
+            print!("{}", diff.unified());
+        }
+    }
+
+    let count = format!("{} section{}", edits.len(), if edits.len() == 1 { "" } else { "s" });
+    if !yes && !ask(&format!("\nImport {} from {}?", count, path.display()))? {
+        println!("Aborted, nothing imported");
+        return Ok(());
+    }
+
+    repository.import_context_file_edits(&proj.id, &edits)?;
+    println!("✓ Imported {} into '{}'", count, proj.name);
+    println!("  Earlier versions are kept as section revisions");
 
     Ok(())
 }
@@ -39,6 +235,17 @@ pub fn push_command(
 ) -> Result<()> {
     let proj = find_project(repository, project)?;
 
+    let summary = if summary == STDIO_PATH {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input).context("Failed to read summary from stdin")?;
+        input.trim().to_string()
+    } else {
+        summary
+    };
+    if summary.is_empty() {
+        bail!("Session summary is empty");
+    }
+
     let payload = SessionPayload {
         project: proj.id.clone(),
         summary,
@@ -46,6 +253,7 @@ pub fn push_command(
         token_count: tokens,
         session_start: Some(chrono::Utc::now()),
         session_end: Some(chrono::Utc::now()),
+        conversation_id: None,
     };
 
     let session = repository.create_session(payload)?;
@@ -87,21 +295,56 @@ pub fn status_command(repository: &Repository, project: Option<String>) -> Resul
 }
 
 fn show_project_status(repository: &Repository, proj: &crate::models::Project) -> Result<()> {
-    let sessions = repository.list_sessions(&proj.id)?;
-    let facts = repository.list_facts(&proj.id, false)?;
+    let stats = repository.project_stats(&proj.id)?;
+    let latest_sessions = repository.list_sessions_page(&proj.id, Page::first(1))?;
 
     println!("  Status: {}", proj.status);
-    println!("  Sessions: {}", sessions.len());
-    println!("  Facts: {}", facts.len());
+    println!("  Sessions: {} ({} tokens total)", stats.session_count, stats.total_tokens);
+    println!("  Facts: {}", stats.fact_count - stats.stale_facts);
 
-    if let Some(latest) = sessions.first() {
+    if let Some(latest) = latest_sessions.first() {
         println!("  Latest: {} tokens", latest.token_count);
         println!("  Usage: {:.1}%", latest.token_percentage());
     }
 
+    if let Some(at) = stats.last_activity {
+        println!("  Last activity: {}", at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"));
+    }
+
+    let dependencies = repository.load_dependency_context(&proj.id)?;
+    if !dependencies.is_empty() {
+        let names: Vec<_> = dependencies.iter().map(|d| d.project.name.as_str()).collect();
+        println!("  Depends on: {}", names.join(", "));
+    }
+
+    let dependents = repository.list_project_dependents(&proj.id)?;
+    if !dependents.is_empty() {
+        let names: Vec<_> = dependents
+            .iter()
+            .filter_map(|d| repository.get_project(&d.project).ok())
+            .map(|p| p.name)
+            .collect();
+        println!("  Used by: {}", names.join(", "));
+    }
+
+    warn_dependency_blockers(&dependencies);
+
     Ok(())
 }
 
+/// Print a warning for each dependency with open blockers
+fn warn_dependency_blockers(dependencies: &[DependencyContext]) {
+    for dependency in dependencies.iter().filter(|d| d.has_open_blockers()) {
+        let count = dependency.open_blockers.len();
+        eprintln!(
+            "  ⚠ Dependency '{}' has {} open blocker{}",
+            dependency.project.name,
+            count,
+            if count == 1 { "" } else { "s" }
+        );
+    }
+}
+
 /// Execute the list command
 pub fn list_command(repository: &Repository, status: Option<String>) -> Result<()> {
     let status_filter = status.as_ref().map(|s| match s.as_str() {
@@ -130,6 +373,16 @@ pub fn list_command(repository: &Repository, status: Option<String>) -> Result<(
         }
     }
 
+    let stats = repository.global_stats()?;
+    println!(
+        "\n{} projects ({} active), {} sessions, {} tokens, {} facts",
+        stats.project_count,
+        stats.count_for_status(ProjectStatus::Active),
+        stats.session_count,
+        stats.total_tokens,
+        stats.fact_count - stats.stale_facts
+    );
+
     Ok(())
 }
 
@@ -174,7 +427,7 @@ pub fn diff_command(
     to: Option<String>,
 ) -> Result<()> {
     let proj = find_project(repository, project)?;
-    let sessions = repository.list_sessions(&proj.id)?;
+    let sessions = repository.list_sessions_page(&proj.id, Page::first(2))?;
 
     if sessions.len() < 2 {
         println!("Need at least 2 sessions to compare");
@@ -208,6 +461,1030 @@ pub fn diff_command(
     Ok(())
 }
 
+/// Execute the depend command
+pub fn depend_command(
+    repository: &Repository,
+    project: &str,
+    on: &str,
+    summary: bool,
+    remove: bool,
+) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let dependency = find_project(repository, on)?;
+
+    if remove {
+        repository.remove_project_dependency(&proj.id, &dependency.id)?;
+        println!("✓ '{}' no longer depends on '{}'", proj.name, dependency.name);
+        return Ok(());
+    }
+
+    repository.add_project_dependency(ProjectDependencyPayload {
+        project: proj.id.clone(),
+        depends_on: dependency.id.clone(),
+        include_summary: summary,
+    })?;
+
+    println!("✓ '{}' now depends on '{}'", proj.name, dependency.name);
+    if summary {
+        println!("  Key decisions of '{}' will be included in generated context", dependency.name);
+    }
+
+    Ok(())
+}
+
+/// Execute the merge command
+pub fn merge_command(repository: &Repository, source: &str, target: &str) -> Result<()> {
+    let src = find_project(repository, source)?;
+    let dst = find_project(repository, target)?;
+
+    let stats = repository.merge_projects(&src.id, &dst.id)?;
+
+    println!("✓ Merged '{}' into '{}'", src.name, dst.name);
+    println!("  {}", stats.summary());
+    println!("  '{}' was moved to the trash", src.name);
+
+    Ok(())
+}
+
+/// Execute the clone command
+pub fn clone_command(repository: &Repository, project: &str, name: &str, facts: bool) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let top_facts = if facts { CLONE_TOP_FACTS } else { 0 };
+
+    let copy = repository.clone_project(&proj.id, name, top_facts)?;
+
+    println!("✓ Cloned '{}' as '{}'", proj.name, copy.name);
+    println!("  ID:   {}", copy.id);
+    println!("  Slug: {}", copy.slug);
+
+    Ok(())
+}
+
+/// Execute the attach command
+pub fn attach_command(
+    repository: &Repository,
+    project: &str,
+    section: &str,
+    file: Option<String>,
+    name: Option<String>,
+    remove: bool,
+) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let sec = repository
+        .list_context_sections(&proj.id)?
+        .into_iter()
+        .find(|s| s.id == section || s.title.eq_ignore_ascii_case(section))
+        .with_context(|| format!("Section not found in '{}': {}", proj.name, section))?;
+
+    let Some(file) = file else {
+        let attachments = repository.list_section_attachments(&sec.id)?;
+        if attachments.is_empty() {
+            println!("No attachments on '{}'", sec.title);
+        }
+        for attachment in attachments {
+            let missing = if Path::new(&attachment.path).exists() { "" } else { " (missing)" };
+            println!("  {}  {}{}", attachment.name, attachment.path, missing);
+        }
+        return Ok(());
+    };
+
+    if remove {
+        let attachment = repository
+            .list_section_attachments(&sec.id)?
+            .into_iter()
+            .find(|a| a.id == file || a.name == file || a.path == file)
+            .with_context(|| format!("No attachment '{}' on '{}'", file, sec.title))?;
+        repository.remove_section_attachment(&attachment.id)?;
+        println!("✓ Detached '{}' from '{}'", attachment.name, sec.title);
+        return Ok(());
+    }
+
+    let path = std::fs::canonicalize(&file).with_context(|| format!("File not found: {}", file))?;
+    if !path.is_file() {
+        bail!("Not a file: {}", path.display());
+    }
+    let name = name.unwrap_or_else(|| {
+        path.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| file.clone())
+    });
+
+    let attachment = repository.add_section_attachment(SectionAttachmentPayload {
+        section: sec.id.clone(),
+        path: path.to_string_lossy().into_owned(),
+        name,
+    })?;
+
+    println!("✓ Attached '{}' to '{}'", attachment.name, sec.title);
+    println!("  {}", attachment.path);
+
+    Ok(())
+}
+
+/// Execute the doctor command
+pub fn doctor_command(repository: &Repository, fix: bool) -> Result<()> {
+    if fix {
+        let report = repository.maintenance()?;
+        if report.full_vacuum {
+            println!("Switched the database to incremental auto-vacuum (one-time full VACUUM)");
+        }
+        if !report.is_healthy() {
+            println!("✗ Integrity check failed, nothing was changed:");
+            for problem in &report.problems {
+                println!("  {}", problem);
+            }
+            bail!("Database is damaged; restore it from a backup");
+        }
+        println!("✓ {}", capitalize(&report.summary()));
+
+        let redacted = repository.redact_stored_secrets()?;
+        if redacted > 0 {
+            println!("✓ Redacted credentials in {} facts and session summaries", redacted);
+        }
+    } else {
+        let problems = repository.integrity_check()?;
+        if problems.is_empty() {
+            println!("✓ Integrity check passed");
+        } else {
+            println!("✗ Integrity check found {} problems:", problems.len());
+            for problem in &problems {
+                println!("  {}", problem);
+            }
+        }
+    }
+
+    let sizes = repository.size_report()?;
+    println!(
+        "\nDatabase: {} ({} free)",
+        format_bytes(sizes.file_bytes),
+        format_bytes(sizes.free_bytes)
+    );
+    for table in &sizes.tables {
+        println!("  {:<24} {:>10}  {:>8} rows", table.name, format_bytes(table.bytes), table.rows);
+    }
+
+    if !fix && sizes.free_bytes > 0 {
+        println!("\nRun `doctor --fix` to reclaim {}", format_bytes(sizes.free_bytes));
+    }
+
+    Ok(())
+}
+
+/// Uppercase the first letter of a message
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Execute the bench command
+pub fn bench_command(sizes: Vec<usize>, no_save: bool) -> Result<()> {
+    let sizes = if sizes.is_empty() {
+        DEFAULT_BENCHMARK_SIZES.to_vec()
+    } else {
+        sizes
+    };
+
+    let path = benchmarks_path();
+    let previous = load_benchmark_runs(&path)?.pop();
+    let run = run_benchmarks(&sizes)?;
+
+    println!(
+        "{:>11}  {:>8}  {:>8}  {:>10}  {:>13}  {:>11}  {:>8}",
+        "Transcripts", "Lines", "Facts", "Time", "Transcripts/s", "Facts/s", "vs last"
+    );
+    for result in &run.results {
+        let change = previous
+            .as_ref()
+            .and_then(|p| p.result_for(result.transcripts))
+            .filter(|p| p.facts_per_sec() > 0.0)
+            .map(|p| format!("{:+.1}%", (result.facts_per_sec() / p.facts_per_sec() - 1.0) * 100.0))
+            .unwrap_or_else(|| "-".to_string());
+
+        println!(
+            "{:>11}  {:>8}  {:>8}  {:>8.1}ms  {:>13.0}  {:>11.0}  {:>8}",
+            result.transcripts,
+            result.lines,
+            result.facts,
+            result.elapsed_ms,
+            result.transcripts_per_sec(),
+            result.facts_per_sec(),
+            change
+        );
+    }
+
+    if let Some(previous) = &previous {
+        println!(
+            "\nCompared with the run of {} (v{})",
+            previous.created.format("%Y-%m-%d %H:%M"),
+            previous.version
+        );
+    }
+
+    if !no_save {
+        save_benchmark_run(&path, &run)?;
+        println!("✓ Results saved to {}", path.display());
+    }
+
+    Ok(())
+}
+
+/// Execute the compact command
+pub fn compact_command(
+    repository: &Repository,
+    project: Option<String>,
+    group: Option<String>,
+    budget: usize,
+    output: Option<String>,
+    translate_to: Option<String>,
+    snapshot: bool,
+) -> Result<()> {
+    let (title, projects) = match (group, project) {
+        (Some(group), _) => {
+            let workspace = find_workspace(repository, &group)?;
+            let projects = repository.list_workspace_projects(&workspace.id)?;
+            if projects.is_empty() {
+                bail!("Workspace '{}' has no projects", workspace.name);
+            }
+            (format!("Workspace Briefing: {}", workspace.name), projects)
+        }
+        (None, Some(project)) => {
+            let proj = find_project(repository, &project)?;
+            (format!("{} (Compressed Context)", proj.name), vec![proj])
+        }
+        (None, None) => bail!("Specify a project or --group <workspace>"),
+    };
+
+    let translator = match &translate_to {
+        Some(_) => Some(load_translator()?),
+        None => None,
+    };
+
+    let mut briefings = Vec::new();
+    let mut translated = 0;
+    for proj in projects {
+        let sections = repository.list_exported_sections(&proj.id)?;
+        let mut facts = repository.list_exported_facts(&proj.id, false)?;
+        if let (Some(translator), Some(target)) = (&translator, &translate_to) {
+            translated += translator.translate_facts(&mut facts, target);
+        }
+        briefings.push(ProjectBriefing::new(proj, &sections, facts));
+    }
+
+    let mut markdown = generate_briefing(&title, &briefings, budget);
+    if snapshot {
+        let project_ids = briefings.iter().map(|briefing| briefing.project.id.clone()).collect();
+        markdown = append_snapshot(&markdown, project_ids);
+    }
+
+    let output_path = output.unwrap_or_else(|| "./CLAUDE.md".to_string());
+    let to_stdout = output_path == STDIO_PATH;
+    if !to_stdout {
+        warn_if_edited(&output_path, to_stdout);
+    }
+    write_output(&output_path, &markdown)?;
+
+    let destination = if to_stdout { "stdout" } else { output_path.as_str() };
+    report(to_stdout, &format!("✓ Compacted {} project(s) to {}", briefings.len(), destination));
+    report(to_stdout, &format!("  ~{} tokens (budget {})", estimate_tokens(&markdown), budget));
+    if let Some(target) = &translate_to {
+        report(to_stdout, &format!("  {} facts translated to '{}'", translated, target));
+    }
+
+    if !to_stdout {
+        let path = Path::new(&output_path).to_path_buf();
+        crate::notifications::notify_context_pulled(&title, Some(&path));
+    }
+
+    Ok(())
+}
+
+/// Dropped facts listed per budget before summarizing the rest
+const COMPACTABLE_LISTED_FACTS: usize = 5;
+
+/// Execute the compactable command
+pub fn compactable_command(repository: &Repository, project: &str, budgets: Vec<usize>) -> Result<()> {
+    let mut budgets = if budgets.is_empty() {
+        COMPACTION_PREVIEW_BUDGETS.to_vec()
+    } else {
+        budgets
+    };
+    budgets.sort_unstable_by(|a, b| b.cmp(a));
+
+    let proj = find_project(repository, project)?;
+    let sections = repository.list_exported_sections(&proj.id)?;
+    let all_facts = repository.list_exported_facts(&proj.id, true)?;
+    let stale = all_facts.iter().filter(|f| f.stale).count();
+
+    // The compressed generator only keeps Current State, pinned sections and facts
+    let left_out: Vec<_> = sections
+        .iter()
+        .filter(|s| s.export == SectionExport::Default && !s.content.trim().is_empty())
+        .filter(|s| s.section_type != SectionType::CurrentState)
+        .collect();
+    let left_out_tokens: usize = left_out.iter().map(|s| estimate_tokens(&s.content)).sum();
+
+    let title = format!("{} (Compressed Context)", proj.name);
+    let briefings = vec![ProjectBriefing::new(proj, &sections, all_facts)];
+    let previews = preview_compaction(&title, &briefings, &budgets);
+
+    println!("Compaction preview for {}", briefings[0].project.name);
+    println!(
+        "  Never included: {} other sections (~{} tokens), {} stale facts",
+        left_out.len(),
+        left_out_tokens,
+        stale
+    );
+    if let Some(preview) = previews.first() {
+        if preview.duplicate_facts > 0 {
+            println!("  Duplicates skipped at every budget: {}", preview.duplicate_facts);
+        }
+    }
+
+    for preview in &previews {
+        println!("\nBudget {} (~{} tokens, {} facts kept)", preview.budget, preview.tokens, preview.kept_facts);
+        if preview.keeps_everything() {
+            println!("  Nothing dropped");
+            continue;
+        }
+
+        if preview.state_tokens_dropped > 0 {
+            println!("  Current State truncated by ~{} tokens", preview.state_tokens_dropped);
+        }
+        for fact in preview.dropped_facts.iter().take(COMPACTABLE_LISTED_FACTS) {
+            println!("  - [{}] {}: {}", fact.importance, fact.fact_type.display_name(), fact.content);
+        }
+        if preview.dropped_facts.len() > COMPACTABLE_LISTED_FACTS {
+            println!("  ...and {} more facts", preview.dropped_facts.len() - COMPACTABLE_LISTED_FACTS);
+        }
+    }
+
+    match previews.iter().rev().find(|p| p.keeps_everything()) {
+        Some(preview) => println!("\nSmallest budget that drops nothing: {}", preview.budget),
+        None => println!("\nEvery budget drops content; try a larger --budgets value"),
+    }
+
+    Ok(())
+}
+
+/// Execute the rescore command
+pub fn rescore_command(repository: &Repository, project: &str) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let scorer = ImportanceScorer::configured()?;
+    let facts = repository.list_facts(&proj.id, true)?;
+
+    let (mut raised, mut lowered) = (0, 0);
+    for fact in &facts {
+        let importance = scorer.calculate_score(fact);
+        if importance == fact.importance {
+            continue;
+        }
+        if importance > fact.importance {
+            raised += 1;
+        } else {
+            lowered += 1;
+        }
+        repository.update_fact(&fact.id, ExtractedFactPayload { importance, ..ExtractedFactPayload::from(fact) })?;
+    }
+
+    println!(
+        "✓ Rescored {} facts in {}: {} raised, {} lowered",
+        facts.len(),
+        proj.name,
+        raised,
+        lowered
+    );
+    Ok(())
+}
+
+/// Execute the capture command
+pub fn capture_command(
+    repository: &Repository,
+    project: &str,
+    audio: Option<String>,
+    image: Option<String>,
+) -> Result<()> {
+    let proj = find_project(repository, project)?;
+
+    if let Some(image) = image {
+        let facts = capture_image(repository, &proj.id, Path::new(&image))?;
+        if facts.is_empty() {
+            println!("No facts found in {}", image);
+            return Ok(());
+        }
+
+        println!("✓ Captured {} fact(s) from {} for '{}'", facts.len(), image, proj.name);
+        for fact in &facts {
+            println!("  [{}] {}", fact.fact_type.display_name(), fact.content_preview());
+        }
+        crate::notifications::notify_facts_extracted(&proj.name, facts.len());
+        return Ok(());
+    }
+
+    let audio = audio.context("Specify --audio <file> or --image <file>")?;
+    let whisper = Whisper::from_env().with_context(|| {
+        format!(
+            "whisper.cpp is not configured. Set {} to the binary and {} to the model file",
+            WHISPER_BIN_ENV, WHISPER_MODEL_ENV
+        )
+    })?;
+
+    let fact = capture_voice_note(repository, &proj.id, Path::new(&audio), &whisper)?;
+
+    println!("✓ Captured voice note for '{}'", proj.name);
+    println!("  Fact ID: {}", fact.id);
+    println!("  {}", fact.content_preview());
+
+    crate::notifications::notify_facts_extracted(&proj.name, 1);
+
+    Ok(())
+}
+
+/// Execute the backfill command
+pub fn backfill_command(
+    repository: &Repository,
+    project: &str,
+    since: Option<chrono::NaiveDate>,
+    logs_dir: Option<String>,
+) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let since = since
+        .map(|date| {
+            date.and_hms_opt(0, 0, 0)
+                .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+                .map(|midnight| midnight.with_timezone(&chrono::Utc))
+                .with_context(|| format!("Invalid date: {}", date))
+        })
+        .transpose()?;
+
+    // Old transcripts aren't news: no notifications or notification history
+    let monitor = LogMonitor::new(proj.id.clone(), repository.clone(), logs_dir.map(PathBuf::from))?.without_events();
+
+    let show_progress = std::io::stderr().is_terminal();
+    let report = monitor.backfill(since, |done, total, path| {
+        if show_progress {
+            draw_progress(done, total, &path.file_name().unwrap_or_default().to_string_lossy());
+        }
+    })?;
+    if show_progress && report.files > 0 {
+        draw_progress(report.files, report.files, "done");
+        eprintln!();
+    }
+
+    println!(
+        "✓ Backfilled '{}' from {} transcript(s): {} session(s) and {} fact(s) created",
+        proj.name, report.files, report.sessions_created, report.facts_created
+    );
+    if report.unchanged > 0 {
+        println!("  {} already processed and unchanged", report.unchanged);
+    }
+    if report.failed > 0 {
+        println!("  {} could not be processed (see the log for details)", report.failed);
+    }
+
+    Ok(())
+}
+
+/// Redraw a progress bar on stderr, e.g. "[#####---------------] 12/48 0b6f.jsonl"
+fn draw_progress(done: usize, total: usize, label: &str) {
+    const WIDTH: usize = 20;
+    const LABEL_CHARS: usize = 40;
+
+    let filled = (done * WIDTH).checked_div(total).unwrap_or(WIDTH);
+    let label: String = label.chars().take(LABEL_CHARS).collect();
+    eprint!(
+        "\r[{}{}] {}/{} {:<width$}",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        done,
+        total,
+        label,
+        width = LABEL_CHARS
+    );
+    let _ = std::io::stderr().flush();
+}
+
+/// Execute the log command
+pub fn log_command(repository: &Repository, project: &str, limit: usize) -> Result<()> {
+    let proj = find_project(repository, project)?;
+    let sessions = repository.list_sessions_page(&proj.id, Page::first(limit))?;
+
+    if sessions.is_empty() {
+        println!("No sessions recorded for '{}'", proj.name);
+        return Ok(());
+    }
+
+    println!("Sessions for '{}' ({} most recent):", proj.name, sessions.len());
+    for session in &sessions {
+        println!(
+            "\n{}  {}  {}",
+            session.session_start.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+            session.duration_display(),
+            session.summary
+        );
+        if let Some(git) = session.git_display() {
+            println!("  Started on {}", git);
+        }
+
+        let commits = repository.list_session_commits(&session.id)?;
+        if let Some(display) = commits_display(&commits) {
+            println!("  {}", display);
+            for commit in &commits {
+                println!("    {} {}", commit.short_sha(), commit.summary);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute the stats command
+pub fn stats_command(repository: &Repository, project: Option<String>) -> Result<()> {
+    if let Some(project) = project {
+        let proj = find_project(repository, &project)?;
+        let stats = repository.project_stats(&proj.id)?;
+        println!("Usage for '{}':", proj.name);
+        print_usage(stats.session_count, &stats.usage);
+        return Ok(());
+    }
+
+    let stats = repository.global_stats()?;
+    println!("Usage across {} projects:", stats.project_count);
+    print_usage(stats.session_count, &stats.usage);
+
+    let mut projects = repository.list_projects(None)?;
+    let all_stats = repository.all_project_stats()?;
+    projects.retain(|proj| all_stats.get(&proj.id).is_some_and(|stats| stats.session_count > 0));
+    projects.sort_by(|a, b| all_stats[&b.id].usage.cost_usd.total_cmp(&all_stats[&a.id].usage.cost_usd));
+
+    if !projects.is_empty() {
+        println!("\nBy project:");
+        for proj in &projects {
+            let stats = &all_stats[&proj.id];
+            println!(
+                "  {:<24} {:>5} sessions  {:>14} tokens  {:>9}",
+                proj.name,
+                stats.session_count,
+                format_number_with_separator(stats.usage.total_tokens()),
+                stats.usage.cost_display()
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Print session count, token breakdown and cost
+fn print_usage(session_count: usize, usage: &SessionUsage) {
+    println!("  Sessions: {}", session_count);
+    println!("  Tokens: {}", format_number_with_separator(usage.total_tokens()));
+    println!("    Input: {}", format_number_with_separator(usage.input_tokens));
+    println!("    Output: {}", format_number_with_separator(usage.output_tokens));
+    println!("    Cache writes: {}", format_number_with_separator(usage.cache_write_tokens));
+    println!("    Cache reads: {}", format_number_with_separator(usage.cache_read_tokens));
+    println!("  Cost: {}", format_cost(usage.cost_usd));
+}
+
+/// Execute the source command
+pub fn source_command(repository: &Repository, fact_id: &str) -> Result<()> {
+    let fact = repository.get_fact(fact_id)?;
+    println!("{}: {}", fact.fact_type.display_name(), fact.content_preview());
+
+    let Some(source) = &fact.metadata.source else {
+        println!("  No source recorded (added by hand, or extracted before sources were kept)");
+        return Ok(());
+    };
+    if let Some(path) = &source.log_path {
+        let message = source.message_index.map(|index| format!(", message {}", index + 1)).unwrap_or_default();
+        println!("  Transcript: {}{}", path, message);
+    }
+    if let Some(conversation_id) = &source.conversation_id {
+        println!("  Conversation: {}", conversation_id);
+    }
+    if let Some(location) = source.file_location() {
+        println!("  File: {}", location);
+    }
+
+    if source.log_path.is_some() && source.message_index.is_some() {
+        match read_fact_message(source) {
+            Ok(message) => {
+                println!();
+                for line in message.content.lines() {
+                    println!("  > {}", line);
+                }
+            }
+            Err(e) => println!("  Message unavailable: {:#}", e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute the promote command
+pub fn promote_command(repository: &Repository, fact_id: &str) -> Result<()> {
+    let fact = repository.get_fact(fact_id)?;
+    let section = repository.promote_fact(fact_id)?;
+    println!("✓ Added {} to {}", fact.fact_type.display_name().to_lowercase(), section.title);
+    Ok(())
+}
+
+/// Execute the history command
+pub fn history_command(
+    repository: &Repository,
+    project: Option<String>,
+    entity_type: Option<String>,
+    id: Option<String>,
+    limit: usize,
+) -> Result<()> {
+    let project = project.map(|p| find_project(repository, &p)).transpose()?;
+
+    let filter = AuditFilter {
+        project: project.as_ref().map(|p| p.id.clone()),
+        entity_type,
+        entity_id: id,
+    };
+    let entries = repository.list_audit_log(&filter, Page::first(limit))?;
+
+    if entries.is_empty() {
+        println!("No changes recorded");
+        return Ok(());
+    }
+
+    match &project {
+        Some(p) => println!("History for '{}' ({} most recent):", p.name, entries.len()),
+        None => println!("History ({} most recent):", entries.len()),
+    }
+
+    for entry in &entries {
+        print_audit_entry(entry);
+    }
+
+    Ok(())
+}
+
+/// Print one audit log entry with its changed fields
+fn print_audit_entry(entry: &AuditEntry) {
+    println!(
+        "\n{}  {:<7}  {:<7}  {} {}",
+        entry.created.format("%Y-%m-%d %H:%M:%S"),
+        entry.actor.as_str(),
+        entry.operation.as_str(),
+        entry.entity_type,
+        entry.entity_id
+    );
+
+    // Updates show old -> new; other operations only list the fields involved
+    if entry.operation != AuditOperation::Update {
+        let fields = entry.changed_fields();
+        if !fields.is_empty() {
+            println!("  Fields: {}", fields.join(", "));
+        }
+        return;
+    }
+
+    if let Some(changes) = entry.changes.as_object() {
+        for (field, change) in changes {
+            println!(
+                "  {}: {} -> {}",
+                field,
+                truncate_value(&change["old"]),
+                truncate_value(&change["new"])
+            );
+        }
+    }
+}
+
+/// Render a JSON value for display, shortened to keep history lines readable
+fn truncate_value(value: &serde_json::Value) -> String {
+    const MAX_CHARS: usize = 60;
+
+    let text = value.to_string();
+    if text.chars().count() <= MAX_CHARS {
+        return text;
+    }
+    format!("{}...", text.chars().take(MAX_CHARS - 3).collect::<String>())
+}
+
+/// Load the translation hook configured in the environment
+fn load_translator() -> Result<Translator> {
+    Translator::from_env().with_context(|| {
+        format!(
+            "No translation command configured. Set {} (text is passed on stdin, target language in $CCD_TARGET_LANG)",
+            TRANSLATE_COMMAND_ENV
+        )
+    })
+}
+
+/// Execute the replicate command
+pub fn replicate_command(
+    repository: &Repository,
+    url: Option<String>,
+    interval: Option<u64>,
+    once: bool,
+    save: bool,
+    disable: bool,
+) -> Result<()> {
+    let mut settings = repository.load_settings()?;
+
+    if disable {
+        repository.set_setting(SETTING_REPLICA_URL, "")?;
+        println!("✓ Replica mode disabled; local data is editable again");
+        return Ok(());
+    }
+
+    let url = url
+        .or_else(|| settings.replica_url.clone())
+        .context("No PocketBase URL given and none saved (use --url)")?;
+    let interval = interval.unwrap_or(settings.replica_interval_secs).max(1);
+
+    if save {
+        settings.replica_url = Some(url.clone());
+        settings.replica_interval_secs = interval;
+        repository.save_settings(&settings)?;
+        println!("✓ Saved replica settings; the GUI will mirror {} every {}s", url, interval);
+    }
+
+    let client = PocketBaseClient::from_env(&url);
+    if once {
+        let stats = replicate_once(repository, &client)?;
+        println!("✓ Replicated {}", stats.summary());
+        return Ok(());
+    }
+
+    println!("Replicating {} every {}s (Ctrl+C to stop)", url, interval);
+    run_replication(repository, &client, std::time::Duration::from_secs(interval));
+    Ok(())
+}
+
+/// Execute the sync command
+pub fn sync_command(
+    repository: &Repository,
+    status: bool,
+    exclude: Option<String>,
+    include: Option<String>,
+) -> Result<()> {
+    if let Some(name) = &exclude {
+        let proj = find_project(repository, name)?;
+        repository.set_project_sync(&proj.id, false)?;
+        println!("✓ '{}' is now local-only", proj.name);
+    }
+    if let Some(name) = &include {
+        let proj = find_project(repository, name)?;
+        repository.set_project_sync(&proj.id, true)?;
+        println!("✓ '{}' will be synced", proj.name);
+    }
+
+    if !status {
+        if exclude.is_none() && include.is_none() {
+            bail!("Pushing local changes is not supported yet; use `sync --status` to review them or `replicate` to pull");
+        }
+        return Ok(());
+    }
+
+    match last_replicated(repository) {
+        Some(at) => println!("Last synced: {}", at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")),
+        None => println!("Never synced"),
+    }
+
+    let pending: Vec<_> = repository
+        .pending_changes()?
+        .into_iter()
+        .filter(|p| p.total() > 0)
+        .collect();
+
+    let local_only = repository.local_only_projects()?;
+    if !local_only.is_empty() {
+        let mut names: Vec<_> = local_only
+            .iter()
+            .filter_map(|id| repository.get_project(id).ok())
+            .map(|p| p.name)
+            .collect();
+        names.sort();
+        println!("Local only: {}", names.join(", "));
+    }
+
+    if pending.is_empty() {
+        println!("✓ No pending changes");
+        return Ok(());
+    }
+
+    println!("Pending changes:");
+    for project in &pending {
+        let mut parts = Vec::new();
+        if project.project_pending {
+            parts.push("project details".to_string());
+        }
+        for (count, noun) in [(project.sections, "section"), (project.sessions, "session"), (project.facts, "fact")] {
+            if count > 0 {
+                parts.push(format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" }));
+            }
+        }
+
+        let state = if project.last_synced.is_some() { "" } else { " (never synced)" };
+        println!("  {}{}: {}", project.name, state, parts.join(", "));
+    }
+
+    Ok(())
+}
+
+/// Execute the daemon command
+pub fn daemon_command(repository: &Repository, action: DaemonAction) -> Result<()> {
+    match action {
+        DaemonAction::Install { project, logs_dir, enable } => {
+            // By ID, so renaming the project doesn't break the service
+            let mut args = Vec::new();
+            if let Some(project) = project {
+                args.push(find_project(repository, &project)?.id);
+            }
+            if let Some(logs_dir) = logs_dir {
+                let logs_dir = std::fs::canonicalize(&logs_dir).with_context(|| format!("No such directory: {}", logs_dir))?;
+                args.push("--logs-dir".to_string());
+                args.push(logs_dir.to_string_lossy().into_owned());
+            }
+
+            let path = daemon::install_unit(&args)?;
+            println!("✓ Wrote {}", path.display());
+
+            daemon::systemctl(&["daemon-reload"])?;
+            if enable {
+                daemon::systemctl(&["enable", "--now", UNIT_NAME])?;
+                println!("✓ Monitor enabled and started");
+            } else {
+                println!("  Start it now and at every login with:");
+                println!("    systemctl --user enable --now {}", UNIT_NAME);
+            }
+        }
+        DaemonAction::Uninstall => {
+            let path = daemon::user_unit_dir()?.join(UNIT_NAME);
+            if !path.exists() {
+                println!("No monitor service installed");
+                return Ok(());
+            }
+
+            daemon::systemctl(&["disable", "--now", UNIT_NAME])?;
+            std::fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+            daemon::systemctl(&["daemon-reload"])?;
+            println!("✓ Removed {}", path.display());
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute a workspace subcommand
+pub fn workspace_command(repository: &Repository, action: WorkspaceAction) -> Result<()> {
+    match action {
+        WorkspaceAction::Create { name, description } => {
+            let workspace = repository.create_workspace(WorkspacePayload { name, description })?;
+            println!("✓ Created workspace '{}'", workspace.name);
+            println!("  ID: {}", workspace.id);
+        }
+        WorkspaceAction::Add { workspace, project } => {
+            let ws = find_workspace(repository, &workspace)?;
+            let proj = find_project(repository, &project)?;
+            repository.add_workspace_member(&ws.id, &proj.id)?;
+            println!("✓ Added '{}' to workspace '{}'", proj.name, ws.name);
+        }
+        WorkspaceAction::Remove { workspace, project } => {
+            let ws = find_workspace(repository, &workspace)?;
+            let proj = find_project(repository, &project)?;
+            repository.remove_workspace_member(&ws.id, &proj.id)?;
+            println!("✓ Removed '{}' from workspace '{}'", proj.name, ws.name);
+        }
+        WorkspaceAction::List => {
+            let workspaces = repository.list_workspaces()?;
+            if workspaces.is_empty() {
+                println!("No workspaces found");
+                return Ok(());
+            }
+
+            println!("Workspaces:");
+            for ws in workspaces {
+                let projects = repository.list_workspace_projects(&ws.id)?;
+                let names: Vec<_> = projects.iter().map(|p| p.name.as_str()).collect();
+                println!("  {} ({} projects)", ws.name, projects.len());
+                if !names.is_empty() {
+                    println!("    {}", names.join(", "));
+                }
+            }
+        }
+        WorkspaceAction::Delete { workspace } => {
+            let ws = find_workspace(repository, &workspace)?;
+            repository.delete_workspace(&ws.id)?;
+            println!("✓ Deleted workspace '{}'", ws.name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute section commands
+pub fn sections_command(repository: &Repository, action: SectionAction) -> Result<()> {
+    match action {
+        SectionAction::Add { project, title, section_type, template } => {
+            let proj = find_project(repository, &project)?;
+            let template = template
+                .map(|name| {
+                    repository
+                        .find_section_template_by_name(&name)?
+                        .with_context(|| format!("Template not found: {}", name))
+                })
+                .transpose()?;
+            let section_type = match section_type {
+                Some(section_type) => parse_section_type(&section_type)?,
+                None => template.as_ref().map_or(SectionType::Custom, |template| template.section_type),
+            };
+            let title = title
+                .or_else(|| template.as_ref().map(|template| template.title.clone()))
+                .unwrap_or_else(|| section_type.default_title().to_string());
+            let content = template
+                .as_ref()
+                .map_or_else(|| section_type.template().to_string(), |template| template.content.clone());
+
+            let order = repository
+                .list_context_sections(&proj.id)?
+                .iter()
+                .map(|section| section.order + 1)
+                .max()
+                .unwrap_or(0);
+            let section = repository.create_context_section(ContextSectionPayload {
+                project: proj.id.clone(),
+                section_type,
+                title,
+                content,
+                order,
+                auto_extracted: Some(false),
+            })?;
+            println!("✓ Added '{}' to '{}'", section.title, proj.name);
+            println!("  ID: {}", section.id);
+        }
+        SectionAction::SaveTemplate { project, section, name } => {
+            let proj = find_project(repository, &project)?;
+            let sec = repository
+                .list_context_sections(&proj.id)?
+                .into_iter()
+                .find(|s| s.id == section || s.title.eq_ignore_ascii_case(&section))
+                .with_context(|| format!("Section not found in '{}': {}", proj.name, section))?;
+            let template = repository.save_section_template(SectionTemplatePayload::from_section(&name, &sec))?;
+            println!("✓ Saved '{}' as template '{}'", sec.title, template.name);
+        }
+        SectionAction::Templates { delete: Some(name) } => {
+            let template = repository
+                .find_section_template_by_name(&name)?
+                .with_context(|| format!("Template not found: {}", name))?;
+            repository.delete_section_template(&template.id)?;
+            println!("✓ Deleted template '{}'", template.name);
+        }
+        SectionAction::Templates { delete: None } => {
+            let templates = repository.list_section_templates()?;
+            if templates.is_empty() {
+                println!("No templates saved");
+                return Ok(());
+            }
+
+            println!("Templates:");
+            for template in templates {
+                println!(
+                    "  {} ({}: '{}', {} lines)",
+                    template.name,
+                    template.section_type.as_str(),
+                    template.title,
+                    template.content.lines().count()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Section type from its name as stored, e.g. "next_steps"
+fn parse_section_type(name: &str) -> Result<SectionType> {
+    SectionType::all()
+        .into_iter()
+        .find(|section_type| section_type.as_str().eq_ignore_ascii_case(name))
+        .with_context(|| {
+            let names: Vec<String> = SectionType::all()
+                .iter()
+                .map(|section_type| section_type.as_str().to_string())
+                .collect();
+            format!("Unknown section type '{}', expected one of: {}", name, names.join(", "))
+        })
+}
+
+/// Find workspace by name or ID
+pub fn find_workspace(repository: &Repository, name_or_id: &str) -> Result<Workspace> {
+    if let Ok(ws) = repository.get_workspace(name_or_id) {
+        return Ok(ws);
+    }
+
+    repository
+        .find_workspace_by_name(name_or_id)?
+        .with_context(|| format!("Workspace not found: {}", name_or_id))
+}
+
 /// Find project by name or ID
 pub fn find_project(repository: &Repository, name_or_id: &str) -> Result<crate::models::Project> {
     // Try by ID first
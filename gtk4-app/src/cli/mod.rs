@@ -18,9 +18,39 @@ pub enum Commands {
         /// Project name or ID
         project: String,
 
-        /// Output file path (default: ./CLAUDE.md)
+        /// Output file path, or - for stdout (default: ./CLAUDE.md)
         #[arg(short, long)]
         output: Option<String>,
+
+        /// Translate facts into this language (ISO 639-3, e.g. "eng") using $CCD_TRANSLATE_COMMAND
+        #[arg(long)]
+        translate_to: Option<String>,
+
+        /// Show a diff against the existing file and ask before overwriting it
+        #[arg(short, long)]
+        preview: bool,
+
+        /// Link section attachments instead of inlining small text files
+        #[arg(long)]
+        link_attachments: bool,
+
+        /// Append a JSON footer recording the project, export time, token estimate and hash
+        #[arg(long)]
+        snapshot: bool,
+    },
+
+    /// Import sections edited by hand in CLAUDE.md back into the project
+    Import {
+        /// Project name or ID
+        project: String,
+
+        /// File to import (default: CLAUDE.md in the project's repository)
+        #[arg(short, long)]
+        file: Option<String>,
+
+        /// Import without asking
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Push session summary to project history
@@ -28,7 +58,7 @@ pub enum Commands {
         /// Project name or ID
         project: String,
 
-        /// Session summary
+        /// Session summary, or - to read it from stdin
         summary: String,
 
         /// Token count for this session
@@ -87,16 +117,381 @@ pub enum Commands {
         description: Option<String>,
     },
 
+    /// Declare that a project depends on another project
+    Depend {
+        /// Project name or ID
+        project: String,
+
+        /// Project name or ID it depends on
+        on: String,
+
+        /// Include a summary of the dependency's key decisions in generated context
+        #[arg(short, long)]
+        summary: bool,
+
+        /// Remove the dependency instead of adding it
+        #[arg(short, long)]
+        remove: bool,
+    },
+
+    /// Merge a duplicate project into another (the source moves to the trash)
+    Merge {
+        /// Project name or ID to merge away
+        source: String,
+
+        /// Project name or ID that receives its sections, sessions and facts
+        target: String,
+    },
+
+    /// Duplicate a project's metadata and context sections as a new project
+    Clone {
+        /// Project name or ID to copy
+        project: String,
+
+        /// Name of the new project
+        name: String,
+
+        /// Also copy the most important facts
+        #[arg(short, long)]
+        facts: bool,
+    },
+
+    /// Attach a local file (diagram, schema dump, ...) to a context section
+    Attach {
+        /// Project name or ID
+        project: String,
+
+        /// Section title or ID
+        section: String,
+
+        /// File to attach (omit to list the section's attachments)
+        file: Option<String>,
+
+        /// Display name (defaults to the file name)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Detach the file instead of attaching it
+        #[arg(short, long, requires = "file")]
+        remove: bool,
+    },
+
+    /// Add context sections, or manage the templates they start from
+    Sections {
+        #[command(subcommand)]
+        action: SectionAction,
+    },
+
+    /// Generate compressed context within a token budget
+    Compact {
+        /// Project name or ID (omit when using --group)
+        #[arg(required_unless_present = "group", conflicts_with = "group")]
+        project: Option<String>,
+
+        /// Build a cross-project briefing for a workspace
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Token budget for the generated context
+        #[arg(short, long, default_value_t = crate::utils::DEFAULT_COMPACT_BUDGET)]
+        budget: usize,
+
+        /// Output file path, or - for stdout (default: ./CLAUDE.md)
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Translate facts into this language (ISO 639-3, e.g. "eng") using $CCD_TRANSLATE_COMMAND
+        #[arg(long)]
+        translate_to: Option<String>,
+
+        /// Append a JSON footer recording the projects, export time, token estimate and hash
+        #[arg(long)]
+        snapshot: bool,
+    },
+
+    /// Show what compressed context would drop at various token budgets
+    Compactable {
+        /// Project name or ID
+        project: String,
+
+        /// Token budgets to compare, comma-separated (default: 150000,100000,50000)
+        #[arg(short, long, value_delimiter = ',')]
+        budgets: Vec<usize>,
+    },
+
+    /// Recompute the importance of a project's facts with the current scoring weights
+    Rescore {
+        /// Project name or ID
+        project: String,
+    },
+
+    /// Capture facts from a voice note (whisper.cpp) or a screenshot (OCR)
+    Capture {
+        /// Project name or ID
+        project: String,
+
+        /// Audio file to transcribe into an Insight fact (16 kHz WAV)
+        #[arg(long, required_unless_present = "image", conflicts_with = "image")]
+        audio: Option<String>,
+
+        /// Image to run OCR on and extract facts from
+        #[arg(long)]
+        image: Option<String>,
+    },
+
+    /// Process existing Claude Code transcripts of a project, oldest first
+    Backfill {
+        /// Project name or ID
+        project: String,
+
+        /// Only transcripts modified on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<chrono::NaiveDate>,
+
+        /// Claude Code logs directory (saved setting or ~/.claude/projects if not specified)
+        #[arg(short, long)]
+        logs_dir: Option<String>,
+    },
+
+    /// List a project's recent sessions with the git commits made during each
+    Log {
+        /// Project name or ID
+        project: String,
+
+        /// Maximum number of sessions to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        limit: usize,
+    },
+
+    /// Show tokens used and their cost, for one project or all of them
+    Stats {
+        /// Project name or ID (omit for all projects)
+        project: Option<String>,
+    },
+
+    /// Show the transcript message and file a fact was extracted from
+    Source {
+        /// Fact ID (as shown by `history`)
+        fact: String,
+    },
+
+    /// Append a fact to the context section its type belongs in
+    Promote {
+        /// Fact ID (as shown by `history`)
+        fact: String,
+    },
+
+    /// Show the audit log of changes
+    History {
+        /// Project name or ID (omit for all projects)
+        project: Option<String>,
+
+        /// Only show changes to this entity type (project, section, session, fact, dependency, workspace)
+        #[arg(short = 't', long = "type")]
+        entity_type: Option<String>,
+
+        /// Only show changes to the record with this ID
+        #[arg(long)]
+        id: Option<String>,
+
+        /// Maximum number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Mirror a PocketBase server into the local database as a read-only replica
+    Replicate {
+        /// PocketBase URL, e.g. http://localhost:8090 (defaults to the saved replica URL)
+        #[arg(short, long)]
+        url: Option<String>,
+
+        /// Seconds between runs (defaults to the saved interval)
+        #[arg(short, long)]
+        interval: Option<u64>,
+
+        /// Replicate once and exit
+        #[arg(long)]
+        once: bool,
+
+        /// Save the URL and interval, putting this database in replica mode
+        #[arg(long)]
+        save: bool,
+
+        /// Leave replica mode (local data is kept and becomes editable again)
+        #[arg(long, conflicts_with_all = ["url", "interval", "once", "save"])]
+        disable: bool,
+    },
+
+    /// Show sync state, or choose which projects take part in sync
+    Sync {
+        /// Summarize pending changes per project
+        #[arg(long)]
+        status: bool,
+
+        /// Keep a project local-only (name or ID)
+        #[arg(long, value_name = "PROJECT", conflicts_with = "include")]
+        exclude: Option<String>,
+
+        /// Sync a project that was local-only (name or ID)
+        #[arg(long, value_name = "PROJECT")]
+        include: Option<String>,
+    },
+
+    /// Manage workspaces (groups of related projects)
+    Workspace {
+        #[command(subcommand)]
+        action: WorkspaceAction,
+    },
+
+    /// Check database health and show how much space each table uses
+    Doctor {
+        /// Also vacuum and re-analyze the database, and redact credentials stored before redaction existed
+        #[arg(long)]
+        fix: bool,
+    },
+
+    /// Benchmark fact extraction throughput on synthetic transcripts
+    Bench {
+        /// Corpus sizes in transcripts, comma-separated (default: 10,100,1000)
+        #[arg(long, value_delimiter = ',')]
+        sizes: Vec<usize>,
+
+        /// Don't save the results for later comparison
+        #[arg(long)]
+        no_save: bool,
+    },
+
     /// Start background monitoring daemon
     Monitor {
-        /// Project name or ID to monitor
-        project: String,
+        /// Project name or ID to monitor (all projects, matched by repository path, if omitted)
+        project: Option<String>,
 
-        /// Claude Code logs directory (auto-detected if not specified)
+        /// Claude Code logs directory (saved setting or ~/.claude/projects if not specified)
         #[arg(short, long)]
         logs_dir: Option<String>,
+
+        /// Also print events to stdout as JSON lines, for plugins and scripts
+        #[arg(long)]
+        events_json: bool,
+
+        /// Run as a service: hold a PID file, report readiness to systemd and
+        /// shut down cleanly on SIGTERM
+        #[arg(long)]
+        daemon: bool,
+    },
+
+    /// Run the monitor as a systemd user service
+    Daemon {
+        #[command(subcommand)]
+        action: DaemonAction,
     },
 
     /// Launch GUI (default if no command specified)
     Gui,
+
+    /// Open a project in the GUI, in the running window if there is one
+    Open {
+        /// Project name or ID
+        project: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DaemonAction {
+    /// Write a user unit that starts the monitor at login
+    Install {
+        /// Project name or ID to monitor (all projects if omitted)
+        project: Option<String>,
+
+        /// Claude Code logs directory (saved setting or ~/.claude/projects if not specified)
+        #[arg(short, long)]
+        logs_dir: Option<String>,
+
+        /// Also enable and start the service
+        #[arg(long)]
+        enable: bool,
+    },
+
+    /// Stop the service and remove its unit
+    Uninstall,
+}
+
+#[derive(Subcommand)]
+pub enum SectionAction {
+    /// Add a section after a project's other sections
+    Add {
+        /// Project name or ID
+        project: String,
+
+        /// Section title (defaults to the template's, or the type's)
+        title: Option<String>,
+
+        /// Section type: architecture, current_state, next_steps, gotchas, decisions or custom
+        /// (defaults to the template's, or custom)
+        #[arg(short = 't', long = "type")]
+        section_type: Option<String>,
+
+        /// Start from this saved template instead of the type's outline
+        #[arg(long)]
+        template: Option<String>,
+    },
+
+    /// Save a section's type, title and content as a template for any project
+    SaveTemplate {
+        /// Project name or ID
+        project: String,
+
+        /// Section title or ID
+        section: String,
+
+        /// Template name (saving under an existing name replaces that template)
+        name: String,
+    },
+
+    /// List the saved templates
+    Templates {
+        /// Delete the template with this name instead
+        #[arg(long, value_name = "NAME")]
+        delete: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum WorkspaceAction {
+    /// Create a new workspace
+    Create {
+        /// Workspace name
+        name: String,
+
+        /// Description
+        #[arg(short, long)]
+        description: Option<String>,
+    },
+
+    /// Add a project to a workspace
+    Add {
+        /// Workspace name or ID
+        workspace: String,
+
+        /// Project name or ID
+        project: String,
+    },
+
+    /// Remove a project from a workspace
+    Remove {
+        /// Workspace name or ID
+        workspace: String,
+
+        /// Project name or ID
+        project: String,
+    },
+
+    /// List workspaces and their projects
+    List,
+
+    /// Delete a workspace (its projects are kept)
+    Delete {
+        /// Workspace name or ID
+        workspace: String,
+    },
 }
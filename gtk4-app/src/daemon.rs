@@ -0,0 +1,229 @@
+use crate::monitor::try_lock_exclusive;
+use anyhow::{bail, Context, Result};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+/// Environment variable overriding where the monitor daemon keeps its PID file
+pub const PID_FILE_ENV: &str = "CCD_PID_FILE";
+
+/// systemd user unit written by `daemon install`
+pub const UNIT_NAME: &str = "claude-context-tracker.service";
+
+/// Settings passed on to the installed unit when set; secrets such as API keys never are
+const UNIT_ENV_VARS: &[&str] = &[
+    "CCD_DB_PATH",
+    "CCD_DB_LOCAL_CACHE",
+    "CCD_DB_FLUSH_SECS",
+    "CCD_DB_POOL_SIZE",
+    "CCD_DB_BUSY_TIMEOUT_MS",
+    "CCD_EXTRACTION_RULES",
+    "CCD_REDACTION_RULES",
+    "CCD_SCORING_CONFIG",
+    "CCD_PRICING_CONFIG",
+    "CCD_PID_FILE",
+    "CCD_MONITOR_LOCK_DIR",
+];
+
+/// Location of the PID file: `CCD_PID_FILE`, or monitor.pid in the runtime directory
+pub fn pid_file_path() -> PathBuf {
+    if let Some(path) = std::env::var_os(PID_FILE_ENV).filter(|path| !path.is_empty()) {
+        return PathBuf::from(path);
+    }
+
+    dirs::runtime_dir()
+        .or_else(dirs::cache_dir)
+        .unwrap_or_else(std::env::temp_dir)
+        .join("claude-context-tracker")
+        .join("monitor.pid")
+}
+
+/// Locked PID file of a running daemon, removed again on drop
+///
+/// The lock goes away with the process, so a PID file left behind by a crash
+/// doesn't keep the next daemon from starting.
+pub struct PidFile {
+    path: PathBuf,
+    _file: File,
+}
+
+impl PidFile {
+    /// Lock `path` and write this process's ID to it, failing if another daemon holds it
+    pub fn acquire(path: &Path) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        }
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+
+        if !try_lock_exclusive(&file).context("Failed to lock the PID file")? {
+            let mut pid = String::new();
+            file.read_to_string(&mut pid).ok();
+            bail!("The monitor is already running (PID {}, {})", pid.trim(), path.display());
+        }
+
+        file.set_len(0)?;
+        file.rewind()?;
+        writeln!(file, "{}", std::process::id())?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            _file: file,
+        })
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::warn!("Failed to remove {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+/// Set `stop` on SIGTERM or SIGINT instead of exiting right away
+pub fn stop_on_termination(stop: Arc<AtomicBool>) -> Result<()> {
+    for signal in [signal_hook::consts::SIGTERM, signal_hook::consts::SIGINT] {
+        signal_hook::flag::register(signal, stop.clone()).context("Failed to install signal handler")?;
+    }
+    Ok(())
+}
+
+/// Tell systemd the daemon is up; does nothing when not started by systemd
+pub fn notify_ready() {
+    notify("READY=1\nSTATUS=Watching transcripts");
+}
+
+/// Tell systemd the daemon is shutting down
+pub fn notify_stopping() {
+    notify("STOPPING=1");
+}
+
+#[cfg(target_os = "linux")]
+fn notify(state: &str) {
+    if let Err(e) = sd_notify::notify(false, &[sd_notify::NotifyState::Custom(state)]) {
+        log::warn!("Failed to notify systemd: {}", e);
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn notify(_state: &str) {}
+
+/// Contents of a user unit running `exe monitor --daemon` with `args` appended
+pub fn unit_file(exe: &Path, args: &[String], env: &[(String, String)]) -> String {
+    let mut command = vec![quote_unit_arg(&exe.to_string_lossy()), "monitor".to_string(), "--daemon".to_string()];
+    command.extend(args.iter().map(|arg| quote_unit_arg(arg)));
+
+    let environment: String = env
+        .iter()
+        .map(|(name, value)| format!("Environment={}\n", quote_unit_arg(&format!("{}={}", name, value))))
+        .collect();
+
+    format!(
+        "[Unit]
+Description=Claude Context Tracker monitor
+Documentation=https://github.com/AngelFreak/CCD
+
+[Service]
+Type=notify
+ExecStart={}
+{}Restart=on-failure
+RestartSec=5
+
+[Install]
+WantedBy=default.target
+",
+        command.join(" "),
+        environment
+    )
+}
+
+/// Quote an argument for a unit file, escaping systemd's `%` specifiers
+fn quote_unit_arg(arg: &str) -> String {
+    let arg = arg.replace('%', "%%");
+    if !arg.is_empty() && !arg.contains(|c: char| c.is_whitespace() || c == '"' || c == '\'' || c == '\\') {
+        return arg;
+    }
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Where user units live: ~/.config/systemd/user
+pub fn user_unit_dir() -> Result<PathBuf> {
+    Ok(dirs::config_dir().context("Could not find the config directory")?.join("systemd").join("user"))
+}
+
+/// Write the user unit for monitoring with `args`; returns its path
+pub fn install_unit(args: &[String]) -> Result<PathBuf> {
+    let exe = std::env::current_exe().context("Could not find the running executable")?;
+    let env: Vec<(String, String)> = UNIT_ENV_VARS
+        .iter()
+        .filter_map(|name| Some((name.to_string(), std::env::var(name).ok().filter(|value| !value.is_empty())?)))
+        .collect();
+
+    let dir = user_unit_dir()?;
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(UNIT_NAME);
+    std::fs::write(&path, unit_file(&exe, args, &env)).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// Run `systemctl --user` with `args`
+pub fn systemctl(args: &[&str]) -> Result<()> {
+    let status = Command::new("systemctl")
+        .arg("--user")
+        .args(args)
+        .status()
+        .context("Failed to run systemctl")?;
+    if !status.success() {
+        bail!("systemctl --user {} failed ({})", args.join(" "), status);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pid_file_is_exclusive() {
+        let dir = std::env::temp_dir().join(format!("ccd-daemon-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("monitor.pid");
+
+        let pid_file = PidFile::acquire(&path).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("{}\n", std::process::id()));
+
+        let error = PidFile::acquire(&path).err().unwrap().to_string();
+        assert!(error.contains(&format!("PID {}", std::process::id())), "{}", error);
+
+        drop(pid_file);
+        assert!(!path.exists());
+        drop(PidFile::acquire(&path).unwrap());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_unit_file() {
+        let unit = unit_file(
+            Path::new("/opt/My Apps/claude-context-tracker"),
+            &["shop".to_string(), "--logs-dir".to_string(), "/logs/100%".to_string()],
+            &[("CCD_DB_PATH".to_string(), "/data/ccd.db".to_string())],
+        );
+
+        assert!(unit.contains("Type=notify\n"));
+        assert!(unit.contains(
+            "ExecStart=\"/opt/My Apps/claude-context-tracker\" monitor --daemon shop --logs-dir /logs/100%%\n"
+        ));
+        assert!(unit.contains("Environment=CCD_DB_PATH=/data/ccd.db\n"));
+        assert!(unit.contains("WantedBy=default.target\n"));
+    }
+}
@@ -0,0 +1,224 @@
+use crate::db::{EventFilter, Page, Repository};
+use crate::models::EventRecord;
+use crate::monitor::MonitorEvent;
+use adw::prelude::*;
+use gtk::glib;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Most events listed at once
+const EVENT_HISTORY_LIMIT: usize = 200;
+
+/// History of what the log monitor reported, with filters and read state
+pub struct EventHistoryDialog {
+    window: adw::Window,
+    state: EventHistoryState,
+}
+
+/// Filter widgets and the list they control
+#[derive(Clone)]
+struct EventHistoryState {
+    repository: Repository,
+    list: gtk::ListBox,
+    project_ids: Rc<Vec<Option<String>>>, // Indexed like the project dropdown
+    project_dropdown: gtk::DropDown,
+    type_dropdown: gtk::DropDown,
+    unread_toggle: gtk::ToggleButton,
+    on_change: Rc<RefCell<Option<Box<dyn Fn()>>>>,
+}
+
+impl EventHistoryState {
+    fn filter(&self) -> EventFilter {
+        let event_type = match self.type_dropdown.selected() {
+            0 => None,
+            i => MonitorEvent::TYPES.get(i as usize - 1).map(|(event_type, _)| event_type.to_string()),
+        };
+
+        EventFilter {
+            project: self.project_ids.get(self.project_dropdown.selected() as usize).cloned().flatten(),
+            event_type,
+            unread_only: self.unread_toggle.is_active(),
+        }
+    }
+
+    /// Reload the list for the current filters
+    fn reload(&self) {
+        while let Some(child) = self.list.first_child() {
+            self.list.remove(&child);
+        }
+
+        let events = self
+            .repository
+            .list_events(&self.filter(), Page::first(EVENT_HISTORY_LIMIT))
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load event history: {}", e);
+                Vec::new()
+            });
+
+        if events.is_empty() {
+            let row = adw::ActionRow::builder().title("Nothing reported").build();
+            row.add_css_class("dim-label");
+            self.list.append(&row);
+        }
+
+        for event in events {
+            self.list.append(&self.create_row(event));
+        }
+
+        if let Some(on_change) = self.on_change.borrow().as_ref() {
+            on_change();
+        }
+    }
+
+    /// Row for one event; activating it toggles the read state
+    fn create_row(&self, record: EventRecord) -> adw::ActionRow {
+        let subtitle = format!(
+            "{} · {}",
+            record.event.description(),
+            record.created.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        );
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(record.event.project()).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .activatable(true)
+            .build();
+
+        let icon = match record.event {
+            MonitorEvent::FactsExtracted { .. } => "emblem-documents-symbolic",
+            MonitorEvent::TokenThreshold { .. } => "dialog-warning-symbolic",
+            MonitorEvent::SessionEnded { .. } => "media-playback-stop-symbolic",
+            MonitorEvent::ContextFileEdited { .. } => "document-edit-symbolic",
+        };
+        row.add_prefix(&gtk::Image::from_icon_name(icon));
+
+        if !record.read {
+            let unread = gtk::Image::from_icon_name("mail-unread-symbolic");
+            unread.set_tooltip_text(Some("Unread"));
+            unread.add_css_class("accent");
+            row.add_suffix(&unread);
+        } else {
+            row.add_css_class("dim-label");
+        }
+
+        let state = self.clone();
+        row.connect_activated(move |_| {
+            if let Err(e) = state.repository.set_event_read(record.id, !record.read) {
+                log::error!("Failed to update event: {}", e);
+            }
+            state.reload();
+        });
+
+        row
+    }
+}
+
+impl EventHistoryDialog {
+    /// Create the dialog
+    pub fn new(parent: &impl IsA<gtk::Window>, repository: Repository) -> Self {
+        let window = adw::Window::builder()
+            .title("Notifications")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(520)
+            .default_height(560)
+            .build();
+
+        let header = adw::HeaderBar::new();
+        let mark_all_btn = gtk::Button::builder()
+            .label("Mark All Read")
+            .tooltip_text("Mark the events shown as read")
+            .build();
+        header.pack_start(&mark_all_btn);
+
+        // Filters: project, event type, unread only
+        let projects = repository.list_projects(None).unwrap_or_else(|e| {
+            log::error!("Failed to load projects: {}", e);
+            Vec::new()
+        });
+        let mut project_names = vec!["All Projects".to_string()];
+        let mut project_ids = vec![None];
+        for project in projects {
+            project_names.push(project.name);
+            project_ids.push(Some(project.id));
+        }
+        let project_names: Vec<&str> = project_names.iter().map(String::as_str).collect();
+        let project_dropdown = gtk::DropDown::from_strings(&project_names);
+
+        let type_names: Vec<&str> = std::iter::once("All Events")
+            .chain(MonitorEvent::TYPES.iter().map(|(_, name)| *name))
+            .collect();
+        let type_dropdown = gtk::DropDown::from_strings(&type_names);
+
+        let unread_toggle = gtk::ToggleButton::builder().label("Unread").build();
+
+        let filters = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        filters.set_margin_top(12);
+        filters.set_margin_start(12);
+        filters.set_margin_end(12);
+        filters.append(&project_dropdown);
+        filters.append(&type_dropdown);
+        filters.append(&unread_toggle);
+
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        list.add_css_class("boxed-list");
+        list.set_margin_top(12);
+        list.set_margin_bottom(12);
+        list.set_margin_start(12);
+        list.set_margin_end(12);
+        list.set_valign(gtk::Align::Start);
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&filters);
+        content.append(&scrolled);
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&content));
+        window.set_content(Some(&toolbar));
+
+        let state = EventHistoryState {
+            repository,
+            list,
+            project_ids: Rc::new(project_ids),
+            project_dropdown: project_dropdown.clone(),
+            type_dropdown: type_dropdown.clone(),
+            unread_toggle: unread_toggle.clone(),
+            on_change: Rc::new(RefCell::new(None)),
+        };
+
+        for dropdown in [&project_dropdown, &type_dropdown] {
+            let state = state.clone();
+            dropdown.connect_selected_notify(move |_| state.reload());
+        }
+        let toggle_state = state.clone();
+        unread_toggle.connect_toggled(move |_| toggle_state.reload());
+
+        let mark_state = state.clone();
+        mark_all_btn.connect_clicked(move |_| {
+            if let Err(e) = mark_state.repository.mark_events_read(&mark_state.filter()) {
+                log::error!("Failed to mark events as read: {}", e);
+            }
+            mark_state.reload();
+        });
+
+        Self { window, state }
+    }
+
+    /// Call `f` whenever events were marked read or unread
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.state.on_change.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Load the events and show the dialog
+    pub fn present(&self) {
+        self.state.reload();
+        self.window.present();
+    }
+}
@@ -0,0 +1,66 @@
+use crate::models::LlmProvider;
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Environment variable holding an LLM API key, used instead of the keyring
+pub const LLM_API_KEY_ENV: &str = "CCD_LLM_API_KEY";
+
+/// Service attribute the tracker's secrets are stored under
+const KEYRING_SERVICE: &str = "claude-context-tracker";
+
+/// Keyring client (`secret-tool` from libsecret, backed by GNOME Keyring or KWallet)
+const SECRET_TOOL: &str = "secret-tool";
+
+/// Save a provider's API key in the desktop keyring
+pub fn store_api_key(provider: LlmProvider, key: &str) -> Result<()> {
+    let mut child = Command::new(SECRET_TOOL)
+        .args(["store", "--label", &format!("Claude Context Tracker ({})", provider.display_name())])
+        .args(["service", KEYRING_SERVICE, "provider", provider.as_str()])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {} (is libsecret installed?)", SECRET_TOOL))?;
+
+    // Written without a newline, which would become part of the secret
+    child
+        .stdin
+        .take()
+        .context("Failed to open secret-tool stdin")?
+        .write_all(key.as_bytes())?;
+
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        bail!("Failed to store API key: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
+
+/// A provider's API key: `CCD_LLM_API_KEY` if set, otherwise the one in the keyring
+pub fn load_api_key(provider: LlmProvider) -> Result<Option<String>> {
+    if let Some(key) = std::env::var(LLM_API_KEY_ENV).ok().filter(|key| !key.is_empty()) {
+        return Ok(Some(key));
+    }
+
+    let output = Command::new(SECRET_TOOL)
+        .args(["lookup", "service", KEYRING_SERVICE, "provider", provider.as_str()])
+        .output()
+        .with_context(|| format!("Failed to run {} (is libsecret installed?)", SECRET_TOOL))?;
+
+    // secret-tool exits with 1 when nothing is stored
+    let key = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(Some(key).filter(|key| output.status.success() && !key.is_empty()))
+}
+
+/// Remove a provider's API key from the keyring
+pub fn clear_api_key(provider: LlmProvider) -> Result<()> {
+    let output = Command::new(SECRET_TOOL)
+        .args(["clear", "service", KEYRING_SERVICE, "provider", provider.as_str()])
+        .output()
+        .with_context(|| format!("Failed to run {} (is libsecret installed?)", SECRET_TOOL))?;
+
+    if !output.status.success() {
+        bail!("Failed to remove API key: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(())
+}
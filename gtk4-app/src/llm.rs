@@ -0,0 +1,349 @@
+use crate::db::Repository;
+use crate::keyring::{load_api_key, LLM_API_KEY_ENV};
+use crate::models::{AppSettings, ExtractedFactPayload, FactMetadata, FactType, LlmProvider};
+use crate::monitor::{ConversationLog, Extraction, Extractor, SessionExtractor};
+use crate::utils::detect_language;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Most transcript characters sent per request; older messages are left out first
+const TRANSCRIPT_MAX_CHARS: usize = 48_000;
+
+/// Upper bound on the length of the model's answer (Anthropic requires one)
+const MAX_OUTPUT_TOKENS: u32 = 2048;
+
+/// Importance of facts the model didn't score
+const DEFAULT_LLM_IMPORTANCE: i32 = 3;
+
+/// Version header of the Anthropic Messages API
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+const SYSTEM_PROMPT: &str = "You read transcripts of coding sessions between a developer and an AI assistant \
+and record what is worth remembering for the next session. Reply with one JSON object and nothing else:
+{\"summary\": \"<session title, at most 80 characters, e.g. 'Auth middleware: add JWT validation'>\",
+ \"facts\": [{\"type\": \"<decision|blocker|todo|file_change|dependency|insight>\", \"content\": \"<one sentence>\", \"importance\": <1-5>, \"confidence\": <0-1>}]}
+Only record facts stated in the transcript: decisions and their reasons, open blockers, remaining work, \
+files changed, dependencies added or removed, and non-obvious insights. Leave out greetings and chatter. \
+Confidence is how sure you are that the transcript states the fact, below 0.6 when you are guessing. \
+An empty list is fine when nothing is worth keeping.";
+
+/// Session summarization and fact extraction by a language model
+///
+/// Talks to the Anthropic Messages API, the OpenAI Chat Completions API or a
+/// local Ollama server.
+pub struct LlmExtractor {
+    provider: LlmProvider,
+    model: String,
+    endpoint: String,
+    api_key: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl LlmExtractor {
+    pub fn new(provider: LlmProvider, model: Option<String>, endpoint: Option<String>, api_key: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(120))
+            .build();
+
+        Self {
+            provider,
+            model: model.unwrap_or_else(|| provider.default_model().to_string()),
+            endpoint: endpoint.unwrap_or_else(|| provider.default_endpoint().to_string()),
+            api_key,
+            agent,
+        }
+    }
+
+    /// Extractor configured in the settings, or None when LLM extraction is off
+    ///
+    /// Fails when the provider needs an API key and none is stored.
+    pub fn from_settings(settings: &AppSettings) -> Result<Option<Self>> {
+        let Some(provider) = settings.llm_provider else {
+            return Ok(None);
+        };
+
+        let api_key = load_api_key(provider)?;
+        if provider.needs_api_key() && api_key.is_none() {
+            bail!(
+                "No {} API key found. Add one in Preferences or set {}",
+                provider.display_name(),
+                LLM_API_KEY_ENV
+            );
+        }
+
+        Ok(Some(Self::new(
+            provider,
+            settings.llm_model.clone(),
+            settings.llm_endpoint.clone(),
+            api_key,
+        )))
+    }
+
+    /// Request body asking the model about a transcript
+    fn request_body(&self, transcript: &str) -> Value {
+        match self.provider {
+            LlmProvider::Anthropic => json!({
+                "model": self.model,
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "system": SYSTEM_PROMPT,
+                "messages": [{ "role": "user", "content": transcript }],
+            }),
+            LlmProvider::OpenAi => json!({
+                "model": self.model,
+                "max_tokens": MAX_OUTPUT_TOKENS,
+                "response_format": { "type": "json_object" },
+                "messages": [
+                    { "role": "system", "content": SYSTEM_PROMPT },
+                    { "role": "user", "content": transcript },
+                ],
+            }),
+            LlmProvider::Ollama => json!({
+                "model": self.model,
+                "stream": false,
+                "format": "json",
+                "messages": [
+                    { "role": "system", "content": SYSTEM_PROMPT },
+                    { "role": "user", "content": transcript },
+                ],
+            }),
+        }
+    }
+
+    /// Send a request and return the text of the model's answer
+    fn complete(&self, body: Value) -> Result<String> {
+        let mut request = self.agent.post(&self.endpoint);
+        if let Some(key) = &self.api_key {
+            request = match self.provider {
+                LlmProvider::Anthropic => request
+                    .set("x-api-key", key)
+                    .set("anthropic-version", ANTHROPIC_VERSION),
+                LlmProvider::OpenAi | LlmProvider::Ollama => request.set("Authorization", &format!("Bearer {}", key)),
+            };
+        }
+
+        let response: Value = match request.send_json(body) {
+            Ok(response) => response.into_json().context("Invalid JSON from the LLM API")?,
+            Err(ureq::Error::Status(code, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                bail!("{} API returned {}: {}", self.provider.display_name(), code, detail.trim());
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to reach {}", self.endpoint)),
+        };
+
+        response_text(self.provider, &response).context("No text in the LLM response")
+    }
+}
+
+impl Extractor for LlmExtractor {
+    fn name(&self) -> String {
+        format!("{} ({})", self.provider.display_name(), self.model)
+    }
+
+    fn extract(&self, log: &ConversationLog) -> Result<Extraction> {
+        let transcript = transcript_text(log, TRANSCRIPT_MAX_CHARS);
+        if transcript.is_empty() {
+            return Ok(Extraction::default());
+        }
+
+        let answer = self.complete(self.request_body(&transcript))?;
+        parse_extraction(&answer)
+    }
+}
+
+/// The extractor the monitor should use for ended sessions, if LLM extraction is on
+///
+/// Problems (like a missing API key) are logged and leave the regex extraction
+/// to work alone.
+pub fn configured_session_extractor(repository: &Repository) -> Option<SessionExtractor> {
+    let settings = repository.load_settings().ok()?;
+    match LlmExtractor::from_settings(&settings) {
+        Ok(Some(extractor)) => {
+            log::info!("Ended sessions with few facts are enriched by {}", extractor.name());
+            Some(Arc::new(extractor))
+        }
+        Ok(None) => None,
+        Err(e) => {
+            log::warn!("LLM extraction disabled: {:#}", e);
+            None
+        }
+    }
+}
+
+/// Text of the model's answer in a provider's response
+fn response_text(provider: LlmProvider, response: &Value) -> Option<String> {
+    let text = match provider {
+        LlmProvider::Anthropic => response["content"]
+            .as_array()?
+            .iter()
+            .filter_map(|block| block["text"].as_str())
+            .collect::<String>(),
+        LlmProvider::OpenAi => response["choices"][0]["message"]["content"].as_str()?.to_string(),
+        LlmProvider::Ollama => response["message"]["content"].as_str()?.to_string(),
+    };
+    Some(text)
+}
+
+/// Transcript as plain "role: text" lines, shortened to about `max_chars`
+///
+/// The first message (which usually states the goal) is always kept, then as
+/// many of the latest messages as fit.
+fn transcript_text(log: &ConversationLog, max_chars: usize) -> String {
+    let lines: Vec<String> = log
+        .messages
+        .iter()
+        .filter(|message| !message.content.trim().is_empty())
+        .map(|message| format!("{}: {}", message.role, message.content.trim()))
+        .collect();
+    let Some((first, rest)) = lines.split_first() else {
+        return String::new();
+    };
+
+    let mut budget = max_chars.saturating_sub(first.len());
+    let mut latest: Vec<&str> = Vec::new();
+    for line in rest.iter().rev() {
+        if line.len() > budget {
+            break;
+        }
+        budget -= line.len();
+        latest.push(line);
+    }
+    latest.reverse();
+
+    let mut text = first.clone();
+    if latest.len() < rest.len() {
+        text.push_str("\n\n[...]");
+    }
+    for line in latest {
+        text.push_str("\n\n");
+        text.push_str(line);
+    }
+    text
+}
+
+#[derive(Deserialize)]
+struct LlmAnswer {
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    facts: Vec<LlmFact>,
+}
+
+#[derive(Deserialize)]
+struct LlmFact {
+    #[serde(rename = "type")]
+    fact_type: String,
+    content: String,
+    #[serde(default)]
+    importance: Option<i32>,
+    #[serde(default)]
+    confidence: Option<f64>,
+}
+
+/// Read the JSON object the model answered with
+///
+/// Text around the object (such as a Markdown code fence) is ignored, and so are
+/// facts of unknown types.
+pub fn parse_extraction(answer: &str) -> Result<Extraction> {
+    let (Some(start), Some(end)) = (answer.find('{'), answer.rfind('}')) else {
+        bail!("LLM answer contains no JSON object");
+    };
+    let parsed: LlmAnswer = serde_json::from_str(&answer[start..=end]).context("LLM answer is not valid JSON")?;
+
+    let facts = parsed
+        .facts
+        .into_iter()
+        .filter_map(|fact| {
+            let fact_type: FactType = serde_json::from_value(Value::String(fact.fact_type)).ok()?;
+            let content = fact.content.trim().to_string();
+            if content.is_empty() {
+                return None;
+            }
+            Some(ExtractedFactPayload {
+                project: String::new(),
+                session: None,
+                fact_type,
+                importance: fact.importance.unwrap_or(DEFAULT_LLM_IMPORTANCE).clamp(1, 5),
+                stale: None,
+                metadata: FactMetadata {
+                    language: detect_language(&content),
+                    confidence: fact.confidence.map(|confidence| confidence.clamp(0.0, 1.0)),
+                    ..FactMetadata::default()
+                },
+                content,
+                needs_review: Some(true),
+            })
+        })
+        .collect();
+
+    Ok(Extraction {
+        summary: parsed.summary.map(|summary| summary.trim().to_string()).filter(|s| !s.is_empty()),
+        facts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::parse_transcript_lines;
+
+    #[test]
+    fn test_parse_extraction() {
+        let answer = r#"Here you go:
+```json
+{"summary": "Cart totals: store cents",
+ "facts": [
+   {"type": "decision", "content": "Store prices as integer cents", "importance": 5, "confidence": 1.4},
+   {"type": "todo", "content": "Migrate the old orders"},
+   {"type": "rumor", "content": "Someone likes floats"},
+   {"type": "insight", "content": "  "}
+ ]}
+```"#;
+        let extraction = parse_extraction(answer).unwrap();
+        assert_eq!(extraction.summary.as_deref(), Some("Cart totals: store cents"));
+        assert_eq!(extraction.facts.len(), 2);
+        assert_eq!(extraction.facts[0].fact_type, FactType::Decision);
+        assert_eq!(extraction.facts[0].importance, 5);
+        assert_eq!(extraction.facts[1].importance, DEFAULT_LLM_IMPORTANCE);
+        assert_eq!(extraction.facts[0].metadata.confidence, Some(1.0));
+        assert_eq!(extraction.facts[1].metadata.confidence, None);
+
+        assert!(parse_extraction("I could not find anything.").is_err());
+        assert!(parse_extraction(r#"{"facts": []}"#).unwrap().summary.is_none());
+    }
+
+    #[test]
+    fn test_response_text() {
+        let anthropic = json!({"content": [{"type": "text", "text": "{\"facts\": []}"}]});
+        let openai = json!({"choices": [{"message": {"role": "assistant", "content": "{}"}}]});
+        let ollama = json!({"message": {"role": "assistant", "content": "{}"}});
+
+        assert_eq!(response_text(LlmProvider::Anthropic, &anthropic).as_deref(), Some("{\"facts\": []}"));
+        assert_eq!(response_text(LlmProvider::OpenAi, &openai).as_deref(), Some("{}"));
+        assert_eq!(response_text(LlmProvider::Ollama, &ollama).as_deref(), Some("{}"));
+        assert!(response_text(LlmProvider::OpenAi, &ollama).is_none());
+    }
+
+    #[test]
+    fn test_transcript_keeps_goal_and_latest_messages() {
+        let line = |role: &str, text: &str| {
+            format!(r#"{{"type":"{role}","message":{{"role":"{role}","content":"{text}"}}}}"#)
+        };
+        let log = parse_transcript_lines(
+            &[
+                line("user", "Fix the cart totals"),
+                line("assistant", &"x".repeat(100)),
+                line("assistant", "Stored cents instead"),
+            ]
+            .join("\n"),
+        );
+
+        let full = transcript_text(&log, TRANSCRIPT_MAX_CHARS);
+        assert!(full.starts_with("user: Fix the cart totals\n\nassistant: xxx"));
+
+        let short = transcript_text(&log, 60);
+        assert_eq!(short, "user: Fix the cart totals\n\n[...]\n\nassistant: Stored cents instead");
+    }
+}
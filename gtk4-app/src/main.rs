@@ -1,21 +1,35 @@
+mod async_repository;
+mod capture;
 mod cli;
-mod db;
-mod models;
-mod monitor;
+mod daemon;
+mod event_history;
+mod keyring;
+mod llm;
+mod markdown;
+mod monitor_updates;
 mod notifications;
 mod settings;
-mod utils;
+mod sync;
 mod views;
 mod window;
 
-use anyhow::Result;
+// Core logic lives in the ccd-core library; keep it reachable at the usual paths
+use ccd_core::{db, models, monitor, utils};
+
+use anyhow::{Context, Result};
 use clap::Parser;
 use cli::{Cli, Commands};
 use db::{Database, Repository};
+use models::AuditActor;
+use std::cell::RefCell;
+use std::rc::Rc;
 use window::MainWindow;
 
 const APP_ID: &str = "com.github.claudecontexttracker";
 
+/// Application action opening a project by name or ID, also invoked by later launches
+const OPEN_PROJECT_ACTION: &str = "open-project";
+
 fn main() -> Result<()> {
     // Initialize logger
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
@@ -23,14 +37,54 @@ fn main() -> Result<()> {
     // Parse command line arguments
     let cli = Cli::parse();
 
+    // A GUI launch while the GUI is running is handed to that instance before this
+    // process opens the database as a second writer
+    let project_to_open = match &cli.command {
+        Some(Commands::Open { project }) => Some(project.clone()),
+        _ => None,
+    };
+    let gui_app = match &cli.command {
+        Some(Commands::Gui) | Some(Commands::Open { .. }) | None => match register_gui(project_to_open.as_deref())? {
+            Some(app) => Some(app),
+            None => return Ok(()),
+        },
+        Some(_) => None,
+    };
+
+    // Attribute changes in the audit log to whoever is making them
+    let actor = match &cli.command {
+        Some(Commands::Monitor { .. }) => AuditActor::Monitor,
+        Some(Commands::Gui) | Some(Commands::Open { .. }) | None => AuditActor::Gui,
+        Some(_) => AuditActor::Cli,
+    };
+
     // Initialize database (always needed)
     let database = Database::new(None)?;
-    let repository = Repository::new(database.into_shared());
+    // A database on a network filesystem is used through a local copy; this writes
+    // the copy back periodically and once more on exit
+    let _cache_flusher = database.start_cache_flushing();
+    let snapshot_dir = db::snapshots_dir(database.db_path());
+    let repository = Repository::new(database.into_shared()).with_actor(actor);
+
+    // The GUI and the monitor daemon keep nightly snapshots; one-off commands don't
+    let keeps_snapshots = gui_app.is_some() || matches!(cli.command, Some(Commands::Monitor { .. }));
+    let _snapshot_scheduler = keeps_snapshots.then(|| {
+        db::SnapshotScheduler::start(
+            repository.clone(),
+            snapshot_dir,
+            Some(std::sync::Arc::new(|e: &anyhow::Error| {
+                notifications::notify_snapshot_failed(&format!("{:#}", e))
+            })),
+        )
+    });
 
     // Execute based on command (or launch GUI if no command)
     match cli.command {
-        Some(Commands::Pull { project, output }) => {
-            cli::commands::pull_command(&repository, &project, output)?;
+        Some(Commands::Pull { project, output, translate_to, preview, link_attachments, snapshot }) => {
+            cli::commands::pull_command(&repository, &project, output, translate_to, preview, link_attachments, snapshot)?;
+        }
+        Some(Commands::Import { project, file, yes }) => {
+            cli::commands::import_command(&repository, &project, file, yes)?;
         }
         Some(Commands::Push { project, summary, tokens }) => {
             cli::commands::push_command(&repository, &project, summary, tokens)?;
@@ -47,15 +101,80 @@ fn main() -> Result<()> {
         Some(Commands::Diff { project, from, to }) => {
             cli::commands::diff_command(&repository, &project, from, to)?;
         }
-        Some(Commands::Monitor { project, logs_dir }) => {
-            run_daemon_mode(repository, project, logs_dir)?;
+        Some(Commands::Depend { project, on, summary, remove }) => {
+            cli::commands::depend_command(&repository, &project, &on, summary, remove)?;
+        }
+        Some(Commands::Merge { source, target }) => {
+            cli::commands::merge_command(&repository, &source, &target)?;
+        }
+        Some(Commands::Clone { project, name, facts }) => {
+            cli::commands::clone_command(&repository, &project, &name, facts)?;
+        }
+        Some(Commands::Attach { project, section, file, name, remove }) => {
+            cli::commands::attach_command(&repository, &project, &section, file, name, remove)?;
+        }
+        Some(Commands::Sections { action }) => {
+            cli::commands::sections_command(&repository, action)?;
+        }
+        Some(Commands::Doctor { fix }) => {
+            cli::commands::doctor_command(&repository, fix)?;
+        }
+        Some(Commands::Bench { sizes, no_save }) => {
+            cli::commands::bench_command(sizes, no_save)?;
+        }
+        Some(Commands::Compact { project, group, budget, output, translate_to, snapshot }) => {
+            cli::commands::compact_command(&repository, project, group, budget, output, translate_to, snapshot)?;
+        }
+        Some(Commands::Compactable { project, budgets }) => {
+            cli::commands::compactable_command(&repository, &project, budgets)?;
+        }
+        Some(Commands::Rescore { project }) => {
+            cli::commands::rescore_command(&repository, &project)?;
+        }
+        Some(Commands::Capture { project, audio, image }) => {
+            cli::commands::capture_command(&repository, &project, audio, image)?;
+        }
+        Some(Commands::Backfill { project, since, logs_dir }) => {
+            cli::commands::backfill_command(&repository, &project, since, logs_dir)?;
+        }
+        Some(Commands::Log { project, limit }) => {
+            cli::commands::log_command(&repository, &project, limit)?;
+        }
+        Some(Commands::Stats { project }) => {
+            cli::commands::stats_command(&repository, project)?;
+        }
+        Some(Commands::Source { fact }) => {
+            cli::commands::source_command(&repository, &fact)?;
+        }
+        Some(Commands::Promote { fact }) => {
+            cli::commands::promote_command(&repository, &fact)?;
+        }
+        Some(Commands::History { project, entity_type, id, limit }) => {
+            cli::commands::history_command(&repository, project, entity_type, id, limit)?;
+        }
+        Some(Commands::Replicate { url, interval, once, save, disable }) => {
+            cli::commands::replicate_command(&repository, url, interval, once, save, disable)?;
+        }
+        Some(Commands::Sync { status, exclude, include }) => {
+            cli::commands::sync_command(&repository, status, exclude, include)?;
+        }
+        Some(Commands::Daemon { action }) => {
+            cli::commands::daemon_command(&repository, action)?;
+        }
+        Some(Commands::Workspace { action }) => {
+            cli::commands::workspace_command(&repository, action)?;
+        }
+        Some(Commands::Monitor { project, logs_dir, events_json, daemon }) => {
+            run_daemon_mode(repository, project, logs_dir, events_json, daemon)?;
         }
         Some(Commands::Switch { .. }) => {
             println!("Switch command not yet implemented");
         }
-        Some(Commands::Gui) | None => {
+        Some(Commands::Gui) | Some(Commands::Open { .. }) | None => {
             // Default: launch GUI
-            run_gui_mode(repository)?;
+            if let Some(app) = gui_app {
+                run_gui_mode(app, repository, project_to_open)?;
+            }
         }
     }
 
@@ -63,65 +182,190 @@ fn main() -> Result<()> {
 }
 
 /// Run in daemon mode (file monitoring only)
-fn run_daemon_mode(repository: Repository, project: String, logs_dir: Option<String>) -> Result<()> {
-    log::info!("Starting daemon mode for project: {}", project);
-
-    // Find project
-    let proj = cli::commands::find_project(&repository, &project)?;
+fn run_daemon_mode(
+    repository: Repository,
+    project: Option<String>,
+    logs_dir: Option<String>,
+    events_json: bool,
+    as_service: bool,
+) -> Result<()> {
+    // Held until monitoring stops; a second daemon fails here instead of double-processing
+    let _pid_file = if as_service {
+        Some(daemon::PidFile::acquire(&daemon::pid_file_path())?)
+    } else {
+        None
+    };
 
     // Convert logs_dir to PathBuf
     let logs_path = logs_dir.map(std::path::PathBuf::from);
+    let session_extractor = llm::configured_session_extractor(&repository);
+
+    // One project, or every project its transcripts are routed to
+    let monitor = match project {
+        Some(project) => {
+            log::info!("Starting daemon mode for project: {}", project);
+            let proj = cli::commands::find_project(&repository, &project)?;
+            monitor::LogMonitor::new(proj.id, repository, logs_path)?
+        }
+        None => {
+            log::info!("Starting daemon mode for all projects");
+            monitor::LogMonitor::for_all_projects(repository, logs_path)?
+        }
+    };
+
+    let monitor = match session_extractor {
+        Some(extractor) => monitor.with_session_extractor(extractor),
+        None => monitor,
+    };
 
     // Start monitoring (blocking)
-    let monitor = monitor::LogMonitor::new(proj.id, repository, logs_path)?;
-    monitor.start_monitoring()?;
+    let monitor = monitor
+        .with_event_handler(std::sync::Arc::new(move |event: monitor::MonitorEvent| {
+            if events_json {
+                match monitor::EventEnvelope::new(event.clone()).to_json() {
+                    Ok(json) => println!("{}", json),
+                    Err(e) => log::warn!("Failed to serialize event: {}", e),
+                }
+            }
+            notifications::notify_monitor_event(event);
+        }));
+    let monitor = if as_service {
+        // Returning from here lets the database cache be written back before exit
+        daemon::stop_on_termination(monitor.stop_flag())?;
+        monitor.with_ready_handler(std::sync::Arc::new(daemon::notify_ready))
+    } else {
+        monitor
+    };
+    let result = monitor.start_monitoring();
+    if as_service {
+        daemon::notify_stopping();
+    }
+    result?;
 
     Ok(())
 }
 
-/// Run in GUI mode
-fn run_gui_mode(repository: Repository) -> Result<()> {
+/// Register the GUI application, or hand this launch to the instance already running
+///
+/// Returns None when another instance took over: it presents its window and opens
+/// `project`, and this process can exit without opening the database.
+fn register_gui(project: Option<&str>) -> Result<Option<adw::Application>> {
     use adw::prelude::*;
 
-    log::info!("Starting GUI mode");
-
     // Initialize GTK
-    gtk::init().expect("Failed to initialize GTK");
+    gtk::init().context("Failed to initialize GTK")?;
 
     // Initialize libadwaita
-    adw::init().expect("Failed to initialize libadwaita");
+    adw::init().context("Failed to initialize libadwaita")?;
+
+    // Initialize GtkSourceView (markdown highlighting in the section editor)
+    sourceview5::init();
 
     // Create the application
     let app = adw::Application::builder()
         .application_id(APP_ID)
         .build();
 
-    // Setup signal handlers
+    // Setup signal handlers (startup runs on registration)
     app.connect_startup(|_| {
         log::info!("Application startup");
         load_css();
     });
 
-    // Build UI on activate
+    app.register(None::<&gio::Cancellable>)
+        .context("Failed to register the application")?;
+
+    if !app.is_remote() {
+        return Ok(Some(app));
+    }
+
+    match project {
+        Some(project) => {
+            log::info!("Opening {} in the running window", project);
+            app.activate_action(OPEN_PROJECT_ACTION, Some(&project.to_variant()));
+        }
+        None => {
+            log::info!("Activating the running window");
+            app.activate();
+        }
+    }
+    Ok(None)
+}
+
+/// Run in GUI mode, opening `project` once the window is up
+fn run_gui_mode(app: adw::Application, repository: Repository, project: Option<String>) -> Result<()> {
+    use adw::prelude::*;
+
+    log::info!("Starting GUI mode");
+
+    // Later launches activate the application again; reuse the window
+    let window: Rc<RefCell<Option<MainWindow>>> = Rc::new(RefCell::new(None));
+
+    // Build UI on first activate
     let repo_clone = repository.clone();
+    let window_clone = window.clone();
     app.connect_activate(move |app| {
-        build_ui(app, repo_clone.clone());
+        let mut window = window_clone.borrow_mut();
+        match window.as_ref() {
+            Some(window) => window.present(),
+            None => *window = Some(build_ui(app, repo_clone.clone())),
+        }
     });
 
-    // Run the application
-    let exit_code = app.run();
+    let open_action = gio::SimpleAction::new(OPEN_PROJECT_ACTION, Some(glib::VariantTy::STRING));
+    let app_weak = app.downgrade();
+    open_action.connect_activate(move |_, param| {
+        let (Some(app), Some(name)) = (app_weak.upgrade(), param.and_then(|p| p.get::<String>())) else {
+            return;
+        };
+        app.activate();
+        match cli::commands::find_project(&repository, &name) {
+            Ok(project) => {
+                if let Some(window) = window.borrow().as_ref() {
+                    window.navigate_to_project(project.id);
+                }
+            }
+            Err(e) => log::error!("Failed to open project: {}", e),
+        }
+    });
+    app.add_action(&open_action);
+
+    if let Some(project) = project {
+        let app = app.clone();
+        glib::idle_add_local_once(move || app.activate_action(OPEN_PROJECT_ACTION, Some(&project.to_variant())));
+    }
+
+    // Run the application; clap has already handled the arguments
+    let exit_code = app.run_with_args::<&str>(&[]);
 
     log::info!("Application exiting with code: {:?}", exit_code);
     Ok(())
 }
 
 /// Build the main UI
-fn build_ui(app: &adw::Application, repository: Repository) {
+fn build_ui(app: &adw::Application, repository: Repository) -> MainWindow {
     log::info!("Building UI");
 
+    // Apply the saved color scheme before the window is shown
+    match repository.load_settings() {
+        Ok(settings) => {
+            settings::apply_theme(settings.theme);
+
+            // Keep a read-only mirror of the team's PocketBase server fresh
+            if let Some(url) = settings.replica_url {
+                let interval = std::time::Duration::from_secs(settings.replica_interval_secs.max(1));
+                if let Err(e) = sync::start_replication(repository.clone(), url, interval) {
+                    log::error!("Failed to start replication: {}", e);
+                }
+            }
+        }
+        Err(e) => log::warn!("Failed to load settings: {}", e),
+    }
+
     // Create main window
     let window = MainWindow::new(app, repository);
     window.present();
+    window
 }
 
 /// Load custom CSS for styling
@@ -0,0 +1,225 @@
+use pulldown_cmark::{Event, HeadingLevel, Options, Parser, Tag};
+use std::ops::Range;
+
+/// Render markdown as Pango markup, for previewing it in a gtk::Label
+///
+/// Covers what context sections use: headings, emphasis, code, links, quotes,
+/// nested and task lists, rules and simple tables. Raw HTML is shown as text.
+pub fn markdown_to_pango(markdown: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_TABLES);
+
+    let mut markup = String::new();
+    let mut lists: Vec<Option<u64>> = Vec::new(); // Next number of each open list, None if bulleted
+    let mut first_cell = false;
+
+    for event in Parser::new_ext(markdown, options) {
+        match event {
+            Event::Start(tag) => match tag {
+                Tag::Heading(level, ..) => {
+                    markup.push_str(&format!("<span weight=\"bold\" size=\"{}\">", heading_size(level)));
+                }
+                Tag::BlockQuote | Tag::Emphasis | Tag::Image(..) => markup.push_str("<i>"),
+                Tag::CodeBlock(_) => markup.push_str("<tt>"),
+                Tag::List(start) => lists.push(start),
+                Tag::Item => {
+                    end_line(&mut markup);
+                    markup.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+                    match lists.last_mut() {
+                        Some(Some(number)) => {
+                            markup.push_str(&format!("{}. ", number));
+                            *number += 1;
+                        }
+                        _ => markup.push_str("• "),
+                    }
+                }
+                Tag::Strong => markup.push_str("<b>"),
+                Tag::Strikethrough => markup.push_str("<s>"),
+                Tag::Link(_, url, _) => markup.push_str(&format!("<a href=\"{}\">", escape(&url))),
+                Tag::TableHead => {
+                    markup.push_str("<b>");
+                    first_cell = true;
+                }
+                Tag::TableRow => first_cell = true,
+                Tag::TableCell => {
+                    if !first_cell {
+                        markup.push_str(" │ ");
+                    }
+                    first_cell = false;
+                }
+                Tag::FootnoteDefinition(label) => markup.push_str(&format!("[{}] ", escape(&label))),
+                Tag::Paragraph | Tag::Table(_) => {}
+            },
+            Event::End(tag) => match tag {
+                Tag::Heading(..) => markup.push_str("</span>\n\n"),
+                Tag::Paragraph => markup.push_str("\n\n"),
+                Tag::Emphasis | Tag::Image(..) => markup.push_str("</i>"),
+                Tag::BlockQuote => {
+                    markup.truncate(markup.trim_end_matches('\n').len());
+                    markup.push_str("</i>\n\n");
+                }
+                Tag::CodeBlock(_) => {
+                    markup.truncate(markup.trim_end_matches('\n').len());
+                    markup.push_str("</tt>\n\n");
+                }
+                Tag::List(_) => {
+                    lists.pop();
+                    if lists.is_empty() {
+                        end_line(&mut markup);
+                        markup.push('\n');
+                    }
+                }
+                Tag::Item => end_line(&mut markup),
+                Tag::Strong => markup.push_str("</b>"),
+                Tag::Strikethrough => markup.push_str("</s>"),
+                Tag::Link(..) => markup.push_str("</a>"),
+                Tag::TableHead => markup.push_str("</b>\n"),
+                Tag::TableRow => markup.push('\n'),
+                Tag::Table(_) => markup.push('\n'),
+                Tag::TableCell | Tag::FootnoteDefinition(_) => {}
+            },
+            Event::Text(text) | Event::Html(text) => markup.push_str(&escape(&text)),
+            Event::Code(code) => markup.push_str(&format!("<tt>{}</tt>", escape(&code))),
+            Event::FootnoteReference(label) => markup.push_str(&format!("[{}]", escape(&label))),
+            Event::SoftBreak => markup.push(' '),
+            Event::HardBreak => markup.push('\n'),
+            Event::Rule => markup.push_str("――――――――――\n\n"),
+            Event::TaskListMarker(done) => {
+                // Checkboxes replace the item's bullet
+                if let Some(stripped) = markup.strip_suffix("• ") {
+                    markup.truncate(stripped.len());
+                }
+                markup.push_str(if done { "☑ " } else { "☐ " });
+            }
+        }
+    }
+
+    markup.trim_end().to_string()
+}
+
+/// Byte ranges where `query` occurs in `text` ignoring case, in order and not overlapping
+pub fn find_matches(text: &str, query: &str) -> Vec<Range<usize>> {
+    let needle: Vec<char> = query.trim().chars().flat_map(char::to_lowercase).collect();
+    if needle.is_empty() {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut from = 0;
+    for (start, _) in text.char_indices() {
+        if start < from {
+            continue;
+        }
+        let mut pending = needle.iter();
+        for (offset, c) in text[start..].char_indices() {
+            if !c.to_lowercase().all(|lower| pending.next() == Some(&lower)) {
+                break;
+            }
+            if pending.as_slice().is_empty() {
+                from = start + offset + c.len_utf8();
+                ranges.push(start..from);
+                break;
+            }
+        }
+    }
+
+    ranges
+}
+
+/// Pango markup for `text` with the byte `ranges` (from `find_matches`) highlighted
+pub fn highlight_matches(text: &str, ranges: &[Range<usize>]) -> String {
+    let mut markup = String::new();
+    let mut last = 0;
+    for range in ranges {
+        markup.push_str(&escape(&text[last..range.start]));
+        markup.push_str("<span weight=\"bold\" bgcolor=\"#f6d32d\" bgalpha=\"40%\">");
+        markup.push_str(&escape(&text[range.clone()]));
+        markup.push_str("</span>");
+        last = range.end;
+    }
+    markup.push_str(&escape(&text[last..]));
+    markup
+}
+
+fn heading_size(level: HeadingLevel) -> &'static str {
+    match level {
+        HeadingLevel::H1 => "xx-large",
+        HeadingLevel::H2 => "x-large",
+        HeadingLevel::H3 => "large",
+        _ => "medium",
+    }
+}
+
+/// Start a new line unless the markup is at the start of one
+fn end_line(markup: &mut String) {
+    if !markup.is_empty() && !markup.ends_with('\n') {
+        markup.push('\n');
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_to_pango_inline() {
+        assert_eq!(
+            markdown_to_pango("# Plan\n\nUse **SQLite** with *WAL*, not `r2d2 < 1`.\nSee [docs](https://x.y?a=1&b=2)."),
+            "<span weight=\"bold\" size=\"xx-large\">Plan</span>\n\n\
+             Use <b>SQLite</b> with <i>WAL</i>, not <tt>r2d2 &lt; 1</tt>. \
+             See <a href=\"https://x.y?a=1&amp;b=2\">docs</a>."
+        );
+        // Raw HTML is text, not markup
+        assert_eq!(markdown_to_pango("a <b>b</b>"), "a &lt;b&gt;b&lt;/b&gt;");
+    }
+
+    #[test]
+    fn test_markdown_to_pango_lists() {
+        let markdown = "Steps:\n\n1. Build\n2. Test\n   - unit\n   - integration\n\n- [x] Done\n- [ ] Todo\n\nEnd";
+        assert_eq!(
+            markdown_to_pango(markdown),
+            "Steps:\n\n1. Build\n2. Test\n    • unit\n    • integration\n\n☑ Done\n☐ Todo\n\nEnd"
+        );
+    }
+
+    #[test]
+    fn test_find_matches() {
+        assert_eq!(find_matches("Cache the cache", "CACHE"), vec![0..5, 10..15]);
+        assert_eq!(find_matches("aaa", "aa"), vec![0..2]);
+        assert_eq!(find_matches("Größe ändern", "ÄND"), vec![8..12]);
+        assert!(find_matches("Cache", "  ").is_empty());
+        assert!(find_matches("Cache", "cached").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_matches() {
+        let text = "Use <T> & cache";
+        assert_eq!(
+            highlight_matches(text, &find_matches(text, "cache")),
+            "Use &lt;T&gt; &amp; <span weight=\"bold\" bgcolor=\"#f6d32d\" bgalpha=\"40%\">cache</span>"
+        );
+        assert_eq!(highlight_matches("a < b", &[]), "a &lt; b");
+    }
+
+    #[test]
+    fn test_markdown_to_pango_blocks() {
+        assert_eq!(
+            markdown_to_pango("```rust\nfn main() {}\n```\n\n---\n\n> Quoted"),
+            "<tt>fn main() {}</tt>\n\n――――――――――\n\n<i>Quoted</i>"
+        );
+        assert_eq!(
+            markdown_to_pango("| Key | Value |\n|-----|-------|\n| a | 1 |"),
+            "<b>Key │ Value</b>\na │ 1"
+        );
+    }
+}
@@ -1,9 +0,0 @@
-pub mod project;
-pub mod context_section;
-pub mod session;
-pub mod fact;
-
-pub use project::*;
-pub use context_section::*;
-pub use session::*;
-pub use fact::*;
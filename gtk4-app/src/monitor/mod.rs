@@ -1,7 +0,0 @@
-pub mod watcher;
-pub mod extractor;
-pub mod scorer;
-
-pub use watcher::*;
-pub use extractor::*;
-pub use scorer::*;
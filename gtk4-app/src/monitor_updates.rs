@@ -0,0 +1,102 @@
+use crate::monitor::{MonitorHandle, MonitorUpdate};
+use gtk::glib;
+use gtk::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Widget callback for monitor updates; returning `Break` unsubscribes it
+type Subscriber = Box<dyn Fn(&MonitorUpdate) -> glib::ControlFlow>;
+
+/// Live monitor updates handed to every widget that shows them, on the GTK main loop
+///
+/// Subscriptions outlive monitor restarts, so a view subscribes once and keeps
+/// getting updates while the monitor is switched off and on again.
+#[derive(Clone, Default)]
+pub struct MonitorUpdates {
+    subscribers: Rc<RefCell<Vec<Subscriber>>>,
+}
+
+impl MonitorUpdates {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `subscriber` with every update until it returns `Break`
+    pub fn subscribe(&self, subscriber: impl Fn(&MonitorUpdate) -> glib::ControlFlow + 'static) {
+        self.subscribers.borrow_mut().push(Box::new(subscriber));
+    }
+
+    /// Call `reload` with every update for `project_id` (all projects when `None`) while `widget` is shown
+    ///
+    /// Unsubscribes once the widget is gone from the window, e.g. after its project page is closed.
+    pub fn subscribe_while_shown(
+        &self,
+        widget: &impl IsA<gtk::Widget>,
+        project_id: Option<&str>,
+        reload: impl Fn() + 'static,
+    ) {
+        let widget = widget.clone().upcast::<gtk::Widget>();
+        let project_id = project_id.map(str::to_string);
+        self.subscribe(move |update| {
+            if widget.root().is_none() {
+                return glib::ControlFlow::Break;
+            }
+            if project_id.is_none() || update.project_id() == project_id.as_deref() {
+                reload();
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Pass the updates of a started monitor on to subscribers until it stops
+    pub fn forward(&self, handle: &MonitorHandle) {
+        let updates = handle.updates();
+        let subscribers = self.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(update) = updates.recv().await {
+                subscribers.publish(&update);
+            }
+        });
+    }
+
+    fn publish(&self, update: &MonitorUpdate) {
+        // Taken out so subscribers may subscribe others while being called
+        let mut current = std::mem::take(&mut *self.subscribers.borrow_mut());
+        current.retain(|subscriber| subscriber(update) == glib::ControlFlow::Continue);
+
+        let mut subscribers = self.subscribers.borrow_mut();
+        current.append(&mut subscribers);
+        *subscribers = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_subscribers_can_unsubscribe() {
+        let updates = MonitorUpdates::new();
+        let error = MonitorUpdate::Error {
+            message: "Failed to process session.jsonl".to_string(),
+        };
+
+        let seen = Rc::new(Cell::new(0));
+        let counter = seen.clone();
+        updates.subscribe(move |_| {
+            counter.set(counter.get() + 1);
+            glib::ControlFlow::Continue
+        });
+        let once = seen.clone();
+        updates.subscribe(move |_| {
+            once.set(once.get() + 10);
+            glib::ControlFlow::Break
+        });
+
+        updates.publish(&error);
+        updates.publish(&error);
+        assert_eq!(seen.get(), 12);
+        assert_eq!(updates.subscribers.borrow().len(), 1);
+    }
+}
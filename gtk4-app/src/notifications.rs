@@ -1,3 +1,5 @@
+use crate::models::format_duration;
+use crate::monitor::MonitorEvent;
 use notify_rust::{Notification, Timeout};
 use std::path::PathBuf;
 
@@ -30,17 +32,66 @@ pub fn notify_token_threshold(project_name: &str, current_tokens: usize, thresho
     send_notification(&summary, &body);
 }
 
-/// Send a notification when monitoring starts
-pub fn notify_monitoring_started(project_name: &str) {
-    let summary = "Monitoring Started".to_string();
+/// Send a summary notification when a conversation has gone idle
+pub fn notify_session_ended(project_name: &str, summary: &str, duration_minutes: u64, facts: usize) {
+    let title = format!("Session Ended: {}", project_name);
+    let body = format!(
+        "{}\n{} · {} fact{} extracted",
+        summary,
+        format_duration(chrono::Duration::minutes(duration_minutes as i64)),
+        facts,
+        if facts == 1 { "" } else { "s" }
+    );
+
+    send_notification(&title, &body);
+}
+
+/// Send a notification when a project's CLAUDE.md was edited by hand
+pub fn notify_context_file_edited(project_name: &str, path: &str, sections: usize) {
+    let summary = format!("CLAUDE.md Edited: {}", project_name);
     let body = format!(
-        "Now monitoring Claude Code logs for \"{}\"",
-        project_name
+        "{} section{} in {} differ from the stored context\nImport them with `claude-context-tracker import`",
+        sections,
+        if sections == 1 { "" } else { "s" },
+        path
     );
 
     send_notification(&summary, &body);
 }
 
+/// Send the notification for an event reported by a log monitor
+pub fn notify_monitor_event(event: MonitorEvent) {
+    match event {
+        MonitorEvent::FactsExtracted { project, count } => notify_facts_extracted(&project, count),
+        MonitorEvent::TokenThreshold {
+            project,
+            tokens,
+            threshold,
+        } => notify_token_threshold(&project, tokens, threshold),
+        MonitorEvent::SessionEnded {
+            project,
+            summary,
+            duration_minutes,
+            facts,
+            ..
+        } => notify_session_ended(&project, &summary, duration_minutes, facts),
+        MonitorEvent::ContextFileEdited { project, path, sections } => {
+            notify_context_file_edited(&project, &path, sections)
+        }
+    }
+}
+
+/// Send a notification when monitoring starts (for one project, or all of them)
+pub fn notify_monitoring_started(project_name: Option<&str>) {
+    let summary = "Monitoring Started".to_string();
+    let body = match project_name {
+        Some(name) => format!("Now monitoring Claude Code logs for \"{}\"", name),
+        None => "Now monitoring Claude Code logs for all projects".to_string(),
+    };
+
+    send_notification(&summary, &body);
+}
+
 /// Send a notification when monitoring stops
 pub fn notify_monitoring_stopped() {
     let summary = "Monitoring Stopped".to_string();
@@ -89,6 +140,14 @@ pub fn notify_export_complete(project_name: &str, format: &str) {
     send_notification(&summary, &body);
 }
 
+/// Send a notification when the nightly database snapshot fails
+pub fn notify_snapshot_failed(message: &str) {
+    let summary = "Database Snapshot Failed".to_string();
+    let body = format!("{}\nPrevious snapshots are kept; check Preferences → Snapshots", message);
+
+    send_notification(&summary, &body);
+}
+
 /// Send a notification for errors
 pub fn notify_error(title: &str, message: &str) {
     let summary = format!("⚠ Error: {}", title);
@@ -1,6 +1,36 @@
+use crate::db::Repository;
+use crate::models::{format_bytes, AppSettings, BackgroundAggressiveness, LlmProvider, ThemePreference};
 use adw::prelude::*;
 use gtk::glib;
+use std::cell::RefCell;
 use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Apply a color scheme preference to the running application
+pub fn apply_theme(theme: ThemePreference) {
+    let scheme = match theme {
+        ThemePreference::System => adw::ColorScheme::Default,
+        ThemePreference::Light => adw::ColorScheme::ForceLight,
+        ThemePreference::Dark => adw::ColorScheme::ForceDark,
+    };
+    adw::StyleManager::default().set_color_scheme(scheme);
+}
+
+/// Settings shared by the dialog's pages; every change is saved immediately
+#[derive(Clone)]
+struct SettingsStore {
+    repository: Repository,
+    settings: Rc<RefCell<AppSettings>>,
+}
+
+impl SettingsStore {
+    fn update(&self, change: impl FnOnce(&mut AppSettings)) {
+        change(&mut self.settings.borrow_mut());
+        if let Err(e) = self.repository.save_settings(&self.settings.borrow()) {
+            log::error!("Failed to save settings: {}", e);
+        }
+    }
+}
 
 /// Settings dialog for application preferences
 pub struct SettingsDialog {
@@ -9,7 +39,7 @@ pub struct SettingsDialog {
 
 impl SettingsDialog {
     /// Create a new settings dialog
-    pub fn new(parent: &impl IsA<gtk::Window>) -> Self {
+    pub fn new(parent: &impl IsA<gtk::Window>, repository: Repository) -> Self {
         let dialog = adw::PreferencesWindow::builder()
             .title("Preferences")
             .modal(true)
@@ -17,23 +47,32 @@ impl SettingsDialog {
             .search_enabled(false)
             .build();
 
+        let settings = repository.load_settings().unwrap_or_else(|e| {
+            log::warn!("Failed to load settings, using defaults: {}", e);
+            AppSettings::default()
+        });
+        let store = SettingsStore {
+            repository,
+            settings: Rc::new(RefCell::new(settings)),
+        };
+
         // General settings page
-        let general_page = Self::create_general_page();
+        let general_page = Self::create_general_page(&store);
         dialog.add(&general_page);
 
         // Monitoring settings page
-        let monitoring_page = Self::create_monitoring_page();
+        let monitoring_page = Self::create_monitoring_page(&store);
         dialog.add(&monitoring_page);
 
         // Appearance settings page
-        let appearance_page = Self::create_appearance_page();
+        let appearance_page = Self::create_appearance_page(&store);
         dialog.add(&appearance_page);
 
         Self { dialog }
     }
 
     /// Create general settings page
-    fn create_general_page() -> adw::PreferencesPage {
+    fn create_general_page(store: &SettingsStore) -> adw::PreferencesPage {
         let page = adw::PreferencesPage::builder()
             .title("General")
             .icon_name("preferences-system-symbolic")
@@ -46,6 +85,7 @@ impl SettingsDialog {
             .build();
 
         let db_location = Self::get_database_location();
+        let network_filesystem = crate::db::network_filesystem(&PathBuf::from(&db_location));
         let db_row = adw::ActionRow::builder()
             .title("Database Location")
             .subtitle(&db_location)
@@ -72,12 +112,268 @@ impl SettingsDialog {
         db_row.add_suffix(&db_button);
         db_group.add(&db_row);
 
+        // SQLite locking can't be trusted on NFS/SMB home directories
+        if let Some(fs_type) = network_filesystem {
+            let config = crate::db::DatabaseConfig::from_env();
+            let subtitle = if config.local_cache {
+                format!(
+                    "On {}; working on a local copy written back every {}s",
+                    fs_type,
+                    config.flush_interval.as_secs()
+                )
+            } else {
+                format!(
+                    "On {}, where database locking is unreliable. Set {} to a local path, or {}=1 to work on a local copy",
+                    fs_type,
+                    crate::db::DB_PATH_ENV,
+                    crate::db::LOCAL_CACHE_ENV
+                )
+            };
+            let network_row = adw::ActionRow::builder()
+                .title("Network Filesystem")
+                .subtitle(glib::markup_escape_text(&subtitle).as_str())
+                .build();
+            network_row.add_prefix(&gtk::Image::from_icon_name("dialog-warning-symbolic"));
+            db_group.add(&network_row);
+        }
+
+        let maintenance_row = adw::ActionRow::builder()
+            .title("Maintenance")
+            .subtitle("Check integrity and reclaim unused space")
+            .build();
+
+        let maintenance_button = gtk::Button::builder()
+            .label("Optimize")
+            .valign(gtk::Align::Center)
+            .build();
+
+        let repository = store.repository.clone();
+        let maintenance_row_weak = maintenance_row.downgrade();
+        maintenance_button.connect_clicked(move |_| {
+            let subtitle = match repository.maintenance() {
+                Ok(report) if report.is_healthy() => format!("Done: {}", report.summary()),
+                Ok(report) => format!("Integrity check failed: {}", report.problems.join("; ")),
+                Err(e) => {
+                    log::error!("Database maintenance failed: {}", e);
+                    format!("Maintenance failed: {}", e)
+                }
+            };
+            if let Some(row) = maintenance_row_weak.upgrade() {
+                row.set_subtitle(&glib::markup_escape_text(&subtitle));
+            }
+        });
+
+        maintenance_row.add_suffix(&maintenance_button);
+        db_group.add(&maintenance_row);
+
+        // Replication group
+        let replica_group = adw::PreferencesGroup::builder()
+            .title("PocketBase Replica")
+            .description("Mirror a team PocketBase server into the local database for fast offline reads. \
+                          Local data becomes read-only. Changes apply on next launch.")
+            .build();
+
+        let url_row = adw::EntryRow::builder()
+            .title("Server URL (empty to disable)")
+            .text(store.settings.borrow().replica_url.clone().unwrap_or_default())
+            .show_apply_button(true)
+            .build();
+
+        let store_clone = store.clone();
+        url_row.connect_apply(move |row| {
+            let url = row.text().trim().to_string();
+            store_clone.update(|settings| settings.replica_url = Some(url).filter(|u| !u.is_empty()));
+        });
+
+        let interval_row = adw::SpinRow::builder()
+            .title("Sync Interval")
+            .subtitle("Minutes between replication runs")
+            .build();
+        interval_row.set_adjustment(Some(&gtk::Adjustment::new(
+            (store.settings.borrow().replica_interval_secs / 60).max(1) as f64, // value
+            1.0,    // min
+            1440.0, // max
+            1.0,    // step
+            10.0,   // page increment
+            0.0,    // page size
+        )));
+
+        let store_clone = store.clone();
+        interval_row.connect_value_notify(move |row| {
+            let secs = row.value() as u64 * 60;
+            store_clone.update(|settings| settings.replica_interval_secs = secs);
+        });
+
+        replica_group.add(&url_row);
+        replica_group.add(&interval_row);
+
         page.add(&db_group);
+        page.add(&Self::create_snapshots_group(store));
+        page.add(&replica_group);
         page
     }
 
+    /// Nightly snapshots: schedule, retention and restore
+    fn create_snapshots_group(store: &SettingsStore) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title("Snapshots")
+            .description("A checked copy of the database is kept every night in the snapshots folder next to it")
+            .build();
+
+        let enabled_row = adw::SwitchRow::builder()
+            .title("Nightly Snapshots")
+            .active(store.settings.borrow().snapshots_enabled)
+            .build();
+
+        let store_clone = store.clone();
+        enabled_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            store_clone.update(|settings| settings.snapshots_enabled = enabled);
+        });
+
+        let daily_row = adw::SpinRow::builder()
+            .title("Daily Snapshots")
+            .subtitle("Days to keep one snapshot for")
+            .build();
+        daily_row.set_adjustment(Some(&gtk::Adjustment::new(
+            store.settings.borrow().snapshot_keep_daily as f64, // value
+            1.0,  // min
+            90.0, // max
+            1.0,  // step
+            7.0,  // page increment
+            0.0,  // page size
+        )));
+
+        let store_clone = store.clone();
+        daily_row.connect_value_notify(move |row| {
+            let days = row.value() as usize;
+            store_clone.update(|settings| settings.snapshot_keep_daily = days);
+        });
+
+        let weekly_row = adw::SpinRow::builder()
+            .title("Weekly Snapshots")
+            .subtitle("Weeks to keep one snapshot for, after the daily ones")
+            .build();
+        weekly_row.set_adjustment(Some(&gtk::Adjustment::new(
+            store.settings.borrow().snapshot_keep_weekly as f64, // value
+            0.0,  // min
+            52.0, // max
+            1.0,  // step
+            4.0,  // page increment
+            0.0,  // page size
+        )));
+
+        let store_clone = store.clone();
+        weekly_row.connect_value_notify(move |row| {
+            let weeks = row.value() as usize;
+            store_clone.update(|settings| settings.snapshot_keep_weekly = weeks);
+        });
+
+        for row in [&daily_row, &weekly_row] {
+            enabled_row
+                .bind_property("active", row, "sensitive")
+                .sync_create()
+                .build();
+        }
+
+        let restore_row = adw::ActionRow::builder()
+            .title("Restore")
+            .subtitle("Replace the database with a snapshot on next launch")
+            .build();
+
+        let restore_button = gtk::Button::builder()
+            .label("Choose…")
+            .valign(gtk::Align::Center)
+            .build();
+
+        let restore_row_weak = restore_row.downgrade();
+        restore_button.connect_clicked(move |button| {
+            let parent = button.root().and_downcast::<gtk::Window>();
+            Self::show_restore_dialog(parent.as_ref(), restore_row_weak.clone());
+        });
+
+        restore_row.add_suffix(&restore_button);
+
+        group.add(&enabled_row);
+        group.add(&daily_row);
+        group.add(&weekly_row);
+        group.add(&restore_row);
+        group
+    }
+
+    /// List the snapshots with their dates and sizes; picking one stages it for restore
+    fn show_restore_dialog(parent: Option<&gtk::Window>, restore_row: glib::WeakRef<adw::ActionRow>) {
+        let db_path = crate::db::configured_db_path();
+        let snapshots = crate::db::list_snapshots(&crate::db::snapshots_dir(&db_path)).unwrap_or_else(|e| {
+            log::error!("Failed to list snapshots: {}", e);
+            Vec::new()
+        });
+
+        let dialog = adw::MessageDialog::new(
+            parent,
+            Some("Restore Database"),
+            Some(if snapshots.is_empty() {
+                "No snapshots have been taken yet."
+            } else {
+                "The current database is kept in the snapshots folder. \
+                 The restore finishes when the tracker is next started."
+            }),
+        );
+        dialog.add_responses(&[("close", "Cancel")]);
+        dialog.set_close_response("close");
+
+        if !snapshots.is_empty() {
+            let list = gtk::ListBox::new();
+            list.set_selection_mode(gtk::SelectionMode::None);
+            list.add_css_class("boxed-list");
+
+            for snapshot in snapshots {
+                let taken = snapshot.taken.format("%Y-%m-%d %H:%M").to_string();
+                let row = adw::ActionRow::builder()
+                    .title(&taken)
+                    .subtitle(format_bytes(snapshot.size as i64))
+                    .build();
+
+                let restore_btn = gtk::Button::builder()
+                    .label("Restore")
+                    .valign(gtk::Align::Center)
+                    .build();
+                restore_btn.add_css_class("destructive-action");
+                row.add_suffix(&restore_btn);
+
+                let db_path = db_path.clone();
+                let dialog = dialog.clone();
+                let restore_row = restore_row.clone();
+                restore_btn.connect_clicked(move |_| {
+                    let subtitle = match crate::db::stage_restore(&snapshot.path, &db_path) {
+                        Ok(()) => format!("Restart to finish restoring the snapshot from {}", taken),
+                        Err(e) => {
+                            log::error!("Failed to stage restore: {:#}", e);
+                            format!("Restore failed: {:#}", e)
+                        }
+                    };
+                    if let Some(row) = restore_row.upgrade() {
+                        row.set_subtitle(&glib::markup_escape_text(&subtitle));
+                    }
+                    dialog.close();
+                });
+
+                list.append(&row);
+            }
+
+            let scrolled = gtk::ScrolledWindow::builder()
+                .child(&list)
+                .min_content_height(240)
+                .min_content_width(420)
+                .build();
+            dialog.set_extra_child(Some(&scrolled));
+        }
+
+        dialog.present();
+    }
+
     /// Create monitoring settings page
-    fn create_monitoring_page() -> adw::PreferencesPage {
+    fn create_monitoring_page(store: &SettingsStore) -> adw::PreferencesPage {
         let page = adw::PreferencesPage::builder()
             .title("Monitoring")
             .icon_name("emblem-synchronizing-symbolic")
@@ -92,17 +388,91 @@ impl SettingsDialog {
         let autostart_row = adw::SwitchRow::builder()
             .title("Enable Auto-Start Monitoring")
             .subtitle("Start monitoring active project on launch")
+            .active(store.settings.borrow().autostart_monitoring)
             .build();
 
+        let store_clone = store.clone();
+        autostart_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            store_clone.update(|settings| settings.autostart_monitoring = enabled);
+        });
+
         autostart_group.add(&autostart_row);
 
+        // Session end group
+        let sessions_group = adw::PreferencesGroup::builder()
+            .title("Sessions")
+            .description("Mark a conversation as ended once its log stops changing")
+            .build();
+
+        let idle_row = adw::SpinRow::builder()
+            .title("Idle Timeout")
+            .subtitle("Minutes without new messages (0 to never end sessions)")
+            .build();
+        idle_row.set_adjustment(Some(&gtk::Adjustment::new(
+            store.settings.borrow().session_idle_minutes as f64, // value
+            0.0,    // min
+            1440.0, // max
+            5.0,    // step
+            30.0,   // page increment
+            0.0,    // page size
+        )));
+
+        let store_clone = store.clone();
+        idle_row.connect_value_notify(move |row| {
+            let minutes = row.value() as u64;
+            store_clone.update(|settings| settings.session_idle_minutes = minutes);
+        });
+
+        sessions_group.add(&idle_row);
+
+        let facts_row = adw::SpinRow::builder()
+            .title("Facts per Session")
+            .subtitle("Most facts kept from one conversation, the most important first (0 for no limit)")
+            .build();
+        facts_row.set_adjustment(Some(&gtk::Adjustment::new(
+            store.settings.borrow().max_session_facts as f64, // value
+            0.0,    // min
+            1000.0, // max
+            5.0,    // step
+            25.0,   // page increment
+            0.0,    // page size
+        )));
+
+        let store_clone = store.clone();
+        facts_row.connect_value_notify(move |row| {
+            let max = row.value() as usize;
+            store_clone.update(|settings| settings.max_session_facts = max);
+        });
+
+        sessions_group.add(&facts_row);
+
+        let archive_row = adw::SwitchRow::builder()
+            .title("Archive Transcripts")
+            .subtitle("Keep a compressed copy of each conversation in the database")
+            .active(store.settings.borrow().archive_transcripts)
+            .build();
+
+        let store_clone = store.clone();
+        archive_row.connect_active_notify(move |row| {
+            let enabled = row.is_active();
+            store_clone.update(|settings| settings.archive_transcripts = enabled);
+        });
+
+        sessions_group.add(&archive_row);
+
         // Logs directory group
         let logs_group = adw::PreferencesGroup::builder()
             .title("Claude Code Logs")
             .description("Configure where to find Claude Code conversation logs")
             .build();
 
-        let logs_location = Self::get_default_logs_dir();
+        let logs_location = store
+            .settings
+            .borrow()
+            .logs_dir
+            .clone()
+            .unwrap_or_else(Self::get_default_logs_dir);
         let logs_row = adw::ActionRow::builder()
             .title("Logs Directory")
             .subtitle(&logs_location)
@@ -115,7 +485,11 @@ impl SettingsDialog {
             .build();
         logs_button.add_css_class("flat");
 
+        let store_clone = store.clone();
+        let logs_row_weak = logs_row.downgrade();
         logs_button.connect_clicked(move |btn| {
+            let store = store_clone.clone();
+            let logs_row_weak = logs_row_weak.clone();
             let dialog = gtk::FileDialog::builder()
                 .title("Select Claude Code Logs Directory")
                 .modal(true)
@@ -129,7 +503,11 @@ impl SettingsDialog {
                     if let Ok(file) = result {
                         if let Some(path) = file.path() {
                             log::info!("Selected logs directory: {}", path.display());
-                            // TODO: Save to settings
+                            let dir = path.to_string_lossy().into_owned();
+                            if let Some(row) = logs_row_weak.upgrade() {
+                                row.set_subtitle(&dir);
+                            }
+                            store.update(|settings| settings.logs_dir = Some(dir));
                         }
                     }
                 },
@@ -139,13 +517,141 @@ impl SettingsDialog {
         logs_row.add_suffix(&logs_button);
         logs_group.add(&logs_row);
 
+        // Resource usage group
+        let resources_group = adw::PreferencesGroup::builder()
+            .title("Background Aggressiveness")
+            .description("Lower settings parse fewer logs at once, at a lower CPU and I/O priority")
+            .build();
+
+        let levels = BackgroundAggressiveness::all();
+        let current = store.settings.borrow().monitor_aggressiveness;
+        let scale = gtk::Scale::with_range(gtk::Orientation::Horizontal, 0.0, (levels.len() - 1) as f64, 1.0);
+        scale.set_round_digits(0);
+        scale.set_draw_value(false);
+        scale.set_hexpand(true);
+        scale.set_margin_top(12);
+        scale.set_margin_bottom(12);
+        scale.set_margin_start(12);
+        scale.set_margin_end(12);
+        for (i, level) in levels.iter().enumerate() {
+            scale.add_mark(i as f64, gtk::PositionType::Bottom, Some(level.display_name()));
+        }
+        scale.set_value(levels.iter().position(|l| *l == current).unwrap_or_default() as f64);
+
+        let store_clone = store.clone();
+        scale.connect_value_changed(move |scale| {
+            let Some(level) = levels.get(scale.value().round() as usize).copied() else {
+                return;
+            };
+            if store_clone.settings.borrow().monitor_aggressiveness != level {
+                store_clone.update(|settings| settings.monitor_aggressiveness = level);
+            }
+        });
+
+        resources_group.add(&scale);
+
         page.add(&autostart_group);
+        page.add(&sessions_group);
         page.add(&logs_group);
+        page.add(&resources_group);
+        page.add(&Self::create_llm_group(store));
         page
     }
 
+    /// Create the LLM extraction group
+    fn create_llm_group(store: &SettingsStore) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title("LLM Extraction")
+            .description("When a session ends with fewer facts than expected, send its transcript to a \
+                          language model for a summary and more facts. Applies when monitoring next starts.")
+            .build();
+
+        let providers = LlmProvider::all();
+        let mut names = vec!["Off"];
+        names.extend(providers.iter().map(|p| p.display_name()));
+        let provider_row = adw::ComboRow::builder()
+            .title("Provider")
+            .model(&gtk::StringList::new(&names))
+            .build();
+        let current = store.settings.borrow().llm_provider;
+        let selected = current.and_then(|c| providers.iter().position(|p| *p == c)).map_or(0, |i| i + 1);
+        provider_row.set_selected(selected as u32);
+
+        let model_row = adw::EntryRow::builder()
+            .title("Model (empty for the provider's default)")
+            .text(store.settings.borrow().llm_model.clone().unwrap_or_default())
+            .show_apply_button(true)
+            .build();
+
+        let store_clone = store.clone();
+        model_row.connect_apply(move |row| {
+            let model = row.text().trim().to_string();
+            store_clone.update(|settings| settings.llm_model = Some(model).filter(|m| !m.is_empty()));
+        });
+
+        let endpoint_row = adw::EntryRow::builder()
+            .title("API Endpoint (empty for the default)")
+            .text(store.settings.borrow().llm_endpoint.clone().unwrap_or_default())
+            .show_apply_button(true)
+            .build();
+
+        let store_clone = store.clone();
+        endpoint_row.connect_apply(move |row| {
+            let endpoint = row.text().trim().to_string();
+            store_clone.update(|settings| settings.llm_endpoint = Some(endpoint).filter(|e| !e.is_empty()));
+        });
+
+        // Keys never touch the database; they live in the desktop keyring
+        let key_row = adw::PasswordEntryRow::builder()
+            .title("API Key (stored in the keyring)")
+            .show_apply_button(true)
+            .build();
+
+        let store_clone = store.clone();
+        key_row.connect_apply(move |row| {
+            let Some(provider) = store_clone.settings.borrow().llm_provider else {
+                return;
+            };
+            let key = row.text().trim().to_string();
+            let result = if key.is_empty() {
+                crate::keyring::clear_api_key(provider)
+            } else {
+                crate::keyring::store_api_key(provider, &key)
+            };
+            match result {
+                Ok(()) => row.set_text(""),
+                Err(e) => log::error!("Failed to save API key: {:#}", e),
+            }
+        });
+
+        // Model, endpoint and key only mean something once a provider is chosen
+        let detail_rows: [gtk::Widget; 3] = [
+            model_row.clone().upcast(),
+            endpoint_row.clone().upcast(),
+            key_row.clone().upcast(),
+        ];
+        for row in &detail_rows {
+            row.set_sensitive(current.is_some());
+        }
+
+        let store_clone = store.clone();
+        provider_row.connect_selected_notify(move |row| {
+            let provider = (row.selected() as usize).checked_sub(1).and_then(|i| providers.get(i).copied());
+            for row in &detail_rows {
+                row.set_sensitive(provider.is_some());
+            }
+            store_clone.update(|settings| settings.llm_provider = provider);
+        });
+
+        group.add(&provider_row);
+        group.add(&model_row);
+        group.add(&endpoint_row);
+        group.add(&key_row);
+        group
+    }
+
     /// Create appearance settings page
-    fn create_appearance_page() -> adw::PreferencesPage {
+    fn create_appearance_page(store: &SettingsStore) -> adw::PreferencesPage {
         let page = adw::PreferencesPage::builder()
             .title("Appearance")
             .icon_name("preferences-desktop-theme-symbolic")
@@ -162,14 +668,23 @@ impl SettingsDialog {
             .subtitle("Select light, dark, or follow system")
             .build();
 
-        let model = gtk::StringList::new(&["System Default", "Light", "Dark"]);
+        let themes = ThemePreference::all();
+        let names: Vec<&str> = themes.iter().map(|t| t.display_name()).collect();
+        let model = gtk::StringList::new(&names);
         theme_row.set_model(Some(&model));
-        theme_row.set_selected(0);
 
-        theme_row.connect_selected_notify(|row| {
-            let selected = row.selected();
-            log::info!("Theme changed to: {}", selected);
-            // TODO: Apply theme
+        let current = store.settings.borrow().theme;
+        let selected = themes.iter().position(|t| *t == current).unwrap_or(0);
+        theme_row.set_selected(selected as u32);
+
+        let store_clone = store.clone();
+        theme_row.connect_selected_notify(move |row| {
+            let Some(theme) = themes.get(row.selected() as usize).copied() else {
+                return;
+            };
+            log::info!("Theme changed to: {}", theme.as_str());
+            apply_theme(theme);
+            store_clone.update(|settings| settings.theme = theme);
         });
 
         theme_group.add(&theme_row);
@@ -186,7 +701,7 @@ impl SettingsDialog {
             .build();
 
         let adjustment = gtk::Adjustment::new(
-            170000.0, // value
+            store.settings.borrow().token_threshold as f64, // value
             100000.0, // min
             195000.0, // max
             1000.0,   // step
@@ -195,6 +710,12 @@ impl SettingsDialog {
         );
         token_row.set_adjustment(Some(&adjustment));
 
+        let store_clone = store.clone();
+        token_row.connect_value_notify(move |row| {
+            let threshold = row.value() as i64;
+            store_clone.update(|settings| settings.token_threshold = threshold);
+        });
+
         token_group.add(&token_row);
 
         page.add(&theme_group);
@@ -204,20 +725,15 @@ impl SettingsDialog {
 
     /// Get database location
     fn get_database_location() -> String {
-        if let Some(data_dir) = dirs::data_dir() {
-            let db_path = data_dir.join("claude-context-tracker").join("tracker.db");
-            db_path.to_string_lossy().to_string()
-        } else {
-            "~/.local/share/claude-context-tracker/tracker.db".to_string()
-        }
+        crate::db::configured_db_path().to_string_lossy().to_string()
     }
 
     /// Get default logs directory
     fn get_default_logs_dir() -> String {
         if let Some(home) = home::home_dir() {
-            home.join(".claude").join("logs").to_string_lossy().to_string()
+            home.join(".claude").join("projects").to_string_lossy().to_string()
         } else {
-            "~/.claude/logs".to_string()
+            "~/.claude/projects".to_string()
         }
     }
 
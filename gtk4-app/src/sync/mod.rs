@@ -0,0 +1,5 @@
+pub mod pocketbase;
+pub mod replica;
+
+pub use pocketbase::*;
+pub use replica::*;
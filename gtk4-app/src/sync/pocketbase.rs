@@ -0,0 +1,183 @@
+use crate::models::ReplicaSnapshot;
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+use std::time::Duration;
+
+/// Environment variable holding a PocketBase auth token (superuser or API key)
+pub const POCKETBASE_TOKEN_ENV: &str = "CCD_POCKETBASE_TOKEN";
+
+/// Records fetched per request
+const PAGE_SIZE: usize = 200;
+
+/// Fields PocketBase sends as "" when unset that map to `Option` in our models
+const OPTIONAL_FIELDS: &[&str] = &["repo_path", "description", "session", "session_end"];
+
+/// Date fields that use PocketBase's "YYYY-MM-DD HH:MM:SS.sssZ" format
+const DATE_FIELDS: &[&str] = &["created", "updated", "session_start", "session_end"];
+
+/// Minimal read-only client for the PocketBase REST API
+pub struct PocketBaseClient {
+    base_url: String,
+    token: Option<String>,
+    agent: ureq::Agent,
+}
+
+impl PocketBaseClient {
+    /// Create a client for a server such as "http://localhost:8090"
+    pub fn new(base_url: &str, token: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            token,
+            agent,
+        }
+    }
+
+    /// Create a client, reading the auth token from `CCD_POCKETBASE_TOKEN`
+    pub fn from_env(base_url: &str) -> Self {
+        let token = std::env::var(POCKETBASE_TOKEN_ENV).ok().filter(|t| !t.is_empty());
+        Self::new(base_url, token)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Fetch every record of a collection, following pagination
+    pub fn list_records(&self, collection: &str) -> Result<Vec<Value>> {
+        let url = format!("{}/api/collections/{}/records", self.base_url, collection);
+        let mut records = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let mut request = self
+                .agent
+                .get(&url)
+                .query("page", &page.to_string())
+                .query("perPage", &PAGE_SIZE.to_string())
+                .query("sort", "created");
+            if let Some(token) = &self.token {
+                request = request.set("Authorization", token);
+            }
+
+            let body: Value = request
+                .call()
+                .with_context(|| format!("Failed to fetch {} from {}", collection, self.base_url))?
+                .into_json()
+                .with_context(|| format!("Invalid response listing {}", collection))?;
+
+            let items = body["items"].as_array().cloned().unwrap_or_default();
+            let total_pages = body["totalPages"].as_u64().unwrap_or(1);
+            records.extend(items);
+
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(records)
+    }
+
+    /// Fetch all replicated collections
+    pub fn fetch_snapshot(&self) -> Result<ReplicaSnapshot> {
+        Ok(ReplicaSnapshot {
+            projects: self.fetch_collection("projects")?,
+            sections: self.fetch_collection("context_sections")?,
+            sessions: self.fetch_collection("session_history")?,
+            facts: self.fetch_collection("extracted_facts")?,
+        })
+    }
+
+    fn fetch_collection<T: DeserializeOwned>(&self, collection: &str) -> Result<Vec<T>> {
+        self.list_records(collection)?
+            .iter()
+            .map(|record| decode_record(record).with_context(|| format!("Invalid record in {}", collection)))
+            .collect()
+    }
+}
+
+/// Convert a PocketBase record into one of our models
+///
+/// Normalizes the differences between PocketBase's JSON and our serde layout:
+/// empty strings for unset optional fields, space-separated timestamps, and
+/// fields we track locally that PocketBase doesn't have.
+pub fn decode_record<T: DeserializeOwned>(record: &Value) -> Result<T> {
+    let mut fields: Map<String, Value> = record.as_object().cloned().unwrap_or_default();
+
+    for key in OPTIONAL_FIELDS {
+        if fields.get(*key).is_some_and(|v| v.as_str() == Some("")) {
+            fields.insert(key.to_string(), Value::Null);
+        }
+    }
+
+    for key in DATE_FIELDS {
+        if let Some(Value::String(date)) = fields.get_mut(*key) {
+            *date = date.replacen(' ', "T", 1);
+        }
+    }
+
+    if fields.get("tech_stack").is_some_and(Value::is_null) {
+        fields.insert("tech_stack".to_string(), Value::Array(Vec::new()));
+    }
+    fields.entry("metadata").or_insert_with(|| Value::Object(Map::new()));
+
+    Ok(serde_json::from_value(Value::Object(fields))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ExtractedFact, FactType, Project};
+    use serde_json::json;
+
+    #[test]
+    fn test_decode_project_record() {
+        let record = json!({
+            "id": "abc123",
+            "collectionId": "pbc_1",
+            "collectionName": "projects",
+            "name": "Tracker",
+            "slug": "tracker",
+            "repo_path": "",
+            "status": "paused",
+            "priority": 2,
+            "tech_stack": null,
+            "description": "",
+            "created": "2024-01-02 10:00:00.123Z",
+            "updated": "2024-01-03 11:30:00.000Z"
+        });
+
+        let project: Project = decode_record(&record).unwrap();
+
+        assert_eq!(project.id, "abc123");
+        assert_eq!(project.repo_path, None);
+        assert!(project.tech_stack.is_empty());
+        assert_eq!(project.updated.to_rfc3339(), "2024-01-03T11:30:00+00:00");
+    }
+
+    #[test]
+    fn test_decode_fact_record() {
+        let record = json!({
+            "id": "f1",
+            "project": "abc123",
+            "session": "",
+            "fact_type": "decision",
+            "content": "Use SQLite",
+            "importance": 4,
+            "stale": false,
+            "created": "2024-01-02 10:00:00.000Z",
+            "updated": "2024-01-02 10:00:00.000Z"
+        });
+
+        let fact: ExtractedFact = decode_record(&record).unwrap();
+
+        assert_eq!(fact.session, None);
+        assert_eq!(fact.fact_type, FactType::Decision);
+        assert_eq!(fact.metadata.language, None);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::db::Repository;
+use crate::models::{ReplicaStats, SETTING_REPLICA_LAST_SYNCED};
+use crate::sync::PocketBaseClient;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+/// Pull one snapshot from PocketBase and mirror it into the local database
+pub fn replicate_once(repository: &Repository, client: &PocketBaseClient) -> Result<ReplicaStats> {
+    log::info!("Replicating from {}", client.base_url());
+
+    let snapshot = client.fetch_snapshot()?;
+    let stats = repository.apply_replica(&snapshot)?;
+    repository.set_setting(SETTING_REPLICA_LAST_SYNCED, &Utc::now().to_rfc3339())?;
+
+    log::info!("Replicated {}", stats.summary());
+    Ok(stats)
+}
+
+/// When the local replica was last refreshed, if ever
+pub fn last_replicated(repository: &Repository) -> Option<DateTime<Utc>> {
+    let value = repository.get_setting(SETTING_REPLICA_LAST_SYNCED).ok()??;
+    DateTime::parse_from_rfc3339(&value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok()
+}
+
+/// Replicate on a fixed interval until the process exits (blocking)
+pub fn run_replication(repository: &Repository, client: &PocketBaseClient, interval: Duration) {
+    loop {
+        if let Err(e) = replicate_once(repository, client) {
+            log::error!("Replication failed: {:#}", e);
+        }
+        std::thread::sleep(interval);
+    }
+}
+
+/// Background replication thread
+pub fn start_replication(
+    repository: Repository,
+    base_url: String,
+    interval: Duration,
+) -> Result<std::thread::JoinHandle<()>> {
+    let handle = std::thread::spawn(move || {
+        log::info!("Background replication thread started");
+
+        let client = PocketBaseClient::from_env(&base_url);
+        run_replication(&repository, &client, interval);
+    });
+
+    Ok(handle)
+}
@@ -1,3 +0,0 @@
-pub mod markdown;
-
-pub use markdown::*;
@@ -1,13 +1,31 @@
+use crate::async_repository::AsyncRepository;
 use crate::db::Repository;
-use crate::models::{ContextSection, SectionType};
-use crate::utils::generate_claude_md;
+use crate::models::{
+    format_number_with_separator, ContextSection, ContextSectionPayload, SectionExport, SectionType, TrashKind,
+    DEFAULT_TOKEN_THRESHOLD,
+};
+use crate::monitor::{context_file_path, export_context_file, render_context_file, CONTEXT_FILE_NAME};
+use crate::notifications::notify_context_pulled;
+use crate::utils::{append_snapshot, estimate_tokens, read_snapshot, ContextDiff, DiffLineKind};
+use crate::views::SectionEditor;
 use adw::prelude::*;
-use std::cell::RefCell;
+use anyhow::{Context, Result};
+use gtk::gio;
+use std::cell::{Cell, RefCell};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
+/// Seconds a deleted section can be brought back before it is purged
+const SECTION_UNDO_SECONDS: u32 = 5;
+
 /// Context editor view for managing project context sections
+#[derive(Clone)]
 pub struct ContextEditorView {
     container: gtk::Box,
+    toast_overlay: adw::ToastOverlay,
+    total_label: gtk::Label,
+    pending_box: gtk::Box,
+    pending_list: gtk::ListBox,
     sections_list: gtk::ListBox,
     repository: Repository,
     project_id: String,
@@ -32,6 +50,12 @@ impl ContextEditorView {
         title.set_hexpand(true);
         toolbar.append(&title);
 
+        // Estimated size of the generated CLAUDE.md
+        let total_label = gtk::Label::new(None);
+        total_label.add_css_class("dim-label");
+        total_label.add_css_class("numeric");
+        toolbar.append(&total_label);
+
         // Export button
         let export_btn = gtk::Button::builder()
             .icon_name("document-save-symbolic")
@@ -40,6 +64,14 @@ impl ContextEditorView {
         export_btn.add_css_class("flat");
         toolbar.append(&export_btn);
 
+        let export_repository = repository.clone();
+        let export_project_id = project_id.clone();
+        export_btn.connect_clicked(move |button| {
+            if let Err(e) = Self::export_with_dialog(button, &export_repository, &export_project_id) {
+                log::error!("Failed to export CLAUDE.md: {}", e);
+            }
+        });
+
         // Copy button
         let copy_btn = gtk::Button::builder()
             .icon_name("edit-copy-symbolic")
@@ -48,6 +80,21 @@ impl ContextEditorView {
         copy_btn.add_css_class("flat");
         toolbar.append(&copy_btn);
 
+        // Toasts confirm copies and deletions over the sections
+        let toast_overlay = adw::ToastOverlay::new();
+        let copy_repository = repository.clone();
+        let copy_project_id = project_id.clone();
+        let copy_toasts = toast_overlay.clone();
+        copy_btn.connect_clicked(move |button| {
+            match Self::copy_to_clipboard(button, &copy_repository, &copy_project_id) {
+                Ok(toast) => copy_toasts.add_toast(adw::Toast::new(&toast)),
+                Err(e) => {
+                    log::error!("Failed to copy CLAUDE.md: {}", e);
+                    copy_toasts.add_toast(adw::Toast::new(&format!("Failed to copy: {}", e)));
+                }
+            }
+        });
+
         // Add section button
         let add_btn = gtk::Button::builder()
             .icon_name("list-add-symbolic")
@@ -56,8 +103,6 @@ impl ContextEditorView {
         add_btn.add_css_class("flat");
         toolbar.append(&add_btn);
 
-        container.append(&toolbar);
-
         // Create scrolled window for sections
         let scrolled = gtk::ScrolledWindow::builder()
             .hscrollbar_policy(gtk::PolicyType::Never)
@@ -72,11 +117,47 @@ impl ContextEditorView {
         sections_list.set_margin_start(12);
         sections_list.set_margin_end(12);
 
-        scrolled.set_child(Some(&sections_list));
-        container.append(&scrolled);
+        // Auto-extracted sections wait above the others until they are reviewed
+        let pending_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        pending_box.set_margin_top(12);
+        pending_box.set_margin_start(12);
+        pending_box.set_margin_end(12);
+        pending_box.set_visible(false);
+
+        let pending_title = gtk::Label::new(Some("Pending review"));
+        pending_title.add_css_class("heading");
+        pending_title.set_halign(gtk::Align::Start);
+        pending_box.append(&pending_title);
+
+        let pending_description = gtk::Label::new(Some("Auto-extracted sections stay out of CLAUDE.md until accepted."));
+        pending_description.add_css_class("dim-label");
+        pending_description.set_halign(gtk::Align::Start);
+        pending_description.set_wrap(true);
+        pending_box.append(&pending_description);
+
+        let pending_list = gtk::ListBox::new();
+        pending_list.set_selection_mode(gtk::SelectionMode::None);
+        pending_list.add_css_class("boxed-list");
+        pending_box.append(&pending_list);
+
+        let lists = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        lists.append(&pending_box);
+        lists.append(&sections_list);
+        scrolled.set_child(Some(&lists));
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&toolbar);
+        content.append(&scrolled);
+        toast_overlay.set_child(Some(&content));
+        toast_overlay.set_vexpand(true);
+        container.append(&toast_overlay);
 
         let mut view = Self {
             container,
+            toast_overlay,
+            total_label,
+            pending_box,
+            pending_list,
             sections_list,
             repository,
             project_id,
@@ -84,25 +165,247 @@ impl ContextEditorView {
         };
 
         view.load_sections();
+        view.connect_section_editing();
+
+        let view_for_add = view.clone();
+        add_btn.connect_clicked(move |button| view_for_add.show_add_dialog(button));
 
         view
     }
 
-    /// Load context sections
-    fn load_sections(&self) {
-        match self.repository.list_context_sections(&self.project_id) {
-            Ok(loaded_sections) => {
-                *self.sections.borrow_mut() = loaded_sections.clone();
-                Self::update_sections_list(&self.sections_list, &loaded_sections);
+    /// Open the section editor for the row clicked
+    fn connect_section_editing(&self) {
+        let view = self.clone();
+        self.sections_list.connect_row_activated(move |_, row| {
+            let Some(section_id) = usize::try_from(row.index())
+                .ok()
+                .and_then(|index| view.sections.borrow().get(index).map(|section| section.id.clone()))
+            else {
+                return;
+            };
+            // The list may be older than the section, after a restore from its history
+            match view.repository.get_context_section(&section_id) {
+                Ok(section) => view.open_editor(section),
+                Err(e) => log::error!("Failed to load section {}: {}", section_id, e),
+            }
+        });
+    }
+
+    /// Edit `section`, reloading the list once it saves
+    fn open_editor(&self, section: ContextSection) {
+        let Some(parent) = self.sections_list.root().and_downcast::<gtk::Window>() else {
+            return;
+        };
+        let editor = SectionEditor::new(&parent, self.repository.clone(), section);
+        let view = self.clone();
+        editor.connect_saved(move |_| view.sections_saved());
+        editor.present();
+    }
+
+    /// Offer the section type presets and saved templates, then add the section last and open it in the editor
+    fn show_add_dialog(&self, button: &gtk::Button) {
+        let templates = Rc::new(self.repository.list_section_templates().unwrap_or_else(|e| {
+            log::error!("Failed to load section templates: {}", e);
+            Vec::new()
+        }));
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(
+            parent.as_ref(),
+            Some("Add Section"),
+            Some(if templates.is_empty() {
+                "New sections start with their type's suggested title and outline."
+            } else {
+                "New sections start with their type's suggested title and outline, or a saved template's."
+            }),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("add", "Add")]);
+        dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("add"));
+        dialog.set_close_response("cancel");
+
+        let section_types = SectionType::all();
+        let type_names: Vec<&str> = section_types.iter().map(|section_type| section_type.display_name()).collect();
+        let type_row = adw::ComboRow::builder()
+            .title("Type")
+            .model(&gtk::StringList::new(&type_names))
+            .build();
+        let title_row = adw::EntryRow::builder()
+            .title("Title")
+            .text(section_types[0].default_title())
+            .build();
+
+        // Templates are listed after "None", so their index is one less than the row's
+        let mut template_names = vec!["None"];
+        template_names.extend(templates.iter().map(|template| template.name.as_str()));
+        let template_row = adw::ComboRow::builder()
+            .title("Template")
+            .model(&gtk::StringList::new(&template_names))
+            .visible(!templates.is_empty())
+            .build();
+
+        let rows = gtk::ListBox::new();
+        rows.set_selection_mode(gtk::SelectionMode::None);
+        rows.add_css_class("boxed-list");
+        rows.append(&template_row);
+        rows.append(&type_row);
+        rows.append(&title_row);
+        dialog.set_extra_child(Some(&rows));
+
+        // The title follows the type and template until it is edited
+        let suggested = {
+            let templates = templates.clone();
+            move |title: &str| {
+                title.trim().is_empty()
+                    || SectionType::all().iter().any(|section_type| section_type.default_title() == title)
+                    || templates.iter().any(|template| template.title == title)
+            }
+        };
+        let title_for_type = title_row.clone();
+        let suggested_for_type = suggested.clone();
+        type_row.connect_selected_notify(move |row| {
+            if !suggested_for_type(&title_for_type.text()) {
+                return;
+            }
+            if let Some(section_type) = SectionType::all().get(row.selected() as usize) {
+                title_for_type.set_text(section_type.default_title());
+            }
+        });
+
+        let title_for_template = title_row.clone();
+        let type_for_template = type_row.clone();
+        let templates_for_row = templates.clone();
+        template_row.connect_selected_notify(move |row| {
+            let Some(template) = (row.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| templates_for_row.get(index))
+            else {
+                return;
+            };
+            let suggested = suggested(&title_for_template.text());
+            if let Some(index) = SectionType::all().iter().position(|section_type| *section_type == template.section_type) {
+                type_for_template.set_selected(index as u32);
+            }
+            if suggested {
+                title_for_template.set_text(&template.title);
+            }
+        });
+
+        // A section needs a title
+        let dialog_weak = dialog.downgrade();
+        title_row.connect_changed(move |row| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.set_response_enabled("add", !row.text().trim().is_empty());
+            }
+        });
+
+        let view = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "add" {
+                return;
+            }
+            let section_type = SectionType::all().get(type_row.selected() as usize).copied().unwrap_or_default();
+            let content = (template_row.selected() as usize)
+                .checked_sub(1)
+                .and_then(|index| templates.get(index))
+                .map_or(section_type.template(), |template| template.content.as_str());
+            match view.create_section(section_type, title_row.text().trim(), content) {
+                Ok(section) => {
+                    view.sections_saved();
+                    view.open_editor(section);
+                }
+                Err(e) => log::error!("Failed to add section: {}", e),
             }
+        });
+
+        dialog.present();
+    }
+
+    /// Create a section after all the others
+    fn create_section(&self, section_type: SectionType, title: &str, content: &str) -> Result<ContextSection> {
+        let order = self
+            .repository
+            .list_context_sections(&self.project_id)?
+            .iter()
+            .map(|section| section.order + 1)
+            .max()
+            .unwrap_or(0);
+        self.repository.create_context_section(ContextSectionPayload {
+            project: self.project_id.clone(),
+            section_type,
+            title: title.to_string(),
+            content: content.to_string(),
+            order,
+            auto_extracted: Some(false),
+        })
+    }
+
+    /// Show sections changed here or elsewhere, and export them if the project asks for it
+    pub fn sections_saved(&self) {
+        self.reload_sections();
+        self.export_on_save();
+    }
+
+    /// Rewrite the repository's CLAUDE.md if the project exports on every save
+    fn export_on_save(&self) {
+        match self.repository.exports_on_save(&self.project_id) {
+            Ok(true) => {}
+            Ok(false) => return,
             Err(e) => {
-                log::error!("Failed to load context sections: {}", e);
+                log::error!("Failed to load export setting: {}", e);
+                return;
             }
         }
+        let exported = self
+            .repository
+            .get_project(&self.project_id)
+            .and_then(|project| export_context_file(&self.repository, &project));
+        match exported {
+            Ok(Some(path)) => log::info!("Exported context to {}", path.display()),
+            Ok(None) => {}
+            Err(e) => log::error!("Failed to export CLAUDE.md: {}", e),
+        }
     }
 
-    /// Update the sections list
-    fn update_sections_list(sections_list: &gtk::ListBox, sections: &[ContextSection]) {
+    /// Reload context sections right away
+    fn reload_sections(&self) {
+        match self.repository.list_context_sections(&self.project_id) {
+            Ok(loaded) => self.update_sections_list(loaded),
+            Err(e) => log::error!("Failed to reload context sections: {}", e),
+        }
+    }
+
+    /// Load context sections
+    fn load_sections(&self) {
+        let view = self.clone();
+
+        glib::spawn_future_local(async move {
+            match AsyncRepository::from(view.repository.clone())
+                .list_context_sections(&view.project_id)
+                .await
+            {
+                Ok(loaded_sections) => view.update_sections_list(loaded_sections),
+                Err(e) => {
+                    log::error!("Failed to load context sections: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Update the sections list, and the sections pending review above it
+    fn update_sections_list(&self, sections: Vec<ContextSection>) {
+        let (pending, sections): (Vec<ContextSection>, Vec<ContextSection>) =
+            sections.into_iter().partition(|section| section.auto_extracted);
+
+        while let Some(row) = self.pending_list.first_child() {
+            self.pending_list.remove(&row);
+        }
+        for section in &pending {
+            self.pending_list.append(&self.create_pending_row(section));
+        }
+        self.pending_box.set_visible(!pending.is_empty());
+
+        let sections_list = &self.sections_list;
         // Clear existing rows
         while let Some(row) = sections_list.first_child() {
             sections_list.remove(&row);
@@ -117,17 +420,104 @@ impl ContextEditorView {
             row.set_child(Some(&empty_label));
             row.set_activatable(false);
             sections_list.append(&row);
-            return;
+        } else {
+            for section in &sections {
+                sections_list.append(&self.create_section_row(section));
+            }
         }
 
-        for section in sections {
-            let row = Self::create_section_row(section);
-            sections_list.append(&row);
+        *self.sections.borrow_mut() = sections;
+        self.update_total();
+    }
+
+    /// Row for an auto-extracted section, to accept, edit or discard
+    fn create_pending_row(&self, section: &ContextSection) -> adw::ActionRow {
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&section.title).as_str())
+            .subtitle(glib::markup_escape_text(&section.content_preview()).as_str())
+            .subtitle_lines(3)
+            .build();
+        row.add_prefix(&gtk::Image::from_icon_name(section.section_type.icon_name()));
+
+        let accept_btn = gtk::Button::builder()
+            .icon_name("object-select-symbolic")
+            .tooltip_text("Accept")
+            .valign(gtk::Align::Center)
+            .build();
+        let edit_btn = gtk::Button::builder()
+            .icon_name("document-edit-symbolic")
+            .tooltip_text("Edit")
+            .valign(gtk::Align::Center)
+            .build();
+        let discard_btn = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Discard")
+            .valign(gtk::Align::Center)
+            .build();
+        for button in [&accept_btn, &edit_btn, &discard_btn] {
+            button.add_css_class("flat");
+            row.add_suffix(button);
+        }
+
+        let view = self.clone();
+        let section_id = section.id.clone();
+        accept_btn.connect_clicked(move |_| match view.repository.accept_section(&section_id) {
+            Ok(_) => view.sections_saved(),
+            Err(e) => log::error!("Failed to accept section: {}", e),
+        });
+
+        // Edits keep the section pending, so it is still accepted on its own
+        let view = self.clone();
+        let section_id = section.id.clone();
+        edit_btn.connect_clicked(move |_| match view.repository.get_context_section(&section_id) {
+            Ok(section) => view.open_editor(section),
+            Err(e) => log::error!("Failed to load section {}: {}", section_id, e),
+        });
+
+        let view = self.clone();
+        let discarded = section.clone();
+        discard_btn.connect_clicked(move |_| view.delete_section(&discarded));
+
+        row
+    }
+
+    /// Show the estimated tokens of CLAUDE.md as exported, in red above the configured threshold
+    fn update_total(&self) {
+        let tokens = match self
+            .repository
+            .get_project(&self.project_id)
+            .and_then(|project| render_context_file(&self.repository, &project))
+        {
+            Ok(markdown) => estimate_tokens(&markdown) as i64,
+            Err(e) => {
+                log::error!("Failed to estimate CLAUDE.md size: {}", e);
+                self.total_label.set_text("");
+                return;
+            }
+        };
+        let threshold = self
+            .repository
+            .load_settings()
+            .map(|settings| settings.token_threshold)
+            .unwrap_or(DEFAULT_TOKEN_THRESHOLD);
+
+        self.total_label.set_text(&format!("~{} tokens", format_number_with_separator(tokens)));
+        if tokens > threshold {
+            self.total_label.remove_css_class("dim-label");
+            self.total_label.add_css_class("error");
+            self.total_label.set_tooltip_text(Some(&format!(
+                "CLAUDE.md is above the {} token threshold set in Preferences",
+                format_number_with_separator(threshold)
+            )));
+        } else {
+            self.total_label.remove_css_class("error");
+            self.total_label.add_css_class("dim-label");
+            self.total_label.set_tooltip_text(Some("Estimated size of the exported CLAUDE.md"));
         }
     }
 
     /// Create a section row
-    fn create_section_row(section: &ContextSection) -> gtk::ListBoxRow {
+    fn create_section_row(&self, section: &ContextSection) -> gtk::ListBoxRow {
         let row_box = gtk::Box::new(gtk::Orientation::Vertical, 8);
         row_box.set_margin_top(8);
         row_box.set_margin_bottom(8);
@@ -150,6 +540,95 @@ impl ContextEditorView {
         type_label.add_css_class("dim-label");
         header.append(&type_label);
 
+        let tokens_label = gtk::Label::new(Some(&format!(
+            "~{} tokens",
+            format_number_with_separator(estimate_tokens(&section.content) as i64)
+        )));
+        tokens_label.add_css_class("dim-label");
+        tokens_label.add_css_class("caption");
+        tokens_label.add_css_class("numeric");
+        header.append(&tokens_label);
+
+        // Lock against automated changes
+        let lock_btn = gtk::ToggleButton::builder()
+            .icon_name(Self::lock_icon(section.locked))
+            .active(section.locked)
+            .tooltip_text("Lock against automatic changes")
+            .build();
+        lock_btn.add_css_class("flat");
+        header.append(&lock_btn);
+
+        let lock_repository = self.repository.clone();
+        let section_id = section.id.clone();
+        lock_btn.connect_toggled(move |button| {
+            match lock_repository.set_section_locked(&section_id, button.is_active()) {
+                Ok(section) => button.set_icon_name(Self::lock_icon(section.locked)),
+                Err(e) => log::error!("Failed to lock section: {}", e),
+            }
+        });
+
+        // Whether the section goes into exports
+        let export_btn = gtk::MenuButton::builder()
+            .icon_name(section.export.icon_name())
+            .tooltip_text(section.export.display_name())
+            .build();
+        export_btn.add_css_class("flat");
+        header.append(&export_btn);
+
+        let export_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        let mut group: Option<gtk::CheckButton> = None;
+        for export in SectionExport::all() {
+            let check = gtk::CheckButton::builder()
+                .label(export.display_name())
+                .active(export == section.export)
+                .build();
+            check.set_group(group.as_ref());
+            group.get_or_insert_with(|| check.clone());
+            export_box.append(&check);
+
+            let view = self.clone();
+            let section_id = section.id.clone();
+            let export_btn = export_btn.clone();
+            check.connect_toggled(move |check| {
+                if !check.is_active() {
+                    return;
+                }
+                export_btn.popdown();
+                match view.repository.set_section_export(&section_id, export) {
+                    Ok(_) => view.sections_saved(),
+                    Err(e) => log::error!("Failed to change section export: {}", e),
+                }
+            });
+        }
+        export_btn.set_popover(Some(&gtk::Popover::builder().child(&export_box).build()));
+
+        // History button
+        let history_btn = gtk::Button::builder()
+            .icon_name("document-open-recent-symbolic")
+            .tooltip_text("History")
+            .build();
+        history_btn.add_css_class("flat");
+        header.append(&history_btn);
+
+        let view = self.clone();
+        let history_section = section.clone();
+        history_btn.connect_clicked(move |button| {
+            let parent = button.root().and_downcast::<gtk::Window>();
+            view.show_history_dialog(parent.as_ref(), &history_section);
+        });
+
+        // Delete button
+        let delete_btn = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Delete")
+            .build();
+        delete_btn.add_css_class("flat");
+        header.append(&delete_btn);
+
+        let view = self.clone();
+        let deleted_section = section.clone();
+        delete_btn.connect_clicked(move |_| view.delete_section(&deleted_section));
+
         row_box.append(&header);
 
         // Content preview
@@ -166,6 +645,276 @@ impl ContextEditorView {
         row
     }
 
+    fn lock_icon(locked: bool) -> &'static str {
+        if locked {
+            "changes-prevent-symbolic"
+        } else {
+            "changes-allow-symbolic"
+        }
+    }
+
+    /// Move a section to the trash, for good once its Undo toast is gone
+    fn delete_section(&self, section: &ContextSection) {
+        if let Err(e) = self.repository.delete_context_section(&section.id) {
+            log::error!("Failed to delete section {}: {}", section.title, e);
+            self.toast_overlay.add_toast(adw::Toast::new(&format!("Failed to delete: {}", e)));
+            return;
+        }
+        self.sections_saved();
+
+        let toast = adw::Toast::builder()
+            .title(glib::markup_escape_text(&format!("'{}' deleted", section.title)).as_str())
+            .button_label("Undo")
+            .timeout(SECTION_UNDO_SECONDS)
+            .build();
+
+        let undone = Rc::new(Cell::new(false));
+        let view = self.clone();
+        let section_id = section.id.clone();
+        let undone_for_button = undone.clone();
+        toast.connect_button_clicked(move |_| {
+            undone_for_button.set(true);
+            match view.repository.restore(TrashKind::Section, &section_id) {
+                Ok(()) => view.sections_saved(),
+                Err(e) => log::error!("Failed to restore section: {}", e),
+            }
+        });
+
+        let repository = self.repository.clone();
+        let section_id = section.id.clone();
+        toast.connect_dismissed(move |_| {
+            if undone.get() {
+                return;
+            }
+            if let Err(e) = repository.purge(TrashKind::Section, &section_id) {
+                log::error!("Failed to purge section: {}", e);
+            }
+        });
+
+        self.toast_overlay.add_toast(toast);
+    }
+
+    /// List earlier versions of a section and restore the one picked
+    fn show_history_dialog(&self, parent: Option<&gtk::Window>, section: &ContextSection) {
+        let repository = &self.repository;
+        let revisions = repository.list_section_revisions(&section.id).unwrap_or_else(|e| {
+            log::error!("Failed to load section history: {}", e);
+            Vec::new()
+        });
+
+        let dialog = adw::MessageDialog::new(
+            parent,
+            Some(&format!("History of {}", section.title)),
+            Some(if revisions.is_empty() {
+                "This section has not been edited yet."
+            } else {
+                "Restoring a version keeps the current content in the history."
+            }),
+        );
+        dialog.add_responses(&[("close", "Close")]);
+        dialog.set_close_response("close");
+
+        if !revisions.is_empty() {
+            let list = gtk::ListBox::new();
+            list.set_selection_mode(gtk::SelectionMode::None);
+            list.add_css_class("boxed-list");
+
+            for revision in revisions {
+                let row = adw::ActionRow::builder()
+                    .title(revision.created.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                    .subtitle(glib::markup_escape_text(&revision.content_preview()).as_str())
+                    .build();
+
+                let restore_btn = gtk::Button::builder()
+                    .label("Restore")
+                    .valign(gtk::Align::Center)
+                    .build();
+                row.add_suffix(&restore_btn);
+
+                let view = self.clone();
+                let dialog = dialog.clone();
+                let section_id = section.id.clone();
+                restore_btn.connect_clicked(move |_| {
+                    if let Err(e) = view.repository.revert_section(&section_id, &revision.id) {
+                        log::error!("Failed to restore section: {}", e);
+                        return;
+                    }
+                    view.sections_saved();
+                    dialog.close();
+                });
+
+                list.append(&row);
+            }
+
+            let scrolled = gtk::ScrolledWindow::builder()
+                .child(&list)
+                .min_content_height(240)
+                .min_content_width(420)
+                .build();
+            dialog.set_extra_child(Some(&scrolled));
+        }
+
+        dialog.present();
+    }
+
+    /// Ask where to export CLAUDE.md, the project's repository by default, and write it there
+    fn export_with_dialog(button: &gtk::Button, repository: &Repository, project_id: &str) -> Result<()> {
+        let project = repository.get_project(project_id)?;
+        let markdown = render_context_file(repository, &project)?;
+
+        let dialog = gtk::FileDialog::builder()
+            .title("Export Context")
+            .accept_label("Export")
+            .initial_name(CONTEXT_FILE_NAME)
+            .modal(true)
+            .build();
+        if let Some(path) = context_file_path(&project) {
+            if path.exists() {
+                dialog.set_initial_file(Some(&gio::File::for_path(&path)));
+            } else if let Some(folder) = path.parent().filter(|folder| folder.is_dir()) {
+                dialog.set_initial_folder(Some(&gio::File::for_path(folder)));
+            }
+        }
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let diff_parent = parent.clone();
+        dialog.save(parent.as_ref(), None::<&gio::Cancellable>, move |result| {
+            let file = match result {
+                Ok(file) => file,
+                Err(e) => {
+                    if !e.matches(gtk::DialogError::Dismissed) {
+                        log::error!("Failed to choose where to export: {}", e);
+                    }
+                    return;
+                }
+            };
+            let Some(path) = file.path() else {
+                log::error!("Can only export CLAUDE.md to a local file");
+                return;
+            };
+            if let Err(e) = Self::export_with_preview(diff_parent.as_ref(), path, &project.name, &project.id, &markdown) {
+                log::error!("Failed to export CLAUDE.md: {}", e);
+            }
+        });
+        Ok(())
+    }
+
+    /// Write `markdown` to `path`, previewing changes to an existing file first
+    ///
+    /// The file gets a snapshot footer, so exporting on save can tell later hand edits apart.
+    fn export_with_preview(
+        parent: Option<&gtk::Window>,
+        path: PathBuf,
+        project_name: &str,
+        project_id: &str,
+        markdown: &str,
+    ) -> Result<()> {
+        let exported = append_snapshot(markdown, vec![project_id.to_string()]);
+
+        let current = match std::fs::read_to_string(&path) {
+            Ok(current) => current,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Self::write_export(&path, &exported, project_name)?;
+                return Ok(());
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        };
+
+        // The footer of an earlier export is not a change
+        let content = read_snapshot(&current).map_or(current.as_str(), |(content, _)| content);
+        let name = path.file_name().map_or_else(|| CONTEXT_FILE_NAME.into(), |name| name.to_string_lossy());
+        let diff = ContextDiff::new(content, markdown, &name);
+        if !diff.has_changes() {
+            log::info!("{} is already up to date", path.display());
+            return Ok(());
+        }
+
+        Self::show_diff_dialog(parent, &diff, path, exported, project_name.to_string());
+        Ok(())
+    }
+
+    /// Write an export and let the user know where it went
+    fn write_export(path: &Path, markdown: &str, project_name: &str) -> Result<()> {
+        std::fs::write(path, markdown).with_context(|| format!("Failed to write {}", path.display()))?;
+        log::info!("Exported context to {}", path.display());
+        notify_context_pulled(project_name, Some(&path.to_path_buf()));
+        Ok(())
+    }
+
+    /// Copy CLAUDE.md as exported to the clipboard, returning the toast saying how much was copied
+    fn copy_to_clipboard(button: &gtk::Button, repository: &Repository, project_id: &str) -> Result<String> {
+        let project = repository.get_project(project_id)?;
+        let markdown = render_context_file(repository, &project)?;
+        button.clipboard().set_text(&markdown);
+
+        Ok(format!(
+            "Copied {} characters (~{} tokens)",
+            format_number_with_separator(markdown.chars().count() as i64),
+            format_number_with_separator(estimate_tokens(&markdown) as i64)
+        ))
+    }
+
+    /// Show the pending CLAUDE.md changes and write them if confirmed
+    fn show_diff_dialog(
+        parent: Option<&gtk::Window>,
+        diff: &ContextDiff,
+        path: PathBuf,
+        markdown: String,
+        project_name: String,
+    ) {
+        let dialog = adw::MessageDialog::new(
+            parent,
+            Some("Overwrite CLAUDE.md?"),
+            Some(&format!("{}\n{}", path.display(), diff.summary())),
+        );
+
+        let buffer = gtk::TextBuffer::new(None);
+        for (name, color) in [("added", "#2ec27e"), ("removed", "#e01b24"), ("hunk", "#3584e4")] {
+            buffer.create_tag(Some(name), &[("foreground", &color)]);
+        }
+        for (kind, line) in diff.lines() {
+            let text = format!("{}\n", line);
+            let tag = match kind {
+                DiffLineKind::Added => Some("added"),
+                DiffLineKind::Removed => Some("removed"),
+                DiffLineKind::Hunk => Some("hunk"),
+                DiffLineKind::Header | DiffLineKind::Context => None,
+            };
+            match tag {
+                Some(tag) => buffer.insert_with_tags_by_name(&mut buffer.end_iter(), &text, &[tag]),
+                None => buffer.insert(&mut buffer.end_iter(), &text),
+            }
+        }
+
+        let text_view = gtk::TextView::builder()
+            .buffer(&buffer)
+            .editable(false)
+            .monospace(true)
+            .build();
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&text_view)
+            .min_content_height(320)
+            .min_content_width(560)
+            .build();
+        dialog.set_extra_child(Some(&scrolled));
+
+        dialog.add_responses(&[("cancel", "Cancel"), ("write", "Write")]);
+        dialog.set_response_appearance("write", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        dialog.connect_response(None, move |_, response| {
+            if response != "write" {
+                return;
+            }
+            if let Err(e) = Self::write_export(&path, &markdown, &project_name) {
+                log::error!("{}", e);
+            }
+        });
+
+        dialog.present();
+    }
+
     /// Get the widget
     pub fn widget(&self) -> gtk::Box {
         self.container.clone()
@@ -1,24 +1,101 @@
-use crate::db::Repository;
-use crate::models::{Project, ProjectPayload, ProjectStatus};
+use crate::async_repository::AsyncRepository;
+use crate::db::{Repository, CLONE_TOP_FACTS};
+use crate::models::{
+    AppSettings, DashboardLayout, DependencyContext, PendingChanges, Project, ProjectPayload, ProjectSort, ProjectStats,
+    ProjectStatus, SETTING_DASHBOARD_LAYOUT, SETTING_DASHBOARD_SORT, TOKEN_TREND_DAYS,
+};
+use crate::sync::last_replicated;
 use adw::prelude::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
-/// Dashboard view showing list of projects
+/// Everything the project list shows, loaded together off the main loop
+struct DashboardData {
+    projects: Vec<Project>,
+    stats: HashMap<String, ProjectStats>,
+    pending: HashMap<String, PendingChanges>,
+    local_only: HashSet<String>,
+    dependencies: HashMap<String, Vec<DependencyContext>>,
+}
+
+impl DashboardData {
+    fn load(repository: &Repository, filter: Option<ProjectStatus>, sort: ProjectSort) -> anyhow::Result<Self> {
+        let projects = repository.list_projects_sorted(filter, sort)?;
+
+        let stats = repository.all_project_stats().unwrap_or_else(|e| {
+            log::warn!("Failed to load project statistics: {}", e);
+            HashMap::new()
+        });
+
+        let local_only = repository.local_only_projects().unwrap_or_default();
+
+        // Sync badges only mean something once the database has been synced
+        let pending = if last_replicated(repository).is_some() {
+            repository
+                .pending_changes()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| (p.project.clone(), p))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        let dependencies = projects
+            .iter()
+            .map(|project| {
+                let dependencies = repository.load_dependency_context(&project.id).unwrap_or_else(|e| {
+                    log::warn!("Failed to load dependencies for {}: {}", project.name, e);
+                    Vec::new()
+                });
+                (project.id.clone(), dependencies)
+            })
+            .collect();
+
+        Ok(Self {
+            projects,
+            stats,
+            pending,
+            local_only,
+            dependencies,
+        })
+    }
+}
+
+/// Status columns of the board layout, left to right
+const BOARD_COLUMNS: [ProjectStatus; 4] = [
+    ProjectStatus::Idea,
+    ProjectStatus::Active,
+    ProjectStatus::Paused,
+    ProjectStatus::Archived,
+];
+
+/// Dashboard view showing projects as a list, a grid of cards or a board by status
 pub struct DashboardView {
     container: gtk::Box,
+    layout_stack: gtk::Stack,
     project_list: gtk::ListBox,
+    card_grid: gtk::FlowBox,
+    board: gtk::Box,
     repository: Repository,
-    navigation_view: adw::NavigationView,
     projects: Rc<RefCell<Vec<Project>>>,
     current_filter: Rc<RefCell<Option<ProjectStatus>>>,
+    current_sort: Rc<Cell<ProjectSort>>,
+    load_generation: Rc<Cell<u64>>,
+    on_project_activated: Rc<RefCell<Option<Box<dyn Fn(String)>>>>,
 }
 
 impl DashboardView {
     /// Create a new dashboard view
-    pub fn new(repository: Repository, navigation_view: adw::NavigationView) -> Self {
+    pub fn new(repository: Repository) -> Self {
         let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
 
+        let settings = repository.load_settings().unwrap_or_else(|e| {
+            log::warn!("Failed to load settings, using defaults: {}", e);
+            AppSettings::default()
+        });
+
         // Create toolbar for filtering
         let toolbar = Self::create_toolbar();
         container.append(&toolbar);
@@ -41,23 +118,259 @@ impl DashboardView {
         project_list.set_margin_end(12);
 
         scrolled.set_child(Some(&project_list));
-        container.append(&scrolled);
+
+        // Create card grid
+        let card_grid = gtk::FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .activate_on_single_click(true)
+            .homogeneous(true)
+            .min_children_per_line(1)
+            .max_children_per_line(4)
+            .valign(gtk::Align::Start)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+
+        let grid_scrolled = gtk::ScrolledWindow::builder()
+            .child(&card_grid)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .vscrollbar_policy(gtk::PolicyType::Automatic)
+            .vexpand(true)
+            .build();
+        grid_scrolled.add_css_class("scrolled-content");
+
+        // Create board, one column per status
+        let board = gtk::Box::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .spacing(12)
+            .homogeneous(true)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+
+        let board_scrolled = gtk::ScrolledWindow::builder()
+            .child(&board)
+            .hscrollbar_policy(gtk::PolicyType::Automatic)
+            .vscrollbar_policy(gtk::PolicyType::Automatic)
+            .vexpand(true)
+            .build();
+        board_scrolled.add_css_class("scrolled-content");
+
+        let layout_stack = gtk::Stack::new();
+        layout_stack.add_named(&scrolled, Some(DashboardLayout::List.as_str()));
+        layout_stack.add_named(&grid_scrolled, Some(DashboardLayout::Grid.as_str()));
+        layout_stack.add_named(&board_scrolled, Some(DashboardLayout::Board.as_str()));
+        container.append(&layout_stack);
 
         let mut view = Self {
             container,
+            layout_stack,
             project_list,
+            card_grid,
+            board,
             repository,
-            navigation_view,
             projects: Rc::new(RefCell::new(Vec::new())),
             current_filter: Rc::new(RefCell::new(None)),
+            current_sort: Rc::new(Cell::new(settings.dashboard_sort)),
+            load_generation: Rc::new(Cell::new(0)),
+            on_project_activated: Rc::new(RefCell::new(None)),
         };
 
         // Load projects initially
         view.load_projects();
+        view.install_project_actions();
+
+        let spacer = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        spacer.set_hexpand(true);
+        toolbar.append(&spacer);
+        view.install_sort_menu(&toolbar);
+        view.install_layout_toggle(&toolbar, settings.dashboard_layout);
+
+        let projects = view.projects.clone();
+        let on_project_activated = view.on_project_activated.clone();
+        view.card_grid.connect_child_activated(move |_, child| {
+            let project_id = usize::try_from(child.index())
+                .ok()
+                .and_then(|index| projects.borrow().get(index).map(|project| project.id.clone()));
+            if let Some(project_id) = project_id {
+                Self::activate_project(&on_project_activated, project_id);
+            }
+        });
 
         view
     }
 
+    /// Add the sort menu to the toolbar; the chosen order is saved in the settings
+    fn install_sort_menu(&self, toolbar: &gtk::Box) {
+        let sort_action = gtk::gio::SimpleAction::new_stateful(
+            "sort",
+            Some(glib::VariantTy::STRING),
+            &self.current_sort.get().as_str().to_variant(),
+        );
+        let view = self.clone();
+        sort_action.connect_activate(move |action, param| {
+            let Some(value) = param.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            let Some(sort) = ProjectSort::all().into_iter().find(|s| s.as_str() == value) else {
+                return;
+            };
+            action.set_state(&value.to_variant());
+            view.current_sort.set(sort);
+            if let Err(e) = view.repository.set_setting(SETTING_DASHBOARD_SORT, sort.as_str()) {
+                log::error!("Failed to save the dashboard sort order: {}", e);
+            }
+            view.load_projects();
+        });
+
+        let group = gtk::gio::SimpleActionGroup::new();
+        group.add_action(&sort_action);
+        self.container.insert_action_group("dashboard", Some(&group));
+
+        let menu = gtk::gio::Menu::new();
+        for sort in ProjectSort::all() {
+            menu.append(Some(sort.display_name()), Some(&format!("dashboard.sort::{}", sort.as_str())));
+        }
+
+        let sort_btn = gtk::MenuButton::builder()
+            .icon_name("view-sort-descending-symbolic")
+            .tooltip_text("Sort Projects")
+            .menu_model(&menu)
+            .build();
+        toolbar.append(&sort_btn);
+    }
+
+    /// Add the list/grid/board switch to the toolbar, starting with `current`
+    fn install_layout_toggle(&self, toolbar: &gtk::Box, current: DashboardLayout) {
+        self.layout_stack.set_visible_child_name(current.as_str());
+
+        let toggle_box = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        toggle_box.add_css_class("linked");
+
+        let mut group: Option<gtk::ToggleButton> = None;
+        for layout in DashboardLayout::all() {
+            let button = gtk::ToggleButton::builder()
+                .icon_name(layout.icon_name())
+                .tooltip_text(layout.display_name())
+                .active(layout == current)
+                .build();
+            button.set_group(group.as_ref());
+
+            let layout_stack = self.layout_stack.clone();
+            let repository = self.repository.clone();
+            button.connect_toggled(move |button| {
+                if !button.is_active() {
+                    return;
+                }
+                layout_stack.set_visible_child_name(layout.as_str());
+                if let Err(e) = repository.set_setting(SETTING_DASHBOARD_LAYOUT, layout.as_str()) {
+                    log::error!("Failed to save the dashboard layout: {}", e);
+                }
+            });
+
+            toggle_box.append(&button);
+            group.get_or_insert(button);
+        }
+
+        toolbar.append(&toggle_box);
+    }
+
+    /// Register the `project.*` actions used by the row context menu
+    fn install_project_actions(&self) {
+        let group = gtk::gio::SimpleActionGroup::new();
+
+        let open_action = gtk::gio::SimpleAction::new("open", Some(glib::VariantTy::STRING));
+        let on_project_activated = self.on_project_activated.clone();
+        open_action.connect_activate(move |_, param| {
+            if let Some(project_id) = param.and_then(|p| p.get::<String>()) {
+                Self::activate_project(&on_project_activated, project_id);
+            }
+        });
+        group.add_action(&open_action);
+
+        let pin_action = gtk::gio::SimpleAction::new("pin", Some(glib::VariantTy::STRING));
+        let view = self.clone();
+        pin_action.connect_activate(move |_, param| {
+            let Some(project_id) = param.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            let pinned = view.projects.borrow().iter().find(|p| p.id == project_id).map(|p| p.pinned);
+            if let Some(pinned) = pinned {
+                match view.repository.set_project_pinned(&project_id, !pinned) {
+                    Ok(_) => view.refresh(),
+                    Err(e) => log::error!("Failed to pin project: {}", e),
+                }
+            }
+        });
+        group.add_action(&pin_action);
+
+        let clone_action = gtk::gio::SimpleAction::new("clone", Some(glib::VariantTy::STRING));
+        let view = self.clone();
+        clone_action.connect_activate(move |_, param| {
+            let Some(project_id) = param.and_then(|p| p.get::<String>()) else {
+                return;
+            };
+            let project = view.projects.borrow().iter().find(|p| p.id == project_id).cloned();
+            if let Some(project) = project {
+                view.show_clone_dialog(&project);
+            }
+        });
+        group.add_action(&clone_action);
+
+        self.container.insert_action_group("project", Some(&group));
+    }
+
+    /// Ask for a name and duplicate a project
+    fn show_clone_dialog(&self, project: &Project) {
+        let parent = self.container.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(
+            parent.as_ref(),
+            Some("Duplicate Project"),
+            Some(&format!("Copies the details and context sections of '{}' into a new project.", project.name)),
+        );
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        let name_entry = gtk::Entry::builder()
+            .text(format!("{} (copy)", project.name))
+            .activates_default(true)
+            .build();
+        content.append(&name_entry);
+        let facts_check = gtk::CheckButton::with_label(&format!("Include the top {} facts", CLONE_TOP_FACTS));
+        content.append(&facts_check);
+        dialog.set_extra_child(Some(&content));
+
+        dialog.add_responses(&[("cancel", "Cancel"), ("clone", "Duplicate")]);
+        dialog.set_response_appearance("clone", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("clone"));
+        dialog.set_close_response("cancel");
+
+        let view = self.clone();
+        let project_id = project.id.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "clone" {
+                return;
+            }
+            let name = name_entry.text().trim().to_string();
+            if name.is_empty() {
+                return;
+            }
+            let top_facts = if facts_check.is_active() { CLONE_TOP_FACTS } else { 0 };
+            match view.repository.clone_project(&project_id, &name, top_facts) {
+                Ok(copy) => {
+                    log::info!("Cloned project {} as {}", project_id, copy.id);
+                    view.refresh();
+                }
+                Err(e) => log::error!("Failed to clone project: {}", e),
+            }
+        });
+
+        dialog.present();
+    }
+
     /// Create the toolbar with filter buttons
     fn create_toolbar() -> gtk::Box {
         let toolbar = gtk::Box::new(gtk::Orientation::Horizontal, 8);
@@ -75,58 +388,369 @@ impl DashboardView {
     }
 
     /// Load projects from database
+    ///
+    /// The queries run on a worker thread; if another load starts before this one
+    /// finishes, the older results are dropped.
     pub fn load_projects(&self) {
         let filter = *self.current_filter.borrow();
-
-        match self.repository.list_projects(filter) {
-            Ok(loaded_projects) => {
-                *self.projects.borrow_mut() = loaded_projects.clone();
-                Self::update_project_list_static(
-                    &self.project_list,
-                    &loaded_projects,
-                    self.navigation_view.clone(),
-                );
+        let sort = self.current_sort.get();
+        let generation = self.load_generation.get() + 1;
+        self.load_generation.set(generation);
+
+        let view = self.clone();
+        glib::spawn_future_local(async move {
+            let result = AsyncRepository::from(view.repository.clone())
+                .run(move |repository| DashboardData::load(repository, filter, sort))
+                .await;
+
+            if view.load_generation.get() != generation {
+                return;
             }
-            Err(e) => {
-                log::error!("Failed to load projects: {}", e);
-                Self::show_error_state(&self.project_list, &e.to_string());
+
+            match result {
+                Ok(data) => {
+                    *view.projects.borrow_mut() = data.projects.clone();
+                    Self::update_project_list_static(&view.project_list, &data, &view.on_project_activated);
+                    Self::update_card_grid(&view.card_grid, &data);
+                    view.update_board(&data);
+                }
+                Err(e) => {
+                    log::error!("Failed to load projects: {}", e);
+                    Self::show_error_state(&view.project_list, &e.to_string());
+                    Self::show_grid_state(&view.card_grid, Self::error_state_box(&e.to_string()));
+                }
             }
-        }
+        });
     }
 
     /// Update the project list with loaded projects
     fn update_project_list_static(
         project_list: &gtk::ListBox,
-        projects: &[Project],
-        nav_view: adw::NavigationView,
+        data: &DashboardData,
+        on_project_activated: &Rc<RefCell<Option<Box<dyn Fn(String)>>>>,
     ) {
         // Clear existing rows
         while let Some(row) = project_list.first_child() {
             project_list.remove(&row);
         }
 
-        if projects.is_empty() {
+        if data.projects.is_empty() {
             Self::show_empty_state(project_list);
             return;
         }
 
         // Add project rows
-        for project in projects {
-            let row = Self::create_project_row(project, nav_view.clone());
+        for project in &data.projects {
+            let row = Self::create_project_row(
+                project,
+                data.stats.get(&project.id),
+                data.pending.get(&project.id),
+                data.local_only.contains(&project.id),
+                data.dependencies.get(&project.id).map(Vec::as_slice).unwrap_or_default(),
+                on_project_activated.clone(),
+            );
             project_list.append(&row);
         }
     }
 
+    /// Fill the card grid with loaded projects, in the same order as the list
+    fn update_card_grid(card_grid: &gtk::FlowBox, data: &DashboardData) {
+        if data.projects.is_empty() {
+            Self::show_grid_state(card_grid, Self::empty_state_box());
+            return;
+        }
+
+        card_grid.remove_all();
+        for project in &data.projects {
+            card_grid.append(&Self::create_project_card(project, data.stats.get(&project.id)));
+        }
+    }
+
+    /// Replace the cards with an empty or error state
+    fn show_grid_state(card_grid: &gtk::FlowBox, state: gtk::Box) {
+        card_grid.remove_all();
+        let child = gtk::FlowBoxChild::new();
+        child.set_child(Some(&state));
+        child.set_can_target(false);
+        card_grid.append(&child);
+    }
+
+    /// Fill the board's status columns; cards dragged to another column change the project's status
+    fn update_board(&self, data: &DashboardData) {
+        while let Some(column) = self.board.first_child() {
+            self.board.remove(&column);
+        }
+
+        for status in BOARD_COLUMNS {
+            let projects: Vec<_> = data.projects.iter().filter(|p| p.status == status).collect();
+
+            let column = gtk::Box::new(gtk::Orientation::Vertical, 8);
+            column.add_css_class("board-column");
+
+            let title = gtk::Label::new(Some(&format!("{} ({})", status.display_name(), projects.len())));
+            title.add_css_class("heading");
+            title.set_xalign(0.0);
+            column.append(&title);
+
+            for project in projects {
+                let card = Self::create_card_box(project, data.stats.get(&project.id));
+
+                let drag = gtk::DragSource::new();
+                drag.set_actions(gtk::gdk::DragAction::MOVE);
+                let project_id = project.id.clone();
+                drag.connect_prepare(move |_, _, _| Some(gtk::gdk::ContentProvider::for_value(&project_id.to_value())));
+                card.add_controller(drag);
+
+                let click = gtk::GestureClick::new();
+                let on_project_activated = self.on_project_activated.clone();
+                let project_id = project.id.clone();
+                click.connect_released(move |_, _, _, _| {
+                    Self::activate_project(&on_project_activated, project_id.clone());
+                });
+                card.add_controller(click);
+
+                column.append(&card);
+            }
+
+            let drop = gtk::DropTarget::new(String::static_type(), gtk::gdk::DragAction::MOVE);
+            let view = self.clone();
+            drop.connect_drop(move |_, value, _, _| match value.get::<String>() {
+                Ok(project_id) => view.move_project(&project_id, status),
+                Err(_) => false,
+            });
+            column.add_controller(drop);
+
+            self.board.append(&column);
+        }
+    }
+
+    /// Give a project dropped on a board column that column's status
+    fn move_project(&self, project_id: &str, status: ProjectStatus) -> bool {
+        let project = self.projects.borrow().iter().find(|p| p.id == project_id).cloned();
+        let Some(project) = project else {
+            return false;
+        };
+        if project.status == status {
+            return false;
+        }
+
+        let mut payload = ProjectPayload::from(&project);
+        payload.status = status;
+        match self.repository.update_project(project_id, payload) {
+            Ok(_) => {
+                log::info!("Moved project {} to {}", project.name, status.as_str());
+                self.refresh();
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to change the status of {}: {}", project.name, e);
+                false
+            }
+        }
+    }
+
+    /// Create a grid card for a project
+    fn create_project_card(project: &Project, stats: Option<&ProjectStats>) -> gtk::FlowBoxChild {
+        let child = gtk::FlowBoxChild::new();
+        child.set_child(Some(&Self::create_card_box(project, stats)));
+        child
+    }
+
+    /// Card contents: status, priority, token trend and last activity
+    fn create_card_box(project: &Project, stats: Option<&ProjectStats>) -> gtk::Box {
+        let card = gtk::Box::new(gtk::Orientation::Vertical, 8);
+        card.add_css_class("project-card");
+
+        let header = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        let title = gtk::Label::builder()
+            .label(&project.name)
+            .xalign(0.0)
+            .hexpand(true)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        title.add_css_class("project-card-title");
+        header.append(&title);
+
+        if project.pinned {
+            let pinned_icon = gtk::Image::from_icon_name("starred-symbolic");
+            pinned_icon.set_tooltip_text(Some("Pinned"));
+            header.append(&pinned_icon);
+        }
+
+        let status_label = gtk::Label::new(Some(project.status.display_name()));
+        status_label.add_css_class("status-badge");
+        status_label.add_css_class(&format!("status-{}", project.status.as_str()));
+        status_label.set_valign(gtk::Align::Center);
+        header.append(&status_label);
+        card.append(&header);
+
+        let mut subtitle = format!("Priority {}", project.priority);
+        if !project.tech_stack.is_empty() {
+            subtitle.push_str(&format!(" · {}", project.tech_stack_display()));
+        }
+        let subtitle_label = gtk::Label::builder()
+            .label(&subtitle)
+            .xalign(0.0)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        subtitle_label.add_css_class("project-card-subtitle");
+        card.append(&subtitle_label);
+
+        let trend = stats.map(|s| s.token_trend.clone()).unwrap_or_default();
+        let sparkline = Self::create_sparkline(&trend);
+        sparkline.set_tooltip_text(Some(&format!(
+            "{} tokens over the last {} days",
+            trend.iter().sum::<i64>(),
+            TOKEN_TREND_DAYS
+        )));
+        card.append(&sparkline);
+
+        let activity = match stats.and_then(|s| s.last_activity) {
+            Some(at) => format!(
+                "Last activity: {}",
+                at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+            ),
+            None => "No activity yet".to_string(),
+        };
+        let activity_label = gtk::Label::builder().label(&activity).xalign(0.0).build();
+        activity_label.add_css_class("dim-label");
+        activity_label.add_css_class("caption");
+        card.append(&activity_label);
+
+        card
+    }
+
+    /// Line chart of daily token use, scaled to the busiest day
+    fn create_sparkline(trend: &[i64]) -> gtk::DrawingArea {
+        let area = gtk::DrawingArea::builder().content_height(32).hexpand(true).build();
+        area.add_css_class("token-sparkline");
+
+        let trend = trend.to_vec();
+        area.set_draw_func(move |area, cr, width, height| {
+            let peak = trend.iter().copied().max().unwrap_or(0);
+            if trend.len() < 2 || peak <= 0 {
+                return;
+            }
+
+            let color = area.color();
+            cr.set_source_rgba(
+                color.red().into(),
+                color.green().into(),
+                color.blue().into(),
+                color.alpha().into(),
+            );
+            cr.set_line_width(1.5);
+
+            let (width, height) = (f64::from(width), f64::from(height) - 2.0);
+            let step = width / (trend.len() - 1) as f64;
+            for (i, tokens) in trend.iter().enumerate() {
+                let x = i as f64 * step;
+                let y = 1.0 + height - height * *tokens as f64 / peak as f64;
+                if i == 0 {
+                    cr.move_to(x, y);
+                } else {
+                    cr.line_to(x, y);
+                }
+            }
+            if let Err(e) = cr.stroke() {
+                log::debug!("Failed to draw token trend: {}", e);
+            }
+        });
+
+        area
+    }
+
     /// Create a project row widget
-    fn create_project_row(project: &Project, nav_view: adw::NavigationView) -> gtk::ListBoxRow {
+    fn create_project_row(
+        project: &Project,
+        stats: Option<&ProjectStats>,
+        pending: Option<&PendingChanges>,
+        local_only: bool,
+        dependencies: &[DependencyContext],
+        on_project_activated: Rc<RefCell<Option<Box<dyn Fn(String)>>>>,
+    ) -> gtk::ListBoxRow {
         let row = adw::ActionRow::builder()
             .title(&project.name)
             .subtitle(&project.tech_stack_display())
             .build();
 
+        // Star button pinning the project to the top
+        let pin_btn = gtk::Button::builder()
+            .icon_name(if project.pinned { "starred-symbolic" } else { "non-starred-symbolic" })
+            .tooltip_text(if project.pinned { "Unpin" } else { "Pin to Top" })
+            .action_name("project.pin")
+            .action_target(&project.id.to_variant())
+            .valign(gtk::Align::Center)
+            .build();
+        pin_btn.add_css_class("flat");
+        row.add_prefix(&pin_btn);
+
         // Add status badge
         let status_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
 
+        // Dependencies badge
+        if !dependencies.is_empty() {
+            let names: Vec<_> = dependencies.iter().map(|d| d.project.name.as_str()).collect();
+
+            let deps_label = gtk::Label::new(Some(&format!("⇢ {}", names.join(", "))));
+            deps_label.add_css_class("dependency-badge");
+            deps_label.set_tooltip_text(Some(&format!("Depends on: {}", names.join(", "))));
+            status_box.append(&deps_label);
+
+            let blocked: Vec<_> = dependencies
+                .iter()
+                .filter(|d| d.has_open_blockers())
+                .map(|d| format!("{} ({} open)", d.project.name, d.open_blockers.len()))
+                .collect();
+
+            if !blocked.is_empty() {
+                let warning_icon = gtk::Image::from_icon_name("dialog-warning-symbolic");
+                warning_icon.add_css_class("warning");
+                warning_icon.set_tooltip_text(Some(&format!(
+                    "Dependencies with open blockers: {}",
+                    blocked.join(", ")
+                )));
+                status_box.append(&warning_icon);
+            }
+        }
+
+        // Activity summary
+        if let Some(stats) = stats.filter(|s| s.session_count > 0 || s.fact_count > 0) {
+            let stats_label = gtk::Label::new(Some(&format!(
+                "{} facts · {} sessions",
+                stats.fact_count, stats.session_count
+            )));
+            stats_label.add_css_class("dim-label");
+            stats_label.add_css_class("caption");
+
+            let mut tooltip = format!("{} tokens across all sessions", stats.total_tokens);
+            if let Some(at) = stats.last_activity {
+                tooltip.push_str(&format!(
+                    "\nLast activity: {}",
+                    at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+                ));
+            }
+            stats_label.set_tooltip_text(Some(&tooltip));
+            status_box.append(&stats_label);
+        }
+
+        // Sync badge
+        if local_only {
+            let local_icon = gtk::Image::from_icon_name("computer-symbolic");
+            local_icon.add_css_class("dim-label");
+            local_icon.set_tooltip_text(Some("Local only, never synced"));
+            status_box.append(&local_icon);
+        } else if let Some(pending) = pending {
+            let status = pending.status();
+            let sync_icon = gtk::Image::from_icon_name(status.icon_name());
+            sync_icon.add_css_class("dim-label");
+            sync_icon.set_tooltip_text(Some(&match pending.total() {
+                0 => status.display_name().to_string(),
+                n => format!("{}: {} local change{}", status.display_name(), n, if n == 1 { "" } else { "s" }),
+            }));
+            status_box.append(&sync_icon);
+        }
+
         let status_label = gtk::Label::new(Some(project.status.display_name()));
         status_label.add_css_class("status-badge");
         status_label.add_css_class(&format!("status-{}", project.status.as_str()));
@@ -152,10 +776,8 @@ impl DashboardView {
 
         // Handle click to navigate to project detail
         let project_id = project.id.clone();
-        let list_row_for_activation = list_row.clone();
         row.connect_activated(move |_| {
-            log::info!("Project row activated: {}", project_id);
-            // Navigation will be wired up through callbacks
+            Self::activate_project(&on_project_activated, project_id.clone());
         });
 
         // Add context menu (right-click)
@@ -174,6 +796,19 @@ impl DashboardView {
         list_row
     }
 
+    /// Hand `project_id` to the callback registered with `connect_project_activated`
+    fn activate_project(on_project_activated: &Rc<RefCell<Option<Box<dyn Fn(String)>>>>, project_id: String) {
+        log::info!("Project row activated: {}", project_id);
+        if let Some(on_project_activated) = on_project_activated.borrow().as_ref() {
+            on_project_activated(project_id);
+        }
+    }
+
+    /// Call `f` with the project's ID when a project row is activated
+    pub fn connect_project_activated(&self, f: impl Fn(String) + 'static) {
+        *self.on_project_activated.borrow_mut() = Some(Box::new(f));
+    }
+
     /// Show context menu for a project
     fn show_project_context_menu(widget: &gtk::Widget, project_name: &str, project_id: &str) {
         let menu = gtk::gio::Menu::new();
@@ -184,6 +819,9 @@ impl DashboardView {
         // Edit menu item
         menu.append(Some("Edit Details"), Some(&format!("project.edit::{}", project_id)));
 
+        // Duplicate menu item
+        menu.append(Some("Duplicate..."), Some(&format!("project.clone::{}", project_id)));
+
         menu.append_section(None, &{
             let section = gtk::gio::Menu::new();
 
@@ -221,6 +859,16 @@ impl DashboardView {
 
     /// Show empty state
     fn show_empty_state(project_list: &gtk::ListBox) {
+        let row = gtk::ListBoxRow::new();
+        row.set_child(Some(&Self::empty_state_box()));
+        row.set_activatable(false);
+        row.set_selectable(false);
+
+        project_list.append(&row);
+    }
+
+    /// Empty state shown in either layout
+    fn empty_state_box() -> gtk::Box {
         let empty_box = gtk::Box::new(gtk::Orientation::Vertical, 12);
         empty_box.add_css_class("empty-state");
 
@@ -237,12 +885,7 @@ impl DashboardView {
         subtitle.add_css_class("empty-state-subtitle");
         empty_box.append(&subtitle);
 
-        let row = gtk::ListBoxRow::new();
-        row.set_child(Some(&empty_box));
-        row.set_activatable(false);
-        row.set_selectable(false);
-
-        project_list.append(&row);
+        empty_box
     }
 
     /// Show error state
@@ -251,6 +894,16 @@ impl DashboardView {
             project_list.remove(&row);
         }
 
+        let row = gtk::ListBoxRow::new();
+        row.set_child(Some(&Self::error_state_box(error)));
+        row.set_activatable(false);
+        row.set_selectable(false);
+
+        project_list.append(&row);
+    }
+
+    /// Error state shown in either layout
+    fn error_state_box(error: &str) -> gtk::Box {
         let error_box = gtk::Box::new(gtk::Orientation::Vertical, 12);
         error_box.add_css_class("empty-state");
 
@@ -268,12 +921,7 @@ impl DashboardView {
         subtitle.set_wrap(true);
         error_box.append(&subtitle);
 
-        let row = gtk::ListBoxRow::new();
-        row.set_child(Some(&error_box));
-        row.set_activatable(false);
-        row.set_selectable(false);
-
-        project_list.append(&row);
+        error_box
     }
 
     /// Clear the project list
@@ -306,11 +954,16 @@ impl Clone for DashboardView {
     fn clone(&self) -> Self {
         Self {
             container: self.container.clone(),
+            layout_stack: self.layout_stack.clone(),
             project_list: self.project_list.clone(),
+            card_grid: self.card_grid.clone(),
+            board: self.board.clone(),
             repository: self.repository.clone(),
-            navigation_view: self.navigation_view.clone(),
             projects: self.projects.clone(),
             current_filter: self.current_filter.clone(),
+            current_sort: self.current_sort.clone(),
+            load_generation: self.load_generation.clone(),
+            on_project_activated: self.on_project_activated.clone(),
         }
     }
 }
@@ -1,22 +1,40 @@
-use crate::db::Repository;
-use crate::models::ExtractedFact;
+use crate::db::{Page, Repository};
+use crate::markdown::{find_matches, highlight_matches};
+use crate::models::{ContextSection, ExtractedFact, FactBulkAction, FactFilter, FactSource, FactType, SyncStatus};
+use crate::monitor::read_fact_message;
+use crate::monitor_updates::MonitorUpdates;
+use crate::sync::last_replicated;
+use crate::views::SectionEditor;
 use adw::prelude::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+/// Facts shown at once, most important first (all of them while selecting)
+const FACTS_SHOWN: usize = 10;
+
 /// Facts list view showing extracted facts
+#[derive(Clone)]
 pub struct FactsListView {
     container: gtk::Box,
     facts_list: gtk::ListBox,
     repository: Repository,
     project_id: String,
     facts: Rc<RefCell<Vec<ExtractedFact>>>,
+    filter: Rc<Cell<FactFilter>>,
+    query: Rc<RefCell<String>>,
+    selecting: Rc<Cell<bool>>,
+    selected: Rc<RefCell<HashSet<String>>>,
+    action_bar: gtk::ActionBar,
+    selected_label: gtk::Label,
+    bulk_actions: gtk::Box,
+    on_promoted: Rc<RefCell<Option<Box<dyn Fn(&ContextSection)>>>>,
 }
 
 impl FactsListView {
     /// Create a new facts list view
     pub fn new(repository: Repository, project_id: String) -> Self {
-        let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 6);
 
         // Create scrolled window
         let scrolled = gtk::ScrolledWindow::builder()
@@ -33,27 +51,334 @@ impl FactsListView {
         scrolled.set_child(Some(&facts_list));
         container.append(&scrolled);
 
+        // Bulk actions, shown while selecting
+        let action_bar = gtk::ActionBar::new();
+        action_bar.set_revealed(false);
+        container.append(&action_bar);
+
+        let selected_label = gtk::Label::new(None);
+        selected_label.add_css_class("caption");
+        selected_label.add_css_class("numeric");
+
         let mut view = Self {
             container,
             facts_list,
             repository,
             project_id,
             facts: Rc::new(RefCell::new(Vec::new())),
+            filter: Rc::new(Cell::new(FactFilter::default())),
+            query: Rc::new(RefCell::new(String::new())),
+            selecting: Rc::new(Cell::new(false)),
+            selected: Rc::new(RefCell::new(HashSet::new())),
+            action_bar,
+            selected_label,
+            bulk_actions: gtk::Box::new(gtk::Orientation::Horizontal, 0),
+            on_promoted: Rc::new(RefCell::new(None)),
         };
 
+        view.container.prepend(&view.create_filter_bar());
+        view.setup_action_bar();
         view.load_facts();
 
         view
     }
 
+    /// Reload facts from the database (e.g. after capturing a new one)
+    pub fn reload(&self) {
+        self.load_facts();
+    }
+
+    /// Reload as the monitor extracts, resolves or expires facts of this project
+    pub fn subscribe(&self, updates: &MonitorUpdates) {
+        let view = self.clone();
+        updates.subscribe_while_shown(&self.container, Some(&self.project_id), move || view.reload());
+    }
+
+    /// Search entry, type chips, minimum importance and the stale toggle above the list
+    fn create_filter_bar(&self) -> gtk::Box {
+        let filter_bar = gtk::Box::new(gtk::Orientation::Vertical, 4);
+
+        let search_entry = gtk::SearchEntry::builder()
+            .placeholder_text("Search facts")
+            .build();
+        let view = self.clone();
+        search_entry.connect_search_changed(move |entry| {
+            let query = entry.text().trim().to_string();
+            if query != *view.query.borrow() {
+                *view.query.borrow_mut() = query;
+                view.load_facts();
+            }
+        });
+        filter_bar.append(&search_entry);
+
+        let chips = gtk::FlowBox::builder()
+            .selection_mode(gtk::SelectionMode::None)
+            .column_spacing(4)
+            .row_spacing(4)
+            .max_children_per_line(8)
+            .build();
+
+        let mut group: Option<gtk::ToggleButton> = None;
+        let choices = std::iter::once(None).chain(FactType::all().into_iter().map(Some));
+        for fact_type in choices {
+            let chip = gtk::ToggleButton::builder()
+                .label(fact_type.as_ref().map_or("All", FactType::display_name))
+                .active(fact_type.is_none())
+                .build();
+            chip.add_css_class("flat");
+            chip.add_css_class("caption");
+            chip.set_group(group.as_ref());
+
+            let view = self.clone();
+            chip.connect_toggled(move |chip| {
+                if chip.is_active() {
+                    view.update_filter(|filter| filter.fact_type = fact_type);
+                }
+            });
+
+            chips.append(&chip);
+            group.get_or_insert(chip);
+        }
+        filter_bar.append(&chips);
+
+        let options = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+
+        let importance_label = gtk::Label::new(Some("Min. importance"));
+        importance_label.add_css_class("dim-label");
+        importance_label.add_css_class("caption");
+        options.append(&importance_label);
+
+        let importance = gtk::Scale::with_range(gtk::Orientation::Horizontal, 1.0, 5.0, 1.0);
+        importance.set_value(FactFilter::default().min_importance as f64);
+        importance.set_round_digits(0);
+        importance.set_draw_value(true);
+        importance.set_value_pos(gtk::PositionType::Right);
+        importance.set_hexpand(true);
+        for value in 1..=5 {
+            importance.add_mark(value as f64, gtk::PositionType::Bottom, None);
+        }
+
+        let view = self.clone();
+        importance.connect_value_changed(move |scale| {
+            let min_importance = scale.value().round() as i32;
+            view.update_filter(|filter| filter.min_importance = min_importance);
+        });
+        options.append(&importance);
+
+        let stale = gtk::CheckButton::with_label("Show stale");
+        stale.add_css_class("caption");
+        let view = self.clone();
+        stale.connect_toggled(move |check| {
+            let include_stale = check.is_active();
+            view.update_filter(|filter| filter.include_stale = include_stale);
+        });
+        options.append(&stale);
+
+        let select_btn = gtk::ToggleButton::builder()
+            .icon_name("object-select-symbolic")
+            .tooltip_text("Select facts")
+            .build();
+        select_btn.add_css_class("flat");
+        let view = self.clone();
+        select_btn.connect_toggled(move |button| view.set_selecting(button.is_active()));
+        options.append(&select_btn);
+
+        filter_bar.append(&options);
+        filter_bar
+    }
+
+    /// Fill the action bar with the count, select all and the bulk actions
+    fn setup_action_bar(&self) {
+        self.action_bar.pack_start(&self.selected_label);
+
+        let select_all_btn = gtk::Button::builder()
+            .icon_name("edit-select-all-symbolic")
+            .tooltip_text("Select all shown")
+            .build();
+        select_all_btn.add_css_class("flat");
+        let view = self.clone();
+        select_all_btn.connect_clicked(move |_| {
+            let all: HashSet<String> = view.facts.borrow().iter().map(|fact| fact.id.clone()).collect();
+            let mut selected = view.selected.borrow_mut();
+            *selected = if *selected == all { HashSet::new() } else { all };
+            drop(selected);
+            view.load_facts();
+        });
+        self.action_bar.pack_start(&select_all_btn);
+
+        let delete_btn = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text("Move to trash")
+            .build();
+        delete_btn.add_css_class("flat");
+        delete_btn.add_css_class("error");
+        let view = self.clone();
+        delete_btn.connect_clicked(move |button| view.confirm_delete(button));
+
+        let importance_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let importance_btn = gtk::MenuButton::builder()
+            .icon_name("starred-symbolic")
+            .tooltip_text("Set importance")
+            .build();
+        importance_btn.add_css_class("flat");
+        for importance in (1..=5).rev() {
+            let stars = format!("{}{}", "★".repeat(importance as usize), "☆".repeat(5 - importance as usize));
+            let button = gtk::Button::with_label(&stars);
+            button.add_css_class("flat");
+            let view = self.clone();
+            let menu_btn = importance_btn.clone();
+            button.connect_clicked(move |_| {
+                menu_btn.popdown();
+                view.apply(FactBulkAction::SetImportance(importance));
+            });
+            importance_box.append(&button);
+        }
+        importance_btn.set_popover(Some(&gtk::Popover::builder().child(&importance_box).build()));
+
+        let type_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let type_btn = gtk::MenuButton::builder()
+            .icon_name("document-edit-symbolic")
+            .tooltip_text("Change type")
+            .build();
+        type_btn.add_css_class("flat");
+        for fact_type in FactType::all() {
+            let button = gtk::Button::with_label(fact_type.display_name());
+            button.add_css_class("flat");
+            let view = self.clone();
+            let menu_btn = type_btn.clone();
+            button.connect_clicked(move |_| {
+                menu_btn.popdown();
+                view.apply(FactBulkAction::SetType(fact_type));
+            });
+            type_box.append(&button);
+        }
+        type_btn.set_popover(Some(&gtk::Popover::builder().child(&type_box).build()));
+
+        let stale_btn = gtk::Button::builder()
+            .icon_name("alarm-symbolic")
+            .tooltip_text("Mark stale")
+            .build();
+        stale_btn.add_css_class("flat");
+        let view = self.clone();
+        stale_btn.connect_clicked(move |_| view.apply(FactBulkAction::MarkStale));
+
+        self.bulk_actions.append(&stale_btn);
+        self.bulk_actions.append(&type_btn);
+        self.bulk_actions.append(&importance_btn);
+        self.bulk_actions.append(&delete_btn);
+        self.action_bar.pack_end(&self.bulk_actions);
+    }
+
+    /// Enter or leave selection mode; while selecting every fact matching the filter is listed
+    fn set_selecting(&self, selecting: bool) {
+        self.selecting.set(selecting);
+        self.selected.borrow_mut().clear();
+        self.action_bar.set_revealed(selecting);
+        self.load_facts();
+    }
+
+    /// Check or uncheck a fact for bulk actions
+    fn toggle_selected(&self, fact_id: &str, selected: bool) {
+        if selected {
+            self.selected.borrow_mut().insert(fact_id.to_string());
+        } else {
+            self.selected.borrow_mut().remove(fact_id);
+        }
+        self.update_selected_label();
+    }
+
+    fn update_selected_label(&self) {
+        let count = self.selected.borrow().len();
+        self.selected_label.set_label(&format!("{} selected", count));
+        self.bulk_actions.set_sensitive(count > 0);
+    }
+
+    /// Apply a bulk action to the selected facts
+    fn apply(&self, action: FactBulkAction) {
+        let ids: Vec<String> = self.selected.borrow().iter().cloned().collect();
+        if ids.is_empty() {
+            return;
+        }
+
+        match self.repository.apply_fact_action(&ids, action) {
+            Ok(changed) => log::info!("{}", action.summary(changed)),
+            Err(e) => log::error!("Failed to update the selected facts: {}", e),
+        }
+        self.selected.borrow_mut().clear();
+        self.load_facts();
+    }
+
+    /// Ask before moving the selected facts to the trash
+    fn confirm_delete(&self, button: &gtk::Button) {
+        let count = self.selected.borrow().len();
+        if count == 0 {
+            return;
+        }
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(
+            parent.as_ref(),
+            Some(&format!("Delete {} {}?", count, if count == 1 { "fact" } else { "facts" })),
+            Some("They can be restored from the trash."),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("delete", "Delete")]);
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_close_response("cancel");
+
+        let view = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response == "delete" {
+                view.apply(FactBulkAction::Delete);
+            }
+        });
+        dialog.present();
+    }
+
+    /// Change the filter and reload if that changes anything
+    fn update_filter(&self, change: impl FnOnce(&mut FactFilter)) {
+        let mut filter = self.filter.get();
+        change(&mut filter);
+        if filter != self.filter.get() {
+            self.filter.set(filter);
+            self.load_facts();
+        }
+    }
+
     /// Load facts from database
     fn load_facts(&self) {
-        match self.repository.list_facts(&self.project_id, false) {
-            Ok(loaded_facts) => {
-                // Take top 10 most important facts
-                let top_facts: Vec<_> = loaded_facts.into_iter().take(10).collect();
+        let filter = self.filter.get();
+        let query = self.query.borrow().clone();
+        let shown = if self.selecting.get() { usize::MAX } else { FACTS_SHOWN };
+
+        // Most important first, so the top page only loses facts below the minimum;
+        // searching looks through all of them
+        let loaded = match filter.fact_type {
+            Some(fact_type) => self.repository.list_facts_by_type(&self.project_id, fact_type),
+            None if self.selecting.get() || !query.is_empty() => {
+                self.repository.list_facts(&self.project_id, filter.include_stale)
+            }
+            None => self
+                .repository
+                .list_facts_page(&self.project_id, filter.include_stale, Page::first(FACTS_SHOWN)),
+        };
+
+        match loaded {
+            Ok(facts) => {
+                let top_facts: Vec<_> = facts
+                    .into_iter()
+                    .filter(|f| filter.matches(f))
+                    .filter(|f| query.is_empty() || !find_matches(&f.content, &query).is_empty())
+                    .take(shown)
+                    .collect();
+
+                // Facts gone or filtered out are no longer selected
+                let ids: HashSet<&str> = top_facts.iter().map(|fact| fact.id.as_str()).collect();
+                self.selected.borrow_mut().retain(|id| ids.contains(id.as_str()));
+                self.update_selected_label();
+
                 *self.facts.borrow_mut() = top_facts.clone();
-                Self::update_facts_list(&self.facts_list, &top_facts);
+                let statuses = self.sync_statuses();
+                self.update_facts_list(&top_facts, &statuses, filter.is_active() || !query.is_empty());
             }
             Err(e) => {
                 log::error!("Failed to load facts: {}", e);
@@ -61,15 +386,32 @@ impl FactsListView {
         }
     }
 
+    /// Sync state per fact, empty if the database has never been synced
+    fn sync_statuses(&self) -> HashMap<String, SyncStatus> {
+        if last_replicated(&self.repository).is_none() {
+            return HashMap::new();
+        }
+
+        self.repository.sync_statuses(&self.project_id).unwrap_or_else(|e| {
+            log::warn!("Failed to load sync status: {}", e);
+            HashMap::new()
+        })
+    }
+
     /// Update the facts list
-    fn update_facts_list(facts_list: &gtk::ListBox, facts: &[ExtractedFact]) {
+    fn update_facts_list(&self, facts: &[ExtractedFact], statuses: &HashMap<String, SyncStatus>, filtered: bool) {
+        let facts_list = &self.facts_list;
         // Clear existing rows
         while let Some(row) = facts_list.first_child() {
             facts_list.remove(&row);
         }
 
         if facts.is_empty() {
-            let empty_label = gtk::Label::new(Some("No facts extracted yet"));
+            let empty_label = gtk::Label::new(Some(if filtered {
+                "No facts match the filter"
+            } else {
+                "No facts extracted yet"
+            }));
             empty_label.add_css_class("dim-label");
             empty_label.set_margin_top(16);
             empty_label.set_margin_bottom(16);
@@ -81,13 +423,13 @@ impl FactsListView {
         }
 
         for fact in facts {
-            let row = Self::create_fact_row(fact);
+            let row = self.create_fact_row(fact, statuses.get(&fact.id).copied());
             facts_list.append(&row);
         }
     }
 
     /// Create a fact row
-    fn create_fact_row(fact: &ExtractedFact) -> gtk::ListBoxRow {
+    fn create_fact_row(&self, fact: &ExtractedFact, sync_status: Option<SyncStatus>) -> gtk::ListBoxRow {
         let row_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
         row_box.set_margin_top(6);
         row_box.set_margin_bottom(6);
@@ -97,6 +439,17 @@ impl FactsListView {
         // Header with type and importance
         let header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
 
+        if self.selecting.get() {
+            let check = gtk::CheckButton::builder()
+                .active(self.selected.borrow().contains(&fact.id))
+                .valign(gtk::Align::Center)
+                .build();
+            let view = self.clone();
+            let fact_id = fact.id.clone();
+            check.connect_toggled(move |check| view.toggle_selected(&fact_id, check.is_active()));
+            header.append(&check);
+        }
+
         let type_label = gtk::Label::new(Some(fact.fact_type.display_name()));
         type_label.add_css_class("fact-badge");
         type_label.add_css_class(&format!("fact-{}", fact.fact_type.as_str()));
@@ -111,10 +464,71 @@ impl FactsListView {
         }
         header.append(&importance_label);
 
+        if let Some(attachment) = &fact.metadata.attachment {
+            let attachment_icon = gtk::Image::from_icon_name("mail-attachment-symbolic");
+            attachment_icon.set_tooltip_text(Some(attachment));
+            attachment_icon.add_css_class("dim-label");
+            header.append(&attachment_icon);
+        }
+
+        if let Some(resolution) = &fact.metadata.resolved_by {
+            let resolved_icon = gtk::Image::from_icon_name("object-select-symbolic");
+            resolved_icon.set_tooltip_text(Some(&format!("Resolved: {}", resolution)));
+            resolved_icon.add_css_class("dim-label");
+            header.append(&resolved_icon);
+        }
+
+        if fact.is_low_confidence() {
+            let confidence_icon = gtk::Image::from_icon_name("dialog-question-symbolic");
+            confidence_icon.set_tooltip_text(Some(&format!(
+                "{:.0}% confidence, left out of exports until reviewed",
+                fact.confidence() * 100.0
+            )));
+            confidence_icon.add_css_class("dim-label");
+            header.append(&confidence_icon);
+        }
+
         let spacer = gtk::Box::new(gtk::Orientation::Horizontal, 0);
         spacer.set_hexpand(true);
         header.append(&spacer);
 
+        if let Some(source) = fact.metadata.source.clone().filter(|source| source.log_path.is_some()) {
+            let source_btn = gtk::Button::builder()
+                .icon_name("find-location-symbolic")
+                .tooltip_text("Show where this was found")
+                .valign(gtk::Align::Center)
+                .build();
+            source_btn.add_css_class("flat");
+            source_btn.connect_clicked(move |button| Self::show_source_dialog(button, &source));
+            header.append(&source_btn);
+        }
+
+        if fact.metadata.promoted_to.is_some() {
+            let promoted_icon = gtk::Image::from_icon_name("go-up-symbolic");
+            promoted_icon.set_tooltip_text(Some("Promoted to a context section"));
+            promoted_icon.add_css_class("dim-label");
+            header.append(&promoted_icon);
+        } else if let Some(section_type) = fact.fact_type.promoted_section_type() {
+            let promote_btn = gtk::Button::builder()
+                .icon_name("go-up-symbolic")
+                .tooltip_text(format!("Promote to {}", section_type.display_name()))
+                .valign(gtk::Align::Center)
+                .build();
+            promote_btn.add_css_class("flat");
+            let view = self.clone();
+            let fact_id = fact.id.clone();
+            promote_btn.connect_clicked(move |button| view.promote(button, &fact_id));
+            header.append(&promote_btn);
+        }
+
+        // Only pending and synced badges: without an upload path, pulls can't conflict with local edits
+        if let Some(status) = sync_status {
+            let sync_icon = gtk::Image::from_icon_name(status.icon_name());
+            sync_icon.set_tooltip_text(Some(status.display_name()));
+            sync_icon.add_css_class("dim-label");
+            header.append(&sync_icon);
+        }
+
         let age_label = gtk::Label::new(Some(&fact.age_display()));
         age_label.add_css_class("dim-label");
         age_label.set_css_classes(&["dim-label", "caption"]);
@@ -122,8 +536,13 @@ impl FactsListView {
 
         row_box.append(&header);
 
-        // Content
-        let content_label = gtk::Label::new(Some(&fact.content_preview()));
+        // Content, with what was searched for highlighted
+        let preview = fact.content_preview();
+        let content_label = gtk::Label::new(Some(&preview));
+        let matches = find_matches(&preview, &self.query.borrow());
+        if !matches.is_empty() {
+            content_label.set_markup(&highlight_matches(&preview, &matches));
+        }
         content_label.set_wrap(true);
         content_label.set_xalign(0.0);
         content_label.set_css_classes(&["caption"]);
@@ -139,6 +558,74 @@ impl FactsListView {
         row
     }
 
+    /// Add a fact to its context section, then open that section for polish
+    fn promote(&self, button: &gtk::Button, fact_id: &str) {
+        let section = match self.repository.promote_fact(fact_id) {
+            Ok(section) => section,
+            Err(e) => {
+                log::error!("Failed to promote fact {}: {}", fact_id, e);
+                return;
+            }
+        };
+        self.reload();
+        self.notify_promoted(&section);
+
+        let Some(parent) = button.root().and_downcast::<gtk::Window>() else {
+            return;
+        };
+        let editor = SectionEditor::new(&parent, self.repository.clone(), section);
+        let view = self.clone();
+        editor.connect_saved(move |saved| view.notify_promoted(saved));
+        editor.present();
+    }
+
+    fn notify_promoted(&self, section: &ContextSection) {
+        if let Some(on_promoted) = self.on_promoted.borrow().as_ref() {
+            on_promoted(section);
+        }
+    }
+
+    /// Connect to a fact promoted into a context section (and to later edits of it there)
+    pub fn connect_promoted(&self, f: impl Fn(&ContextSection) + 'static) {
+        *self.on_promoted.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Show the transcript message a fact was extracted from
+    pub(crate) fn show_source_dialog(button: &gtk::Button, source: &FactSource) {
+        let mut location = source.log_path.clone().unwrap_or_default();
+        if let Some(index) = source.message_index {
+            location.push_str(&format!(", message {}", index + 1));
+        }
+        if let Some(file) = source.file_location() {
+            location.push_str(&format!("\nFile: {}", file));
+        }
+
+        let parent = button.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(parent.as_ref(), Some("Source"), Some(&location));
+        dialog.add_responses(&[("close", "Close")]);
+        dialog.set_close_response("close");
+
+        let text = match read_fact_message(source) {
+            Ok(message) => message.content,
+            Err(e) => format!("Message unavailable: {:#}", e),
+        };
+        let label = gtk::Label::builder()
+            .label(text)
+            .wrap(true)
+            .selectable(true)
+            .xalign(0.0)
+            .yalign(0.0)
+            .build();
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&label)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .min_content_height(240)
+            .min_content_width(420)
+            .build();
+        dialog.set_extra_child(Some(&scrolled));
+        dialog.present();
+    }
+
     /// Get the widget
     pub fn widget(&self) -> gtk::Box {
         self.container.clone()
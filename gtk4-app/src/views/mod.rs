@@ -1,11 +1,29 @@
 pub mod dashboard;
 pub mod project_detail;
 pub mod context_editor;
+pub mod section_editor;
 pub mod facts_list;
 pub mod session_monitor;
+pub mod session_list;
+pub mod session_detail;
+pub mod session_comparison;
+pub mod timeline;
+pub mod token_chart;
+pub mod statistics;
+pub mod triage_list;
+pub mod search_palette;
 
 pub use dashboard::*;
 pub use project_detail::*;
 pub use context_editor::*;
+pub use section_editor::*;
 pub use facts_list::*;
 pub use session_monitor::*;
+pub use session_list::*;
+pub use session_detail::*;
+pub use session_comparison::*;
+pub use timeline::*;
+pub use token_chart::*;
+pub use statistics::*;
+pub use triage_list::*;
+pub use search_palette::*;
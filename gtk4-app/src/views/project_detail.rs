@@ -1,16 +1,53 @@
+use crate::capture::{
+    capture_image, capture_voice_note, is_image_file, Recorder, Whisper, WHISPER_BIN_ENV, WHISPER_MODEL_ENV,
+};
 use crate::db::Repository;
-use crate::models::{ContextSection, ExtractedFact, Project, SessionHistory};
-use crate::views::{ContextEditorView, FactsListView, SessionMonitorView};
+use crate::models::{
+    ContextSection, ExtractedFact, ExtractionPreferences, FactType, Project, ProjectPayload, ProjectStatus,
+    SessionHistory,
+};
+use crate::monitor_updates::MonitorUpdates;
+use crate::views::{
+    ContextEditorView, FactsListView, SessionComparisonView, SessionDetailView, SessionListView, SessionMonitorView,
+    TimelineView, TokenChartView,
+};
 use adw::prelude::*;
-use std::cell::RefCell;
+use anyhow::Context;
+use chrono::{DateTime, Utc};
+use gtk::{gio, glib};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
+/// Highest priority offered when editing a project
+const MAX_PRIORITY: f64 = 10.0;
+
 /// Project detail view with tabbed interface
 pub struct ProjectDetailView {
+    toolbar: adw::ToolbarView,
+    header: HeaderWidgets,
     container: gtk::Box,
     repository: Repository,
     project_id: String,
     project: Rc<RefCell<Option<Project>>>,
+    navigation_view: adw::NavigationView,
+    updates: MonitorUpdates,
+    on_deleted: Rc<RefCell<Option<Box<dyn Fn(&Project)>>>>,
+}
+
+/// Parts of the header and overview banner that follow the project's details
+#[derive(Clone)]
+struct HeaderWidgets {
+    title: adw::WindowTitle,
+    archive_action: gio::SimpleAction,
+    name: gtk::Label,
+    status: gtk::DropDown,
+    priority: gtk::Label,
+    tech_stack: gtk::Box,
+    repo_button: gtk::Button,
+    repo_path: gtk::Label,
+    activity: gtk::Label,
+    last_activity: Rc<Cell<Option<DateTime<Utc>>>>,
+    showing: Rc<Cell<bool>>, // Set while show() moves the status dropdown, so that isn't saved
 }
 
 impl ProjectDetailView {
@@ -18,15 +55,21 @@ impl ProjectDetailView {
     pub fn new(
         repository: Repository,
         project_id: String,
-        _navigation_view: adw::NavigationView,
+        navigation_view: adw::NavigationView,
+        updates: MonitorUpdates,
     ) -> Self {
         let container = gtk::Box::new(gtk::Orientation::Horizontal, 0);
 
         let mut view = Self {
+            toolbar: adw::ToolbarView::new(),
+            header: HeaderWidgets::new(),
             container,
             repository,
             project_id,
             project: Rc::new(RefCell::new(None)),
+            navigation_view,
+            updates,
+            on_deleted: Rc::new(RefCell::new(None)),
         };
 
         view.setup_ui();
@@ -37,6 +80,15 @@ impl ProjectDetailView {
 
     /// Setup the UI
     fn setup_ui(&mut self) {
+        self.toolbar.add_top_bar(&self.create_header());
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&self.create_banner());
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+        self.container.set_vexpand(true);
+        content.append(&self.container);
+        self.toolbar.set_content(Some(&content));
+
         // Main content area with tabs
         let main_content = gtk::Box::new(gtk::Orientation::Vertical, 0);
         main_content.set_hexpand(true);
@@ -52,19 +104,49 @@ impl ProjectDetailView {
         let context_page = tab_view.append(&context_editor.widget());
         context_page.set_title("Context");
 
-        // Session History Tab (placeholder)
-        let session_box = gtk::Box::new(gtk::Orientation::Vertical, 12);
-        session_box.set_margin_top(16);
-        session_box.set_margin_bottom(16);
-        session_box.set_margin_start(16);
-        session_box.set_margin_end(16);
+        // Session History Tab
+        let session_list = SessionListView::new(self.repository.clone(), self.project_id.clone());
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        session_list.connect_session_activated(move |session| {
+            navigation_view.push(&SessionDetailView::new(&repository, session).page());
+        });
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        session_list.connect_compare(move |first, second| match repository.compare_sessions(first, second) {
+            Ok(comparison) => navigation_view.push(&SessionComparisonView::new(&comparison).page()),
+            Err(e) => log::error!("Failed to compare sessions: {}", e),
+        });
+        let session_page = tab_view.append(&session_list.widget());
+        session_page.set_title("Sessions");
 
-        let session_label = gtk::Label::new(Some("Session history will be displayed here"));
-        session_label.add_css_class("dim-label");
-        session_box.append(&session_label);
+        // Timeline Tab
+        let timeline = TimelineView::new(self.repository.clone(), self.project_id.clone());
+        timeline.subscribe(&self.updates);
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        timeline.connect_session_activated(move |session_id| match repository.get_session(session_id) {
+            Ok(session) => navigation_view.push(&SessionDetailView::new(&repository, &session).page()),
+            Err(e) => log::error!("Failed to load session {}: {}", session_id, e),
+        });
+        let timeline_page = tab_view.append(&timeline.widget());
+        timeline_page.set_title("Timeline");
 
-        let session_page = tab_view.append(&session_box);
-        session_page.set_title("Sessions");
+        // Usage Tab
+        let token_charts = TokenChartView::new(self.repository.clone(), Some(self.project_id.clone()));
+        token_charts.subscribe(&self.updates);
+        let usage_box = token_charts.widget();
+        usage_box.set_margin_top(16);
+        usage_box.set_margin_bottom(16);
+        usage_box.set_margin_start(16);
+        usage_box.set_margin_end(16);
+        let usage_scrolled = gtk::ScrolledWindow::builder()
+            .child(&usage_box)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .vexpand(true)
+            .build();
+        let usage_page = tab_view.append(&usage_scrolled);
+        usage_page.set_title("Usage");
 
         // Compressed Context Tab (placeholder)
         let compressed_box = gtk::Box::new(gtk::Orientation::Vertical, 12);
@@ -91,12 +173,336 @@ impl ProjectDetailView {
         self.container.append(&main_content);
 
         // Sidebar for facts and session monitor
-        let sidebar = self.create_sidebar();
+        let sidebar = self.create_sidebar(&context_editor);
         self.container.append(&sidebar);
     }
 
+    /// Header with the project's name and status, and a menu to edit, archive or delete it
+    fn create_header(&self) -> adw::HeaderBar {
+        let header = adw::HeaderBar::new();
+        header.set_title_widget(Some(&self.header.title));
+
+        let edit_action = gio::SimpleAction::new("edit", None);
+        let toolbar = self.toolbar.clone();
+        let repository = self.repository.clone();
+        let project = self.project.clone();
+        let widgets = self.header.clone();
+        edit_action.connect_activate(move |_, _| {
+            Self::show_edit_dialog(&toolbar, &repository, &project, &widgets);
+        });
+
+        let repository = self.repository.clone();
+        let project = self.project.clone();
+        let widgets = self.header.clone();
+        self.header.archive_action.connect_activate(move |_, _| {
+            Self::set_status(&repository, &project, &widgets, ProjectStatus::Archived);
+        });
+
+        let delete_action = gio::SimpleAction::new("delete", None);
+        let toolbar = self.toolbar.clone();
+        let repository = self.repository.clone();
+        let project = self.project.clone();
+        let on_deleted = self.on_deleted.clone();
+        delete_action.connect_activate(move |_, _| {
+            Self::show_delete_dialog(&toolbar, &repository, &project, &on_deleted);
+        });
+
+        let group = gio::SimpleActionGroup::new();
+        group.add_action(&edit_action);
+        group.add_action(&self.header.archive_action);
+        group.add_action(&delete_action);
+        self.toolbar.insert_action_group("project", Some(&group));
+
+        let menu = gio::Menu::new();
+        menu.append(Some("Edit Project…"), Some("project.edit"));
+        menu.append_section(None, &{
+            let section = gio::Menu::new();
+            section.append(Some("Archive Project"), Some("project.archive"));
+            section.append(Some("Delete…"), Some("project.delete"));
+            section
+        });
+
+        let menu_button = gtk::MenuButton::builder()
+            .icon_name("view-more-symbolic")
+            .tooltip_text("Project Menu")
+            .menu_model(&menu)
+            .build();
+        menu_button.add_css_class("flat");
+        header.pack_end(&menu_button);
+
+        header
+    }
+
+    /// Banner with the project's name, a status switcher, priority, tech stack, repository and last activity
+    fn create_banner(&self) -> gtk::Box {
+        let widgets = &self.header;
+
+        let banner = gtk::Box::new(gtk::Orientation::Vertical, 8);
+        banner.add_css_class("project-banner");
+
+        let title_row = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        title_row.append(&widgets.name);
+        title_row.append(&widgets.status);
+        banner.append(&title_row);
+
+        let details_row = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        details_row.append(&widgets.priority);
+        details_row.append(&widgets.activity);
+        banner.append(&details_row);
+
+        let stack_row = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        stack_row.append(&widgets.tech_stack);
+        stack_row.append(&widgets.repo_button);
+        banner.append(&stack_row);
+
+        let repository = self.repository.clone();
+        let project = self.project.clone();
+        let header = widgets.clone();
+        widgets.status.connect_selected_notify(move |dropdown| {
+            if header.showing.get() {
+                return;
+            }
+            if let Some(status) = ProjectStatus::all().get(dropdown.selected() as usize) {
+                Self::set_status(&repository, &project, &header, *status);
+            }
+        });
+
+        let project = self.project.clone();
+        widgets.repo_button.connect_clicked(move |button| {
+            let Some(path) = project.borrow().as_ref().and_then(|project| project.repo_path.clone()) else {
+                return;
+            };
+            let window = button.root().and_downcast::<gtk::Window>();
+            gtk::FileLauncher::new(Some(&gio::File::for_path(&path))).launch(
+                window.as_ref(),
+                None::<&gio::Cancellable>,
+                move |result| {
+                    if let Err(e) = result {
+                        log::error!("Failed to open {}: {}", path, e);
+                    }
+                },
+            );
+        });
+
+        // Anything the monitor records for the project is activity
+        let repository = self.repository.clone();
+        let project_id = self.project_id.clone();
+        let header = widgets.clone();
+        self.updates.subscribe(move |update| {
+            if header.name.root().is_none() {
+                return glib::ControlFlow::Break;
+            }
+            if update.project_id() == Some(project_id.as_str()) {
+                header.touch(Self::last_activity(&repository, &project_id));
+            }
+            glib::ControlFlow::Continue
+        });
+
+        banner
+    }
+
+    /// Save a new status for the project, putting the old one back on the header if that fails
+    fn set_status(
+        repository: &Repository,
+        project: &Rc<RefCell<Option<Project>>>,
+        widgets: &HeaderWidgets,
+        status: ProjectStatus,
+    ) {
+        let Some(current) = project.borrow().clone() else {
+            return;
+        };
+        if current.status == status {
+            return;
+        }
+
+        let mut payload = ProjectPayload::from(&current);
+        payload.status = status;
+        match repository.update_project(&current.id, payload) {
+            Ok(updated) => {
+                log::info!("Project {} is now {}", updated.name, updated.status.display_name());
+                widgets.show(&updated);
+                *project.borrow_mut() = Some(updated);
+            }
+            Err(e) => {
+                log::error!("Failed to change the status of {}: {}", current.name, e);
+                widgets.show(&current);
+            }
+        }
+    }
+
+    fn last_activity(repository: &Repository, project_id: &str) -> Option<DateTime<Utc>> {
+        repository
+            .project_stats(project_id)
+            .map_err(|e| log::warn!("Failed to load activity of project {}: {}", project_id, e))
+            .ok()
+            .and_then(|stats| stats.last_activity)
+    }
+
+    /// Ask for new project details and save them
+    fn show_edit_dialog(
+        widget: &impl IsA<gtk::Widget>,
+        repository: &Repository,
+        project: &Rc<RefCell<Option<Project>>>,
+        widgets: &HeaderWidgets,
+    ) {
+        let Some(current) = project.borrow().clone() else {
+            return;
+        };
+
+        let parent = widget.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(parent.as_ref(), Some("Edit Project"), None);
+
+        let rows = gtk::ListBox::new();
+        rows.set_selection_mode(gtk::SelectionMode::None);
+        rows.add_css_class("boxed-list");
+
+        let name_row = adw::EntryRow::builder().title("Name").text(&current.name).build();
+        rows.append(&name_row);
+
+        let statuses = ProjectStatus::all();
+        let status_names: Vec<&str> = statuses.iter().map(|status| status.display_name()).collect();
+        let status_row = adw::ComboRow::builder()
+            .title("Status")
+            .model(&gtk::StringList::new(&status_names))
+            .selected(statuses.iter().position(|status| *status == current.status).unwrap_or(0) as u32)
+            .build();
+        rows.append(&status_row);
+
+        let priority_row = adw::SpinRow::builder().title("Priority").build();
+        priority_row.set_adjustment(Some(&gtk::Adjustment::new(
+            current.priority as f64,                   // value
+            0.0,                                       // min
+            MAX_PRIORITY.max(current.priority as f64), // max
+            1.0,                                       // step
+            1.0,                                       // page increment
+            0.0,                                       // page size
+        )));
+        rows.append(&priority_row);
+
+        let repo_path_row = adw::EntryRow::builder()
+            .title("Repository path")
+            .text(current.repo_path.as_deref().unwrap_or_default())
+            .build();
+        rows.append(&repo_path_row);
+
+        let tech_stack_row = adw::EntryRow::builder()
+            .title("Tech stack (comma separated)")
+            .text(current.tech_stack.join(", "))
+            .build();
+        rows.append(&tech_stack_row);
+
+        let description_row = adw::EntryRow::builder()
+            .title("Description")
+            .text(current.description.as_deref().unwrap_or_default())
+            .build();
+        rows.append(&description_row);
+
+        dialog.set_extra_child(Some(&rows));
+        dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("save"));
+        dialog.set_close_response("cancel");
+
+        // A project needs a name
+        let dialog_weak = dialog.downgrade();
+        name_row.connect_changed(move |row| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.set_response_enabled("save", !row.text().trim().is_empty());
+            }
+        });
+
+        let repository = repository.clone();
+        let project = project.clone();
+        let widgets = widgets.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "save" {
+                return;
+            }
+
+            let optional = |text: glib::GString| Some(text.trim().to_string()).filter(|text| !text.is_empty());
+            let mut payload = ProjectPayload::from(&current);
+            payload.name = name_row.text().trim().to_string();
+            payload.status = statuses.get(status_row.selected() as usize).copied().unwrap_or(current.status);
+            payload.priority = priority_row.value() as i32;
+            payload.repo_path = optional(repo_path_row.text());
+            payload.tech_stack = tech_stack_row
+                .text()
+                .split(',')
+                .map(|tech| tech.trim().to_string())
+                .filter(|tech| !tech.is_empty())
+                .collect();
+            payload.description = optional(description_row.text());
+
+            match repository.update_project(&current.id, payload) {
+                Ok(updated) => {
+                    log::info!("Updated project {}", updated.name);
+                    widgets.show(&updated);
+                    *project.borrow_mut() = Some(updated);
+                }
+                Err(e) => log::error!("Failed to update project {}: {}", current.name, e),
+            }
+        });
+
+        dialog.present();
+    }
+
+    /// Confirm with what the project holds, then move it and all of that to the trash
+    fn show_delete_dialog(
+        widget: &impl IsA<gtk::Widget>,
+        repository: &Repository,
+        project: &Rc<RefCell<Option<Project>>>,
+        on_deleted: &Rc<RefCell<Option<Box<dyn Fn(&Project)>>>>,
+    ) {
+        let Some(current) = project.borrow().clone() else {
+            return;
+        };
+        let contents = repository.project_contents(&current.id).unwrap_or_else(|e| {
+            log::warn!("Failed to count the contents of {}: {}", current.name, e);
+            Default::default()
+        });
+
+        let parent = widget.root().and_downcast::<gtk::Window>();
+        let dialog = adw::MessageDialog::new(
+            parent.as_ref(),
+            Some("Delete Project?"),
+            Some(&format!(
+                "'{}' and its {} will be moved to the trash.",
+                current.name,
+                contents.summary()
+            )),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("delete", "Delete")]);
+        dialog.set_response_appearance("delete", adw::ResponseAppearance::Destructive);
+        dialog.set_default_response(Some("cancel"));
+        dialog.set_close_response("cancel");
+
+        let repository = repository.clone();
+        let on_deleted = on_deleted.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "delete" {
+                return;
+            }
+            match repository.delete_project(&current.id) {
+                Ok(()) => {
+                    log::info!("Moved project {} to the trash", current.name);
+                    if let Some(on_deleted) = on_deleted.borrow().as_ref() {
+                        on_deleted(&current);
+                    }
+                }
+                Err(e) => log::error!("Failed to delete project {}: {}", current.name, e),
+            }
+        });
+
+        dialog.present();
+    }
+
+    /// Call `f` with the project once it has been moved to the trash
+    pub fn connect_deleted(&self, f: impl Fn(&Project) + 'static) {
+        *self.on_deleted.borrow_mut() = Some(Box::new(f));
+    }
+
     /// Create the right sidebar
-    fn create_sidebar(&self) -> gtk::Box {
+    fn create_sidebar(&self, context_editor: &ContextEditorView) -> gtk::Box {
         let sidebar = gtk::Box::new(gtk::Orientation::Vertical, 0);
         sidebar.set_width_request(320);
         sidebar.add_css_class("sidebar");
@@ -121,33 +527,292 @@ impl ProjectDetailView {
         monitor_section.append(&monitor_title);
 
         let session_monitor = SessionMonitorView::new(self.repository.clone(), self.project_id.clone());
+        session_monitor.subscribe(&self.updates);
         monitor_section.append(&session_monitor.widget());
 
         sidebar_content.append(&monitor_section);
 
         // Facts List
         let facts_section = gtk::Box::new(gtk::Orientation::Vertical, 8);
+        let facts_header = gtk::Box::new(gtk::Orientation::Horizontal, 6);
         let facts_title = gtk::Label::new(Some("Extracted Facts"));
         facts_title.add_css_class("sidebar-title");
         facts_title.set_xalign(0.0);
-        facts_section.append(&facts_title);
+        facts_title.set_hexpand(true);
+        facts_header.append(&facts_title);
 
-        let facts_list = FactsListView::new(self.repository.clone(), self.project_id.clone());
+        let facts_list = Rc::new(FactsListView::new(self.repository.clone(), self.project_id.clone()));
+        facts_list.subscribe(&self.updates);
+        let context_editor = context_editor.clone();
+        facts_list.connect_promoted(move |_| context_editor.sections_saved());
+
+        let voice_button = Self::create_voice_note_button(
+            self.repository.clone(),
+            self.project_id.clone(),
+            facts_list.clone(),
+        );
+        facts_header.append(&voice_button);
+        facts_section.append(&facts_header);
         facts_section.append(&facts_list.widget());
 
+        // Screenshots dropped anywhere on the project view are OCR'd into facts
+        self.container.add_controller(Self::create_image_drop_target(
+            self.repository.clone(),
+            self.project_id.clone(),
+            facts_list.clone(),
+        ));
+
         sidebar_content.append(&facts_section);
 
+        // Sync enablement
+        let sync_group = adw::PreferencesGroup::builder().title("Sync").build();
+        let sync_row = adw::SwitchRow::builder()
+            .title("Sync with server")
+            .subtitle("Off keeps this project local-only")
+            .active(self.repository.is_project_synced(&self.project_id).unwrap_or(true))
+            .build();
+
+        let repository = self.repository.clone();
+        let project_id = self.project_id.clone();
+        sync_row.connect_active_notify(move |row| {
+            if let Err(e) = repository.set_project_sync(&project_id, row.is_active()) {
+                log::error!("Failed to update sync setting: {}", e);
+            }
+        });
+
+        sync_group.add(&sync_row);
+        sidebar_content.append(&sync_group);
+
+        // Keeping the repository's CLAUDE.md current
+        let export_group = adw::PreferencesGroup::builder().title("CLAUDE.md").build();
+        let export_row = adw::SwitchRow::builder()
+            .title("Export on every save")
+            .subtitle("Rewrite it in the repository when context is edited here")
+            .active(self.repository.exports_on_save(&self.project_id).unwrap_or(false))
+            .build();
+
+        let repository = self.repository.clone();
+        let project_id = self.project_id.clone();
+        export_row.connect_active_notify(move |row| {
+            if let Err(e) = repository.set_project_export_on_save(&project_id, row.is_active()) {
+                log::error!("Failed to update export setting: {}", e);
+            }
+        });
+
+        export_group.add(&export_row);
+        sidebar_content.append(&export_group);
+
+        // Fact approval
+        let approval_group = adw::PreferencesGroup::builder().title("Extracted Facts").build();
+        let approval_row = adw::SwitchRow::builder()
+            .title("Require approval")
+            .subtitle("New facts stay out of exports and stats until reviewed")
+            .active(self.repository.requires_fact_approval(&self.project_id).unwrap_or(false))
+            .build();
+
+        let repository = self.repository.clone();
+        let project_id = self.project_id.clone();
+        approval_row.connect_active_notify(move |row| {
+            if let Err(e) = repository.set_project_fact_approval(&project_id, row.is_active()) {
+                log::error!("Failed to update fact approval setting: {}", e);
+            }
+        });
+
+        approval_group.add(&approval_row);
+
+        // Extraction preferences
+        let preferences = self.repository.extraction_preferences(&self.project_id).unwrap_or_default();
+
+        let importance_row = adw::SpinRow::builder()
+            .title("Minimum importance")
+            .subtitle("Less important facts are not extracted")
+            .build();
+        importance_row.set_adjustment(Some(&gtk::Adjustment::new(
+            preferences.min_importance as f64, // value
+            1.0,                               // min
+            5.0,                               // max
+            1.0,                               // step
+            1.0,                               // page increment
+            0.0,                               // page size
+        )));
+
+        let repository = self.repository.clone();
+        let project_id = self.project_id.clone();
+        importance_row.connect_value_notify(move |row| {
+            let min_importance = row.value() as i32;
+            Self::update_extraction_preferences(&repository, &project_id, |preferences| {
+                preferences.min_importance = min_importance;
+            });
+        });
+        approval_group.add(&importance_row);
+
+        let types_row = adw::ExpanderRow::builder()
+            .title("Fact types")
+            .subtitle("Kinds of facts extracted from sessions")
+            .build();
+        for fact_type in FactType::all() {
+            let type_row = adw::SwitchRow::builder()
+                .title(fact_type.display_name())
+                .active(preferences.is_enabled(fact_type))
+                .build();
+
+            let repository = self.repository.clone();
+            let project_id = self.project_id.clone();
+            type_row.connect_active_notify(move |row| {
+                let enabled = row.is_active();
+                Self::update_extraction_preferences(&repository, &project_id, |preferences| {
+                    preferences.set_enabled(fact_type, enabled);
+                });
+            });
+            types_row.add_row(&type_row);
+        }
+        approval_group.add(&types_row);
+
+        sidebar_content.append(&approval_group);
+
         scrolled.set_child(Some(&sidebar_content));
         sidebar.append(&scrolled);
 
         sidebar
     }
 
+    /// Change a project's extraction preferences as stored, so rows don't overwrite each other
+    fn update_extraction_preferences(
+        repository: &Repository,
+        project_id: &str,
+        change: impl FnOnce(&mut ExtractionPreferences),
+    ) {
+        let result = repository.extraction_preferences(project_id).and_then(|mut preferences| {
+            change(&mut preferences);
+            repository.set_extraction_preferences(project_id, &preferences)
+        });
+        if let Err(e) = result {
+            log::error!("Failed to update extraction preferences: {}", e);
+        }
+    }
+
+    /// Create the record/stop toggle for voice notes
+    ///
+    /// The first click starts recording and the second stops it; the note is then
+    /// transcribed with whisper.cpp in the background and saved as an Insight.
+    fn create_voice_note_button(
+        repository: Repository,
+        project_id: String,
+        facts_list: Rc<FactsListView>,
+    ) -> gtk::ToggleButton {
+        let button = gtk::ToggleButton::builder()
+            .icon_name("audio-input-microphone-symbolic")
+            .tooltip_text("Record voice note")
+            .valign(gtk::Align::Center)
+            .build();
+        button.add_css_class("flat");
+
+        let recorder: Rc<RefCell<Option<Recorder>>> = Rc::new(RefCell::new(None));
+
+        button.connect_toggled(move |button| {
+            if button.is_active() {
+                match Recorder::start() {
+                    Ok(started) => {
+                        *recorder.borrow_mut() = Some(started);
+                        button.set_tooltip_text(Some("Stop and save voice note"));
+                    }
+                    Err(e) => {
+                        log::error!("Failed to start recording: {}", e);
+                        button.set_active(false);
+                    }
+                }
+                return;
+            }
+
+            let Some(active) = recorder.borrow_mut().take() else {
+                return;
+            };
+
+            button.set_tooltip_text(Some("Transcribing voice note..."));
+            button.set_sensitive(false);
+
+            let button = button.clone();
+            let repository = repository.clone();
+            let project_id = project_id.clone();
+            let facts_list = facts_list.clone();
+
+            glib::spawn_future_local(async move {
+                let result = gio::spawn_blocking(move || {
+                    let audio = active.stop()?;
+                    let whisper = Whisper::from_env().with_context(|| {
+                        format!("Voice notes need {} and {} to be set", WHISPER_BIN_ENV, WHISPER_MODEL_ENV)
+                    });
+                    let fact = whisper.and_then(|w| capture_voice_note(&repository, &project_id, &audio, &w));
+                    std::fs::remove_file(&audio).ok();
+                    fact
+                })
+                .await;
+
+                match result {
+                    Ok(Ok(fact)) => {
+                        log::info!("Captured voice note as fact {}", fact.id);
+                        facts_list.reload();
+                    }
+                    Ok(Err(e)) => log::error!("Failed to capture voice note: {}", e),
+                    Err(_) => log::error!("Voice note transcription panicked"),
+                }
+
+                button.set_tooltip_text(Some("Record voice note"));
+                button.set_sensitive(true);
+            });
+        });
+
+        button
+    }
+
+    /// Create a drop target that captures facts from dropped screenshots
+    fn create_image_drop_target(
+        repository: Repository,
+        project_id: String,
+        facts_list: Rc<FactsListView>,
+    ) -> gtk::DropTarget {
+        let drop_target = gtk::DropTarget::new(gio::File::static_type(), gtk::gdk::DragAction::COPY);
+
+        drop_target.connect_drop(move |_, value, _, _| {
+            let Some(path) = value.get::<gio::File>().ok().and_then(|file| file.path()) else {
+                return false;
+            };
+            if !is_image_file(&path) {
+                log::warn!("Ignoring dropped file that is not an image: {}", path.display());
+                return false;
+            }
+
+            let repository = repository.clone();
+            let project_id = project_id.clone();
+            let facts_list = facts_list.clone();
+
+            glib::spawn_future_local(async move {
+                let result = gio::spawn_blocking(move || capture_image(&repository, &project_id, &path)).await;
+
+                match result {
+                    Ok(Ok(facts)) if facts.is_empty() => log::info!("No facts found in dropped image"),
+                    Ok(Ok(facts)) => {
+                        log::info!("Captured {} fact(s) from dropped image", facts.len());
+                        facts_list.reload();
+                    }
+                    Ok(Err(e)) => log::error!("Failed to capture image: {}", e),
+                    Err(_) => log::error!("Image capture panicked"),
+                }
+            });
+
+            true
+        });
+
+        drop_target
+    }
+
     /// Load project details
     fn load_project(&self) {
         match self.repository.get_project(&self.project_id) {
             Ok(loaded_project) => {
                 log::info!("Loaded project: {}", loaded_project.name);
+                self.header.show(&loaded_project);
+                self.header.touch(Self::last_activity(&self.repository, &self.project_id));
                 *self.project.borrow_mut() = Some(loaded_project);
             }
             Err(e) => {
@@ -157,7 +822,101 @@ impl ProjectDetailView {
     }
 
     /// Get the widget
-    pub fn widget(&self) -> gtk::Box {
-        self.container.clone()
+    pub fn widget(&self) -> adw::ToolbarView {
+        self.toolbar.clone()
+    }
+}
+
+impl HeaderWidgets {
+    fn new() -> Self {
+        let name = gtk::Label::builder()
+            .xalign(0.0)
+            .hexpand(true)
+            .ellipsize(gtk::pango::EllipsizeMode::End)
+            .build();
+        name.add_css_class("title-2");
+
+        let statuses = ProjectStatus::all();
+        let status_names: Vec<&str> = statuses.iter().map(|status| status.display_name()).collect();
+        let status = gtk::DropDown::from_strings(&status_names);
+        status.set_valign(gtk::Align::Center);
+        status.set_tooltip_text(Some("Status"));
+
+        let priority = gtk::Label::new(None);
+        priority.add_css_class("dim-label");
+        let activity = gtk::Label::new(Some("No activity yet"));
+        activity.add_css_class("dim-label");
+
+        let repo_path = gtk::Label::builder()
+            .ellipsize(gtk::pango::EllipsizeMode::Middle)
+            .max_width_chars(48)
+            .build();
+        let repo_content = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        repo_content.append(&gtk::Image::from_icon_name("folder-open-symbolic"));
+        repo_content.append(&repo_path);
+        let repo_button = gtk::Button::builder()
+            .child(&repo_content)
+            .tooltip_text("Open the repository folder")
+            .visible(false)
+            .build();
+        repo_button.add_css_class("flat");
+
+        let tech_stack = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+        tech_stack.set_hexpand(true);
+
+        Self {
+            title: adw::WindowTitle::new("", ""),
+            archive_action: gio::SimpleAction::new("archive", None),
+            name,
+            status,
+            priority,
+            tech_stack,
+            repo_button,
+            repo_path,
+            activity,
+            last_activity: Rc::new(Cell::new(None)),
+            showing: Rc::new(Cell::new(false)),
+        }
+    }
+
+    /// Show the project's details, offering Archive only while it isn't archived
+    fn show(&self, project: &Project) {
+        self.title.set_title(&project.name);
+        self.title.set_subtitle(project.status.display_name());
+        self.archive_action.set_enabled(project.status != ProjectStatus::Archived);
+
+        self.name.set_text(&project.name);
+        self.showing.set(true);
+        let statuses = ProjectStatus::all();
+        self.status
+            .set_selected(statuses.iter().position(|status| *status == project.status).unwrap_or(0) as u32);
+        self.showing.set(false);
+        self.priority.set_text(&format!("Priority {}", project.priority));
+
+        while let Some(chip) = self.tech_stack.first_child() {
+            self.tech_stack.remove(&chip);
+        }
+        for tech in &project.tech_stack {
+            let chip = gtk::Label::new(Some(tech));
+            chip.add_css_class("tech-chip");
+            self.tech_stack.append(&chip);
+        }
+
+        self.repo_path.set_text(project.repo_path.as_deref().unwrap_or_default());
+        self.repo_button.set_visible(project.repo_path.is_some());
+
+        self.touch(Some(project.updated));
+    }
+
+    /// Show newer activity
+    fn touch(&self, at: Option<DateTime<Utc>>) {
+        let Some(latest) = self.last_activity.get().max(at) else {
+            return;
+        };
+        self.last_activity.set(Some(latest));
+        self.activity.set_text(&format!(
+            "Last activity: {}",
+            latest.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M")
+        ));
     }
 }
@@ -0,0 +1,191 @@
+use crate::async_repository::AsyncRepository;
+use crate::db::Repository;
+use crate::models::{SearchKind, SearchResult};
+use adw::prelude::*;
+use gtk::glib;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Most results listed at once
+const SEARCH_RESULT_LIMIT: usize = 50;
+
+/// Command palette searching every project, section and fact (Ctrl+K)
+#[derive(Clone)]
+pub struct SearchPalette {
+    window: adw::Window,
+    entry: gtk::SearchEntry,
+    list: gtk::ListBox,
+    repository: Repository,
+    results: Rc<RefCell<Vec<SearchResult>>>,
+    search_generation: Rc<Cell<u64>>,
+    on_activate: Rc<RefCell<Option<Box<dyn Fn(&SearchResult)>>>>,
+}
+
+impl SearchPalette {
+    /// Create the palette over `parent`
+    pub fn new(parent: &impl IsA<gtk::Window>, repository: Repository) -> Self {
+        let entry = gtk::SearchEntry::builder()
+            .placeholder_text("Search projects, sections and facts")
+            .hexpand(true)
+            .build();
+
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        list.add_css_class("boxed-list");
+        list.set_valign(gtk::Align::Start);
+        list.set_margin_top(12);
+        list.set_margin_bottom(12);
+        list.set_margin_start(12);
+        list.set_margin_end(12);
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+
+        let header = adw::HeaderBar::new();
+        header.set_title_widget(Some(&entry));
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&scrolled));
+
+        let window = adw::Window::builder()
+            .title("Search")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(560)
+            .default_height(420)
+            .content(&toolbar)
+            .build();
+
+        let palette = Self {
+            window,
+            entry,
+            list,
+            repository,
+            results: Rc::new(RefCell::new(Vec::new())),
+            search_generation: Rc::new(Cell::new(0)),
+            on_activate: Rc::new(RefCell::new(None)),
+        };
+
+        palette.connect_signals();
+        palette.show_results();
+
+        palette
+    }
+
+    fn connect_signals(&self) {
+        let palette = self.clone();
+        self.entry.connect_search_changed(move |_| palette.search());
+
+        // Enter opens the first result, Escape closes the palette
+        let palette = self.clone();
+        self.entry.connect_activate(move |_| {
+            let first = palette.results.borrow().first().cloned();
+            if let Some(result) = first {
+                palette.activate(&result);
+            }
+        });
+        let window = self.window.clone();
+        self.entry.connect_stop_search(move |_| window.close());
+
+        let palette = self.clone();
+        self.list.connect_row_activated(move |_, row| {
+            let result = usize::try_from(row.index())
+                .ok()
+                .and_then(|index| palette.results.borrow().get(index).cloned());
+            if let Some(result) = result {
+                palette.activate(&result);
+            }
+        });
+    }
+
+    /// Query the repository for the entry's text off the main loop
+    ///
+    /// Results of a search overtaken by newer typing are dropped.
+    fn search(&self) {
+        let query = self.entry.text().to_string();
+        let generation = self.search_generation.get() + 1;
+        self.search_generation.set(generation);
+
+        let palette = self.clone();
+        glib::spawn_future_local(async move {
+            let result = AsyncRepository::from(palette.repository.clone())
+                .run(move |repository| repository.search(&query, SEARCH_RESULT_LIMIT))
+                .await;
+
+            if palette.search_generation.get() != generation {
+                return;
+            }
+
+            *palette.results.borrow_mut() = result.unwrap_or_else(|e| {
+                log::error!("Search failed: {}", e);
+                Vec::new()
+            });
+            palette.show_results();
+        });
+    }
+
+    fn show_results(&self) {
+        while let Some(row) = self.list.first_child() {
+            self.list.remove(&row);
+        }
+
+        let results = self.results.borrow();
+        if results.is_empty() {
+            let message = if self.entry.text().trim().is_empty() {
+                "Type to search projects, sections and facts"
+            } else {
+                "No matches"
+            };
+            let row = adw::ActionRow::builder().title(message).activatable(false).build();
+            row.add_css_class("dim-label");
+            self.list.append(&row);
+            return;
+        }
+
+        for result in results.iter() {
+            self.list.append(&Self::create_result_row(result));
+        }
+    }
+
+    fn create_result_row(result: &SearchResult) -> adw::ActionRow {
+        let subtitle = match result.kind {
+            SearchKind::Project => result.kind.display_name().to_string(),
+            _ => format!("{} · {}", result.kind.display_name(), result.project_name),
+        };
+        let title = result.title.lines().next().unwrap_or_default();
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(title).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .title_lines(1)
+            .activatable(true)
+            .build();
+        row.set_tooltip_text(Some(&result.title));
+        row.add_prefix(&gtk::Image::from_icon_name(result.kind.icon_name()));
+        row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+
+        row
+    }
+
+    fn activate(&self, result: &SearchResult) {
+        self.window.close();
+        if let Some(on_activate) = self.on_activate.borrow().as_ref() {
+            on_activate(result);
+        }
+    }
+
+    /// Call `f` with the result picked from the palette, after it closes
+    pub fn connect_result_activated(&self, f: impl Fn(&SearchResult) + 'static) {
+        *self.on_activate.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Show the palette with the entry focused
+    pub fn present(&self) {
+        self.window.present();
+        self.entry.grab_focus();
+    }
+}
@@ -0,0 +1,289 @@
+use crate::db::Repository;
+use crate::markdown::markdown_to_pango;
+use crate::models::{ContextSection, ContextSectionPayload, SectionTemplatePayload, SectionType};
+use adw::prelude::*;
+use gtk::glib;
+use sourceview5::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Window editing a context section's markdown, with a live rendered preview beside it
+#[derive(Clone)]
+pub struct SectionEditor {
+    window: adw::Window,
+    toast_overlay: adw::ToastOverlay,
+    title_entry: gtk::Entry,
+    type_dropdown: gtk::DropDown,
+    buffer: sourceview5::Buffer,
+    save_btn: gtk::Button,
+    repository: Repository,
+    section: ContextSection,
+    on_saved: Rc<RefCell<Option<Box<dyn Fn(&ContextSection)>>>>,
+}
+
+impl SectionEditor {
+    /// Create the editor for `section` over `parent`
+    pub fn new(parent: &impl IsA<gtk::Window>, repository: Repository, section: ContextSection) -> Self {
+        let title_entry = gtk::Entry::builder()
+            .text(section.title.as_str())
+            .placeholder_text("Title")
+            .hexpand(true)
+            .build();
+
+        let section_types = SectionType::all();
+        let type_names: Vec<&str> = section_types.iter().map(|section_type| section_type.display_name()).collect();
+        let type_dropdown = gtk::DropDown::from_strings(&type_names);
+        type_dropdown.set_selected(
+            section_types
+                .iter()
+                .position(|section_type| *section_type == section.section_type)
+                .unwrap_or(0) as u32,
+        );
+        type_dropdown.set_tooltip_text(Some("Section type"));
+
+        let details = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        details.set_margin_top(12);
+        details.set_margin_bottom(12);
+        details.set_margin_start(12);
+        details.set_margin_end(12);
+        details.append(&title_entry);
+        details.append(&type_dropdown);
+
+        let buffer = sourceview5::Buffer::new(None);
+        if let Some(language) = sourceview5::LanguageManager::default().language("markdown") {
+            buffer.set_language(Some(&language));
+        }
+        buffer.set_highlight_syntax(true);
+        Self::follow_style(&buffer);
+        buffer.set_text(&section.content);
+
+        let source_view = sourceview5::View::with_buffer(&buffer);
+        source_view.set_monospace(true);
+        source_view.set_wrap_mode(gtk::WrapMode::WordChar);
+        source_view.set_top_margin(12);
+        source_view.set_bottom_margin(12);
+        source_view.set_left_margin(12);
+        source_view.set_right_margin(12);
+
+        let preview = gtk::Label::builder()
+            .wrap(true)
+            .wrap_mode(gtk::pango::WrapMode::WordChar)
+            .xalign(0.0)
+            .yalign(0.0)
+            .valign(gtk::Align::Start)
+            .selectable(true)
+            .margin_top(12)
+            .margin_bottom(12)
+            .margin_start(12)
+            .margin_end(12)
+            .build();
+        preview.set_markup(&markdown_to_pango(&section.content));
+
+        let paned = gtk::Paned::builder()
+            .orientation(gtk::Orientation::Horizontal)
+            .start_child(
+                &gtk::ScrolledWindow::builder()
+                    .child(&source_view)
+                    .hscrollbar_policy(gtk::PolicyType::Never)
+                    .build(),
+            )
+            .end_child(
+                &gtk::ScrolledWindow::builder()
+                    .child(&preview)
+                    .hscrollbar_policy(gtk::PolicyType::Never)
+                    .build(),
+            )
+            .resize_start_child(true)
+            .resize_end_child(true)
+            .vexpand(true)
+            .build();
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&details);
+        content.append(&gtk::Separator::new(gtk::Orientation::Horizontal));
+        content.append(&paned);
+
+        let toast_overlay = adw::ToastOverlay::new();
+        toast_overlay.set_child(Some(&content));
+
+        let cancel_btn = gtk::Button::with_label("Cancel");
+        let save_btn = gtk::Button::with_label("Save");
+        save_btn.add_css_class("suggested-action");
+        let template_btn = gtk::Button::builder()
+            .icon_name("document-save-as-symbolic")
+            .tooltip_text("Save as Template")
+            .build();
+
+        let header = adw::HeaderBar::builder()
+            .show_start_title_buttons(false)
+            .show_end_title_buttons(false)
+            .build();
+        header.set_title_widget(Some(&adw::WindowTitle::new("Edit Section", &section.title)));
+        header.pack_start(&cancel_btn);
+        header.pack_end(&save_btn);
+        header.pack_end(&template_btn);
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&toast_overlay));
+
+        let window = adw::Window::builder()
+            .title("Edit Section")
+            .modal(true)
+            .transient_for(parent)
+            .default_width(1000)
+            .default_height(640)
+            .content(&toolbar)
+            .build();
+
+        let editor = Self {
+            window,
+            toast_overlay,
+            title_entry,
+            type_dropdown,
+            buffer,
+            save_btn,
+            repository,
+            section,
+            on_saved: Rc::new(RefCell::new(None)),
+        };
+
+        // Render as the text changes
+        editor.buffer.connect_changed(move |buffer| {
+            let (start, end) = buffer.bounds();
+            preview.set_markup(&markdown_to_pango(&buffer.text(&start, &end, false)));
+        });
+
+        // A section needs a title
+        let save_btn = editor.save_btn.clone();
+        editor.title_entry.connect_changed(move |entry| {
+            save_btn.set_sensitive(!entry.text().trim().is_empty());
+        });
+
+        let window = editor.window.clone();
+        cancel_btn.connect_clicked(move |_| window.close());
+
+        let editor_for_save = editor.clone();
+        editor.save_btn.connect_clicked(move |_| editor_for_save.save());
+
+        let editor_for_template = editor.clone();
+        template_btn.connect_clicked(move |_| editor_for_template.show_template_dialog());
+
+        editor
+    }
+
+    /// Highlight with the light or dark Adwaita scheme, whichever the app uses
+    fn follow_style(buffer: &sourceview5::Buffer) {
+        let apply = |buffer: &sourceview5::Buffer, dark: bool| {
+            let scheme_id = if dark { "Adwaita-dark" } else { "Adwaita" };
+            if let Some(scheme) = sourceview5::StyleSchemeManager::default().scheme(scheme_id) {
+                buffer.set_style_scheme(Some(&scheme));
+            }
+        };
+
+        let style_manager = adw::StyleManager::default();
+        apply(buffer, style_manager.is_dark());
+        let buffer_weak = buffer.downgrade();
+        style_manager.connect_dark_notify(move |style_manager| {
+            if let Some(buffer) = buffer_weak.upgrade() {
+                apply(&buffer, style_manager.is_dark());
+            }
+        });
+    }
+
+    /// The section as edited so far
+    fn edited(&self) -> ContextSectionPayload {
+        let (start, end) = self.buffer.bounds();
+        let mut payload = ContextSectionPayload::from(&self.section);
+        payload.title = self.title_entry.text().trim().to_string();
+        payload.content = self.buffer.text(&start, &end, false).to_string();
+        payload.section_type = SectionType::all()
+            .get(self.type_dropdown.selected() as usize)
+            .copied()
+            .unwrap_or(self.section.section_type);
+        payload
+    }
+
+    /// Write the title, type and content back to the section and close
+    fn save(&self) {
+        match self.repository.update_context_section(&self.section.id, self.edited()) {
+            Ok(saved) => {
+                log::info!("Saved section {}", saved.title);
+                if let Some(on_saved) = self.on_saved.borrow().as_ref() {
+                    on_saved(&saved);
+                }
+                self.window.close();
+            }
+            Err(e) => {
+                log::error!("Failed to save section {}: {}", self.section.title, e);
+                self.toast_overlay.add_toast(adw::Toast::new(&format!("Failed to save: {}", e)));
+            }
+        }
+    }
+
+    /// Ask for a name and save the type, title and content as edited as a template
+    fn show_template_dialog(&self) {
+        let dialog = adw::MessageDialog::new(
+            Some(&self.window),
+            Some("Save as Template"),
+            Some("Templates can start new sections in any project. Saving under an existing name replaces that template."),
+        );
+        dialog.add_responses(&[("cancel", "Cancel"), ("save", "Save")]);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("save"));
+        dialog.set_close_response("cancel");
+
+        let name_row = adw::EntryRow::builder()
+            .title("Name")
+            .text(self.title_entry.text().trim())
+            .build();
+        let rows = gtk::ListBox::new();
+        rows.set_selection_mode(gtk::SelectionMode::None);
+        rows.add_css_class("boxed-list");
+        rows.append(&name_row);
+        dialog.set_extra_child(Some(&rows));
+
+        // A template needs a name
+        dialog.set_response_enabled("save", !name_row.text().trim().is_empty());
+        let dialog_weak = dialog.downgrade();
+        name_row.connect_changed(move |row| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.set_response_enabled("save", !row.text().trim().is_empty());
+            }
+        });
+
+        let editor = self.clone();
+        dialog.connect_response(None, move |_, response| {
+            if response != "save" {
+                return;
+            }
+            let edited = editor.edited();
+            let payload = SectionTemplatePayload {
+                name: name_row.text().trim().to_string(),
+                section_type: edited.section_type,
+                title: edited.title,
+                content: edited.content,
+            };
+            let toast = match editor.repository.save_section_template(payload) {
+                Ok(template) => format!("Saved template '{}'", template.name),
+                Err(e) => {
+                    log::error!("Failed to save template: {}", e);
+                    format!("Failed to save template: {}", e)
+                }
+            };
+            editor.toast_overlay.add_toast(adw::Toast::new(&glib::markup_escape_text(&toast)));
+        });
+
+        dialog.present();
+    }
+
+    /// Call `f` with the section once it is saved
+    pub fn connect_saved(&self, f: impl Fn(&ContextSection) + 'static) {
+        *self.on_saved.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Show the editor
+    pub fn present(&self) {
+        self.window.present();
+    }
+}
@@ -0,0 +1,232 @@
+use crate::models::{format_number_with_separator, ExtractedFact, SectionChange, SessionComparison, SessionHistory};
+use crate::utils::{ContextDiff, DiffLineKind};
+use crate::views::token_bar;
+use adw::prelude::*;
+use gtk::glib;
+
+/// Two sessions of a project side by side, with what changed in between
+pub struct SessionComparisonView {
+    page: adw::NavigationPage,
+}
+
+impl SessionComparisonView {
+    /// Create a navigation page for `comparison`
+    pub fn new(comparison: &SessionComparison) -> Self {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 24);
+        content.set_margin_top(16);
+        content.set_margin_bottom(16);
+        content.set_margin_start(16);
+        content.set_margin_end(16);
+
+        let sessions = gtk::Box::new(gtk::Orientation::Horizontal, 16);
+        sessions.set_homogeneous(true);
+        sessions.append(&Self::create_session_group("Before", &comparison.from));
+        sessions.append(&Self::create_session_group("After", &comparison.to));
+        content.append(&sessions);
+
+        content.append(&Self::create_delta_group(comparison));
+
+        if !comparison.has_changes() {
+            let label = gtk::Label::new(Some("No facts or context sections changed between these sessions"));
+            label.add_css_class("dim-label");
+            content.append(&label);
+        }
+        if !comparison.added_facts.is_empty() {
+            content.append(&Self::create_facts_group("Added Facts", "list-add-symbolic", &comparison.added_facts));
+        }
+        if !comparison.removed_facts.is_empty() {
+            content.append(&Self::create_facts_group(
+                "Removed Facts",
+                "list-remove-symbolic",
+                &comparison.removed_facts,
+            ));
+        }
+        for change in &comparison.changed_sections {
+            content.append(&Self::create_section_change(change));
+        }
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&content)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scrolled.add_css_class("scrolled-content");
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        toolbar.set_content(Some(&scrolled));
+
+        let page = adw::NavigationPage::builder()
+            .title("Compare Sessions")
+            .child(&toolbar)
+            .build();
+
+        Self { page }
+    }
+
+    /// Summary, time, token bar and fact count of one of the sessions
+    fn create_session_group(title: &str, session: &SessionHistory) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(title)
+            .description(glib::markup_escape_text(&session.summary).as_str())
+            .build();
+
+        let started = session.session_start.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M");
+        let row = adw::ActionRow::builder()
+            .title(started.to_string())
+            .subtitle(format!(
+                "{} · {} fact{}",
+                session.duration_display(),
+                session.facts_extracted,
+                if session.facts_extracted == 1 { "" } else { "s" }
+            ))
+            .build();
+        group.add(&row);
+
+        let tokens_row = adw::ActionRow::builder().title("Tokens").build();
+        let bar = token_bar(session);
+        bar.set_hexpand(true);
+        bar.set_valign(gtk::Align::Center);
+        tokens_row.add_suffix(&bar);
+        group.add(&tokens_row);
+
+        group
+    }
+
+    /// How much the later session differs in tokens and facts
+    fn create_delta_group(comparison: &SessionComparison) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Changes").build();
+
+        let token_delta = comparison.token_delta();
+        let sign = if token_delta < 0 { "-" } else { "+" };
+        group.add(&Self::delta_row(
+            "Tokens",
+            &format!("{}{}", sign, format_number_with_separator(token_delta.abs())),
+        ));
+        group.add(&Self::delta_row("Facts extracted", &format!("{:+}", comparison.fact_delta())));
+        group.add(&Self::delta_row(
+            "Facts in the project",
+            &format!("+{} -{}", comparison.added_facts.len(), comparison.removed_facts.len()),
+        ));
+        group.add(&Self::delta_row(
+            "Context sections changed",
+            &comparison.changed_sections.len().to_string(),
+        ));
+
+        group
+    }
+
+    fn delta_row(title: &str, value: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder().title(title).build();
+        let label = gtk::Label::new(Some(value));
+        label.add_css_class("numeric");
+        row.add_suffix(&label);
+        row
+    }
+
+    fn create_facts_group(title: &str, icon_name: &str, facts: &[ExtractedFact]) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title(title).build();
+        for fact in facts {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&fact.content_preview()).as_str())
+                .subtitle(
+                    glib::markup_escape_text(&format!(
+                        "{} · {}",
+                        fact.fact_type.display_name(),
+                        fact.importance_stars()
+                    ))
+                    .as_str(),
+                )
+                .build();
+            row.set_tooltip_text(Some(&fact.content));
+            row.add_prefix(&gtk::Image::from_icon_name(icon_name));
+            group.add(&row);
+        }
+        group
+    }
+
+    /// A changed section as it read before and after, removed and added lines highlighted
+    fn create_section_change(change: &SectionChange) -> adw::PreferencesGroup {
+        let before = change.before.as_deref().unwrap_or_default();
+        let after = change.after.as_deref().unwrap_or_default();
+        let diff = ContextDiff::new(before, after, &change.title);
+
+        let description = match (&change.before, &change.after) {
+            (None, _) => "New section".to_string(),
+            (_, None) => "Deleted section".to_string(),
+            _ => diff.summary(),
+        };
+        let group = adw::PreferencesGroup::builder()
+            .title(glib::markup_escape_text(&change.title).as_str())
+            .description(description)
+            .build();
+
+        let before_buffer = Self::create_diff_buffer();
+        let after_buffer = Self::create_diff_buffer();
+        for (kind, line) in diff.lines() {
+            // Drop the +/-/space marker, the column and color already say it
+            let text = format!("{}\n", line.get(1..).unwrap_or_default());
+            match kind {
+                DiffLineKind::Header => {}
+                DiffLineKind::Removed => {
+                    before_buffer.insert_with_tags_by_name(&mut before_buffer.end_iter(), &text, &["removed"]);
+                }
+                DiffLineKind::Added => {
+                    after_buffer.insert_with_tags_by_name(&mut after_buffer.end_iter(), &text, &["added"]);
+                }
+                DiffLineKind::Hunk => {
+                    for buffer in [&before_buffer, &after_buffer] {
+                        buffer.insert_with_tags_by_name(&mut buffer.end_iter(), &format!("{}\n", line), &["hunk"]);
+                    }
+                }
+                DiffLineKind::Context => {
+                    for buffer in [&before_buffer, &after_buffer] {
+                        buffer.insert(&mut buffer.end_iter(), &text);
+                    }
+                }
+            }
+        }
+
+        let columns = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        columns.set_homogeneous(true);
+        columns.append(&Self::create_diff_column(&before_buffer));
+        columns.append(&Self::create_diff_column(&after_buffer));
+        group.add(&columns);
+
+        group
+    }
+
+    fn create_diff_buffer() -> gtk::TextBuffer {
+        let buffer = gtk::TextBuffer::new(None);
+        for (name, color) in [("added", "#2ec27e"), ("removed", "#e01b24"), ("hunk", "#3584e4")] {
+            buffer.create_tag(Some(name), &[("foreground", &color)]);
+        }
+        buffer
+    }
+
+    fn create_diff_column(buffer: &gtk::TextBuffer) -> gtk::ScrolledWindow {
+        let text_view = gtk::TextView::builder()
+            .buffer(buffer)
+            .editable(false)
+            .monospace(true)
+            .wrap_mode(gtk::WrapMode::WordChar)
+            .top_margin(8)
+            .bottom_margin(8)
+            .left_margin(8)
+            .right_margin(8)
+            .build();
+        text_view.add_css_class("card");
+        gtk::ScrolledWindow::builder()
+            .child(&text_view)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .min_content_height(120)
+            .max_content_height(360)
+            .propagate_natural_height(true)
+            .build()
+    }
+
+    /// Get the navigation page
+    pub fn page(&self) -> adw::NavigationPage {
+        self.page.clone()
+    }
+}
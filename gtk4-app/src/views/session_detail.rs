@@ -0,0 +1,218 @@
+use crate::db::Repository;
+use crate::models::{day_label, format_number_with_separator, SessionHistory};
+use crate::views::{token_bar, FactsListView};
+use adw::prelude::*;
+use gtk::glib;
+
+/// Everything recorded about one session: timing, token usage, commits and facts
+pub struct SessionDetailView {
+    page: adw::NavigationPage,
+}
+
+impl SessionDetailView {
+    /// Create a navigation page for `session`
+    pub fn new(repository: &Repository, session: &SessionHistory) -> Self {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 24);
+        content.set_margin_top(16);
+        content.set_margin_bottom(16);
+        content.set_margin_start(16);
+        content.set_margin_end(16);
+
+        content.append(&Self::create_overview_group(repository, session));
+        if session.usage.total_tokens() > 0 {
+            content.append(&Self::create_usage_group(session));
+        }
+        if let Some(group) = Self::create_commits_group(repository, session) {
+            content.append(&group);
+        }
+        content.append(&Self::create_facts_group(repository, session));
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&content)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scrolled.add_css_class("scrolled-content");
+
+        let started = session.session_start.with_timezone(&chrono::Local);
+        let title = adw::WindowTitle::new(
+            session.summary.lines().next().unwrap_or_default(),
+            &format!(
+                "{}, {}",
+                day_label(session.start_day(), chrono::Local::now().date_naive()),
+                started.format("%H:%M")
+            ),
+        );
+        let header = adw::HeaderBar::new();
+        header.set_title_widget(Some(&title));
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&header);
+        toolbar.set_content(Some(&scrolled));
+
+        let page = adw::NavigationPage::builder()
+            .title("Session")
+            .child(&toolbar)
+            .build();
+
+        Self { page }
+    }
+
+    /// When the session ran, how full its context got and what it cost
+    fn create_overview_group(repository: &Repository, session: &SessionHistory) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Overview").build();
+
+        let time_format = "%Y-%m-%d %H:%M";
+        group.add(&Self::info_row(
+            "Started",
+            &session.session_start.with_timezone(&chrono::Local).format(time_format).to_string(),
+        ));
+        let ended = match session.session_end {
+            Some(end) => end.with_timezone(&chrono::Local).format(time_format).to_string(),
+            None => "Still running".to_string(),
+        };
+        group.add(&Self::info_row("Ended", &ended));
+        group.add(&Self::info_row("Duration", &session.duration_display()));
+        if let Some(git) = session.git_display() {
+            group.add(&Self::info_row("Git", &git));
+        }
+
+        let tokens_row = adw::ActionRow::builder().title("Tokens").build();
+        let bar = token_bar(session);
+        bar.set_hexpand(true);
+        bar.set_valign(gtk::Align::Center);
+        bar.set_size_request(240, -1);
+        tokens_row.add_suffix(&bar);
+        group.add(&tokens_row);
+
+        if session.usage.cost_usd > 0.0 {
+            group.add(&Self::info_row("Cost", &session.usage.cost_display()));
+        }
+
+        let transcripts = repository.list_session_transcripts(&session.id).unwrap_or_else(|e| {
+            log::warn!("Failed to load transcripts for session {}: {:#}", session.id, e);
+            Vec::new()
+        });
+        for transcript in transcripts {
+            let row = Self::info_row("Archived transcript", &transcript.path);
+            row.set_tooltip_text(Some(&format!(
+                "{} bytes, {} compressed",
+                format_number_with_separator(transcript.size),
+                format_number_with_separator(transcript.compressed_size)
+            )));
+            group.add(&row);
+        }
+
+        group
+    }
+
+    /// Tokens billed by kind, as the transcript reported them
+    fn create_usage_group(session: &SessionHistory) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Usage").build();
+        let usage = &session.usage;
+        for (title, tokens) in [
+            ("Input", usage.input_tokens),
+            ("Output", usage.output_tokens),
+            ("Cache writes", usage.cache_write_tokens),
+            ("Cache reads", usage.cache_read_tokens),
+        ] {
+            group.add(&Self::info_row(title, &format_number_with_separator(tokens)));
+        }
+        group
+    }
+
+    /// Commits made while the session ran, None if there were none
+    fn create_commits_group(repository: &Repository, session: &SessionHistory) -> Option<adw::PreferencesGroup> {
+        let commits = repository.list_session_commits(&session.id).unwrap_or_else(|e| {
+            log::warn!("Failed to load commits for session {}: {:#}", session.id, e);
+            Vec::new()
+        });
+        if commits.is_empty() {
+            return None;
+        }
+
+        let group = adw::PreferencesGroup::builder().title("Commits").build();
+        for commit in &commits {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&commit.summary).as_str())
+                .subtitle(
+                    glib::markup_escape_text(&format!(
+                        "{} · {} · {}",
+                        commit.short_sha(),
+                        commit.author,
+                        commit.committed.with_timezone(&chrono::Local).format("%H:%M")
+                    ))
+                    .as_str(),
+                )
+                .build();
+            group.add(&row);
+        }
+        Some(group)
+    }
+
+    /// Facts the monitor extracted from the session, each linking back to its transcript message
+    fn create_facts_group(repository: &Repository, session: &SessionHistory) -> adw::PreferencesGroup {
+        let facts = repository.list_session_facts(&session.id).unwrap_or_else(|e| {
+            log::error!("Failed to load facts for session {}: {}", session.id, e);
+            Vec::new()
+        });
+
+        let group = adw::PreferencesGroup::builder().title("Facts Extracted").build();
+        if facts.is_empty() {
+            let row = adw::ActionRow::builder().title("No facts from this session").build();
+            row.add_css_class("dim-label");
+            group.add(&row);
+            return group;
+        }
+        group.set_description(Some(&format!("{} fact{}", facts.len(), if facts.len() == 1 { "" } else { "s" })));
+
+        for fact in &facts {
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&fact.content_preview()).as_str())
+                .subtitle(
+                    glib::markup_escape_text(&format!(
+                        "{} · {} · {}",
+                        fact.fact_type.display_name(),
+                        fact.importance_stars(),
+                        fact.created.with_timezone(&chrono::Local).format("%H:%M")
+                    ))
+                    .as_str(),
+                )
+                .build();
+            row.set_tooltip_text(Some(&fact.content));
+            row.add_prefix(&gtk::Image::from_icon_name(fact.fact_type.icon_name()));
+            if fact.stale {
+                row.add_css_class("fact-stale");
+            }
+
+            if let Some(source) = fact.metadata.source.clone().filter(|source| source.log_path.is_some()) {
+                let source_btn = gtk::Button::builder()
+                    .icon_name("find-location-symbolic")
+                    .tooltip_text("Show where this was found")
+                    .valign(gtk::Align::Center)
+                    .build();
+                source_btn.add_css_class("flat");
+                source_btn.connect_clicked(move |button| FactsListView::show_source_dialog(button, &source));
+                row.add_suffix(&source_btn);
+            }
+            group.add(&row);
+        }
+
+        group
+    }
+
+    fn info_row(title: &str, value: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder().title(title).build();
+        let label = gtk::Label::new(Some(value));
+        label.add_css_class("dim-label");
+        label.set_selectable(true);
+        label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+        row.add_suffix(&label);
+        row
+    }
+
+    /// Get the navigation page
+    pub fn page(&self) -> adw::NavigationPage {
+        self.page.clone()
+    }
+}
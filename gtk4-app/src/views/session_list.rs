@@ -0,0 +1,294 @@
+use crate::db::{Page, Repository};
+use crate::models::{commits_display, day_label, SessionHistory};
+use crate::monitor::regenerate_session_title;
+use adw::prelude::*;
+use gtk::glib;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Most sessions listed at once
+const SESSION_LIST_LIMIT: usize = 100;
+
+/// Token usage above which a session's bar turns to a warning
+const TOKEN_WARNING_PERCENTAGE: f64 = 60.0;
+
+/// Session history of a project, newest first and grouped by day
+#[derive(Clone)]
+pub struct SessionListView {
+    container: gtk::Box,
+    days_box: gtk::Box,
+    repository: Repository,
+    project_id: String,
+    compare_mode: Rc<Cell<bool>>,
+    compare_checks: Rc<RefCell<Vec<(String, gtk::CheckButton)>>>,
+    on_activate: Rc<RefCell<Option<Box<dyn Fn(&SessionHistory)>>>>,
+    on_compare: Rc<RefCell<Option<Box<dyn Fn(&str, &str)>>>>,
+}
+
+impl SessionListView {
+    /// Create a new session list view
+    pub fn new(repository: Repository, project_id: String) -> Self {
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        let toolbar = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+        toolbar.set_margin_top(12);
+        toolbar.set_margin_start(16);
+        toolbar.set_margin_end(16);
+        let hint = gtk::Label::new(Some("Pick two sessions to compare"));
+        hint.add_css_class("dim-label");
+        hint.set_hexpand(true);
+        hint.set_xalign(0.0);
+        hint.set_visible(false);
+        toolbar.append(&hint);
+        let compare_btn = gtk::ToggleButton::builder()
+            .label("Compare")
+            .tooltip_text("Compare two sessions")
+            .build();
+        toolbar.append(&compare_btn);
+        container.append(&toolbar);
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .vscrollbar_policy(gtk::PolicyType::Automatic)
+            .vexpand(true)
+            .build();
+
+        let days_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        days_box.set_valign(gtk::Align::Start);
+        days_box.set_margin_top(16);
+        days_box.set_margin_bottom(16);
+        days_box.set_margin_start(16);
+        days_box.set_margin_end(16);
+
+        scrolled.set_child(Some(&days_box));
+        container.append(&scrolled);
+
+        let view = Self {
+            container,
+            days_box,
+            repository,
+            project_id,
+            compare_mode: Rc::new(Cell::new(false)),
+            compare_checks: Rc::new(RefCell::new(Vec::new())),
+            on_activate: Rc::new(RefCell::new(None)),
+            on_compare: Rc::new(RefCell::new(None)),
+        };
+
+        let view_for_toggle = view.clone();
+        compare_btn.connect_toggled(move |button| {
+            hint.set_visible(button.is_active());
+            view_for_toggle.set_compare_mode(button.is_active());
+        });
+
+        view.reload();
+
+        view
+    }
+
+    /// Reload sessions from the database
+    pub fn reload(&self) {
+        while let Some(child) = self.days_box.first_child() {
+            self.days_box.remove(&child);
+        }
+        self.compare_checks.borrow_mut().clear();
+
+        let sessions = self
+            .repository
+            .list_sessions_page(&self.project_id, Page::first(SESSION_LIST_LIMIT))
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load sessions: {}", e);
+                Vec::new()
+            });
+
+        if sessions.is_empty() {
+            let list = Self::create_day_list();
+            let row = adw::ActionRow::builder().title("No sessions recorded yet").build();
+            row.add_css_class("dim-label");
+            list.append(&row);
+            self.days_box.append(&list);
+            return;
+        }
+
+        // Sessions come newest first, so each day's sessions are consecutive
+        let today = chrono::Local::now().date_naive();
+        let mut current: Option<(chrono::NaiveDate, gtk::ListBox)> = None;
+        for session in &sessions {
+            let day = session.start_day();
+            let list = match &current {
+                Some((current_day, list)) if *current_day == day => list.clone(),
+                _ => {
+                    let heading = gtk::Label::new(Some(&day_label(day, today)));
+                    heading.set_xalign(0.0);
+                    heading.add_css_class("heading");
+                    if current.is_some() {
+                        heading.set_margin_top(12);
+                    }
+                    let list = Self::create_day_list();
+                    self.days_box.append(&heading);
+                    self.days_box.append(&list);
+                    current = Some((day, list.clone()));
+                    list
+                }
+            };
+            list.append(&self.create_session_row(session));
+        }
+    }
+
+    fn create_day_list() -> gtk::ListBox {
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        list.add_css_class("boxed-list");
+        list
+    }
+
+    /// Row for one session, with a button to rebuild its title from the transcript
+    fn create_session_row(&self, session: &SessionHistory) -> adw::ActionRow {
+        let mut subtitle = format!(
+            "{} · {} · {} fact{}",
+            session.session_start.with_timezone(&chrono::Local).format("%H:%M"),
+            session.duration_display(),
+            session.facts_extracted,
+            if session.facts_extracted == 1 { "" } else { "s" }
+        );
+        if let Some(git) = session.git_display() {
+            subtitle.push_str(&format!(" · {}", git));
+        }
+
+        let commits = self.repository.list_session_commits(&session.id).unwrap_or_else(|e| {
+            log::warn!("Failed to load commits for session {}: {:#}", session.id, e);
+            Vec::new()
+        });
+        if let Some(display) = commits_display(&commits) {
+            subtitle.push_str(&format!("\n{}", display));
+        }
+
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&session.summary).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .activatable(true)
+            .build();
+
+        let compare_check = gtk::CheckButton::builder()
+            .valign(gtk::Align::Center)
+            .visible(self.compare_mode.get())
+            .build();
+        let view = self.clone();
+        compare_check.connect_toggled(move |_| view.compare_if_ready());
+        row.add_prefix(&compare_check);
+        self.compare_checks.borrow_mut().push((session.id.clone(), compare_check.clone()));
+        if !commits.is_empty() {
+            let lines: Vec<String> = commits
+                .iter()
+                .map(|commit| format!("{} {}", commit.short_sha(), commit.summary))
+                .collect();
+            row.set_tooltip_text(Some(&lines.join("\n")));
+        }
+
+        let regenerate_btn = gtk::Button::builder()
+            .icon_name("view-refresh-symbolic")
+            .tooltip_text("Regenerate title")
+            .valign(gtk::Align::Center)
+            .build();
+        regenerate_btn.add_css_class("flat");
+
+        let repository = self.repository.clone();
+        let session_id = session.id.clone();
+        let title_row = row.clone();
+        regenerate_btn.connect_clicked(move |_| match regenerate_session_title(&repository, &session_id) {
+            Ok(title) => title_row.set_title(glib::markup_escape_text(&title).as_str()),
+            Err(e) => {
+                log::warn!("Failed to regenerate session title: {:#}", e);
+                title_row.set_tooltip_text(Some(&format!("Could not regenerate title: {:#}", e)));
+            }
+        });
+        let token_bar = token_bar(session);
+        token_bar.set_show_text(false);
+        token_bar.set_size_request(96, -1);
+        token_bar.set_valign(gtk::Align::Center);
+        token_bar.set_tooltip_text(Some(&format!("{} tokens", session.token_count_display())));
+        row.add_suffix(&token_bar);
+        row.add_suffix(&regenerate_btn);
+        row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+
+        let on_activate = self.on_activate.clone();
+        let compare_mode = self.compare_mode.clone();
+        let session = session.clone();
+        row.connect_activated(move |_| {
+            if compare_mode.get() {
+                compare_check.set_active(!compare_check.is_active());
+            } else if let Some(on_activate) = on_activate.borrow().as_ref() {
+                on_activate(&session);
+            }
+        });
+
+        row
+    }
+
+    /// Show or hide the checkboxes picking sessions to compare
+    fn set_compare_mode(&self, enabled: bool) {
+        self.compare_mode.set(enabled);
+        for (_, check) in self.compare_checks.borrow().iter() {
+            check.set_active(false);
+            check.set_visible(enabled);
+        }
+    }
+
+    /// Compare the two checked sessions as soon as the second is picked
+    fn compare_if_ready(&self) {
+        let checked: Vec<String> = self
+            .compare_checks
+            .borrow()
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .map(|(id, _)| id.clone())
+            .collect();
+        if checked.len() < 2 {
+            return;
+        }
+
+        // Unchecking calls back in here, so the list must not be borrowed meanwhile
+        let checks: Vec<gtk::CheckButton> = self.compare_checks.borrow().iter().map(|(_, check)| check.clone()).collect();
+        for check in checks {
+            check.set_active(false);
+        }
+        if let Some(on_compare) = self.on_compare.borrow().as_ref() {
+            on_compare(&checked[0], &checked[1]);
+        }
+    }
+
+    /// Call `f` with the IDs of two sessions picked in compare mode
+    pub fn connect_compare(&self, f: impl Fn(&str, &str) + 'static) {
+        *self.on_compare.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Call `f` with the session whose row was activated
+    pub fn connect_session_activated(&self, f: impl Fn(&SessionHistory) + 'static) {
+        *self.on_activate.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Get the widget
+    pub fn widget(&self) -> gtk::Box {
+        self.container.clone()
+    }
+}
+
+/// Bar filling with a session's share of the context window, colored as it nears the limit
+pub fn token_bar(session: &SessionHistory) -> gtk::ProgressBar {
+    let percentage = session.token_percentage();
+    let bar = gtk::ProgressBar::new();
+    bar.set_fraction((percentage / 100.0).clamp(0.0, 1.0));
+    bar.set_text(Some(&format!(
+        "{} / 200,000 tokens ({:.0}%)",
+        session.token_count_display(),
+        percentage
+    )));
+    bar.set_show_text(true);
+    bar.add_css_class(if session.is_near_limit() {
+        "token-progress-critical"
+    } else if percentage > TOKEN_WARNING_PERCENTAGE {
+        "token-progress-warning"
+    } else {
+        "token-progress-normal"
+    });
+    bar
+}
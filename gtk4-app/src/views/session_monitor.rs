@@ -1,37 +1,58 @@
+use crate::async_repository::AsyncRepository;
 use crate::db::Repository;
-use crate::models::SessionHistory;
+use crate::models::{format_cost, SessionHistory};
+use crate::monitor_updates::MonitorUpdates;
 use adw::prelude::*;
 use std::cell::RefCell;
 use std::rc::Rc;
 
 /// Session monitor view showing current session token usage
+#[derive(Clone)]
 pub struct SessionMonitorView {
     container: gtk::Box,
     repository: Repository,
     project_id: String,
     current_session: Rc<RefCell<Option<SessionHistory>>>,
+    widgets: SessionWidgets,
+}
+
+/// Parts of the card that show the current session
+#[derive(Clone)]
+struct SessionWidgets {
+    progress_bar: gtk::ProgressBar,
+    duration_label: gtk::Label,
+    facts_label: gtk::Label,
+    cost_label: gtk::Label,
+    warning_box: gtk::Box,
 }
 
 impl SessionMonitorView {
     /// Create a new session monitor view
     pub fn new(repository: Repository, project_id: String) -> Self {
         let container = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        let widgets = Self::setup_ui(&container);
 
-        let mut view = Self {
+        let view = Self {
             container,
             repository,
             project_id,
             current_session: Rc::new(RefCell::new(None)),
+            widgets,
         };
 
-        view.setup_ui();
         view.load_current_session();
 
         view
     }
 
+    /// Reload the current session as the monitor reports changes to this project
+    pub fn subscribe(&self, updates: &MonitorUpdates) {
+        let view = self.clone();
+        updates.subscribe_while_shown(&self.container, Some(&self.project_id), move || view.load_current_session());
+    }
+
     /// Setup the UI
-    fn setup_ui(&mut self) {
+    fn setup_ui(container: &gtk::Box) -> SessionWidgets {
         // Session info card
         let card = gtk::Box::new(gtk::Orientation::Vertical, 8);
         card.set_margin_top(8);
@@ -83,7 +104,21 @@ impl SessionMonitorView {
 
         card.append(&facts_box);
 
-        self.container.append(&card);
+        // What the session and the project as a whole have cost
+        let cost_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+
+        let cost_icon = gtk::Image::from_icon_name("emblem-money-symbolic");
+        cost_box.append(&cost_icon);
+
+        let cost_label = gtk::Label::new(Some("$0.00"));
+        cost_label.add_css_class("caption");
+        cost_label.set_hexpand(true);
+        cost_label.set_xalign(0.0);
+        cost_box.append(&cost_label);
+
+        card.append(&cost_box);
+
+        container.append(&card);
 
         // Warning message if near limit
         let warning_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
@@ -99,36 +134,41 @@ impl SessionMonitorView {
         warning_label.add_css_class("caption");
         warning_box.append(&warning_label);
 
-        self.container.append(&warning_box);
+        container.append(&warning_box);
+
+        SessionWidgets {
+            progress_bar,
+            duration_label,
+            facts_label,
+            cost_label,
+            warning_box,
+        }
     }
 
     /// Load current session
     fn load_current_session(&self) {
-        match self.repository.list_sessions(&self.project_id) {
-            Ok(sessions) => {
-                // Get the most recent active session
-                let active = sessions.into_iter().find(|s| s.is_active());
-                *self.current_session.borrow_mut() = active;
-                // Update UI with session data
-                // This would be implemented with proper state management
-            }
-            Err(e) => {
-                log::error!("Failed to load sessions: {}", e);
+        let repository = AsyncRepository::from(self.repository.clone());
+        let project_id = self.project_id.clone();
+        let current_session = self.current_session.clone();
+        let widgets = self.widgets.clone();
+
+        glib::spawn_future_local(async move {
+            let loaded = repository
+                .run(move |repository| Ok((repository.list_sessions(&project_id)?, repository.project_stats(&project_id)?)))
+                .await;
+            match loaded {
+                Ok((sessions, stats)) => {
+                    // Get the most recent active session
+                    let active = sessions.into_iter().find(|s| s.is_active());
+                    widgets.show(active.as_ref());
+                    widgets.show_cost(active.as_ref(), stats.usage.cost_usd);
+                    *current_session.borrow_mut() = active;
+                }
+                Err(e) => {
+                    log::error!("Failed to load sessions: {}", e);
+                }
             }
-        }
-    }
-
-    /// Update the UI with session data
-    fn update_ui(&self, session: Option<&SessionHistory>) {
-        // This would update the progress bar, labels, etc.
-        // For now, this is a placeholder
-        if let Some(sess) = session {
-            log::info!(
-                "Session: {} tokens ({:.1}%)",
-                sess.token_count,
-                sess.token_percentage()
-            );
-        }
+        });
     }
 
     /// Get the widget
@@ -136,3 +176,46 @@ impl SessionMonitorView {
         self.container.clone()
     }
 }
+
+impl SessionWidgets {
+    /// Update the card for `session`, or show that none is running
+    fn show(&self, session: Option<&SessionHistory>) {
+        let Some(session) = session else {
+            self.progress_bar.set_fraction(0.0);
+            self.progress_bar.set_text(Some("0 / 200,000 tokens (0%)"));
+            self.duration_label.set_text("No active session");
+            self.facts_label.set_text("0 facts extracted");
+            self.warning_box.set_visible(false);
+            return;
+        };
+
+        let percentage = session.token_percentage();
+        self.progress_bar.set_fraction((percentage / 100.0).clamp(0.0, 1.0));
+        self.progress_bar.set_text(Some(&format!(
+            "{} / 200,000 tokens ({:.0}%)",
+            session.token_count_display(),
+            percentage
+        )));
+        self.duration_label.set_text(&format!(
+            "Started {}",
+            session.session_start.with_timezone(&chrono::Local).format("%H:%M")
+        ));
+        self.facts_label.set_text(&format!(
+            "{} fact{} extracted",
+            session.facts_extracted,
+            if session.facts_extracted == 1 { "" } else { "s" }
+        ));
+        self.warning_box.set_visible(session.is_near_limit());
+    }
+
+    /// Show what `session` has cost so far next to the project's total
+    fn show_cost(&self, session: Option<&SessionHistory>, project_cost: f64) {
+        let total = format!("{} for the project", format_cost(project_cost));
+        match session {
+            Some(session) => self
+                .cost_label
+                .set_text(&format!("{} this session · {}", session.usage.cost_display(), total)),
+            None => self.cost_label.set_text(&total),
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::db::Repository;
+use crate::models::format_number_with_separator;
+use crate::monitor_updates::MonitorUpdates;
+use crate::views::TokenChartView;
+use adw::prelude::*;
+
+/// Totals and token usage across every project
+pub struct StatisticsView {
+    page: adw::NavigationPage,
+}
+
+impl StatisticsView {
+    /// Create a navigation page, its charts following `updates`
+    pub fn new(repository: &Repository, updates: &MonitorUpdates) -> Self {
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 24);
+        content.set_margin_top(16);
+        content.set_margin_bottom(16);
+        content.set_margin_start(16);
+        content.set_margin_end(16);
+
+        content.append(&Self::create_totals_group(repository));
+
+        let charts = TokenChartView::new(repository.clone(), None);
+        charts.subscribe(updates);
+        content.append(&charts.widget());
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&content)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scrolled.add_css_class("scrolled-content");
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        toolbar.set_content(Some(&scrolled));
+
+        let page = adw::NavigationPage::builder()
+            .title("Statistics")
+            .child(&toolbar)
+            .build();
+
+        Self { page }
+    }
+
+    /// Project, session, token and fact counts of all live projects
+    fn create_totals_group(repository: &Repository) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder().title("Totals").build();
+        let stats = match repository.global_stats() {
+            Ok(stats) => stats,
+            Err(e) => {
+                log::error!("Failed to load statistics: {}", e);
+                return group;
+            }
+        };
+
+        group.add(&Self::info_row("Projects", &stats.project_count.to_string()));
+        group.add(&Self::info_row("Sessions", &stats.session_count.to_string()));
+        group.add(&Self::info_row("Tokens", &format_number_with_separator(stats.total_tokens)));
+        if stats.usage.cost_usd > 0.0 {
+            group.add(&Self::info_row("Cost", &stats.usage.cost_display()));
+        }
+        group.add(&Self::info_row(
+            "Facts",
+            &format!("{} ({} stale)", stats.fact_count, stats.stale_facts),
+        ));
+
+        group
+    }
+
+    fn info_row(title: &str, value: &str) -> adw::ActionRow {
+        let row = adw::ActionRow::builder().title(title).build();
+        let label = gtk::Label::new(Some(value));
+        label.add_css_class("dim-label");
+        label.add_css_class("numeric");
+        row.add_suffix(&label);
+        row
+    }
+
+    /// Get the navigation page
+    pub fn page(&self) -> adw::NavigationPage {
+        self.page.clone()
+    }
+}
@@ -0,0 +1,156 @@
+use crate::db::Repository;
+use crate::models::{day_label, TimelineEntry, TimelineKind};
+use crate::monitor_updates::MonitorUpdates;
+use adw::prelude::*;
+use gtk::glib;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Most events shown on the timeline
+const TIMELINE_LIMIT: usize = 200;
+
+/// What happened on a project: sessions, important facts, status changes and
+/// context edits, newest first and grouped by day
+#[derive(Clone)]
+pub struct TimelineView {
+    container: gtk::ScrolledWindow,
+    days_box: gtk::Box,
+    repository: Repository,
+    project_id: String,
+    on_session_activated: Rc<RefCell<Option<Box<dyn Fn(&str)>>>>,
+}
+
+impl TimelineView {
+    /// Create a new timeline view
+    pub fn new(repository: Repository, project_id: String) -> Self {
+        let days_box = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        days_box.set_valign(gtk::Align::Start);
+        days_box.set_margin_top(16);
+        days_box.set_margin_bottom(16);
+        days_box.set_margin_start(16);
+        days_box.set_margin_end(16);
+
+        let container = gtk::ScrolledWindow::builder()
+            .child(&days_box)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .vscrollbar_policy(gtk::PolicyType::Automatic)
+            .vexpand(true)
+            .build();
+
+        let view = Self {
+            container,
+            days_box,
+            repository,
+            project_id,
+            on_session_activated: Rc::new(RefCell::new(None)),
+        };
+        view.reload();
+
+        view
+    }
+
+    /// Reload when the monitor records something for this project
+    pub fn subscribe(&self, updates: &MonitorUpdates) {
+        let view = self.clone();
+        updates.subscribe_while_shown(&self.container, Some(&self.project_id), move || view.reload());
+    }
+
+    /// Reload the timeline from the database
+    pub fn reload(&self) {
+        while let Some(child) = self.days_box.first_child() {
+            self.days_box.remove(&child);
+        }
+
+        let entries = self
+            .repository
+            .project_timeline(&self.project_id, TIMELINE_LIMIT)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load timeline: {}", e);
+                Vec::new()
+            });
+
+        if entries.is_empty() {
+            let list = Self::create_day_list();
+            let row = adw::ActionRow::builder().title("Nothing has happened yet").build();
+            row.add_css_class("dim-label");
+            list.append(&row);
+            self.days_box.append(&list);
+            return;
+        }
+
+        // Entries come newest first, so each day's entries are consecutive
+        let today = chrono::Local::now().date_naive();
+        let mut current: Option<(chrono::NaiveDate, gtk::ListBox)> = None;
+        for entry in &entries {
+            let day = entry.time.with_timezone(&chrono::Local).date_naive();
+            let list = match &current {
+                Some((current_day, list)) if *current_day == day => list.clone(),
+                _ => {
+                    let heading = gtk::Label::new(Some(&day_label(day, today)));
+                    heading.set_xalign(0.0);
+                    heading.add_css_class("heading");
+                    if current.is_some() {
+                        heading.set_margin_top(12);
+                    }
+                    let list = Self::create_day_list();
+                    self.days_box.append(&heading);
+                    self.days_box.append(&list);
+                    current = Some((day, list.clone()));
+                    list
+                }
+            };
+            list.append(&self.create_entry_row(entry));
+        }
+    }
+
+    fn create_day_list() -> gtk::ListBox {
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        list.add_css_class("boxed-list");
+        list
+    }
+
+    /// Row for one event; sessions open their detail page
+    fn create_entry_row(&self, entry: &TimelineEntry) -> adw::ActionRow {
+        let title = entry.title.lines().next().unwrap_or_default();
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(title).as_str())
+            .subtitle(
+                glib::markup_escape_text(&format!(
+                    "{} · {} · {}",
+                    entry.time.with_timezone(&chrono::Local).format("%H:%M"),
+                    entry.kind.display_name(),
+                    entry.detail
+                ))
+                .as_str(),
+            )
+            .title_lines(1)
+            .build();
+        row.set_tooltip_text(Some(&entry.title));
+        row.add_prefix(&gtk::Image::from_icon_name(entry.kind.icon_name()));
+
+        if entry.kind == TimelineKind::Session {
+            row.set_activatable(true);
+            row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+            let on_session_activated = self.on_session_activated.clone();
+            let session_id = entry.entity_id.clone();
+            row.connect_activated(move |_| {
+                if let Some(callback) = on_session_activated.borrow().as_ref() {
+                    callback(&session_id);
+                }
+            });
+        }
+
+        row
+    }
+
+    /// Call `f` with the ID of a session picked from the timeline
+    pub fn connect_session_activated(&self, f: impl Fn(&str) + 'static) {
+        *self.on_session_activated.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Get the widget
+    pub fn widget(&self) -> gtk::ScrolledWindow {
+        self.container.clone()
+    }
+}
@@ -0,0 +1,231 @@
+use crate::db::Repository;
+use crate::models::{format_number_with_separator, TokenHistory, TOKEN_HISTORY_WEEKS};
+use crate::monitor_updates::MonitorUpdates;
+use adw::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Height of each chart
+const CHART_HEIGHT: i32 = 140;
+
+/// Space between a chart's bars and its edges
+const CHART_PADDING: f64 = 8.0;
+
+/// Token use over the last weeks: one bar per session, and the total of each week
+#[derive(Clone)]
+pub struct TokenChartView {
+    container: gtk::Box,
+    sessions_group: adw::PreferencesGroup,
+    weekly_group: adw::PreferencesGroup,
+    sessions_area: gtk::DrawingArea,
+    weekly_area: gtk::DrawingArea,
+    repository: Repository,
+    project_id: Option<String>,
+    history: Rc<RefCell<TokenHistory>>,
+}
+
+impl TokenChartView {
+    /// Create charts for one project, or for all projects if `project_id` is None
+    pub fn new(repository: Repository, project_id: Option<String>) -> Self {
+        let container = gtk::Box::new(gtk::Orientation::Vertical, 24);
+
+        let sessions_group = adw::PreferencesGroup::builder().title("Tokens per Session").build();
+        let sessions_area = Self::create_chart_area();
+        sessions_group.add(&sessions_area);
+        container.append(&sessions_group);
+
+        let weekly_group = adw::PreferencesGroup::builder().title("Tokens per Week").build();
+        let weekly_area = Self::create_chart_area();
+        weekly_group.add(&weekly_area);
+        container.append(&weekly_group);
+
+        let view = Self {
+            container,
+            sessions_group,
+            weekly_group,
+            sessions_area,
+            weekly_area,
+            repository,
+            project_id,
+            history: Rc::new(RefCell::new(TokenHistory::new(
+                chrono::Utc::now().date_naive(),
+                TOKEN_HISTORY_WEEKS,
+            ))),
+        };
+
+        view.setup_drawing();
+        view.reload();
+
+        view
+    }
+
+    fn create_chart_area() -> gtk::DrawingArea {
+        let area = gtk::DrawingArea::builder()
+            .content_height(CHART_HEIGHT)
+            .hexpand(true)
+            .has_tooltip(true)
+            .build();
+        area.add_css_class("card");
+        area.add_css_class("token-chart");
+        area
+    }
+
+    /// Reload when the monitor records something for the charted project(s)
+    pub fn subscribe(&self, updates: &MonitorUpdates) {
+        let view = self.clone();
+        updates.subscribe_while_shown(&self.container, self.project_id.as_deref(), move || view.reload());
+    }
+
+    /// Reload the sessions from the database and redraw
+    pub fn reload(&self) {
+        let history = self
+            .repository
+            .token_history(self.project_id.as_deref(), TOKEN_HISTORY_WEEKS)
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load token history: {}", e);
+                TokenHistory::new(chrono::Utc::now().date_naive(), TOKEN_HISTORY_WEEKS)
+            });
+
+        let mut sessions = format!(
+            "{} session{} over the last {} weeks",
+            history.sessions.len(),
+            if history.sessions.len() == 1 { "" } else { "s" },
+            TOKEN_HISTORY_WEEKS
+        );
+        if let Some(largest) = history.sessions.iter().map(|session| session.tokens).max() {
+            sessions.push_str(&format!(", the largest {} tokens", format_number_with_separator(largest)));
+        }
+        self.sessions_group.set_description(Some(&sessions));
+        self.weekly_group.set_description(Some(&format!(
+            "{} tokens in total",
+            format_number_with_separator(history.total_tokens())
+        )));
+
+        *self.history.borrow_mut() = history;
+        self.sessions_area.queue_draw();
+        self.weekly_area.queue_draw();
+    }
+
+    fn setup_drawing(&self) {
+        // Sessions sit along a time axis spanning the covered weeks
+        let history = self.history.clone();
+        self.sessions_area.set_draw_func(move |area, cr, width, height| {
+            let history = history.borrow();
+            let bars: Vec<(f64, i64)> = history
+                .sessions
+                .iter()
+                .map(|session| (Self::time_position(&history, session.start), session.tokens))
+                .collect();
+            Self::draw_bars(area, cr, width, height, &bars, 3.0);
+        });
+
+        let history = self.history.clone();
+        self.sessions_area.connect_query_tooltip(move |area, x, _, _, tooltip| {
+            let history = history.borrow();
+            let (position, width) = Self::pointer_position(area, x);
+            // Closest session within a few pixels of the pointer
+            let closest = history
+                .sessions
+                .iter()
+                .map(|session| ((Self::time_position(&history, session.start) - position).abs(), session))
+                .filter(|(distance, _)| *distance * width <= 6.0)
+                .min_by(|a, b| a.0.total_cmp(&b.0));
+            let Some((_, session)) = closest else {
+                return false;
+            };
+            tooltip.set_text(Some(&format!(
+                "{}\n{} tokens",
+                session.start.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M"),
+                format_number_with_separator(session.tokens)
+            )));
+            true
+        });
+
+        // Weeks are evenly spaced bars, centered in their slot
+        let history = self.history.clone();
+        self.weekly_area.set_draw_func(move |area, cr, width, height| {
+            let history = history.borrow();
+            let weeks = history.weekly.len() as f64;
+            let bars: Vec<(f64, i64)> = history
+                .weekly
+                .iter()
+                .enumerate()
+                .map(|(i, tokens)| ((i as f64 + 0.5) / weeks, *tokens))
+                .collect();
+            let bar_width = ((f64::from(width) - 2.0 * CHART_PADDING) / weeks * 0.7).max(1.0);
+            Self::draw_bars(area, cr, width, height, &bars, bar_width);
+        });
+
+        let history = self.history.clone();
+        self.weekly_area.connect_query_tooltip(move |area, x, _, _, tooltip| {
+            let history = history.borrow();
+            let (position, _) = Self::pointer_position(area, x);
+            let index = (position * history.weekly.len() as f64) as usize;
+            let Some(tokens) = history.weekly.get(index).filter(|_| (0.0..1.0).contains(&position)) else {
+                return false;
+            };
+            tooltip.set_text(Some(&format!(
+                "Week of {}\n{} tokens",
+                history.week_start(index).format("%B %-d"),
+                format_number_with_separator(*tokens)
+            )));
+            true
+        });
+    }
+
+    /// Pointer position across the plotted width, from 0 to 1, and that width in pixels
+    fn pointer_position(area: &gtk::DrawingArea, x: i32) -> (f64, f64) {
+        let width = (f64::from(area.width()) - 2.0 * CHART_PADDING).max(1.0);
+        ((f64::from(x) - CHART_PADDING) / width, width)
+    }
+
+    /// Where `time` falls between the start of the history and the end of the current week, from 0 to 1
+    fn time_position(history: &TokenHistory, time: chrono::DateTime<chrono::Utc>) -> f64 {
+        let span = history.weekly.len().max(1) as f64 * 7.0 * 86_400.0;
+        let start = history.week_start(0).and_hms_opt(0, 0, 0).unwrap_or_default().and_utc();
+        ((time - start).num_seconds() as f64 / span).clamp(0.0, 1.0)
+    }
+
+    /// Vertical bars at horizontal positions 0 to 1, scaled to the tallest, with a baseline
+    fn draw_bars(
+        area: &gtk::DrawingArea,
+        cr: &gtk::cairo::Context,
+        width: i32,
+        height: i32,
+        bars: &[(f64, i64)],
+        bar_width: f64,
+    ) {
+        let color = area.color();
+        let (red, green, blue) = (color.red().into(), color.green().into(), color.blue().into());
+        let padding = CHART_PADDING;
+        let (width, height) = (f64::from(width) - 2.0 * padding, f64::from(height) - 2.0 * padding);
+
+        cr.set_source_rgba(red, green, blue, 0.25);
+        cr.set_line_width(1.0);
+        cr.move_to(padding, padding + height + 0.5);
+        cr.line_to(padding + width, padding + height + 0.5);
+        if let Err(e) = cr.stroke() {
+            log::debug!("Failed to draw chart baseline: {}", e);
+        }
+
+        let peak = bars.iter().map(|(_, tokens)| *tokens).max().unwrap_or(0);
+        if peak <= 0 {
+            return;
+        }
+
+        cr.set_source_rgba(red, green, blue, 0.85);
+        for (position, tokens) in bars {
+            let bar_height = height * *tokens as f64 / peak as f64;
+            let x = padding + (position * width - bar_width / 2.0).clamp(0.0, (width - bar_width).max(0.0));
+            cr.rectangle(x, padding + height - bar_height, bar_width, bar_height);
+        }
+        if let Err(e) = cr.fill() {
+            log::debug!("Failed to draw token chart: {}", e);
+        }
+    }
+
+    /// Get the widget
+    pub fn widget(&self) -> gtk::Box {
+        self.container.clone()
+    }
+}
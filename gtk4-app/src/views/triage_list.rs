@@ -0,0 +1,182 @@
+use crate::db::{Page, Repository};
+use crate::models::{ExtractedFact, TriageFilter};
+use adw::prelude::*;
+use gtk::glib;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Most facts listed at once
+const TRIAGE_LIST_LIMIT: usize = 200;
+
+/// One triage list (e.g. all facts needing review), across every project
+#[derive(Clone)]
+pub struct TriageListView {
+    page: adw::NavigationPage,
+    list: gtk::ListBox,
+    repository: Repository,
+    filter: TriageFilter,
+    on_change: Rc<RefCell<Option<Box<dyn Fn()>>>>,
+}
+
+impl TriageListView {
+    /// Create a navigation page for the list
+    pub fn new(repository: Repository, filter: TriageFilter) -> Self {
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        list.add_css_class("boxed-list");
+        list.set_valign(gtk::Align::Start);
+        list.set_margin_top(12);
+        list.set_margin_bottom(12);
+        list.set_margin_start(12);
+        list.set_margin_end(12);
+
+        let scrolled = gtk::ScrolledWindow::builder()
+            .child(&list)
+            .vexpand(true)
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .build();
+        scrolled.add_css_class("scrolled-content");
+
+        let toolbar = adw::ToolbarView::new();
+        toolbar.add_top_bar(&adw::HeaderBar::new());
+        toolbar.set_content(Some(&scrolled));
+
+        let page = adw::NavigationPage::builder()
+            .title(filter.display_name())
+            .child(&toolbar)
+            .build();
+
+        let view = Self {
+            page,
+            list,
+            repository,
+            filter,
+            on_change: Rc::new(RefCell::new(None)),
+        };
+
+        view.reload();
+
+        view
+    }
+
+    /// Reload the facts in the list
+    pub fn reload(&self) {
+        while let Some(row) = self.list.first_child() {
+            self.list.remove(&row);
+        }
+
+        let facts = self
+            .repository
+            .list_triage_facts(self.filter, Page::first(TRIAGE_LIST_LIMIT))
+            .unwrap_or_else(|e| {
+                log::error!("Failed to load {}: {}", self.filter.display_name(), e);
+                Vec::new()
+            });
+
+        if facts.is_empty() {
+            let row = adw::ActionRow::builder().title("Nothing left to triage").build();
+            row.add_css_class("dim-label");
+            self.list.append(&row);
+            return;
+        }
+
+        let project_names: HashMap<String, String> = self
+            .repository
+            .list_projects(None)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|project| (project.id, project.name))
+            .collect();
+
+        for fact in &facts {
+            let project_name = project_names.get(&fact.project).map(String::as_str).unwrap_or("Unknown project");
+            self.list.append(&self.create_fact_row(fact, project_name));
+        }
+    }
+
+    /// Row for one fact, with the actions that clear it from this list
+    fn create_fact_row(&self, fact: &ExtractedFact, project_name: &str) -> adw::ActionRow {
+        let mut subtitle = format!(
+            "{} · {} · {} · {}",
+            project_name,
+            fact.fact_type.display_name(),
+            fact.importance_stars(),
+            fact.age_display()
+        );
+        if fact.is_low_confidence() {
+            subtitle.push_str(&format!(" · {:.0}% confidence", fact.confidence() * 100.0));
+        }
+        let row = adw::ActionRow::builder()
+            .title(glib::markup_escape_text(&fact.content_preview()).as_str())
+            .subtitle(glib::markup_escape_text(&subtitle).as_str())
+            .build();
+        row.set_tooltip_text(Some(&fact.content));
+        row.add_prefix(&gtk::Image::from_icon_name(fact.fact_type.icon_name()));
+
+        match self.filter {
+            TriageFilter::NeedsReview => {
+                self.add_action(&row, &fact.id, "emblem-ok-symbolic", "Mark Reviewed", |repository, id| {
+                    repository.mark_fact_reviewed(id).map(|_| ())
+                });
+            }
+            TriageFilter::LowConfidence => {
+                self.add_action(&row, &fact.id, "emblem-ok-symbolic", "Confirm", |repository, id| {
+                    repository.mark_fact_reviewed(id).map(|_| ())
+                });
+            }
+            TriageFilter::OpenBlockers => {
+                self.add_action(&row, &fact.id, "emblem-ok-symbolic", "Mark Resolved", |repository, id| {
+                    repository.mark_fact_stale(id).map(|_| ())
+                });
+            }
+            TriageFilter::Stale => {}
+        }
+        self.add_action(&row, &fact.id, "user-trash-symbolic", "Move to Trash", |repository, id| {
+            repository.delete_fact(id)
+        });
+
+        row
+    }
+
+    /// Suffix button running `action` on the fact, then reloading the list
+    fn add_action(
+        &self,
+        row: &adw::ActionRow,
+        fact_id: &str,
+        icon_name: &str,
+        tooltip: &str,
+        action: impl Fn(&Repository, &str) -> anyhow::Result<()> + 'static,
+    ) {
+        let button = gtk::Button::builder()
+            .icon_name(icon_name)
+            .tooltip_text(tooltip)
+            .valign(gtk::Align::Center)
+            .build();
+        button.add_css_class("flat");
+
+        let view = self.clone();
+        let fact_id = fact_id.to_string();
+        button.connect_clicked(move |_| {
+            if let Err(e) = action(&view.repository, &fact_id) {
+                log::error!("Failed to update fact: {}", e);
+            }
+            view.reload();
+            if let Some(on_change) = view.on_change.borrow().as_ref() {
+                on_change();
+            }
+        });
+
+        row.add_suffix(&button);
+    }
+
+    /// Call `f` whenever a fact was cleared from the list
+    pub fn connect_changed(&self, f: impl Fn() + 'static) {
+        *self.on_change.borrow_mut() = Some(Box::new(f));
+    }
+
+    /// Get the navigation page
+    pub fn page(&self) -> adw::NavigationPage {
+        self.page.clone()
+    }
+}
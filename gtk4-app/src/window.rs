@@ -1,10 +1,15 @@
 use crate::db::Repository;
-use crate::models::Project;
-use crate::monitor::start_background_monitor;
-use crate::views::{DashboardView, ProjectDetailView};
+use crate::models::{Project, TrashKind, TriageFilter};
+use crate::monitor::{read_context_file_edits, start_background_monitor, MonitorEvent, MonitorHandle, MonitorUpdate};
+use crate::monitor_updates::MonitorUpdates;
+use crate::sync::last_replicated;
+use crate::utils::ContextFileEdit;
+use crate::views::{DashboardView, ProjectDetailView, SearchPalette, StatisticsView, TriageListView};
 use adw::prelude::*;
 use gtk::glib;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::Mutex;
@@ -20,10 +25,13 @@ pub enum NavigationState {
 pub struct MainWindow {
     window: adw::ApplicationWindow,
     navigation_view: adw::NavigationView,
+    toast_overlay: adw::ToastOverlay,
+    dashboard: DashboardView,
     repository: Repository,
     state: Rc<RefCell<NavigationState>>,
     monitoring_active: Rc<RefCell<bool>>,
-    monitor_handle: Arc<Mutex<Option<std::thread::JoinHandle<()>>>>,
+    monitor_handle: Arc<Mutex<Option<MonitorHandle>>>,
+    monitor_updates: MonitorUpdates,
 }
 
 impl MainWindow {
@@ -45,10 +53,13 @@ impl MainWindow {
         let mut main_window = Self {
             window,
             navigation_view,
+            toast_overlay: adw::ToastOverlay::new(),
+            dashboard: DashboardView::new(repository.clone()),
             repository,
             state,
             monitoring_active: Rc::new(RefCell::new(false)),
             monitor_handle: Arc::new(Mutex::new(None)),
+            monitor_updates: MonitorUpdates::new(),
         };
 
         main_window.setup_ui();
@@ -68,14 +79,143 @@ impl MainWindow {
 
         self.navigation_view.add(&dashboard_page);
 
-        // Set navigation view as window content
-        self.window.set_content(Some(&self.navigation_view));
+        // Set navigation view as window content, below the replica banner
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&self.create_replica_banner());
+        self.navigation_view.set_vexpand(true);
+        content.append(&self.navigation_view);
+        self.toast_overlay.set_child(Some(&content));
+        self.window.set_content(Some(&self.toast_overlay));
 
         // Setup keyboard shortcuts
         self.setup_shortcuts();
 
         // Setup menu actions
         self.setup_actions();
+
+        self.offer_context_file_imports();
+    }
+
+    /// Offer to import the CLAUDE.md edits the monitor notices
+    fn offer_context_file_imports(&self) {
+        let window_weak = self.window.downgrade();
+        let repository = self.repository.clone();
+        let prompting: Rc<RefCell<HashSet<String>>> = Rc::default(); // Projects with a dialog open
+        self.monitor_updates.subscribe(move |update| {
+            let Some(window) = window_weak.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+            if let MonitorUpdate::Event {
+                project_id,
+                event: MonitorEvent::ContextFileEdited { path, .. },
+            } = update
+            {
+                if prompting.borrow_mut().insert(project_id.clone()) {
+                    let on_close = {
+                        let prompting = prompting.clone();
+                        let project_id = project_id.clone();
+                        move || {
+                            prompting.borrow_mut().remove(&project_id);
+                        }
+                    };
+                    if let Err(e) = Self::show_import_dialog(&window, &repository, project_id, Path::new(path), on_close) {
+                        log::error!("Failed to compare {} with the database: {}", path, e);
+                        prompting.borrow_mut().remove(project_id);
+                    }
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Ask whether to import a project's edited CLAUDE.md; `on_close` runs once it is answered
+    fn show_import_dialog(
+        window: &adw::ApplicationWindow,
+        repository: &Repository,
+        project_id: &str,
+        path: &Path,
+        on_close: impl Fn() + 'static,
+    ) -> anyhow::Result<()> {
+        // The file may have been imported or exported over since the monitor looked
+        let project = repository.get_project(project_id)?;
+        let edits = read_context_file_edits(repository, &project, path)?;
+        if edits.is_empty() {
+            on_close();
+            return Ok(());
+        }
+
+        let sections: Vec<String> = edits
+            .iter()
+            .map(|edit| match edit {
+                ContextFileEdit::Changed { title, .. } => format!("• {}", title),
+                ContextFileEdit::Added { title, .. } => format!("• {} (new section)", title),
+            })
+            .collect();
+        let dialog = adw::MessageDialog::new(
+            Some(window),
+            Some("Import CLAUDE.md Edits?"),
+            Some(&format!(
+                "{} was edited outside the app. Import these sections into {}?\n\n{}",
+                path.display(),
+                project.name,
+                sections.join("\n")
+            )),
+        );
+        dialog.add_responses(&[("ignore", "Ignore"), ("import", "Import")]);
+        dialog.set_response_appearance("import", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("import"));
+        dialog.set_close_response("ignore");
+
+        let repository = repository.clone();
+        let path: PathBuf = path.to_path_buf();
+        dialog.connect_response(None, move |_, response| {
+            on_close();
+            if response != "import" {
+                return;
+            }
+            match repository.import_context_file_edits(&project.id, &edits) {
+                Ok(count) => log::info!("Imported {} sections from {}", count, path.display()),
+                Err(e) => log::error!("Failed to import {}: {}", path.display(), e),
+            }
+        });
+
+        dialog.present();
+        Ok(())
+    }
+
+    /// Banner marking the data as a read-only PocketBase mirror (hidden otherwise)
+    fn create_replica_banner(&self) -> adw::Banner {
+        let banner = adw::Banner::new("");
+
+        let Some(url) = self.repository.load_settings().ok().and_then(|s| s.replica_url) else {
+            return banner;
+        };
+
+        let repository = self.repository.clone();
+        let update_title = move |banner: &adw::Banner| {
+            let synced = last_replicated(&repository)
+                .map(|dt| dt.with_timezone(&chrono::Local).format("%H:%M").to_string())
+                .unwrap_or_else(|| "never".to_string());
+            banner.set_title(&glib::markup_escape_text(&format!(
+                "Read-only replica of {} · last synced {}",
+                url, synced
+            )));
+        };
+
+        update_title(&banner);
+        banner.set_revealed(true);
+
+        // Keep the last-synced time current while the window is open
+        let banner_weak = banner.downgrade();
+        glib::timeout_add_seconds_local(30, move || match banner_weak.upgrade() {
+            Some(banner) => {
+                update_title(&banner);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        banner
     }
 
     /// Setup menu actions
@@ -84,14 +224,25 @@ impl MainWindow {
 
         // Preferences action
         let window = self.window.clone();
+        let repository = self.repository.clone();
         let prefs_action = gtk::gio::SimpleAction::new("preferences", None);
         prefs_action.connect_activate(move |_, _| {
             log::info!("Opening preferences");
-            let settings = crate::settings::SettingsDialog::new(&window);
+            let settings = crate::settings::SettingsDialog::new(&window, repository.clone());
             settings.present();
         });
         app.add_action(&prefs_action);
 
+        // Statistics action
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        let monitor_updates = self.monitor_updates.clone();
+        let statistics_action = gtk::gio::SimpleAction::new("statistics", None);
+        statistics_action.connect_activate(move |_, _| {
+            navigation_view.push(&StatisticsView::new(&repository, &monitor_updates).page());
+        });
+        app.add_action(&statistics_action);
+
         // Keyboard shortcuts action
         let window_clone = self.window.clone();
         let shortcuts_action = gtk::gio::SimpleAction::new("shortcuts", None);
@@ -136,7 +287,7 @@ impl MainWindow {
 
         let shortcut_search = gtk::ShortcutsShortcut::builder()
             .title("Search")
-            .accelerator("<Ctrl>F")
+            .accelerator("<Ctrl>K <Ctrl>F")
             .build();
 
         // Create groups using grid layout
@@ -223,26 +374,31 @@ impl MainWindow {
         monitor_box.append(&monitor_switch);
 
         header.pack_start(&monitor_box);
+        header.set_title_widget(Some(&self.create_triage_chips()));
 
         // Wire up monitoring toggle
         let repository_clone = self.repository.clone();
         let monitoring_active = self.monitoring_active.clone();
         let monitor_handle = self.monitor_handle.clone();
+        let monitor_updates = self.monitor_updates.clone();
         let monitor_label_weak = monitor_label.downgrade();
+        Self::show_monitor_updates(&monitor_label, &self.monitor_updates);
 
         monitor_switch.connect_state_set(move |switch, enabled| {
             log::info!("Monitor toggle: {}", enabled);
             *monitoring_active.borrow_mut() = enabled;
 
             if enabled {
-                // Start background monitoring
-                // For now, monitor all projects (could be enhanced to track active project)
+                // Monitor all projects, filing each conversation under the project it ran in
                 match start_background_monitor(
-                    "default".to_string(),
+                    None,
                     repository_clone.clone(),
                     None,
+                    Some(Arc::new(crate::notifications::notify_monitor_event)),
+                    crate::llm::configured_session_extractor(&repository_clone),
                 ) {
                     Ok(handle) => {
+                        monitor_updates.forward(&handle);
                         *monitor_handle.lock().unwrap() = Some(handle);
                         log::info!("Background monitoring started");
                         if let Some(label) = monitor_label_weak.upgrade() {
@@ -250,7 +406,7 @@ impl MainWindow {
                             label.add_css_class("monitoring-active");
                         }
                         // Send notification
-                        crate::notifications::notify_monitoring_started("default");
+                        crate::notifications::notify_monitoring_started(None);
                     }
                     Err(e) => {
                         log::error!("Failed to start monitoring: {}", e);
@@ -258,12 +414,20 @@ impl MainWindow {
                     }
                 }
             } else {
-                // Stop background monitoring
-                // Note: We can't easily stop the thread, but we log the state change
-                log::info!("Background monitoring stopped (thread continues)");
+                // Stop background monitoring and wait for the thread off the main loop
+                if let Some(handle) = monitor_handle.lock().unwrap().take() {
+                    handle.stop();
+                    glib::spawn_future_local(async move {
+                        if gio::spawn_blocking(move || handle.join()).await.is_ok() {
+                            log::info!("Background monitoring stopped");
+                        }
+                    });
+                }
                 if let Some(label) = monitor_label_weak.upgrade() {
                     label.set_text("Monitor");
+                    label.set_tooltip_text(None);
                     label.remove_css_class("monitoring-active");
+                    label.remove_css_class("error");
                 }
                 // Send notification
                 crate::notifications::notify_monitoring_stopped();
@@ -272,6 +436,16 @@ impl MainWindow {
             glib::Propagation::Proceed
         });
 
+        let settings = self.repository.load_settings().unwrap_or_default();
+        if settings.is_replica() {
+            // Replicated data is overwritten on every sync, so there is nothing to record into
+            monitor_switch.set_sensitive(false);
+            monitor_switch.set_tooltip_text(Some("Monitoring is unavailable on a read-only replica"));
+        } else if settings.autostart_monitoring {
+            // Start monitoring right away if the user asked for it
+            monitor_switch.set_active(true);
+        }
+
         // Menu button (right side)
         let menu_button = gtk::MenuButton::builder()
             .icon_name("open-menu-symbolic")
@@ -286,6 +460,10 @@ impl MainWindow {
         let prefs_item = gtk::gio::MenuItem::new(Some("Preferences"), Some("app.preferences"));
         menu.append_item(&prefs_item);
 
+        // Statistics menu item
+        let statistics_item = gtk::gio::MenuItem::new(Some("Statistics"), Some("app.statistics"));
+        menu.append_item(&statistics_item);
+
         // Keyboard shortcuts menu item
         let shortcuts_item = gtk::gio::MenuItem::new(Some("Keyboard Shortcuts"), Some("app.shortcuts"));
         menu.append_item(&shortcuts_item);
@@ -299,6 +477,8 @@ impl MainWindow {
         menu_button.set_menu_model(Some(&menu));
         header.pack_end(&menu_button);
 
+        header.pack_end(&self.create_notifications_button());
+
         // Add new project button
         let new_project_btn = gtk::Button::builder()
             .icon_name("list-add-symbolic")
@@ -322,13 +502,40 @@ impl MainWindow {
         refresh_btn.add_css_class("flat");
         header.pack_end(&refresh_btn);
 
+        let search_btn = gtk::Button::builder()
+            .icon_name("system-search-symbolic")
+            .tooltip_text("Search (Ctrl+K)")
+            .build();
+        search_btn.add_css_class("flat");
+        let window = self.window.clone();
+        let repository = self.repository.clone();
+        let open_project = self.project_opener();
+        search_btn.connect_clicked(move |_| {
+            Self::show_search_palette(&window, repository.clone(), open_project.clone());
+        });
+        header.pack_end(&search_btn);
+
         container.append(&header);
 
         // Dashboard content
-        let dashboard_view = DashboardView::new(self.repository.clone(), self.navigation_view.clone());
+        let dashboard_view = self.dashboard.clone();
+        let open_project = self.project_opener();
+        dashboard_view.connect_project_activated(move |project_id| open_project(project_id));
         let dashboard_widget = dashboard_view.widget();
         container.append(&dashboard_widget);
 
+        // Show what was edited, archived or deleted on a project page once back
+        let dashboard_on_pop = dashboard_view.clone();
+        self.navigation_view.connect_popped(move |navigation_view, _| {
+            let at_dashboard = match navigation_view.visible_page() {
+                Some(page) => navigation_view.previous_page(&page).is_none(),
+                None => true,
+            };
+            if at_dashboard {
+                dashboard_on_pop.refresh();
+            }
+        });
+
         // Connect refresh button - clone repository for refresh
         let repo_for_refresh = self.repository.clone();
         let nav_for_refresh = self.navigation_view.clone();
@@ -340,6 +547,185 @@ impl MainWindow {
         container
     }
 
+    /// Show the monitor's latest activity, or its last error, on the header label
+    fn show_monitor_updates(label: &gtk::Label, updates: &MonitorUpdates) {
+        let label_weak = label.downgrade();
+        updates.subscribe(move |update| {
+            let Some(label) = label_weak.upgrade() else {
+                return glib::ControlFlow::Break;
+            };
+
+            let time = chrono::Local::now().format("%H:%M");
+            match update {
+                MonitorUpdate::Error { message } => {
+                    label.set_text("Monitor error");
+                    label.add_css_class("error");
+                    label.set_tooltip_text(Some(&format!("{} at {}", message, time)));
+                }
+                MonitorUpdate::Event { event, .. } => {
+                    label.set_text("Monitoring");
+                    label.remove_css_class("error");
+                    label.set_tooltip_text(Some(&format!("{}: {} at {}", event.project(), event.description(), time)));
+                }
+                MonitorUpdate::SessionUpdated { .. } => {
+                    label.set_text("Monitoring");
+                    label.remove_css_class("error");
+                    label.set_tooltip_text(Some(&format!("Last activity at {}", time)));
+                }
+                MonitorUpdate::Deferred { projects: 0, .. } => {
+                    label.set_text("Monitoring");
+                    label.set_tooltip_text(Some(&format!("Took over from the other monitor at {}", time)));
+                }
+                MonitorUpdate::Deferred { projects, pid } => {
+                    label.set_text("Monitoring elsewhere");
+                    label.remove_css_class("error");
+                    let by = pid.map_or_else(|| "another process".to_string(), |pid| format!("PID {}", pid));
+                    label.set_tooltip_text(Some(&format!(
+                        "{} projects are monitored by {}; taking over when it stops",
+                        projects, by
+                    )));
+                }
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// Header button opening the notification history, highlighted while events are unread
+    fn create_notifications_button(&self) -> gtk::Button {
+        let button = gtk::Button::builder()
+            .icon_name("preferences-system-notifications-symbolic")
+            .build();
+        button.add_css_class("flat");
+
+        let repository = self.repository.clone();
+        let update = move |button: &gtk::Button| {
+            let unread = repository.count_unread_events().unwrap_or(0);
+            button.set_tooltip_text(Some(&match unread {
+                0 => "Notifications".to_string(),
+                n => format!("Notifications ({} unread)", n),
+            }));
+            if unread > 0 {
+                button.add_css_class("accent");
+            } else {
+                button.remove_css_class("accent");
+            }
+        };
+        update(&button);
+
+        let repository = self.repository.clone();
+        let window = self.window.clone();
+        let update_on_change = update.clone();
+        button.connect_clicked(move |button| {
+            let dialog = crate::event_history::EventHistoryDialog::new(&window, repository.clone());
+            let button_weak = button.downgrade();
+            let update = update_on_change.clone();
+            dialog.connect_changed(move || {
+                if let Some(button) = button_weak.upgrade() {
+                    update(&button);
+                }
+            });
+            dialog.present();
+        });
+
+        // Events from this window's monitor show up right away
+        let button_weak = button.downgrade();
+        let update_on_event = update.clone();
+        self.monitor_updates.subscribe(move |monitor_update| match button_weak.upgrade() {
+            Some(button) => {
+                if matches!(monitor_update, MonitorUpdate::Event { .. }) {
+                    update_on_event(&button);
+                }
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        // Other processes (the monitor daemon, the CLI) record events too; pick them up periodically
+        let button_weak = button.downgrade();
+        glib::timeout_add_seconds_local(30, move || match button_weak.upgrade() {
+            Some(button) => {
+                update(&button);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        button
+    }
+
+    /// Header chips with the size of each triage list, opening the list when clicked
+    fn create_triage_chips(&self) -> gtk::Box {
+        let chips = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        let buttons: Vec<(TriageFilter, gtk::Button)> = TriageFilter::all()
+            .into_iter()
+            .map(|filter| {
+                let button = gtk::Button::new();
+                button.add_css_class("triage-chip");
+                chips.append(&button);
+                (filter, button)
+            })
+            .collect();
+
+        let repository = self.repository.clone();
+        let update = Rc::new(move |buttons: &[(TriageFilter, gtk::Button)]| {
+            let counts = repository.triage_counts().unwrap_or_else(|e| {
+                log::warn!("Failed to count triage lists: {}", e);
+                Default::default()
+            });
+            for (filter, button) in buttons {
+                button.set_label(&counts.chip_label(*filter));
+                button.set_sensitive(counts.count(*filter) > 0);
+            }
+        });
+        update(&buttons);
+
+        let buttons = Rc::new(buttons);
+        for (filter, button) in buttons.iter() {
+            let repository = self.repository.clone();
+            let nav_view = self.navigation_view.clone();
+            let filter = *filter;
+            let update = update.clone();
+            let buttons_weak = Rc::downgrade(&buttons);
+            button.connect_clicked(move |_| {
+                let list = TriageListView::new(repository.clone(), filter);
+                let update = update.clone();
+                let buttons_weak = buttons_weak.clone();
+                list.connect_changed(move || {
+                    if let Some(buttons) = buttons_weak.upgrade() {
+                        update(&buttons);
+                    }
+                });
+                nav_view.push(&list.page());
+            });
+        }
+
+        // Facts from this window's monitor are counted right away
+        let chips_weak = chips.downgrade();
+        let update_on_event = update.clone();
+        let buttons_on_event = buttons.clone();
+        self.monitor_updates.subscribe(move |monitor_update| match chips_weak.upgrade() {
+            Some(_) => {
+                if matches!(monitor_update, MonitorUpdate::Event { .. }) {
+                    update_on_event(&buttons_on_event);
+                }
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        // Other processes (the monitor daemon, the CLI) extract facts too; pick up new ones periodically
+        let chips_weak = chips.downgrade();
+        glib::timeout_add_seconds_local(30, move || match chips_weak.upgrade() {
+            Some(_) => {
+                update(&buttons);
+                glib::ControlFlow::Continue
+            }
+            None => glib::ControlFlow::Break,
+        });
+
+        chips
+    }
+
     /// Show dialog to create a new project
     fn show_new_project_dialog(repository: Repository, nav_view: adw::NavigationView) {
         // This will be implemented when we create the dashboard view
@@ -353,6 +739,7 @@ impl MainWindow {
         let window = self.window.clone();
         let repository = self.repository.clone();
         let nav_view = self.navigation_view.clone();
+        let open_project = self.project_opener();
 
         shortcuts.connect_key_pressed(move |_, key, _, modifier| {
             if modifier.contains(gtk::gdk::ModifierType::CONTROL_MASK) {
@@ -371,13 +758,13 @@ impl MainWindow {
                     // Ctrl+,: Preferences
                     gtk::gdk::Key::comma => {
                         log::info!("Opening preferences (Ctrl+,)");
-                        let settings = crate::settings::SettingsDialog::new(&window);
+                        let settings = crate::settings::SettingsDialog::new(&window, repository.clone());
                         settings.present();
                         return glib::Propagation::Stop;
                     }
-                    // Ctrl+F: Search (placeholder)
-                    gtk::gdk::Key::f => {
-                        log::info!("Search (Ctrl+F) - not yet implemented");
+                    // Ctrl+K or Ctrl+F: Search
+                    gtk::gdk::Key::k | gtk::gdk::Key::f => {
+                        Self::show_search_palette(&window, repository.clone(), open_project.clone());
                         return glib::Propagation::Stop;
                     }
                     _ => {}
@@ -400,21 +787,91 @@ impl MainWindow {
 
     /// Navigate to project detail view
     pub fn navigate_to_project(&self, project_id: String) {
-        *self.state.borrow_mut() = NavigationState::ProjectDetail(project_id.clone());
+        Self::show_project(
+            &self.repository,
+            &self.navigation_view,
+            &self.monitor_updates,
+            &self.state,
+            &self.project_remover(),
+            project_id,
+        );
+    }
+
+    /// Open the search palette; picking a result opens the project it belongs to
+    fn show_search_palette(window: &adw::ApplicationWindow, repository: Repository, open_project: Rc<dyn Fn(String)>) {
+        let palette = SearchPalette::new(window, repository);
+        palette.connect_result_activated(move |result| open_project(result.project.clone()));
+        palette.present();
+    }
+
+    /// Callback pushing the detail page of a project, for widgets that outlive `&self`
+    fn project_opener(&self) -> Rc<dyn Fn(String)> {
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        let monitor_updates = self.monitor_updates.clone();
+        let state = self.state.clone();
+        let on_deleted = self.project_remover();
+        Rc::new(move |project_id| {
+            Self::show_project(&repository, &navigation_view, &monitor_updates, &state, &on_deleted, project_id)
+        })
+    }
+
+    /// Callback leaving the page of a project moved to the trash, with a toast to undo it
+    fn project_remover(&self) -> Rc<dyn Fn(&Project)> {
+        let repository = self.repository.clone();
+        let navigation_view = self.navigation_view.clone();
+        let toast_overlay = self.toast_overlay.clone();
+        let dashboard = self.dashboard.clone();
+        let state = self.state.clone();
+        Rc::new(move |project| {
+            *state.borrow_mut() = NavigationState::Dashboard;
+            navigation_view.pop();
+
+            let toast = adw::Toast::builder()
+                .title(glib::markup_escape_text(&format!("'{}' moved to the trash", project.name)).as_str())
+                .button_label("Undo")
+                .build();
+            let repository = repository.clone();
+            let dashboard = dashboard.clone();
+            let project_id = project.id.clone();
+            toast.connect_button_clicked(move |_| {
+                match repository.restore(TrashKind::Project, &project_id) {
+                    Ok(()) => dashboard.refresh(),
+                    Err(e) => log::error!("Failed to restore project: {}", e),
+                }
+            });
+            toast_overlay.add_toast(toast);
+        })
+    }
+
+    /// Push the detail page of a project, for callers that don't hold the window
+    fn show_project(
+        repository: &Repository,
+        navigation_view: &adw::NavigationView,
+        monitor_updates: &MonitorUpdates,
+        state: &Rc<RefCell<NavigationState>>,
+        on_deleted: &Rc<dyn Fn(&Project)>,
+        project_id: String,
+    ) {
+        *state.borrow_mut() = NavigationState::ProjectDetail(project_id.clone());
 
         // Create project detail view
         let project_detail = ProjectDetailView::new(
-            self.repository.clone(),
+            repository.clone(),
             project_id,
-            self.navigation_view.clone(),
+            navigation_view.clone(),
+            monitor_updates.clone(),
         );
 
+        let on_deleted = on_deleted.clone();
+        project_detail.connect_deleted(move |project| on_deleted(project));
+
         let page = adw::NavigationPage::builder()
             .title("Project Details")
             .child(&project_detail.widget())
             .build();
 
-        self.navigation_view.push(&page);
+        navigation_view.push(&page);
     }
 
     /// Navigate back to dashboard